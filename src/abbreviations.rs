@@ -0,0 +1,41 @@
+use std::collections::BTreeMap;
+
+/// カーソルの直前が略語で終わっていれば、その略語と展開後の文字列を返す。
+/// `;k` と `;ket` のように重なるときは長い方を使う
+pub fn find_expansion<'a>(
+    before_cursor: &str,
+    abbreviations: &'a BTreeMap<String, String>,
+) -> Option<(&'a str, &'a str)> {
+    abbreviations
+        .iter()
+        .filter(|(abbreviation, _)| {
+            !abbreviation.is_empty() && before_cursor.ends_with(abbreviation.as_str())
+        })
+        .max_by_key(|(abbreviation, _)| abbreviation.chars().count())
+        .map(|(abbreviation, expansion)| (abbreviation.as_str(), expansion.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_matching_abbreviation_is_expanded() {
+        let abbreviations: BTreeMap<String, String> =
+            [(";k", "結果として、"), (";ket", "結論として、"), ("", "空")]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect();
+
+        assert_eq!(
+            find_expansion("本文では;ket", &abbreviations),
+            Some((";ket", "結論として、"))
+        );
+        assert_eq!(
+            find_expansion(";k", &abbreviations),
+            Some((";k", "結果として、"))
+        );
+        assert_eq!(find_expansion(";ke", &abbreviations), None);
+        assert_eq!(find_expansion("", &abbreviations), None);
+    }
+}
@@ -1,11 +1,70 @@
+use crate::appeal::{self, Exchange};
+use crate::audit::{AuditEntry, AuditLog};
+use crate::coach::{self, CoachConversation, CoachMessage, CoachRole};
+use crate::config::{NetworkConfig, Provider, RubricConfig};
 use crate::error::AppError;
-use crate::evaluation::build_evaluation_prompt;
+use crate::evaluation::{build_evaluation_prompt, fill_evaluation_template};
+use crate::furigana;
+use crate::glossary;
+use crate::guided;
+use crate::history::SessionRecord;
+use crate::redact::Redactor;
+use crate::retry::{self, RetryNotice, RetryPolicy, RetryStatus};
+use crate::rubric;
+use crate::strictness::Strictness;
+use crate::summary_length::LengthTarget;
+use crate::synthesis;
+use crate::word_lookup;
+use chrono::Local;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize)]
 struct ChatRequest<'a> {
     model: &'a str,
-    messages: Vec<ChatMessage<'a>>,
+    messages: &'a [ChatMessage<'a>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
+}
+
+#[derive(Serialize)]
+struct ResponseFormat {
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+#[derive(Serialize)]
+struct OllamaChatRequest<'a> {
+    model: &'a str,
+    messages: &'a [ChatMessage<'a>],
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<&'static str>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OllamaChatResponse {
+    message: ChatResponseMessage,
+}
+
+#[derive(Deserialize, Debug)]
+struct ModelsResponse {
+    data: Vec<ModelEntry>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ModelEntry {
+    id: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct OllamaTagsResponse {
+    models: Vec<OllamaModelEntry>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OllamaModelEntry {
+    name: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -29,52 +88,341 @@ struct ChatResponseMessage {
     content: Option<String>,
 }
 
+/// 評価では JSON モードを使い、構造化されたスコアを確実に受け取る
+#[derive(Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
 const API_BASE_URL: &str = "https://api.groq.com/openai/v1";
 const CHAT_COMPLETIONS_ENDPOINT: &str = "/chat/completions";
 const MODELS_ENDPOINT: &str = "/models";
 const CHAT_MODEL: &str = "openai/gpt-oss-120b";
+const OLLAMA_BASE_URL: &str = "http://localhost:11434";
+const OLLAMA_CHAT_ENDPOINT: &str = "/api/chat";
+const OLLAMA_TAGS_ENDPOINT: &str = "/api/tags";
+const OLLAMA_CHAT_MODEL: &str = "llama3.1";
 const API_TIMEOUT_SECS: u64 = 60;
+// ローカル LLM は生成が遅いことが多いので長めに待つ
+const OLLAMA_TIMEOUT_SECS: u64 = 300;
+const CONNECT_TIMEOUT_SECS: u64 = 10;
+/// 複数の発言を送ったときに、監査ログで発言を区切る行
+const AUDIT_MESSAGE_SEPARATOR: &str = "\n\n---\n\n";
 
+#[derive(Clone)]
 pub struct ApiClient {
     client: reqwest::Client,
+    provider: Provider,
+    base_url: String,
     api_key: String,
+    model: Option<String>,
+    redactor: Option<Redactor>,
+    audit_log: Option<AuditLog>,
+    /// `prompts.toml` の評価プロンプト。`None` は組み込みのプロンプトを使う
+    evaluation_template: Option<String>,
+    /// 評価プロンプトに加える厳しさの指示
+    strictness: Strictness,
+    /// 組み込みの評価プロンプトに加える独自の評価観点
+    rubric: RubricConfig,
+    retry_policy: RetryPolicy,
+    retry_status: RetryStatus,
+    /// コーチ画面の会話。複製どうしで共有する
+    coach: CoachConversation,
 }
 
 impl ApiClient {
-    pub fn new(api_key: String) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(API_TIMEOUT_SECS))
-            .build()
-            .unwrap_or_else(|_| reqwest::Client::new());
+    pub fn new(api_key: String) -> Result<Self, AppError> {
+        Self::build(Provider::Groq, API_BASE_URL, api_key, API_TIMEOUT_SECS)
+    }
+
+    /// API キーなしでローカルの Ollama サーバーと通信するクライアント
+    pub fn ollama() -> Result<Self, AppError> {
+        Self::build(
+            Provider::Ollama,
+            OLLAMA_BASE_URL,
+            String::new(),
+            OLLAMA_TIMEOUT_SECS,
+        )
+    }
+
+    fn build(
+        provider: Provider,
+        base_url: &str,
+        api_key: String,
+        timeout_secs: u64,
+    ) -> Result<Self, AppError> {
+        Ok(Self {
+            client: http_client(timeout_secs, CONNECT_TIMEOUT_SECS)?,
+            provider,
+            base_url: base_url.to_string(),
+            api_key,
+            model: None,
+            redactor: None,
+            audit_log: None,
+            evaluation_template: None,
+            strictness: Strictness::default(),
+            rubric: RubricConfig::default(),
+            retry_policy: RetryPolicy::default(),
+            retry_status: RetryStatus::default(),
+            coach: CoachConversation::default(),
+        })
+    }
+
+    pub fn with_base_url(mut self, base_url: Option<&str>) -> Self {
+        if let Some(base_url) = base_url {
+            self.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        self
+    }
+
+    pub fn with_model(mut self, model: Option<String>) -> Self {
+        self.model = model;
+        self
+    }
+
+    pub fn set_model(&mut self, model: String) {
+        self.model = Some(model);
+    }
+
+    /// 設定やモデル選択画面で選んだモデル。未指定なら提供元の既定モデル
+    pub fn current_model(&self) -> &str {
+        self.model.as_deref().unwrap_or(self.default_model())
+    }
+
+    pub fn with_audit_log(mut self, audit_log: Option<AuditLog>) -> Self {
+        self.audit_log = audit_log;
+        self
+    }
 
-        Self { client, api_key }
+    /// 監査ログに書き込めなかったことがあれば、その理由を一度だけ返す
+    pub fn take_audit_failure(&self) -> Option<String> {
+        self.audit_log.as_ref()?.take_failure()
+    }
+
+    pub fn with_redactor(mut self, redactor: Option<Redactor>) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    pub fn with_evaluation_template(mut self, template: Option<String>) -> Self {
+        self.evaluation_template = template;
+        self
+    }
+
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    pub fn with_rubric(mut self, rubric: RubricConfig) -> Self {
+        self.rubric = rubric;
+        self
+    }
+
+    /// `[network]` の待ち時間で HTTP クライアントを作り直す。書かなかった項目は提供元の既定値
+    pub fn with_timeouts(mut self, network: &NetworkConfig) -> Result<Self, AppError> {
+        let default_timeout = match self.provider {
+            Provider::Groq => API_TIMEOUT_SECS,
+            Provider::Ollama => OLLAMA_TIMEOUT_SECS,
+        };
+        self.client = http_client(
+            network.timeout_secs.unwrap_or(default_timeout),
+            network.connect_timeout_secs.unwrap_or(CONNECT_TIMEOUT_SECS),
+        )?;
+        Ok(self)
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// 再送を待っているリクエストがあれば、その状況
+    pub fn retry_notice(&self) -> Option<RetryNotice> {
+        self.retry_status.current()
+    }
+
+    /// 利用上限やサーバーエラーなど一時的なエラーなら、待ち時間を倍々に延ばしながら送り直す
+    async fn with_retry<T, F, Fut>(&self, mut request: F) -> Result<T, AppError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let result = request().await;
+            let reason = match &result {
+                Err(e) if attempt < self.retry_policy.max_attempts => retry::retry_reason(e),
+                _ => None,
+            };
+            let Some(reason) = reason else {
+                self.retry_status.set(None);
+                return result;
+            };
+            let wait = self
+                .retry_policy
+                .delay(attempt, self.retry_policy.jitter_ratio());
+            attempt += 1;
+            self.retry_status.set(Some(RetryNotice {
+                attempt,
+                max_attempts: self.retry_policy.max_attempts,
+                wait,
+                reason,
+            }));
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn redact(&self, text: &str) -> String {
+        match &self.redactor {
+            Some(redactor) => redactor.redact(text),
+            None => text.to_string(),
+        }
+    }
+
+    fn default_model(&self) -> &'static str {
+        match self.provider {
+            Provider::Groq => CHAT_MODEL,
+            Provider::Ollama => OLLAMA_CHAT_MODEL,
+        }
     }
 
     pub async fn validate_credentials(&self) -> Result<(), AppError> {
-        let url = format!("{API_BASE_URL}{MODELS_ENDPOINT}");
+        self.with_retry(|| self.check_credentials()).await
+    }
+
+    async fn check_credentials(&self) -> Result<(), AppError> {
+        if self.provider == Provider::Ollama {
+            return self.check_ollama_server().await;
+        }
+
+        let url = format!("{}{MODELS_ENDPOINT}", self.base_url);
         let response = self
             .client
             .get(&url)
             .bearer_auth(&self.api_key)
             .send()
             .await?;
+        // 利用上限やサーバーエラーはキーの誤りとせず、再送に回す
+        check_status(response)?;
+        Ok(())
+    }
 
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            Err(AppError::InvalidApiKey)
+    pub async fn list_models(&self) -> Result<Vec<String>, AppError> {
+        self.with_retry(|| self.fetch_models()).await
+    }
+
+    async fn fetch_models(&self) -> Result<Vec<String>, AppError> {
+        let mut models: Vec<String> = match self.provider {
+            Provider::Groq => {
+                let url = format!("{}{MODELS_ENDPOINT}", self.base_url);
+                let response: ModelsResponse = self
+                    .client
+                    .get(&url)
+                    .bearer_auth(&self.api_key)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                response.data.into_iter().map(|model| model.id).collect()
+            }
+            Provider::Ollama => {
+                let url = format!("{}{OLLAMA_TAGS_ENDPOINT}", self.base_url);
+                let response: OllamaTagsResponse = self
+                    .client
+                    .get(&url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                response
+                    .models
+                    .into_iter()
+                    .map(|model| model.name)
+                    .collect()
+            }
+        };
+        models.sort();
+        Ok(models)
+    }
+
+    async fn check_ollama_server(&self) -> Result<(), AppError> {
+        let url = format!("{}{OLLAMA_TAGS_ENDPOINT}", self.base_url);
+        match self.client.get(&url).send().await {
+            Ok(response) if response.status().is_success() => Ok(()),
+            _ => Err(AppError::BackendUnavailable(self.base_url.clone())),
         }
     }
 
-    async fn send_chat_request(&self, prompt: &str) -> Result<String, AppError> {
-        let url = format!("{API_BASE_URL}{CHAT_COMPLETIONS_ENDPOINT}");
-        let messages = vec![ChatMessage {
+    async fn send_chat_request(
+        &self,
+        model: &str,
+        prompt: &str,
+        format: OutputFormat,
+    ) -> Result<String, AppError> {
+        let messages = [ChatMessage {
             role: "user",
             content: prompt,
         }];
+        self.send_chat_messages(model, &messages, format).await
+    }
+
+    /// 会話の発言をまとめて送る。監査ログには発言を区切り線でつないで 1 つのプロンプトとして残す
+    async fn send_chat_messages(
+        &self,
+        model: &str,
+        messages: &[ChatMessage<'_>],
+        format: OutputFormat,
+    ) -> Result<String, AppError> {
+        let result = self
+            .with_retry(|| self.send_chat_request_unaudited(model, messages, format))
+            .await;
+
+        if let Some(audit_log) = &self.audit_log {
+            let (response, error) = match &result {
+                Ok(content) => (Some(content.as_str()), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            let prompt = messages
+                .iter()
+                .map(|message| message.content)
+                .collect::<Vec<_>>()
+                .join(AUDIT_MESSAGE_SEPARATOR);
+            let entry = AuditEntry {
+                timestamp: Local::now(),
+                model,
+                prompt: &prompt,
+                response,
+                error,
+            };
+            if let Err(e) = audit_log.record(&entry) {
+                audit_log.report_failure(&e);
+            }
+        }
+
+        result
+    }
+
+    async fn send_chat_request_unaudited(
+        &self,
+        model: &str,
+        messages: &[ChatMessage<'_>],
+        format: OutputFormat,
+    ) -> Result<String, AppError> {
+        if self.provider == Provider::Ollama {
+            return self.send_ollama_chat_request(model, messages, format).await;
+        }
+
+        let url = format!("{}{CHAT_COMPLETIONS_ENDPOINT}", self.base_url);
         let request_body = ChatRequest {
-            model: CHAT_MODEL,
+            model,
             messages,
+            response_format: (format == OutputFormat::Json).then_some(ResponseFormat {
+                kind: "json_object",
+            }),
         };
 
         let response = self
@@ -84,13 +432,7 @@ impl ApiClient {
             .json(&request_body)
             .send()
             .await?;
-
-        if !response.status().is_success() {
-            let Err(err) = response.error_for_status() else {
-                unreachable!("response status was already checked as unsuccessful");
-            };
-            return Err(AppError::ApiError(err));
-        }
+        let response = check_status(response)?;
 
         let chat_response: ChatResponse = response.json().await?;
 
@@ -101,16 +443,285 @@ impl ApiClient {
         }
     }
 
+    async fn send_ollama_chat_request(
+        &self,
+        model: &str,
+        messages: &[ChatMessage<'_>],
+        format: OutputFormat,
+    ) -> Result<String, AppError> {
+        let url = format!("{}{OLLAMA_CHAT_ENDPOINT}", self.base_url);
+        let request_body = OllamaChatRequest {
+            model,
+            messages,
+            stream: false,
+            format: (format == OutputFormat::Json).then_some("json"),
+        };
+
+        let response = self.client.post(&url).json(&request_body).send().await?;
+        let response = check_status(response)?;
+
+        let chat_response: OllamaChatResponse = response.json().await?;
+        Ok(chat_response.message.content.unwrap_or_default())
+    }
+
     pub async fn generate_text(&self, prompt: &str) -> Result<String, AppError> {
-        self.send_chat_request(prompt).await
+        self.send_chat_request(self.current_model(), prompt, OutputFormat::Text)
+            .await
+    }
+
+    /// 本文と 4 択問題を JSON で生成する
+    pub async fn generate_quiz(&self, prompt: &str) -> Result<String, AppError> {
+        self.send_chat_request(self.current_model(), prompt, OutputFormat::Json)
+            .await
+    }
+
+    /// 原文の難しい用語とその解説を JSON で生成する
+    pub async fn generate_glossary(&self, text: &str) -> Result<String, AppError> {
+        let prompt = glossary::build_glossary_prompt(&self.redact(text));
+        self.send_chat_request(self.current_model(), &prompt, OutputFormat::Json)
+            .await
+    }
+
+    /// 原文の漢字に読み仮名を付けた文章を生成する
+    pub async fn generate_furigana(&self, text: &str) -> Result<String, AppError> {
+        let prompt = furigana::build_furigana_prompt(&self.redact(text));
+        self.send_chat_request(self.current_model(), &prompt, OutputFormat::Text)
+            .await
+    }
+
+    /// 原文の語の読みと意味を、語を含む一文を手がかりに JSON で生成する
+    pub async fn lookup_word(&self, word: &str, sentence: &str) -> Result<String, AppError> {
+        let prompt = word_lookup::build_lookup_prompt(word, &self.redact(sentence));
+        self.send_chat_request(self.current_model(), &prompt, OutputFormat::Json)
+            .await
+    }
+
+    /// 評価への異議を、原文・要約・評価とこれまでのやり取りを添えて送り、返事と考え直した合否を JSON で受け取る
+    pub async fn appeal_evaluation(
+        &self,
+        record: &SessionRecord,
+        exchanges: &[Exchange],
+        argument: &str,
+    ) -> Result<String, AppError> {
+        let prompt = appeal::build_appeal_prompt(
+            &self.redact(&record.original_text),
+            &self.redact(&record.summary),
+            &record.evaluation_text,
+            exchanges,
+            &self.redact(argument),
+        );
+        self.send_chat_request(self.current_model(), &prompt, OutputFormat::Json)
+            .await
+    }
+
+    /// 段階練習の 1 つの段落の要約を講評させ、要点を押さえているかを JSON で受け取る
+    pub async fn review_paragraph(
+        &self,
+        paragraph: &str,
+        summary: &str,
+    ) -> Result<String, AppError> {
+        let prompt = guided::build_review_prompt(&self.redact(paragraph), &self.redact(summary));
+        self.send_chat_request(self.current_model(), &prompt, OutputFormat::Json)
+            .await
+    }
+
+    /// `record` の原文・要約・評価を最初の指示にして、コーチとの会話を始め直す
+    pub fn start_coaching(&self, record: &SessionRecord) {
+        self.coach.restart(coach::build_coach_context(
+            &self.redact(&record.original_text),
+            &self.redact(&record.summary),
+            &record.evaluation_text,
+        ));
+    }
+
+    /// これまでの会話に質問を加えて送る。返事を受け取れなかった質問は会話から取り除く
+    pub async fn ask_coach(&self, question: &str) -> Result<String, AppError> {
+        self.coach.push(CoachRole::User, self.redact(question));
+        let history = self.coach.messages();
+        let messages: Vec<ChatMessage> = history
+            .iter()
+            .map(|message| ChatMessage {
+                role: message.role.api_name(),
+                content: &message.content,
+            })
+            .collect();
+        let result = self
+            .send_chat_messages(self.current_model(), &messages, OutputFormat::Text)
+            .await;
+        match &result {
+            Ok(reply) => self.coach.push(CoachRole::Assistant, reply.clone()),
+            Err(_) => self.coach.pop_question(),
+        }
+        result
+    }
+
+    /// 画面に表示する質問と返事
+    pub fn coach_transcript(&self) -> Vec<CoachMessage> {
+        self.coach.transcript()
     }
 
+    /// 統合要約モードの 2 つの資料を JSON で生成する
+    pub async fn generate_synthesis_sources(&self, prompt: &str) -> Result<String, AppError> {
+        self.send_chat_request(self.current_model(), prompt, OutputFormat::Json)
+            .await
+    }
+
+    /// 2 つの資料をまとめた要約を、資料ごとの反映度も含めて評価する。`prompts.toml` の評価プロンプトは使わない
+    pub async fn evaluate_synthesis(
+        &self,
+        original_text: &str,
+        summary_text: &str,
+    ) -> Result<String, AppError> {
+        let prompt_content = synthesis::build_synthesis_evaluation_prompt(
+            &self.redact(original_text),
+            &self.redact(summary_text),
+        );
+        self.send_chat_request(self.current_model(), &prompt_content, OutputFormat::Json)
+            .await
+    }
+
+    /// `length` があれば、要約がその文字数に収まっているかも評価させる
     pub async fn evaluate_summary(
         &self,
         original_text: &str,
         summary_text: &str,
+        length: Option<LengthTarget>,
+    ) -> Result<String, AppError> {
+        self.evaluate_summary_with(None, None, original_text, summary_text, length)
+            .await
+    }
+
+    /// モデルと評価プロンプトのテンプレートを差し替えて評価する。`None` は既定値
+    /// (テンプレートは `prompts.toml` にあればそれ、なければ組み込みのもの) を使う
+    pub async fn evaluate_summary_with(
+        &self,
+        model: Option<&str>,
+        template: Option<&str>,
+        original_text: &str,
+        summary_text: &str,
+        length: Option<LengthTarget>,
     ) -> Result<String, AppError> {
-        let prompt_content = build_evaluation_prompt(original_text, summary_text);
-        self.send_chat_request(&prompt_content).await
+        let original_text = self.redact(original_text);
+        let summary_text = self.redact(summary_text);
+        // カスタムテンプレートは行形式を求めている場合があるので JSON モードを強制しない
+        let (mut prompt_content, format) = match template.or(self.evaluation_template.as_deref()) {
+            Some(template) => (
+                fill_evaluation_template(template, &original_text, &summary_text),
+                OutputFormat::Text,
+            ),
+            None => (
+                build_evaluation_prompt(&original_text, &summary_text)
+                    + &rubric::evaluation_rule(&self.rubric),
+                OutputFormat::Json,
+            ),
+        };
+        if let Some(length) = length {
+            prompt_content.push_str(&length.evaluation_rule(&summary_text));
+        }
+        prompt_content.push_str(self.strictness.evaluation_rule());
+        self.send_chat_request(
+            model.unwrap_or(self.current_model()),
+            &prompt_content,
+            format,
+        )
+        .await
+    }
+}
+
+/// 応答全体と接続それぞれに待ち時間の上限を付けた HTTP クライアント
+fn http_client(timeout_secs: u64, connect_timeout_secs: u64) -> Result<reqwest::Client, AppError> {
+    Ok(reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_secs))
+        .connect_timeout(std::time::Duration::from_secs(connect_timeout_secs))
+        .build()?)
+}
+
+/// 認証エラーと利用上限は専用のエラーにして、対処方法を案内できるようにする
+fn check_status(response: reqwest::Response) -> Result<reqwest::Response, AppError> {
+    match response.status() {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(AppError::InvalidApiKey),
+        StatusCode::TOO_MANY_REQUESTS => Err(AppError::RateLimited),
+        _ => Ok(response.error_for_status()?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RetryConfig;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 受けたリクエストに `statuses` の順で応答するサーバーを立て、その URL と応答した回数を返す
+    async fn serve_statuses(statuses: Vec<u16>) -> Result<(String, Arc<AtomicUsize>), String> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| e.to_string())?;
+        let url = format!(
+            "http://{}",
+            listener.local_addr().map_err(|e| e.to_string())?
+        );
+        let served = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&served);
+        tokio::spawn(async move {
+            for status in statuses {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut request = [0; 4096];
+                let _ = stream.read(&mut request).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                let body = r#"{"data": []}"#;
+                let response = format!(
+                    "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        Ok((url, served))
+    }
+
+    fn client(url: &str) -> Result<ApiClient, String> {
+        let policy = RetryPolicy::from_config(&RetryConfig {
+            max_attempts: Some(3),
+            base_delay_ms: Some(1),
+            max_delay_ms: Some(1),
+            jitter: false,
+        });
+        Ok(ApiClient::new("test-key".to_string())
+            .map_err(|e| e.to_string())?
+            .with_base_url(Some(url))
+            .with_retry_policy(policy))
+    }
+
+    #[tokio::test]
+    async fn rate_limited_credential_check_is_retried() -> Result<(), String> {
+        let (url, served) = serve_statuses(vec![429, 200]).await?;
+        let result = client(&url)?.validate_credentials().await;
+        if let Err(e) = result {
+            return Err(format!("429 should be retried, got {e:?}"));
+        }
+        let count = served.load(Ordering::SeqCst);
+        if count != 2 {
+            return Err(format!("expected 2 requests, got {count}"));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejected_key_is_reported_without_retry() -> Result<(), String> {
+        let (url, served) = serve_statuses(vec![401, 200]).await?;
+        let result = client(&url)?.validate_credentials().await;
+        if !matches!(result, Err(AppError::InvalidApiKey)) {
+            return Err(format!("401 should be an invalid key, got {result:?}"));
+        }
+        let count = served.load(Ordering::SeqCst);
+        if count != 1 {
+            return Err(format!("expected 1 request, got {count}"));
+        }
+        Ok(())
     }
 }
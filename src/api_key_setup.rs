@@ -0,0 +1,120 @@
+use rat_text::text_input::TextInputState;
+
+/// Groq の API キーの接頭辞。`base_url` で別のサーバーを使うときは確かめない
+const GROQ_KEY_PREFIX: &str = "gsk_";
+/// 貼り付けたキーを見分けられるよう、末尾だけ伏せずに表示する文字数
+const VISIBLE_SUFFIX_CHARS: usize = 4;
+
+/// 入力中のキーの形式を確かめた結果。入力のたびに画面に表示する
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeyFormat {
+    Empty,
+    /// 空白や改行を含む。貼り付けの失敗とみなして送らない
+    ContainsWhitespace,
+    /// Groq のキーの形ではないが、送って確かめることはできる
+    UnexpectedPrefix,
+    Plausible,
+}
+
+impl KeyFormat {
+    pub fn check(key: &str, expects_groq_key: bool) -> Self {
+        let key = key.trim();
+        if key.is_empty() {
+            Self::Empty
+        } else if key.chars().any(char::is_whitespace) {
+            Self::ContainsWhitespace
+        } else if expects_groq_key && !key.starts_with(GROQ_KEY_PREFIX) {
+            Self::UnexpectedPrefix
+        } else {
+            Self::Plausible
+        }
+    }
+
+    /// API に送って確かめてよいか
+    pub fn can_submit(&self) -> bool {
+        matches!(self, Self::UnexpectedPrefix | Self::Plausible)
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::Empty => "API キーを貼り付けてください。",
+            Self::ContainsWhitespace => {
+                "空白や改行が含まれています。キーだけを貼り付けてください。"
+            }
+            Self::UnexpectedPrefix => {
+                "Groq の API キーは gsk_ で始まります。このまま確かめることもできます。"
+            }
+            Self::Plausible => "Enter で API に接続して確かめ、設定に保存します。",
+        }
+    }
+}
+
+/// API キーを入力して確かめ、`config.toml` に保存する画面の状態
+pub struct ApiKeySetup {
+    pub input: TextInputState,
+    /// API に送って確かめている間は入力を受け付けない
+    pub checking: bool,
+    /// 直前に確かめたときの失敗の理由
+    pub error: Option<String>,
+}
+
+impl ApiKeySetup {
+    /// 設定済みのキーがあれば、書き換えられるよう入力欄に入れておく
+    pub fn new(current_key: Option<&str>) -> Self {
+        let mut input = TextInputState::new();
+        if let Some(key) = current_key {
+            input.set_text(key);
+        }
+        input.focus.set(true);
+        Self {
+            input,
+            checking: false,
+            error: None,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        self.input.text().trim()
+    }
+
+    /// 入力した文字数と、伏せずに表示する末尾
+    pub fn describe_key(&self) -> Option<(usize, String)> {
+        let key = self.key();
+        let count = key.chars().count();
+        if count <= VISIBLE_SUFFIX_CHARS {
+            return None;
+        }
+        let suffix = key.chars().skip(count - VISIBLE_SUFFIX_CHARS).collect();
+        Some((count, suffix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_format_is_checked_while_typing() {
+        assert_eq!(KeyFormat::check("  ", true), KeyFormat::Empty);
+        assert_eq!(
+            KeyFormat::check("gsk_abc def", true),
+            KeyFormat::ContainsWhitespace
+        );
+        assert_eq!(
+            KeyFormat::check("sk-abc", true),
+            KeyFormat::UnexpectedPrefix
+        );
+        assert_eq!(KeyFormat::check("sk-abc", false), KeyFormat::Plausible);
+        assert_eq!(KeyFormat::check(" gsk_abc\n", true), KeyFormat::Plausible);
+        assert!(!KeyFormat::Empty.can_submit());
+        assert!(KeyFormat::UnexpectedPrefix.can_submit());
+    }
+
+    #[test]
+    fn only_the_end_of_the_key_is_shown() {
+        let setup = ApiKeySetup::new(Some("gsk_0123456789wxyz"));
+        assert_eq!(setup.describe_key(), Some((18, "wxyz".to_string())));
+        assert_eq!(ApiKeySetup::new(Some("gsk")).describe_key(), None);
+        assert_eq!(ApiKeySetup::new(None).key(), "");
+    }
+}
@@ -1,115 +1,689 @@
+use crate::abbreviations;
 use crate::api_client::ApiClient;
+use crate::api_key_setup::{ApiKeySetup, KeyFormat};
+use crate::appeal::{Appeal, Exchange};
+use crate::challenge::Challenge;
+use crate::cli::TrainOptions;
+use crate::clipboard::{Clipboard, CopyMethod};
+use crate::coach::CoachChat;
+use crate::config::{self, ApiProfile, Config, DEFAULT_PROFILE_NAME, LabelLanguage, Provider};
+use crate::daily_challenge::DailyChallenge;
+use crate::draft::{DraftAutosaver, SessionDraft};
+use crate::error::{AppError, ErrorCategory, ErrorNotice};
+use crate::evaluation::Verdict;
+use crate::exam::{self, ExamRound, ExamSession};
+use crate::furigana::{self, Annotation, Furigana};
+use crate::glossary::{self, Glossary};
+use crate::guided::{self, GuidedSession};
+use crate::help::{self, HelpHeading, HelpSearch};
+use crate::history::{self, HistoryBrowser, SessionRecord};
+use crate::hot_seat::HotSeat;
+use crate::i18n::{tr, trf};
+use crate::journal;
+use crate::kana_input::KanaInput;
+use crate::keybindings::KeyBindings;
+use crate::keyring;
+use crate::keyword_prediction::{KeywordPrediction, KeywordScore};
+use crate::levels::{self, LevelRound};
+use crate::library::{self, Library};
+use crate::models::{BreakRecord, Difficulty, EvaluationScores, TextStyle, Weakness};
+use crate::offline::OfflineCorpus;
+use crate::pane_layout::{PaneAreas, PaneLayout, ZenMode};
+use crate::pending::PendingQueue;
+use crate::prefetch::{PrefetchKey, Prefetcher};
+use crate::prompts::{self, PromptTemplates};
+use crate::quiz::{self, Quiz, QuizSession};
+use crate::read_aloud::{self, ReadAloudSession};
+use crate::reports::ReportTab;
+use crate::result_tabs::{ResultTab, ResultTabs};
+use crate::retry::RetryNotice;
+use crate::review::{self, ReviewQueue, ReviewSession};
+use crate::score_normalization::ScoreScale;
+use crate::scratchpad::Scratchpad;
+use crate::side_request::SideRequest;
+use crate::speed_reading::{self, SpeedReadingSession};
 use crate::stats::TrainingStats;
+use crate::stats_sync::StatsSaver;
+use crate::strictness::Strictness;
+use crate::summary_length::{LengthGoal, LengthTarget};
+use crate::summary_metrics::SummaryMetrics;
+use crate::synthesis::{self, SynthesisSession};
+use crate::text_cache::{self, CacheKey, TextCache};
+use crate::text_import::{self, FileBrowser, TextSource};
+use crate::theme::{Theme, ThemeName};
+use crate::tutorial::{self, TutorialStep};
+use crate::verbatim;
+use crate::word_lookup::{self, Dictionary, LookupState, VocabList, WordSelection};
+use chrono::{DateTime, Local};
 use rand::RngExt;
 use rat_text::text_area::{TextAreaState, TextWrap};
 use ratatui::layout::Rect;
+use std::time::{Duration, Instant};
 
 #[derive(PartialEq, Clone, Copy)]
 pub enum ViewMode {
     Menu,
     Normal,
     Report,
+    /// レポートから開く、獲得できるすべてのバッジの一覧
+    BadgeGallery,
     Help,
+    ModelSelect,
+    ReadAloud,
+    /// 原文を一定のペースで少しずつ表示する速読練習
+    SpeedReading,
+    FileBrowser,
+    History,
+    ApiKeySetup,
+    ProfileSelect,
+    /// 評価について質問するチャット
+    Coach,
 }
 
+/// 通常画面の上に重ねて表示し、キー入力を占有する小さな確認ダイアログ
+#[derive(PartialEq, Clone, Copy)]
+pub enum Popup {
+    SlowEvaluation,
+    SelfGrade,
+    Break {
+        started_at: Instant,
+    },
+    /// `App::last_error` の内容を表示する
+    Error,
+    /// 評価の失敗を `App::last_error` で示し、自己採点で記録するかを尋ねる
+    EvaluationFailed,
+    /// `App::word_lookup` の内容を表示する
+    WordLookup,
+    /// `App::appeal` の内容を表示する
+    Appeal,
+    /// `App::keyword_prediction` の冒頭の段落と予想の入力欄を表示する
+    KeywordPrediction,
+    /// 新しい段位に上がったことを祝う
+    RankUp {
+        rank: usize,
+    },
+}
+
+/// 提出前のセッションの一時停止。止めていた時間は読み書きの時間に数えない
+#[derive(Default)]
+pub struct SessionPause {
+    /// 一時停止した時刻。一時停止中だけ `Some`
+    pub since: Option<DateTime<Local>>,
+    /// 出題してから一時停止していた時間の合計
+    pub total: chrono::Duration,
+}
+
+/// 出題の進み具合。原文欄の文字列からは判定しない
+#[derive(PartialEq, Clone, Copy, Default, Debug)]
+pub enum TrainingState {
+    #[default]
+    NotStarted,
+    /// 文章の生成を始めた時刻からの応答待ち
+    Generating(Instant),
+    Ready,
+    GenerationFailed,
+}
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const SPINNER_FRAME_MILLIS: u128 = 100;
+
 pub const MENU_OPTIONS: [u16; 4] = [400, 720, 1440, 2880];
 pub const OVERLAY_SIZE_PERCENT: u16 = 75;
 pub const TEXT_WRAP_MARGIN: u16 = 2;
 pub const OVERLAY_MARGIN: u16 = 2;
 pub const MIN_OVERLAY_WIDTH: u16 = 40;
 pub const MIN_OVERLAY_HEIGHT: u16 = 10;
+pub const RESULT_TAB_BAR_HEIGHT: u16 = 1;
+pub const VERDICT_BANNER_HEIGHT: u16 = 1;
 const HEADER_HEIGHT: u16 = 1;
 const STATUS_HEIGHT: u16 = 3;
+const ERROR_LINE_HEIGHT: u16 = 1;
+/// 集中モードで画面の下に出す操作の案内の高さ
+pub const ZEN_HINT_HEIGHT: u16 = 1;
 const BLOCK_BORDER_SIZE: u16 = 2;
+/// 原文欄の下の用語解説欄の高さ (用語ごとに 1 行と枠)
+pub const GLOSSARY_FOOTER_HEIGHT: u16 = glossary::GLOSSARY_TERM_COUNT + BLOCK_BORDER_SIZE;
 
 pub const INITIAL_ORIGINAL_TEXT: &str = "認証しています...";
-pub const GENERATION_ERROR_PREFIX: &str = "文章生成に失敗しました: ";
+/// 評価結果を開いたときに最初に見せる行 (合否)
+const VERDICT_MARKER: &str = "総合評価";
+/// `i` で移動する改善点の見出し
+const IMPROVEMENTS_MARKER: &str = "改善点";
 pub const STATUS_MENU: &str = "文字数を選び、開始してください。";
 pub const STATUS_NORMAL: &str = "通常モードです。'i' で入力します。";
+pub const STATUS_PAUSED: &str = "一時停止中です。'P' で再開します。";
 pub const STATUS_EDITING: &str = "入力モードです。Esc で戻ります。";
-pub const STATUS_REPORT: &str = "レポート表示中です。'r' で閉じます。";
-pub const STATUS_HELP: &str = "ヘルプ表示中です。'h' で閉じます。";
-pub const STATUS_GENERATING: &str = "文章を生成しています...";
-pub const STATUS_NEXT_GENERATING: &str = "次の文章を生成しています...";
-pub const STATUS_EVALUATING: &str = "要約を評価しています...";
+pub const STATUS_SCRATCHPAD: &str =
+    "メモを入力しています。Esc で戻ります。メモは評価に送られません。";
+pub const STATUS_REPORT: &str = "レポート表示中です。'Tab' か '1'〜'5' でタブを、'n' で素点とモデル補正を切り替え、'j'/'k' でスクロール、'b' でバッジ一覧、'r' で閉じます。";
+pub const STATUS_BADGE_GALLERY: &str =
+    "バッジ一覧を表示中です。'j'/'k' でスクロール、'b' でレポートに戻り、'r' で閉じます。";
+pub const STATUS_HELP: &str = "ヘルプ表示中です。'/' で検索、't' で目次、'h' で閉じます。";
+pub const STATUS_GENERATING: &str = "文章を生成しています... (Esc: キャンセル)";
+pub const STATUS_NEXT_GENERATING: &str = "次の文章を生成しています... (Esc: キャンセル)";
+pub const STATUS_EVALUATING: &str = "要約を評価しています... (Esc: キャンセル)";
 pub const STATUS_EVALUATED: &str = "評価が完了しました。'e' で切替、'n' で次へ進みます。";
 pub const STATUS_INVALID_EVALUATION: &str = "評価結果の形式が不正です。";
-pub const STATUS_RUNTIME_ERROR: &str = "エラーが発生しました。";
+pub const STATUS_RUNTIME_ERROR: &str = "エラーが発生しました。Enter で閉じます。";
+pub const STATUS_DEFERRED: &str =
+    "文章を後回しにしました。次の文章を生成しています... (Esc: キャンセル)";
+pub const STATUS_NO_REVIEWS: &str = "今日復習する文章はありません。";
+pub const STATUS_REVIEWS_FINISHED: &str = "今日の復習は終わりました。新しい文章を生成します。";
+pub const STATUS_PENDING_RESUMED: &str = "未使用テキストから出題しました。'i' で入力します。";
+pub const STATUS_SLOW_EVALUATION: &str =
+    "評価に時間がかかっています。w: 待つ / c: キャンセル / s: 自己採点";
+pub const STATUS_GENERATION_CANCELLED: &str = "文章の生成をキャンセルしました。";
+pub const STATUS_EVALUATION_CANCELLED: &str = "評価をキャンセルしました。Ctrl+S で再送信できます。";
+pub const STATUS_EVALUATION_FAILED: &str =
+    "評価に失敗しました。y/n で自己採点して記録するか、Esc で閉じてください。";
+pub const STATUS_EVALUATION_RETRYABLE: &str =
+    "要約はそのまま残っています。Ctrl+S で再送信できます。";
+pub const STATUS_SELF_GRADE: &str = "自己採点: y で合格、n で不合格、Esc で戻ります。";
+pub const STATUS_SELF_GRADED: &str = "自己採点を記録しました。'n' で次へ進みます。";
+pub const STATUS_QUIZ: &str = "↑/↓ or j/k で選択、Enter で解答します。";
+pub const STATUS_LIBRARY_QUIZ: &str =
+    "ライブラリに残した問題から出題しました。↑/↓ or j/k で選択、Enter で解答します。";
+pub const STATUS_SYNTHESIS: &str =
+    "2 つの資料を読み、両方の内容を 1 つの要約にまとめてください。'i' で入力します。";
+pub const STATUS_QUIZ_GRADED: &str = "採点しました。'n' で次へ進みます。";
+pub const STATUS_EXAM_NEXT: &str = "提出しました。'n' で次の問題へ進みます。";
+pub const STATUS_EXAM_FINISHED: &str = "模擬試験が終わりました。'n' で通常の練習に戻ります。";
+pub const STATUS_TIME_UP: &str = "時間切れのため不合格として記録しました。'n' で次へ進みます。";
+pub const STATUS_READ_ALOUD: &str =
+    "声に出して読み、段落を読み終えたら Space を押します。Esc で戻ります。";
+pub const STATUS_READ_ALOUD_FINISHED: &str = "音読が終わりました。Esc で戻ります。";
+pub const STATUS_SPEED_READING: &str =
+    "表示された部分だけを読みます。Space: 一時停止, +/-: ペース, Esc: 戻る";
+pub const STATUS_SPEED_READING_FINISHED: &str =
+    "最後まで表示しました。Esc で戻って要約を書きます。";
+pub const STATUS_CHALLENGE: &str = "チャレンジに挑戦します。'i' で入力します。";
+pub const STATUS_PROMOTION_EXAM: &str = "昇級試験です。'i' で入力します。";
+pub const STATUS_LOADING_MODELS: &str = "モデル一覧を取得しています...";
+pub const STATUS_MODEL_SELECT: &str = "j/k で選択、Enter で決定、Esc で戻ります。";
+pub const STATUS_KANA_INPUT: &str =
+    "かな入力モードです。Space で変換、Enter で確定、Ctrl+J で切り替えます。";
+pub const STATUS_ZEN_ON: &str =
+    "集中モードです。入力中は要約欄だけを表示し、Ctrl+P で原文をのぞけます。";
+pub const STATUS_ZEN_OFF: &str = "集中モードを終了しました。";
+pub const STATUS_FILE_BROWSER: &str =
+    "j/k で選択、Enter で開く、Backspace で上の階層、Esc で戻ります。";
+pub const STATUS_IMPORTED: &str = "読み込んだ文章で練習します。'i' で入力します。";
+pub const STATUS_WORD_SELECTION: &str = "←/→ or h/l で語を選び、d で調べます。Esc で戻ります。";
+pub const STATUS_TUTORIAL: &str = "チュートリアルです。画面の案内に沿って進めてください。";
+pub const STATUS_LIBRARY: &str = "ライブラリの文章で練習します。'i' で入力します。";
+pub const STATUS_HISTORY: &str = "j/k で選択、Enter で開く、R で再挑戦、Esc で戻ります。";
+pub const STATUS_HISTORY_DETAIL: &str =
+    "過去のセッションを表示しています (読み取り専用)。R で再挑戦、Esc で一覧に戻ります。";
+pub const STATUS_FAILED_RETRY_HINT: &str =
+    "不合格でした。'R' で同じ文章に再挑戦、'n' で次へ進みます。";
+pub const STATUS_RETRY: &str = "同じ文章に再挑戦します。'i' で入力します。";
+pub const STATUS_FLASHBACK_RETRY: &str = "以前の文章に再挑戦します。'i' で入力します。";
+pub const STATUS_OFFLINE: &str =
+    "オフラインで起動しました。手元の文章から出題し、評価は自己採点で行います。";
+pub const STATUS_OFFLINE_TEXT: &str =
+    "手元の文章から出題しました。'i' で入力し、Ctrl+S で自己採点します。";
+pub const STATUS_OFFLINE_UNAVAILABLE: &str = "このモードはオフラインでは使えません。";
+pub const STATUS_HOT_SEAT_OFFLINE: &str =
+    "オフラインのため対戦の評価はできません。2人分の要約はそのまま残っています。";
+pub const STATUS_CACHED_TEXT: &str =
+    "API の応答が得られないため、キャッシュした未出題の文章から出題しました。'i' で入力します。";
+pub const STATUS_API_KEY_SETUP: &str =
+    "API キーを貼り付け、Enter で確かめて保存します。Esc で戻ります。";
+pub const STATUS_API_KEY_CHECKING: &str = "API キーを確かめています... (Esc: 中止)";
+pub const STATUS_API_KEY_SAVED: &str = "API キーを保存しました。文字数を選び、開始してください。";
+pub const STATUS_API_KEY_SAVED_TO_KEYRING: &str =
+    "API キーを OS のキーチェーンに保存しました。文字数を選び、開始してください。";
+pub const STATUS_NO_PROFILES: &str =
+    "config.toml に [[profiles]] を書くと、接続先を切り替えられます。";
+pub const STATUS_API_KEY_NOT_NEEDED: &str = "Ollama を使うときは API キーは要りません。";
+pub const STATUS_GUIDED_REVIEWING: &str = "段落の要約を講評しています...";
+pub const STATUS_GUIDED_NEXT: &str = "講評を原文欄に表示しました。次の段落を要約してください。";
+pub const STATUS_GUIDED_FINAL: &str =
+    "段落ごとの要約を入力欄に並べました。全体の要約にまとめて Ctrl+S で送信します。";
+pub const STATUS_COACH: &str =
+    "評価について質問できます。Enter で送信、↑/↓ でスクロール、Esc で戻ります。";
+pub const STATUS_DRAFT_RESTORED: &str =
+    "前回の書きかけの要約を復元しました。'i' で続きを入力します。";
 
 pub struct App {
     pub api_client: Option<ApiClient>,
+    /// オフラインで起動したときに出題する文章。`Some` の間は API を使わない
+    pub offline: Option<OfflineCorpus>,
     pub original_text: String,
     pub original_text_scroll: u16,
     pub evaluation_text: String,
     pub status_message: String,
     pub text_area_state: TextAreaState,
-    pub evaluation_overlay_scroll: u16,
+    pub result_tabs: ResultTabs,
     pub view_mode: ViewMode,
     pub stats: TrainingStats,
+    pub stats_saver: StatsSaver,
     pub character_count: u16,
     pub selected_menu_item: usize,
     pub help_scroll: u16,
+    pub help_search: HelpSearch,
+    /// 目次の表示中なら選択中の見出しの位置
+    pub help_toc: Option<usize>,
     pub should_quit: bool,
-    pub evaluation_passed: bool,
+    pub verdict: Verdict,
     pub show_evaluation_overlay: bool,
     pub terminal_width: u16,
     pub terminal_height: u16,
+    pub flashback: Option<SessionRecord>,
+    /// 前回のセッションで評価に出さないまま残った下書き。起動時のメニューで復元するか選ぶ
+    pub recovered_draft: Option<SessionDraft>,
+    pub draft_autosaver: DraftAutosaver,
+    pub comparison_base: Option<SessionRecord>,
+    pub pending: PendingQueue,
+    pub review_queue: ReviewQueue,
+    pub library: Library,
+    /// 復習モード中なら `Some`
+    pub review_session: Option<ReviewSession>,
+    pub session_started_at: DateTime<Local>,
+    pub config: Config,
+    /// `config.keys` から作った操作のキー。起動時に設定を検証してから差し替える
+    pub keys: KeyBindings,
+    pub prompts: PromptTemplates,
+    pub popup: Option<Popup>,
+    pub available_models: Vec<String>,
+    pub selected_model_item: usize,
+    /// 切り替えられる接続設定。先頭はトップレベルの設定
+    pub profiles: Vec<ApiProfile>,
+    pub active_profile: usize,
+    pub selected_profile_item: usize,
+    pub difficulty: Difficulty,
+    pub selected_difficulty: Difficulty,
+    pub level_round: Option<LevelRound>,
+    pub hot_seat: Option<HotSeat>,
+    pub evaluation_started_at: Option<Instant>,
+    /// 用語解説やコーチなど、評価以外の問い合わせを待ち始めた時刻
+    pub side_request_started_at: Option<Instant>,
+    pub challenge: Option<Challenge>,
+    pub last_session: Option<SessionRecord>,
+    pub read_aloud: Option<ReadAloudSession>,
+    pub speed_reading: Option<SpeedReadingSession>,
+    pub reading_since: Instant,
+    pub file_browser: Option<FileBrowser>,
+    pub api_key_setup: Option<ApiKeySetup>,
+    pub imported_from: Option<TextSource>,
+    pub kana_input: KanaInput,
+    pub scratchpad: Scratchpad,
+    pub glossary: Glossary,
+    pub furigana: Furigana,
+    /// 原文欄で語を選んでいる間のカーソル
+    pub word_selection: Option<WordSelection>,
+    pub word_lookup: Option<LookupState>,
+    /// 評価者への異議申し立てのダイアログを開いている間は `Some`
+    pub appeal: Option<Appeal>,
+    /// コーチ画面の入力欄と送信状態。画面を閉じても同じセッションの会話は続けられる
+    pub coach: Option<CoachChat>,
+    /// 本文を読む前のキーワード予想。予想しない設定や対象外のモードでは `None`
+    pub keyword_prediction: Option<KeywordPrediction>,
+    pub dictionary: Dictionary,
+    pub vocab: VocabList,
+    pub clipboard: Clipboard,
+    pub text_style: Option<TextStyle>,
+    /// `train --genre` で指定した文体。指定がなければ文章ごとに選ぶ
+    pub fixed_style: Option<TextStyle>,
+    pub text_shown_at: Option<DateTime<Local>>,
+    pub pause: SessionPause,
+    /// 要約を提出した時刻。出題時刻からここまでを読み書きにかかった時間とする
+    pub submitted_at: Option<DateTime<Local>>,
+    /// 制限時間付きモードの持ち時間。通常の練習では `None`
+    pub time_limit: Option<Duration>,
+    /// 4 択問題モードの解答状況。通常の練習では `None`
+    pub quiz: Option<QuizSession>,
+    /// 模擬試験の進み具合。通常の練習では `None`
+    pub exam: Option<ExamSession>,
+    /// 統合要約モードの資料。通常の練習では `None`
+    pub synthesis: Option<SynthesisSession>,
+    /// 段落ごとに要約してから全体をまとめる段階練習の進み具合。通常の練習では `None`
+    pub guided: Option<GuidedSession>,
+    /// 組み込みの文章と固定の評価で操作を覚えるチュートリアルの最中か
+    pub tutorial: bool,
+    /// 今日のお題に挑戦中なら、そのお題。通常の練習では `None`
+    pub daily_challenge: Option<DailyChallenge>,
+    /// レポートの評価スコアを素点で見るか、モデルごとに補正して見るか
+    pub score_scale: ScoreScale,
+    /// レポートで表示しているタブ
+    pub report_tab: ReportTab,
+    /// レポートのタブの内容を何行目から表示するか。描画時に内容の高さに合わせて丸める
+    pub report_scroll: u16,
+    /// バッジ一覧を何行目から表示するか。開くたびに先頭に戻す
+    pub badge_gallery_scroll: u16,
+    /// 原文欄と要約欄の幅の比率
+    pub pane_layout: PaneLayout,
+    pub zen: ZenMode,
+    pub theme: ThemeName,
+    /// メニューの `g` で選ぶ要約の長さの条件
+    pub length_goal: LengthGoal,
+    /// メニューの `x` で選ぶ評価の厳しさ
+    pub strictness: Strictness,
+    pub history_browser: Option<HistoryBrowser>,
+    pub training_state: TrainingState,
+    /// 直近のエラー。次に生成か評価が成功するまでエラー欄に表示する
+    pub last_error: Option<ErrorNotice>,
+    pub prefetcher: Prefetcher,
+    /// 生成した文章のキャッシュ。`--no-cache` を付けて起動したら `None`
+    pub text_cache: Option<TextCache>,
 }
 
 impl Default for App {
     fn default() -> Self {
         let stats = TrainingStats::load().unwrap_or_default();
-
-        let text_area_state = Self::new_text_area_state();
-
+        let config = config::load_config().unwrap_or_default();
+        let theme = stats.theme.unwrap_or(config.theme);
+        let pane_layout = PaneLayout::new(
+            stats.split_percent.unwrap_or(config.layout.split_percent),
+            stats.pane_orientation.unwrap_or(config.layout.orientation),
+        );
         Self {
             api_client: None,
+            offline: None,
             original_text: INITIAL_ORIGINAL_TEXT.to_string(),
             original_text_scroll: 0,
             evaluation_text: String::new(),
             status_message: STATUS_MENU.to_string(),
-            text_area_state,
-            evaluation_overlay_scroll: 0,
+            text_area_state: Self::new_text_area_state(),
+            result_tabs: ResultTabs::default(),
             view_mode: ViewMode::Menu,
+            length_goal: stats.summary_length.unwrap_or_default(),
+            strictness: stats.strictness.unwrap_or(config.strictness),
             stats,
+            stats_saver: StatsSaver::default(),
             character_count: 400,
             selected_menu_item: 0,
             help_scroll: 0,
+            help_search: HelpSearch::default(),
+            help_toc: None,
             should_quit: false,
-            evaluation_passed: false,
+            verdict: Verdict::default(),
             show_evaluation_overlay: false,
             terminal_width: 100,
             terminal_height: 30,
+            flashback: Self::todays_flashback(),
+            recovered_draft: SessionDraft::load().ok().flatten(),
+            draft_autosaver: DraftAutosaver::default(),
+            comparison_base: None,
+            pending: PendingQueue::load().unwrap_or_default(),
+            review_queue: ReviewQueue::load().unwrap_or_default(),
+            library: Library::load().unwrap_or_default(),
+            review_session: None,
+            session_started_at: Local::now(),
+            prefetcher: Prefetcher::new(config.prefetch.queue_size()),
+            profiles: config.api_profiles(),
+            config,
+            keys: KeyBindings::default(),
+            prompts: prompts::load_prompts(),
+            popup: None,
+            available_models: Vec::new(),
+            selected_model_item: 0,
+            active_profile: 0,
+            selected_profile_item: 0,
+            difficulty: Difficulty::default(),
+            selected_difficulty: Difficulty::default(),
+            level_round: None,
+            hot_seat: None,
+            evaluation_started_at: None,
+            side_request_started_at: None,
+            challenge: None,
+            last_session: None,
+            read_aloud: None,
+            speed_reading: None,
+            reading_since: Instant::now(),
+            file_browser: None,
+            api_key_setup: None,
+            imported_from: None,
+            kana_input: KanaInput::default(),
+            scratchpad: Scratchpad::default(),
+            glossary: Glossary::default(),
+            furigana: Furigana::default(),
+            word_selection: None,
+            word_lookup: None,
+            appeal: None,
+            coach: None,
+            keyword_prediction: None,
+            dictionary: Dictionary::default(),
+            vocab: VocabList::load().unwrap_or_default(),
+            clipboard: Clipboard::default(),
+            text_style: None,
+            fixed_style: None,
+            text_shown_at: None,
+            pause: SessionPause::default(),
+            submitted_at: None,
+            time_limit: None,
+            quiz: None,
+            exam: None,
+            synthesis: None,
+            guided: None,
+            tutorial: false,
+            daily_challenge: None,
+            score_scale: ScoreScale::default(),
+            report_tab: ReportTab::default(),
+            report_scroll: 0,
+            badge_gallery_scroll: 0,
+            pane_layout,
+            zen: ZenMode::Off,
+            theme,
+            history_browser: None,
+            training_state: TrainingState::default(),
+            last_error: None,
+            text_cache: (!text_cache::is_disabled()).then(|| TextCache::load().unwrap_or_default()),
         }
     }
 }
 
 impl App {
+    /// 起動時にメニューへ重ねて見せる、過去の同じ日付のセッション
+    fn todays_flashback() -> Option<SessionRecord> {
+        let records = history::load_records().ok()?;
+        history::find_flashback(&records, Local::now().date_naive()).cloned()
+    }
+
     pub fn new_text_area_state() -> TextAreaState {
         let mut state = TextAreaState::default();
         state.set_text_wrap(TextWrap::Word(TEXT_WRAP_MARGIN));
         state
     }
 
-    pub fn generate_text_prompt(&self) -> String {
-        let mut rng = rand::rng();
+    /// 文体を選んで生成プロンプトを作る。選んだ文体はパンくずリストに表示する。
+    /// `prompts.toml` に生成プロンプトがあれば、文体を選ばずにそれを使う
+    pub fn generate_text_prompt(&mut self) -> String {
+        let (prompt, style) = self.text_prompt();
+        self.text_style = style;
+        prompt
+    }
+
+    /// 文章を生成するプロンプトと、生成される文章の文体
+    fn text_prompt(&self) -> (String, Option<TextStyle>) {
+        if let Some(challenge) = self.daily_challenge {
+            return (challenge.prompt(), Some(challenge.style));
+        }
+        if let Some(prompt) = self.prompts.generation_prompt(self.character_count) {
+            return (prompt, None);
+        }
+
+        let style = self.fixed_style.unwrap_or_else(|| {
+            if rand::rng().random_bool(0.7) {
+                TextStyle::Official
+            } else {
+                TextStyle::Newspaper
+            }
+        });
+
+        let weakness_hint = self.weakness_focus().map_or("", Weakness::prompt_hint);
+
+        let prompt = format!(
+            "{}{}{}文字程度で生成してください。{}",
+            self.difficulty.prompt_hint(),
+            style.prompt(),
+            self.character_count,
+            weakness_hint
+        )
+        .repeat(2);
+        (prompt, Some(style))
+    }
+
+    /// 先読みした文章を使える出題なら、その条件。
+    /// 今日のお題・4 択問題・統合要約・チュートリアルとオフラインでは使わない
+    fn prefetch_key(&self) -> Option<PrefetchKey> {
+        (self.daily_challenge.is_none()
+            && self.quiz.is_none()
+            && self.synthesis.is_none()
+            && !self.tutorial
+            && self.offline.is_none())
+        .then(|| PrefetchKey {
+            character_count: self.character_count,
+            difficulty: self.difficulty,
+            weakness: self.weakness_focus(),
+        })
+    }
+
+    /// 要約を提出した後で次の文章を先読みするなら、その条件とプロンプトと文体
+    pub fn prefetch_request(&self) -> Option<(PrefetchKey, String, Option<TextStyle>)> {
+        self.submitted_at?;
+        let key = self.prefetch_key()?;
+        if !self.prefetcher.needs(key, Instant::now()) {
+            return None;
+        }
+        let (prompt, style) = self.text_prompt();
+        Some((key, prompt, style))
+    }
+
+    /// 先読みした文章があれば、生成を待たずに出題する
+    pub fn use_prefetched_text(&mut self) -> bool {
+        let Some(prefetched) = self
+            .prefetch_key()
+            .and_then(|key| self.prefetcher.take(key))
+        else {
+            return false;
+        };
+        self.text_style = prefetched.style;
+        self.mark_cached_text_seen(&prefetched.text);
+        self.apply_generated_text(prefetched.text);
+        true
+    }
+
+    /// 今の出題の条件。文体は `generate_text_prompt()` で選んだもの
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            character_count: self.character_count,
+            style: self.text_style,
+            difficulty: self.difficulty,
+        }
+    }
+
+    /// 生成した文章をキャッシュに残す。`seen` は出題したか。
+    /// キャッシュは失っても困らないので、保存の失敗は知らせない
+    pub fn cache_text(&mut self, key: CacheKey, text: &str, seen: bool) {
+        if let Some(cache) = self.text_cache.as_mut() {
+            cache.add(key, text, seen, Local::now());
+            let _ = cache.save();
+        }
+    }
+
+    /// 生成して出題した文章をキャッシュに残す
+    pub fn cache_generated_text(&mut self, text: &str) {
+        self.cache_text(self.cache_key(), text, true);
+    }
+
+    fn mark_cached_text_seen(&mut self, text: &str) {
+        if let Some(cache) = self.text_cache.as_mut() {
+            cache.mark_seen(text);
+            let _ = cache.save();
+        }
+    }
+
+    /// 今の出題の条件で、まだ出題していないキャッシュの文章があるか
+    pub fn has_unseen_cached_text(&self) -> bool {
+        self.prefetch_key().is_some()
+            && self
+                .text_cache
+                .as_ref()
+                .is_some_and(|cache| cache.has_unseen(self.cache_key()))
+    }
+
+    /// API の応答が遅いときや利用上限に達したときに、キャッシュの未出題の文章から出題する。
+    /// `prompt` は送らずに済んだプロンプトで、節約したトークン数の見積もりに使う
+    pub fn serve_cached_text(&mut self, prompt: &str) -> bool {
+        if self.prefetch_key().is_none() {
+            return false;
+        }
+        let key = self.cache_key();
+        let Some(text) = self
+            .text_cache
+            .as_mut()
+            .and_then(|cache| cache.take_unseen(key))
+        else {
+            return false;
+        };
+        if let Some(cache) = self.text_cache.as_ref() {
+            let _ = cache.save();
+        }
+        self.prefetcher.discard(&text);
+        self.stats.text_cache.record(prompt, &text);
+        self.save_stats();
+        self.apply_generated_text(text);
+        self.status_message = STATUS_CACHED_TEXT.to_string();
+        true
+    }
 
-        let style_prompt = if rng.random_bool(0.7) {
-            "日本の公的文書（省庁や自治体が発行する通知や報告書）の文体で、感情表現や口語表現を避け、形式的かつ客観的な文章を"
+    /// 原文欄のタイトルに表示する、今の出題の条件
+    pub fn breadcrumb(&self) -> Vec<String> {
+        let mode = if self.hot_seat.is_some() {
+            "2人対戦"
+        } else if self.quiz.is_some() {
+            "4択問題"
+        } else if self.exam.is_some() {
+            "模擬試験"
+        } else if self.synthesis.is_some() {
+            "統合要約"
+        } else if self.guided.is_some() {
+            "段階練習"
+        } else if self.daily_challenge.is_some() {
+            "今日のお題"
+        } else if self.level_round == Some(LevelRound::Promotion) {
+            "昇級試験"
+        } else if self.level_round.is_some() {
+            "レベル挑戦"
+        } else if self.challenge.is_some() {
+            "チャレンジ"
+        } else if self.comparison_base.is_some() {
+            "再挑戦"
         } else {
-            "日本の新聞記事の本文として、事実関係を中心に客観的かつ簡潔な文体で文章を"
+            "要約"
         };
+        let mut crumbs = vec![format!("モード: {mode}")];
+        if let Some(exam) = self.exam.as_ref() {
+            crumbs.push(format!(
+                "第{}問/{}",
+                exam.round_number(),
+                exam::EXAM_LENGTHS.len()
+            ));
+        }
+        crumbs.push(format!("{}字", self.character_count));
+        if let Some(source) = self.imported_from.as_ref() {
+            crumbs.push(source.label());
+            return crumbs;
+        }
+        crumbs.push(self.difficulty.display_name());
+        if let Some(style) = self.text_style {
+            crumbs.push(style.label().to_string());
+        }
+        crumbs
+    }
 
-        format!(
-            "{}{}文字程度で生成してください。",
-            style_prompt, self.character_count
-        )
-        .repeat(2)
+    /// `adaptive_prompt` が有効なとき、出題に反映する苦手な観点
+    pub fn weakness_focus(&self) -> Option<Weakness> {
+        // 模擬試験では苦手に合わせた出題をしない
+        if !self.config.adaptive_prompt || self.exam.is_some() {
+            return None;
+        }
+        self.stats.get_weakness()
     }
 
     pub fn has_training_started(&self) -> bool {
-        self.original_text != INITIAL_ORIGINAL_TEXT
-            && !self.original_text.starts_with(GENERATION_ERROR_PREFIX)
+        self.training_state == TrainingState::Ready
     }
 
     pub fn return_from_aux_view(&mut self) {
@@ -122,139 +696,2495 @@ impl App {
         }
     }
 
+    /// 保存済みのセッション履歴を新しい順に一覧表示する
+    pub fn enter_history_view(&mut self) {
+        match history::load_records() {
+            Ok(records) => {
+                self.history_browser = Some(HistoryBrowser::new(records));
+                self.view_mode = ViewMode::History;
+                self.status_message = STATUS_HISTORY.to_string();
+            }
+            Err(e) => {
+                self.status_message = trf(
+                    self.config.locale,
+                    "履歴の読み込みに失敗しました: {}",
+                    &[&e],
+                );
+            }
+        }
+    }
+
+    pub fn open_history_detail(&mut self) {
+        if let Some(browser) = self.history_browser.as_mut()
+            && browser.open_selected()
+        {
+            self.status_message = STATUS_HISTORY_DETAIL.to_string();
+        }
+    }
+
+    /// 詳細表示中なら一覧に、一覧表示中なら元の画面に戻る
+    pub fn back_from_history(&mut self) {
+        if let Some(browser) = self.history_browser.as_mut()
+            && browser.is_detail_open()
+        {
+            browser.close_detail();
+            self.status_message = STATUS_HISTORY.to_string();
+            return;
+        }
+        self.history_browser = None;
+        self.return_from_aux_view();
+    }
+
     pub fn enter_report_view(&mut self) {
         self.view_mode = ViewMode::Report;
         self.status_message = STATUS_REPORT.to_string();
     }
 
+    pub fn toggle_score_scale(&mut self) {
+        self.score_scale = self.score_scale.toggle();
+    }
+
+    pub fn enter_badge_gallery(&mut self) {
+        self.view_mode = ViewMode::BadgeGallery;
+        self.badge_gallery_scroll = 0;
+        self.status_message = STATUS_BADGE_GALLERY.to_string();
+    }
+
+    /// タブを切り替え、先頭から表示する
+    pub fn select_report_tab(&mut self, tab: ReportTab) {
+        self.report_tab = tab;
+        self.report_scroll = 0;
+    }
+
     pub fn enter_help_view(&mut self) {
         self.view_mode = ViewMode::Help;
         self.status_message = STATUS_HELP.to_string();
     }
 
-    pub fn begin_editing(&mut self) {
-        self.text_area_state.focus.set(true);
-        self.text_area_state.scroll_cursor_to_visible();
-        self.status_message = STATUS_EDITING.to_string();
+    pub fn leave_help_view(&mut self) {
+        self.return_from_aux_view();
+        self.help_scroll = 0;
+        self.help_search = HelpSearch::default();
+        self.help_toc = None;
     }
 
-    pub fn stop_editing(&mut self) {
-        self.text_area_state.focus.set(false);
-        self.status_message = STATUS_NORMAL.to_string();
+    /// 目次に載せる見出し (文書タイトルの `#` は除く)
+    pub fn help_toc_headings() -> Vec<HelpHeading> {
+        help::parse_headings(help::HELP_CONTENT)
+            .into_iter()
+            .filter(|heading| heading.level > 1)
+            .collect()
     }
 
-    pub fn begin_training_generation(&mut self, is_next_training: bool) {
-        self.view_mode = ViewMode::Normal;
-        self.status_message = if is_next_training {
-            STATUS_NEXT_GENERATING
+    fn jump_help_to_line(&mut self, line: usize) {
+        let width = self.terminal_width.saturating_sub(BLOCK_BORDER_SIZE);
+        self.help_scroll = help::scroll_for_line(help::HELP_CONTENT, line, width);
+    }
+
+    pub fn begin_help_search(&mut self) {
+        self.help_search.begin();
+        self.status_message = self.help_search.status();
+    }
+
+    pub fn edit_help_search(&mut self, c: Option<char>) {
+        match c {
+            Some(c) => self.help_search.query.push(c),
+            None => {
+                self.help_search.query.pop();
+            }
+        }
+        self.status_message = self.help_search.status();
+    }
+
+    pub fn cancel_help_search(&mut self) {
+        self.help_search = HelpSearch::default();
+        self.status_message = STATUS_HELP.to_string();
+    }
+
+    pub fn confirm_help_search(&mut self) {
+        if self.help_search.query.is_empty() {
+            self.cancel_help_search();
+            return;
+        }
+        if let Some(line) = self.help_search.confirm(help::HELP_CONTENT) {
+            self.jump_help_to_line(line);
+        }
+        self.status_message = self.help_search.status();
+    }
+
+    pub fn move_help_match(&mut self, forward: bool) {
+        let line = if forward {
+            self.help_search.next()
         } else {
-            STATUS_GENERATING
+            self.help_search.previous()
+        };
+        if let Some(line) = line {
+            self.jump_help_to_line(line);
+            self.status_message = self.help_search.status();
         }
-        .to_string();
     }
 
-    pub fn apply_generated_text(&mut self, text: String) {
-        self.original_text = text;
-        self.status_message = STATUS_NORMAL.to_string();
+    /// 今表示している位置の前後の見出しへ移動する
+    pub fn jump_help_section(&mut self, forward: bool) {
+        let width = self.terminal_width.saturating_sub(BLOCK_BORDER_SIZE);
+        let rows = Self::help_toc_headings()
+            .into_iter()
+            .map(|heading| help::scroll_for_line(help::HELP_CONTENT, heading.line, width));
+        let target = if forward {
+            rows.into_iter().find(|&row| row > self.help_scroll)
+        } else {
+            rows.into_iter().rev().find(|&row| row < self.help_scroll)
+        };
+        self.help_scroll = target.unwrap_or(if forward { self.help_scroll } else { 0 });
     }
 
-    pub fn apply_generation_error(&mut self, error: &impl std::fmt::Display) {
-        self.original_text = format!("{GENERATION_ERROR_PREFIX}{error}");
-        self.status_message = STATUS_RUNTIME_ERROR.to_string();
+    pub fn toggle_help_toc(&mut self) {
+        self.help_toc = match self.help_toc {
+            Some(_) => None,
+            None => Some(0),
+        };
     }
 
-    pub fn begin_evaluation(&mut self) {
-        self.status_message = STATUS_EVALUATING.to_string();
+    pub fn move_help_toc(&mut self, forward: bool) {
+        let count = Self::help_toc_headings().len();
+        if let Some(selected) = self.help_toc.as_mut() {
+            *selected = if forward {
+                (*selected + 1).min(count.saturating_sub(1))
+            } else {
+                selected.saturating_sub(1)
+            };
+        }
     }
 
-    pub fn finish_evaluation(&mut self, text: String, passed: bool) {
-        self.evaluation_text = text;
-        self.evaluation_passed = passed;
-        self.show_evaluation_overlay = true;
-        self.evaluation_overlay_scroll = 0;
-        self.status_message = STATUS_EVALUATED.to_string();
+    /// 目次で選んだ見出しへ移動して目次を閉じる
+    pub fn jump_to_help_toc_selection(&mut self) {
+        let Some(selected) = self.help_toc.take() else {
+            return;
+        };
+        if let Some(heading) = Self::help_toc_headings().get(selected) {
+            self.jump_help_to_line(heading.line);
+        }
     }
 
-    pub fn fail_evaluation_format(&mut self) {
-        self.evaluation_text = STATUS_INVALID_EVALUATION.to_string();
-        self.evaluation_passed = false;
-        self.show_evaluation_overlay = true;
-        self.evaluation_overlay_scroll = 0;
-        self.status_message = STATUS_INVALID_EVALUATION.to_string();
+    /// API を使わずに練習する。`reason` は認証に失敗して切り替えた場合のエラー
+    pub fn enter_offline_mode(&mut self, corpus: OfflineCorpus, reason: Option<&AppError>) {
+        self.offline = Some(corpus);
+        self.status_message = match reason {
+            Some(e) => format!("API に接続できないため、オフラインで起動しました ({e})。"),
+            None => STATUS_OFFLINE.to_string(),
+        };
     }
 
-    pub fn fail_evaluation_request(&mut self, error: &impl std::fmt::Display) {
-        self.evaluation_text = format!("エラー: {error}");
-        self.evaluation_passed = false;
-        self.show_evaluation_overlay = true;
-        self.evaluation_overlay_scroll = 0;
-        self.status_message = STATUS_RUNTIME_ERROR.to_string();
+    /// オフラインの文章を出題する。復習や未使用テキストがあればそちらを先に出す
+    pub fn start_offline_text(&mut self) {
+        if self.resume_review_text() || self.resume_pending_text() {
+            return;
+        }
+        let character_count = self.character_count;
+        let Some(text) = self
+            .offline
+            .as_mut()
+            .and_then(|corpus| corpus.take_for(character_count))
+        else {
+            self.apply_generation_error(&AppError::Offline);
+            return;
+        };
+        self.apply_generated_text(text);
+        self.status_message = STATUS_OFFLINE_TEXT.to_string();
     }
 
-    pub fn prepare_next_training(&mut self) {
-        self.show_evaluation_overlay = false;
-        self.evaluation_text.clear();
-        self.evaluation_passed = false;
-        self.text_area_state = Self::new_text_area_state();
-        self.original_text_scroll = 0;
-        self.evaluation_overlay_scroll = 0;
-        self.begin_training_generation(true);
+    /// 生成した文章や API の評価が要るモードをオフラインで選んだときの案内
+    pub fn reject_offline_mode(&mut self) {
+        self.status_message = STATUS_OFFLINE_UNAVAILABLE.to_string();
     }
 
-    pub fn update_terminal_size(&mut self, width: u16, height: u16) {
-        self.terminal_width = width;
-        self.terminal_height = height;
+    /// API キーを入力する画面を開く。`reason` は起動時の認証に失敗した理由で、
+    /// キーをまだ設定していない初回の起動では表示しない
+    pub fn open_api_key_setup(&mut self, reason: Option<&AppError>) {
+        if self.config.provider == Provider::Ollama {
+            self.status_message = STATUS_API_KEY_NOT_NEEDED.to_string();
+            return;
+        }
+        let mut setup = ApiKeySetup::new(self.config.api_key.as_deref());
+        if !setup.key().is_empty() {
+            setup.error = reason.map(ToString::to_string);
+        }
+        self.api_key_setup = Some(setup);
+        self.view_mode = ViewMode::ApiKeySetup;
+        self.status_message = STATUS_API_KEY_SETUP.to_string();
     }
 
-    pub fn calculate_overlay_area(&self) -> Rect {
-        Self::calculate_overlay_area_for_size(self.terminal_width, self.terminal_height)
+    /// 入力中のキーの形式。`base_url` を変えていなければ Groq のキーとして確かめる
+    pub fn api_key_format(&self) -> KeyFormat {
+        let key = self.api_key_setup.as_ref().map_or("", ApiKeySetup::key);
+        KeyFormat::check(key, self.config.base_url.is_none())
     }
 
-    pub fn calculate_overlay_area_for_size(width: u16, height: u16) -> Rect {
-        let full_area = Rect::new(0, 0, width, height);
-        let max_overlay_width = full_area
-            .width
-            .saturating_sub(OVERLAY_MARGIN.saturating_mul(2));
-        let max_overlay_height = full_area
-            .height
-            .saturating_sub(OVERLAY_MARGIN.saturating_mul(2));
+    /// 入力したキーを API に送って確かめ始める。送れる形式でなければ `None`
+    pub fn begin_api_key_check(&mut self) -> Option<String> {
+        if !self.api_key_format().can_submit() {
+            return None;
+        }
+        let setup = self.api_key_setup.as_mut()?;
+        setup.checking = true;
+        setup.error = None;
+        self.status_message = STATUS_API_KEY_CHECKING.to_string();
+        Some(setup.key().to_string())
+    }
 
-        let overlay_width = full_area
-            .width
-            .saturating_mul(OVERLAY_SIZE_PERCENT)
-            .saturating_div(100)
-            .max(MIN_OVERLAY_WIDTH)
-            .min(max_overlay_width);
-        let overlay_height = full_area
-            .height
-            .saturating_mul(OVERLAY_SIZE_PERCENT)
-            .saturating_div(100)
-            .max(MIN_OVERLAY_HEIGHT)
-            .min(max_overlay_height);
+    /// 確かめたキーを `config.toml` に保存し、このセッションでも使う。オフラインなら接続し直す
+    pub fn apply_api_key(&mut self, client: ApiClient, key: String) {
+        let account = self
+            .active_profile_name()
+            .unwrap_or(DEFAULT_PROFILE_NAME)
+            .to_string();
+        let saved = self.store_api_key(&account, &key);
+        if let Some(profile) = self.profiles.get_mut(self.active_profile) {
+            profile.api_key = Some(key.clone());
+        }
+        self.config.api_key = Some(key);
+        self.api_client = Some(client);
+        self.offline = None;
+        self.api_key_setup = None;
+        self.return_from_aux_view();
+        self.status_message = match saved {
+            Ok(status) => status.to_string(),
+            Err(e) => format!("API キーを確かめましたが、設定に保存できませんでした: {e}"),
+        };
+    }
 
-        Rect {
-            x: full_area.x + full_area.width.saturating_sub(overlay_width) / 2,
-            y: full_area.y + full_area.height.saturating_sub(overlay_height) / 2,
-            width: overlay_width,
-            height: overlay_height,
+    /// `[keyring]` が有効ならキーチェーンに、使えなければ `config.toml` に保存する
+    fn store_api_key(&self, account: &str, key: &str) -> Result<&'static str, AppError> {
+        if self.config.keyring.enabled && keyring::store(account, key).is_ok() {
+            config::remove_api_keys(&[account.to_string()])?;
+            return Ok(STATUS_API_KEY_SAVED_TO_KEYRING);
         }
+        config::save_api_key(key, account)?;
+        Ok(STATUS_API_KEY_SAVED)
     }
 
-    pub fn original_text_viewport_size(&self) -> (u16, u16) {
-        let content_height = self
+    /// `[keyring]` が有効なら、`config.toml` に書かれた API キーをキーチェーンに移し、
+    /// 書かれていないプロファイルのキーをキーチェーンから読む。キーチェーンを使えなければ何もしない
+    pub fn load_keyring_keys(&mut self) {
+        if !self.config.keyring.enabled {
+            return;
+        }
+        let mut migrated = Vec::new();
+        for profile in &mut self.profiles {
+            if profile.provider == Provider::Ollama {
+                continue;
+            }
+            match profile.api_key.as_deref() {
+                Some(key) => {
+                    if keyring::store(&profile.name, key).is_ok() {
+                        migrated.push(profile.name.clone());
+                    }
+                }
+                None => profile.api_key = keyring::read(&profile.name).ok().flatten(),
+            }
+        }
+        if migrated.is_empty() {
+            return;
+        }
+        self.status_message = match config::remove_api_keys(&migrated) {
+            Ok(()) => format!(
+                "config.toml の API キーを OS のキーチェーンに移しました ({})。",
+                migrated.join(", ")
+            ),
+            Err(e) => format!(
+                "API キーをキーチェーンに移しましたが、config.toml から消せませんでした: {e}"
+            ),
+        };
+    }
+
+    pub fn reject_api_key(&mut self, error: &AppError) {
+        if let Some(setup) = self.api_key_setup.as_mut() {
+            setup.checking = false;
+            setup.error = Some(error.to_string());
+        }
+        self.status_message = STATUS_API_KEY_SETUP.to_string();
+    }
+
+    pub fn cancel_api_key_check(&mut self) {
+        if let Some(setup) = self.api_key_setup.as_mut() {
+            setup.checking = false;
+        }
+        self.status_message = STATUS_API_KEY_SETUP.to_string();
+    }
+
+    pub fn close_api_key_setup(&mut self) {
+        self.api_key_setup = None;
+        self.return_from_aux_view();
+        if self.offline.is_some() && !self.has_training_started() {
+            self.status_message = STATUS_OFFLINE.to_string();
+        }
+    }
+
+    /// `config.toml` の `profile` で指定したプロファイルの接続設定で起動する
+    pub fn apply_configured_profile(&mut self) -> Result<(), AppError> {
+        let index = self.config.active_profile_index(&self.profiles)?;
+        if let Some(profile) = self.profiles.get(index) {
+            self.config.use_profile(profile);
+        }
+        self.active_profile = index;
+        Ok(())
+    }
+
+    pub fn active_profile_name(&self) -> Option<&str> {
+        self.profiles
+            .get(self.active_profile)
+            .map(|profile| profile.name.as_str())
+    }
+
+    /// `[[profiles]]` があれば、接続設定を切り替える画面を開く
+    pub fn enter_profile_select(&mut self) {
+        if self.profiles.len() <= 1 {
+            self.status_message = STATUS_NO_PROFILES.to_string();
+            return;
+        }
+        self.selected_profile_item = self.active_profile;
+        self.view_mode = ViewMode::ProfileSelect;
+        self.status_message = STATUS_MODEL_SELECT.to_string();
+    }
+
+    /// 選んだプロファイルに接続し始める。接続できるまでは今の接続を使い続ける
+    pub fn begin_profile_switch(&mut self) -> Option<(usize, ApiProfile)> {
+        let profile = self.profiles.get(self.selected_profile_item)?.clone();
+        self.status_message = trf(
+            self.config.locale,
+            "{} に接続しています... (Esc: 中止)",
+            &[&profile.name],
+        );
+        Some((self.selected_profile_item, profile))
+    }
+
+    /// 接続できたプロファイルに切り替え、次回の起動でも使うよう `config.toml` に書き込む
+    pub fn apply_profile(&mut self, index: usize, client: ApiClient) {
+        let Some(profile) = self.profiles.get(index) else {
+            return;
+        };
+        let name = profile.name.clone();
+        self.config.use_profile(profile);
+        self.active_profile = index;
+        self.api_client = Some(client);
+        self.offline = None;
+        self.return_from_aux_view();
+        let locale = self.config.locale;
+        self.status_message = match config::save_active_profile(self.config.profile.as_deref()) {
+            Ok(()) => trf(locale, "プロファイル {} に切り替えました。", &[&name]),
+            Err(e) => trf(
+                locale,
+                "プロファイル {} に切り替えましたが、設定に保存できませんでした: {}",
+                &[&name, &e],
+            ),
+        };
+    }
+
+    pub fn reject_profile(&mut self, profile: &ApiProfile, error: &AppError) {
+        self.status_message = trf(
+            self.config.locale,
+            "{} に接続できません: {}",
+            &[&profile.name, error],
+        );
+    }
+
+    pub fn cancel_profile_switch(&mut self) {
+        self.status_message = STATUS_MODEL_SELECT.to_string();
+    }
+
+    pub fn current_model(&self) -> Option<&str> {
+        self.api_client.as_ref().map(ApiClient::current_model)
+    }
+
+    pub fn begin_loading_models(&mut self) {
+        self.status_message = STATUS_LOADING_MODELS.to_string();
+    }
+
+    pub fn enter_model_select_view(&mut self, models: Vec<String>) {
+        self.selected_model_item = self
+            .current_model()
+            .and_then(|current| models.iter().position(|model| model == current))
+            .unwrap_or(0);
+        self.available_models = models;
+        self.view_mode = ViewMode::ModelSelect;
+        self.status_message = STATUS_MODEL_SELECT.to_string();
+    }
+
+    pub fn apply_model_list_error(&mut self, error: &impl std::fmt::Display) {
+        self.status_message = trf(
+            self.config.locale,
+            "モデル一覧の取得に失敗しました: {}",
+            &[error],
+        );
+    }
+
+    /// 選んだモデルをこのセッションの生成と評価に使う
+    pub fn select_model(&mut self) {
+        if let Some(model) = self.available_models.get(self.selected_model_item).cloned()
+            && let Some(client) = self.api_client.as_mut()
+        {
+            client.set_model(model);
+        }
+        self.view_mode = ViewMode::Menu;
+        self.status_message = STATUS_MENU.to_string();
+    }
+
+    /// メニューで選んだ文字数と難易度で、レベルとは関係なく練習する
+    pub fn begin_free_training(&mut self) {
+        if let Some(&count) = MENU_OPTIONS.get(self.selected_menu_item) {
+            self.character_count = count;
+        }
+        self.difficulty = self.selected_difficulty;
+        self.level_round = None;
+        self.hot_seat = None;
+        self.review_session = None;
+        self.time_limit = None;
+        self.quiz = None;
+        self.exam = None;
+        self.synthesis = None;
+        self.guided = None;
+        self.daily_challenge = None;
+        self.tutorial = false;
+    }
+
+    /// `yomitore train` の条件をメニューの選択に反映して、メニューを通さずに練習を始める
+    pub fn begin_cli_training(&mut self, options: &TrainOptions) {
+        if let Some(index) = options
+            .character_count
+            .and_then(|count| MENU_OPTIONS.iter().position(|&option| option == count))
+        {
+            self.selected_menu_item = index;
+        }
+        if let Some(difficulty) = options.difficulty {
+            self.selected_difficulty = difficulty;
+        }
+        self.fixed_style = options.style;
+        self.begin_free_training();
+    }
+
+    /// 初めて起動し、まだ 1 回も練習していなければチュートリアルから始める
+    pub fn should_start_tutorial(&self) -> bool {
+        self.view_mode == ViewMode::Menu
+            && self.recovered_draft.is_none()
+            && !self.stats.tutorial_completed
+            && self.stats.results.is_empty()
+    }
+
+    /// 組み込みの文章を出題する。自動で始まるのは 1 回だけにし、ヘルプ画面からはいつでも始められる
+    pub fn start_tutorial(&mut self) {
+        self.begin_free_training();
+        self.clear_round();
+        self.character_count =
+            u16::try_from(tutorial::SAMPLE_TEXT.chars().count()).unwrap_or(u16::MAX);
+        self.set_original_text(tutorial::SAMPLE_TEXT.to_string(), None);
+        self.tutorial = true;
+        self.view_mode = ViewMode::Normal;
+        self.status_message = STATUS_TUTORIAL.to_string();
+        if !self.stats.tutorial_completed {
+            self.stats.tutorial_completed = true;
+            self.save_stats();
+        }
+    }
+
+    /// 原文欄を広げるか狭め、調整した幅を次回の起動でも使えるよう保存する
+    pub fn resize_panes(&mut self, widen_original: bool) {
+        let resized = self.pane_layout.resized(widen_original);
+        if resized == self.pane_layout {
+            return;
+        }
+        self.pane_layout = resized;
+        self.stats.split_percent = Some(resized.original_percent());
+        self.save_stats();
+        self.status_message = trf(
+            self.config.locale,
+            "原文欄の幅を {}% にしました。",
+            &[&resized.original_percent()],
+        );
+    }
+
+    /// 原文欄と要約欄の並べ方を左右と上下で切り替え、次回の起動でも使えるよう保存する
+    pub fn toggle_pane_orientation(&mut self) {
+        self.pane_layout = self.pane_layout.toggled();
+        self.stats.pane_orientation = Some(self.pane_layout.orientation);
+        self.save_stats();
+    }
+
+    pub fn theme(&self) -> &'static Theme {
+        self.theme.theme()
+    }
+
+    /// 次の配色に切り替え、次回の起動でも使えるよう保存する
+    pub fn cycle_theme(&mut self) {
+        self.theme = self.theme.next();
+        self.stats.theme = Some(self.theme);
+        self.save_stats();
+        self.status_message = trf(self.config.locale, "配色: {}", &[&self.theme.label()]);
+    }
+
+    /// 要約の長さの条件を次のものに切り替え、次回の起動でも使えるよう保存する
+    pub fn cycle_length_goal(&mut self) {
+        self.length_goal = self.length_goal.next();
+        self.stats.summary_length = Some(self.length_goal);
+        self.save_stats();
+    }
+
+    /// 評価の厳しさを次のものに切り替え、次回の起動でも使えるよう保存する
+    pub fn cycle_strictness(&mut self) {
+        self.strictness = self.strictness.next();
+        self.stats.strictness = Some(self.strictness);
+        self.save_stats();
+    }
+
+    /// 評価結果を要約欄の下の常設の欄に出すか。設定 `result_overlay` のときはオーバーレイで出す
+    pub fn shows_result_pane(&self) -> bool {
+        !self.config.layout.result_overlay
+    }
+
+    /// 入力中は要約欄だけを全画面に出す集中モードを切り替える
+    pub fn toggle_zen_mode(&mut self) {
+        let (zen, message) = if self.zen == ZenMode::Off {
+            (ZenMode::Writing, STATUS_ZEN_ON)
+        } else {
+            (ZenMode::Off, STATUS_ZEN_OFF)
+        };
+        self.zen = zen;
+        self.status_message = message.to_string();
+    }
+
+    /// 集中モードで入力中に、原文欄をのぞくかどうかを切り替える
+    pub fn toggle_zen_peek(&mut self) {
+        self.zen = match self.zen {
+            ZenMode::Writing => ZenMode::Peeking,
+            ZenMode::Peeking => ZenMode::Writing,
+            ZenMode::Off => ZenMode::Off,
+        };
+    }
+
+    /// 今の画面で効いている集中モード。要約の入力中でなければ `Off`
+    pub fn active_zen_mode(&self) -> ZenMode {
+        if self.view_mode == ViewMode::Normal
+            && self.text_area_state.focus.get()
+            && self.quiz.is_none()
+        {
+            self.zen
+        } else {
+            ZenMode::Off
+        }
+    }
+
+    /// トレーニング画面の各欄の領域。描画と同じ分け方で求める
+    pub fn pane_areas(&self) -> PaneAreas {
+        let zen = self.active_zen_mode();
+        if zen != ZenMode::Off {
+            let screen = Rect::new(
+                0,
+                0,
+                self.terminal_width,
+                self.terminal_height.saturating_sub(ZEN_HINT_HEIGHT),
+            );
+            return PaneAreas::single(screen, zen == ZenMode::Peeking);
+        }
+        let body_height = self
             .terminal_height
-            .saturating_sub(HEADER_HEIGHT + STATUS_HEIGHT);
-        let pane_width = self.terminal_width / 2;
+            .saturating_sub(HEADER_HEIGHT + STATUS_HEIGHT + self.error_line_height());
+        let body = Rect::new(0, HEADER_HEIGHT, self.terminal_width, body_height);
+        self.pane_layout.areas(body, self.shows_result_pane())
+    }
+
+    pub fn tutorial_step(&self) -> Option<TutorialStep> {
+        self.tutorial.then(|| {
+            TutorialStep::current(
+                self.text_area_state.focus.get(),
+                !self.text_area_state.value().trim().is_empty(),
+                !self.evaluation_text.is_empty(),
+            )
+        })
+    }
+
+    /// 日付で決まる話題・文体・文字数の文章を、標準の難易度で出題する
+    pub fn begin_daily_challenge(&mut self) {
+        self.begin_free_training();
+        let challenge = DailyChallenge::for_date(Local::now().date_naive());
+        self.character_count = challenge.character_count;
+        self.difficulty = Difficulty::Standard;
+        self.daily_challenge = Some(challenge);
+    }
+
+    /// 短い順に決まった文字数の文章を制限時間付きで出題し、最後にまとめて採点する模擬試験を始める
+    pub fn begin_exam(&mut self) {
+        self.begin_free_training();
+        self.exam = Some(ExamSession::default());
+        self.set_exam_round();
+    }
+
+    /// 次の問題の文字数と持ち時間にする
+    fn set_exam_round(&mut self) {
+        if let Some(count) = self.exam.as_ref().and_then(ExamSession::current_length) {
+            self.character_count = count;
+            self.time_limit = Some(self.config.timed_challenge.time_limit(count));
+        }
+    }
+
+    /// 直前に記録した結果を模擬試験の 1 問として残す。途中の問では評価の詳細を見せず、
+    /// 全問終えたら成績表を表示して、統計に残す記録を返す
+    pub fn record_exam_round(&mut self) -> Option<exam::ExamRecord> {
+        let result = self.stats.results.last()?;
+        let round = ExamRound {
+            character_count: self.character_count,
+            passed: result.passed,
+            points: result.evaluation.as_ref().map(|scores| {
+                scores
+                    .importance
+                    .saturating_add(scores.conciseness)
+                    .saturating_add(scores.accuracy)
+            }),
+            duration_secs: result.duration_secs,
+            timed_out: result.timed_out,
+        };
+        let session = self.exam.as_mut()?;
+        session.record(round);
+        self.result_tabs = ResultTabs::default();
+        self.show_evaluation_overlay = true;
+        if !session.is_finished() {
+            self.evaluation_text = format!(
+                "- 第{}問を提出しました。結果は試験の最後にまとめて表示します。\n",
+                session.rounds.len()
+            );
+            self.verdict = Verdict::default();
+            self.status_message = STATUS_EXAM_NEXT.to_string();
+            return None;
+        }
+        let record = session.to_record(Local::now());
+        self.evaluation_text = exam::format_report(&record);
+        self.verdict = Verdict::from_passed(record.passed);
+        self.status_message = STATUS_EXAM_FINISHED.to_string();
+        Some(record)
+    }
+
+    /// メニューで選んだ文字数の本文を読み、4 択の読解問題に答えるモードを始める
+    pub fn begin_quiz(&mut self) {
+        self.begin_free_training();
+        self.quiz = Some(QuizSession::default());
+    }
+
+    pub fn quiz_prompt(&mut self) -> String {
+        self.text_style = None;
+        quiz::build_quiz_prompt(self.difficulty.prompt_hint(), self.character_count)
+    }
+
+    /// 生成した問題を読み取り、本文を原文欄に出す。問題は本文ごとライブラリに残す
+    pub fn apply_generated_quiz(&mut self, response: &str) {
+        let quiz = match quiz::parse_quiz(response) {
+            Ok(quiz) => quiz,
+            Err(e) => {
+                self.apply_generation_error(&e);
+                return;
+            }
+        };
+        self.library.add_quiz(&quiz, self.difficulty, Local::now());
+        self.start_quiz(quiz);
+        self.status_message = match self.library.save() {
+            Ok(()) => STATUS_QUIZ.to_string(),
+            Err(e) => trf(
+                self.config.locale,
+                "警告: ライブラリの保存に失敗しました: {}",
+                &[&e],
+            ),
+        };
+    }
+
+    fn start_quiz(&mut self, quiz: Quiz) {
+        self.set_original_text(quiz.passage, None);
+        if let Some(session) = self.quiz.as_mut() {
+            session.start(quiz.questions);
+        }
+    }
+
+    /// 今の文字数と難易度で、ライブラリに残した 4 択問題があるか
+    pub fn has_library_quiz(&self) -> bool {
+        self.library
+            .has_quiz_for(library::bucket_for(self.character_count), self.difficulty)
+    }
+
+    /// API の応答が遅いときや使えないときに、ライブラリに残した 4 択問題から出題する。
+    /// `prompt` は送らずに済んだプロンプトで、節約したトークン数の見積もりに使う
+    pub fn serve_library_quiz(&mut self, prompt: &str) -> bool {
+        let bucket = library::bucket_for(self.character_count);
+        let Some(quiz) = self.library.take_quiz_for(bucket, self.difficulty) else {
+            return false;
+        };
+        let _ = self.library.save();
+        self.stats.text_cache.record(prompt, &quiz.passage);
+        self.save_stats();
+        self.start_quiz(quiz);
+        self.status_message = STATUS_LIBRARY_QUIZ.to_string();
+        true
+    }
+
+    /// メニューで選んだ文字数の文章を段落ごとに要約させ、最後に全体をまとめさせる段階練習を始める
+    pub fn begin_guided(&mut self) {
+        self.begin_free_training();
+        self.guided = Some(GuidedSession::new(""));
+    }
+
+    /// 段階練習で段落の要約を書いているところか。全体の要約は通常どおり評価に出す
+    pub fn is_guided_paragraph_step(&self) -> bool {
+        self.guided
+            .as_ref()
+            .is_some_and(|guided| !guided.is_final())
+    }
+
+    /// 入力欄の要約を今の段落の要約として送り、講評を待つ
+    pub fn submit_guided_paragraph(&mut self) {
+        let summary = self.text_area_state.value();
+        let Some(guided) = self.guided.as_mut() else {
+            return;
+        };
+        if guided.submit(&summary).is_some() {
+            self.status_message = STATUS_GUIDED_REVIEWING.to_string();
+        }
+    }
+
+    /// 講評を待っている段落と、その要約
+    pub fn pending_paragraph_review(&self) -> Option<(String, String)> {
+        let (paragraph, summary) = self.guided.as_ref()?.pending_review()?;
+        Some((paragraph.to_string(), summary.to_string()))
+    }
+
+    /// 講評を記録して入力欄を空にする。最後の段落なら、段落ごとの要約を全体の要約の下書きにする
+    pub fn apply_paragraph_review(&mut self, response: Result<String, AppError>) {
+        let Some(guided) = self.guided.as_mut() else {
+            return;
+        };
+        match response.and_then(|response| guided::parse_review(&response)) {
+            Ok((captured, feedback)) => {
+                guided.record_review(captured, feedback);
+                self.text_area_state = Self::new_text_area_state();
+                if guided.is_final() {
+                    self.text_area_state.insert_str(guided.final_draft());
+                    self.status_message = STATUS_GUIDED_FINAL.to_string();
+                } else {
+                    self.status_message = STATUS_GUIDED_NEXT.to_string();
+                }
+            }
+            Err(e) => {
+                guided.cancel_review();
+                self.show_error(e.notice("段落の講評"));
+            }
+        }
+    }
+
+    /// メニューで選んだ文字数を 2 つの資料に分けて出題し、両方をまとめた要約を書く統合要約モードを始める
+    pub fn begin_synthesis(&mut self) {
+        self.begin_free_training();
+        self.synthesis = Some(SynthesisSession::default());
+    }
+
+    pub fn synthesis_prompt(&mut self) -> String {
+        self.text_style = None;
+        synthesis::build_synthesis_prompt(self.difficulty.prompt_hint(), self.character_count)
+    }
+
+    /// 生成した 2 つの資料を読み取り、原文欄に並べる。統計や履歴にはつなげた文章を残す
+    pub fn apply_generated_sources(&mut self, response: &str) {
+        let sources = match synthesis::parse_sources(response) {
+            Ok(sources) => sources,
+            Err(e) => {
+                self.apply_generation_error(&e);
+                return;
+            }
+        };
+        self.set_original_text(synthesis::combined_text(&sources), None);
+        if let Some(session) = self.synthesis.as_mut() {
+            session.sources = sources;
+        }
+        self.status_message = STATUS_SYNTHESIS.to_string();
+    }
+
+    /// 統合要約モードで、原文欄に並べて表示する資料
+    pub fn synthesis_sources(&self) -> Option<&[String]> {
+        self.synthesis
+            .as_ref()
+            .map(|session| session.sources.as_slice())
+            .filter(|sources| !sources.is_empty())
+    }
+
+    /// 4 択問題に答えている途中か
+    pub fn is_quiz_answering(&self) -> bool {
+        self.quiz.as_ref().is_some_and(QuizSession::is_answering)
+    }
+
+    /// 全問に答えた 4 択問題を採点して結果を表示する。合否・正解数・問題数・解答を返す
+    pub fn finish_quiz(&mut self) -> Option<(bool, u8, u8, String)> {
+        let session = self.quiz.as_ref()?;
+        let passed = session.passed();
+        let correct = u8::try_from(session.correct_count()).unwrap_or(u8::MAX);
+        let total = u8::try_from(session.questions.len()).unwrap_or(u8::MAX);
+        let answers = session.format_answers();
+        self.submitted_at = Some(Local::now());
+        self.evaluation_text = session.format_result();
+        self.verdict = Verdict::from_passed(passed);
+        self.show_evaluation_overlay = true;
+        self.result_tabs = ResultTabs::default();
+        self.status_message = STATUS_QUIZ_GRADED.to_string();
+        Some((passed, correct, total, answers))
+    }
+
+    /// メニューで選んだ文字数で、持ち時間内に要約を提出する制限時間付きモードを始める
+    pub fn begin_timed_challenge(&mut self) {
+        self.begin_free_training();
+        self.time_limit = Some(self.config.timed_challenge.time_limit(self.character_count));
+    }
+
+    /// メニューで選んだ文字数で、2人が交代で同じ文章を要約する対戦モードを始める
+    pub fn begin_hot_seat(&mut self) {
+        self.begin_free_training();
+        self.hot_seat = Some(HotSeat::new(&self.config.hot_seat));
+    }
+
+    fn hot_seat_turn_message(&self) -> Option<String> {
+        self.hot_seat.as_ref().map(|hot_seat| {
+            format!(
+                "{} さんの番です。'i' で入力します。",
+                hot_seat.current_player()
+            )
+        })
+    }
+
+    /// 1人目の要約を預かり、入力欄を空にして2人目に交代する
+    pub fn pass_turn(&mut self) {
+        self.text_area_state = Self::new_text_area_state();
+        self.scratchpad.clear();
+        if let Some(message) = self.hot_seat_turn_message() {
+            self.status_message = message;
+        }
+    }
+
+    /// 現在のレベルの文字数と難易度で出題する。規定回数合格していれば昇級試験になる
+    pub fn begin_level_challenge(&mut self) {
+        let level = levels::level_at(self.stats.reading_level);
+        self.character_count = level.character_count;
+        self.difficulty = level.difficulty;
+        self.level_round = Some(self.stats.next_level_round());
+        self.hot_seat = None;
+        self.review_session = None;
+        self.time_limit = None;
+        self.quiz = None;
+        self.exam = None;
+        self.synthesis = None;
+        self.guided = None;
+    }
+
+    /// レベル挑戦の結果を統計に反映し、昇級試験の結果を評価テキストに追記する
+    pub fn apply_level_result(&mut self, passed: bool) {
+        let Some(round) = self.level_round else {
+            return;
+        };
+        let promoted = self.stats.record_level_result(round, passed);
+        if round != LevelRound::Promotion {
+            return;
+        }
+        let message = match promoted {
+            Some(level) => format!("合格！ {} に昇級しました。", levels::describe_level(level)),
+            None => format!(
+                "不合格でした。あと {} 回合格すると再受験できます。",
+                levels::PROMOTION_INTERVAL
+            ),
+        };
+        self.evaluation_text.push_str("\n# 昇級試験\n- ");
+        self.evaluation_text.push_str(&message);
+        self.evaluation_text.push('\n');
+    }
+
+    /// 今の原文を段落ごとに音読するモードに入る。API は使わない
+    pub fn begin_read_aloud(&mut self) {
+        let target = self
+            .config
+            .read_aloud_cpm
+            .unwrap_or(read_aloud::DEFAULT_TARGET_CHARS_PER_MINUTE);
+        self.read_aloud = Some(ReadAloudSession::new(&self.original_text, target));
+        self.view_mode = ViewMode::ReadAloud;
+        self.status_message = STATUS_READ_ALOUD.to_string();
+    }
+
+    /// 次の段落に進む。最後の段落を読み終えたら記録を保存する
+    pub fn advance_read_aloud(&mut self) {
+        let Some(session) = self.read_aloud.as_mut() else {
+            return;
+        };
+        if session.is_finished() {
+            return;
+        }
+        session.advance();
+        if !session.is_finished() {
+            return;
+        }
+        self.stats.add_read_aloud_result(session.result());
+        self.status_message = STATUS_READ_ALOUD_FINISHED.to_string();
+        self.save_stats();
+    }
+
+    pub fn exit_read_aloud(&mut self) {
+        self.read_aloud = None;
+        self.return_from_aux_view();
+    }
+
+    /// 今の原文を少しずつ表示する速読モードに入る。API は使わない
+    pub fn begin_speed_reading(&mut self) {
+        let pace = self
+            .config
+            .speed_reading_cpm
+            .unwrap_or(speed_reading::DEFAULT_CHARS_PER_MINUTE);
+        self.speed_reading = Some(SpeedReadingSession::new(
+            &self.original_text,
+            pace,
+            Instant::now(),
+        ));
+        self.view_mode = ViewMode::SpeedReading;
+        self.status_message = STATUS_SPEED_READING.to_string();
+    }
+
+    /// 表示時間が過ぎていたら次のかたまりに進める。メインループから毎回呼ぶ
+    pub fn tick_speed_reading(&mut self) {
+        if self.view_mode != ViewMode::SpeedReading {
+            return;
+        }
+        let Some(session) = self.speed_reading.as_mut() else {
+            return;
+        };
+        if session.tick(Instant::now()) && session.is_finished() {
+            self.status_message = STATUS_SPEED_READING_FINISHED.to_string();
+        }
+    }
+
+    pub fn exit_speed_reading(&mut self) {
+        self.speed_reading = None;
+        self.return_from_aux_view();
+    }
+
+    /// 読み込んだチャレンジファイルの文章を、同じ設定で出題する
+    pub fn start_challenge(&mut self, challenge: Challenge) {
+        self.begin_free_training();
+        self.flashback = None;
+        self.character_count = challenge.character_count;
+        self.difficulty = challenge.difficulty;
+        self.set_original_text(challenge.original_text.clone(), None);
+        self.challenge = Some(challenge);
+        self.view_mode = ViewMode::Normal;
+        self.status_message = STATUS_CHALLENGE.to_string();
+    }
+
+    /// カレントディレクトリからテキストファイルを選ぶ画面を開く
+    pub fn open_file_browser(&mut self) {
+        let opened = std::env::current_dir().and_then(FileBrowser::open);
+        match opened {
+            Ok(browser) => {
+                self.file_browser = Some(browser);
+                self.view_mode = ViewMode::FileBrowser;
+                self.status_message = STATUS_FILE_BROWSER.to_string();
+            }
+            Err(e) => {
+                self.status_message =
+                    trf(self.config.locale, "ディレクトリを開けません: {}", &[&e]);
+            }
+        }
+    }
+
+    pub fn close_file_browser(&mut self) {
+        self.file_browser = None;
+        self.return_from_aux_view();
+    }
+
+    /// 選択中のディレクトリに移動するか、ファイルを読み込んで練習を始める
+    pub fn open_selected_file(&mut self) {
+        let Some(browser) = self.file_browser.as_mut() else {
+            return;
+        };
+        let path = match browser.enter_selected() {
+            Ok(Some(path)) => path,
+            Ok(None) => return,
+            Err(e) => {
+                self.status_message =
+                    trf(self.config.locale, "ディレクトリを開けません: {}", &[&e]);
+                return;
+            }
+        };
+        match text_import::load_text(&path) {
+            Ok(text) => {
+                self.file_browser = None;
+                self.start_imported_text(text, TextSource::File(path));
+            }
+            Err(e) => self.status_message = e.to_string(),
+        }
+    }
+
+    pub fn browse_parent_dir(&mut self) {
+        let Some(browser) = self.file_browser.as_mut() else {
+            return;
+        };
+        if let Err(e) = browser.go_parent() {
+            self.status_message = trf(self.config.locale, "ディレクトリを開けません: {}", &[&e]);
+        }
+    }
+
+    /// クリップボードの文章を原文にする。練習中なら今の文章と入力中の要約を置き換える
+    pub fn paste_clipboard_text(&mut self) {
+        match text_import::read_clipboard() {
+            Ok(text) => self.start_imported_text(text, TextSource::Clipboard),
+            Err(e) => self.status_message = e.to_string(),
+        }
+    }
+
+    /// 生成せずに、読み込んだ文章で通常の要約・評価を行う。文章はライブラリにも取り込む
+    pub fn start_imported_text(&mut self, text: String, source: TextSource) {
+        let library_status = (!matches!(source, TextSource::Library))
+            .then(|| self.import_to_library(&text))
+            .flatten();
+        self.begin_free_training();
+        self.clear_round();
+        self.character_count = u16::try_from(text.chars().count()).unwrap_or(u16::MAX);
+        self.set_original_text(text, None);
+        self.imported_from = Some(source);
+        self.view_mode = ViewMode::Normal;
+        self.status_message = library_status.unwrap_or_else(|| STATUS_IMPORTED.to_string());
+    }
+
+    /// ライブラリに取り込み、知らせることがあればステータスの文言を返す
+    fn import_to_library(&mut self, text: &str) -> Option<String> {
+        let locale = self.config.locale;
+        let summary = self.library.import(text, Local::now());
+        if summary.added == 0 {
+            return (summary.duplicates > 0).then(|| {
+                trf(
+                    locale,
+                    "{} (ライブラリに同じ文章があります)",
+                    &[&tr(locale, STATUS_IMPORTED)],
+                )
+            });
+        }
+        if let Err(e) = self.library.save() {
+            return Some(trf(
+                locale,
+                "警告: ライブラリの保存に失敗しました: {}",
+                &[&e],
+            ));
+        }
+        Some(trf(
+            locale,
+            "{} (ライブラリに {} 件追加)",
+            &[&tr(locale, STATUS_IMPORTED), &summary.added],
+        ))
+    }
+
+    /// メニューで選んだ文字数に近いライブラリの文章で練習する
+    pub fn start_library_text(&mut self) {
+        let Some(&bucket) = MENU_OPTIONS.get(self.selected_menu_item) else {
+            return;
+        };
+        let Some(text) = self.library.take_for(bucket) else {
+            self.status_message = trf(
+                self.config.locale,
+                "ライブラリに {} 文字前後の文章がありません。",
+                &[&bucket],
+            );
+            return;
+        };
+        if let Err(e) = self.library.save() {
+            self.status_message = trf(
+                self.config.locale,
+                "警告: ライブラリの保存に失敗しました: {}",
+                &[&e],
+            );
+        }
+        self.start_imported_text(text, TextSource::Library);
+        self.status_message = STATUS_LIBRARY.to_string();
+    }
+
+    /// 過去のセッションと同じ原文で、要約を書き直す。結果は元のセッションとの比較付きで記録する
+    pub fn retry_session(&mut self, record: SessionRecord) {
+        self.begin_free_training();
+        self.clear_round();
+        self.history_browser = None;
+        self.character_count = record.character_count;
+        self.set_original_text(record.original_text.clone(), None);
+        self.comparison_base = Some(record);
+        self.view_mode = ViewMode::Normal;
+        self.status_message = STATUS_RETRY.to_string();
+    }
+
+    /// 記録を終えたセッションを保持する。不合格なら再挑戦できることを案内する
+    pub fn complete_session(&mut self, record: SessionRecord) {
+        let passed = record.passed;
+        self.last_session = Some(record);
+        if !passed
+            && (self.status_message == STATUS_EVALUATED
+                || self.status_message == STATUS_SELF_GRADED
+                || self.status_message == STATUS_TIME_UP)
+        {
+            self.status_message = STATUS_FAILED_RETRY_HINT.to_string();
+        }
+    }
+
+    /// 直前のセッションが不合格なら、同じ原文で再挑戦する
+    pub fn retry_last_failed(&mut self) {
+        // 模擬試験はやり直せない
+        if self.exam.is_some() {
+            return;
+        }
+        if let Some(record) = self.last_session.clone().filter(|record| !record.passed) {
+            self.retry_session(record);
+        }
+    }
+
+    /// 履歴画面で選んでいるセッションに再挑戦する
+    pub fn retry_history_selection(&mut self) {
+        let record = self.history_browser.as_ref().and_then(|browser| {
+            browser
+                .opened_record()
+                .or_else(|| browser.records.get(browser.selected))
+                .cloned()
+        });
+        if let Some(record) = record {
+            self.retry_session(record);
+        }
+    }
+
+    /// 前の出題の状態 (評価結果・入力中の要約・比較対象など) を消す
+    fn clear_round(&mut self) {
+        self.flashback = None;
+        self.comparison_base = None;
+        self.challenge = None;
+        self.last_session = None;
+        self.imported_from = None;
+        self.show_evaluation_overlay = false;
+        self.evaluation_text.clear();
+        self.verdict = Verdict::default();
+        self.text_area_state = Self::new_text_area_state();
+        self.scratchpad.clear();
+        self.original_text_scroll = 0;
+        self.result_tabs = ResultTabs::default();
+    }
+
+    /// 評価結果の選択中のタブに表示する文章
+    pub fn result_tab_text(&self) -> String {
+        match self.result_tabs.active {
+            ResultTab::Evaluation => self.evaluation_text.clone(),
+            tab => self.result_tabs.text(tab),
+        }
+    }
+
+    /// 表示中の評価結果をクリップボードにコピーする
+    pub fn copy_evaluation(&mut self) {
+        self.status_message = match self.clipboard.copy(&self.evaluation_text) {
+            Ok(CopyMethod::System) => "評価結果をコピーしました。".to_string(),
+            Ok(CopyMethod::Osc52) => "評価結果を端末 (OSC 52) 経由でコピーしました。".to_string(),
+            Err(e) => format!("コピーに失敗しました: {e}"),
+        };
+    }
+
+    /// 直前の結果をチャレンジファイルとして書き出す
+    pub fn export_challenge(&mut self) {
+        let Some(record) = self.last_session.as_ref() else {
+            return;
+        };
+        let challenge = Challenge::new(
+            record.character_count,
+            self.difficulty,
+            record.original_text.clone(),
+            record.passed,
+            record.evaluation.clone(),
+        );
+        let locale = self.config.locale;
+        self.status_message = match challenge.export() {
+            Ok(path) => trf(
+                locale,
+                "チャレンジファイルを保存しました: {}",
+                &[&path.display()],
+            ),
+            Err(e) => trf(locale, "チャレンジファイルの保存に失敗しました: {}", &[&e]),
+        };
+    }
+
+    /// 直前のセッションを Markdown の学習記録として書き出す
+    pub fn export_journal(&mut self) {
+        let Some(record) = self.last_session.as_ref() else {
+            return;
+        };
+        let locale = self.config.locale;
+        self.status_message =
+            match journal::export_session(&self.config.journal, record, &self.result_tabs) {
+                Ok(path) => trf(locale, "学習記録を保存しました: {}", &[&path.display()]),
+                Err(e) => trf(locale, "学習記録の保存に失敗しました: {}", &[&e]),
+            };
+    }
+
+    pub fn accept_flashback(&mut self) {
+        let Some(record) = self.flashback.take() else {
+            return;
+        };
+        self.character_count = record.character_count;
+        if let Some(index) = MENU_OPTIONS
+            .iter()
+            .position(|&count| count == record.character_count)
+        {
+            self.selected_menu_item = index;
+        }
+        self.set_original_text(record.original_text.clone(), None);
+        self.comparison_base = Some(record);
+        self.view_mode = ViewMode::Normal;
+        self.status_message = STATUS_FLASHBACK_RETRY.to_string();
+    }
+
+    pub fn dismiss_flashback(&mut self) {
+        self.flashback = None;
+    }
+
+    /// 前回の下書きの原文と要約を戻し、続きから書けるようにする
+    pub fn restore_draft(&mut self) {
+        let Some(draft) = self.recovered_draft.take() else {
+            return;
+        };
+        self.character_count = draft.character_count;
+        if let Some(index) = MENU_OPTIONS
+            .iter()
+            .position(|&count| count == draft.character_count)
+        {
+            self.selected_menu_item = index;
+        }
+        self.set_original_text(draft.original_text, None);
+        self.text_area_state = Self::new_text_area_state();
+        self.text_area_state.insert_str(&draft.summary);
+        self.view_mode = ViewMode::Normal;
+        self.status_message = STATUS_DRAFT_RESTORED.to_string();
+    }
+
+    pub fn discard_draft(&mut self) {
+        self.recovered_draft = None;
+        self.clear_draft();
+    }
+
+    /// 評価に出す前の書きかけの要約。何も書いていないときや、保存しても続きを書けないモードでは `None`
+    fn current_draft(&self) -> Option<SessionDraft> {
+        let summary = self.text_area_state.value();
+        (self.has_unconsumed_text()
+            && self.quiz.is_none()
+            && self.hot_seat.is_none()
+            && self.synthesis.is_none()
+            && !summary.trim().is_empty())
+        .then(|| SessionDraft {
+            original_text: self.original_text.clone(),
+            summary,
+            character_count: self.character_count,
+            saved_at: Local::now(),
+        })
+    }
+
+    /// 一定の間隔で書きかけの要約を保存する。評価に出すか次の文章に進んだら下書きを消す
+    pub fn autosave_draft(&mut self) {
+        if self.recovered_draft.is_some() || !self.draft_autosaver.is_due(Instant::now()) {
+            return;
+        }
+        let draft = self.current_draft();
+        if let Err(e) = self.draft_autosaver.update(draft) {
+            self.status_message = trf(
+                self.config.locale,
+                "警告: 下書きの保存に失敗しました: {}",
+                &[&e],
+            );
+        }
+    }
+
+    /// 終了するときや復元しないことにしたときに、保存した下書きを消す
+    pub fn clear_draft(&mut self) {
+        if let Err(e) = self.draft_autosaver.clear() {
+            self.status_message = trf(
+                self.config.locale,
+                "警告: 下書きの削除に失敗しました: {}",
+                &[&e],
+            );
+        }
+    }
+
+    pub fn begin_editing(&mut self) {
+        self.scratchpad.text_area.focus.set(false);
+        self.text_area_state.focus.set(true);
+        self.text_area_state.scroll_cursor_to_visible();
+        self.status_message = STATUS_EDITING.to_string();
+    }
+
+    /// 要約欄の入力方式 (通常 / 内蔵のかな入力) を切り替える
+    pub fn toggle_kana_input(&mut self) {
+        if let Some(text) = self.kana_input.toggle() {
+            self.text_area_state.insert_str(text);
+        }
+        self.status_message = if self.kana_input.enabled {
+            STATUS_KANA_INPUT
+        } else {
+            STATUS_EDITING
+        }
+        .to_string();
+    }
+
+    /// かな入力で変換中の文字列を確定して要約欄に挿入する
+    pub fn commit_kana_input(&mut self) {
+        if self.kana_input.is_composing() {
+            let text = self.kana_input.commit();
+            self.text_area_state.insert_str(text);
+        }
+    }
+
+    /// メモ欄を開いて入力を始める
+    pub fn open_scratchpad(&mut self) {
+        self.text_area_state.focus.set(false);
+        self.scratchpad.open();
+        self.status_message = STATUS_SCRATCHPAD.to_string();
+    }
+
+    pub fn toggle_scratchpad(&mut self) {
+        self.scratchpad.toggle();
+        if !self.scratchpad.is_editing() {
+            self.status_message = STATUS_NORMAL.to_string();
+        }
+    }
+
+    /// 原文欄の下の用語解説を切り替える。表示したときに未取得ならメインループが取得する
+    pub fn toggle_glossary(&mut self) {
+        self.glossary.toggle();
+    }
+
+    /// 表示中の原文の用語解説を取得する必要があるか
+    pub fn needs_glossary_lookup(&self) -> bool {
+        self.view_mode == ViewMode::Normal
+            && self.has_training_started()
+            && self.glossary.needs_lookup(&self.original_text)
+    }
+
+    /// 取得した用語解説を原文に結び付けて覚える。失敗したときは空の一覧を覚え、再取得を繰り返さない
+    pub fn apply_glossary(&mut self, text: &str, response: Result<String, AppError>) {
+        let entries = response
+            .and_then(|response| glossary::parse_glossary(&response))
+            .unwrap_or_default();
+        self.glossary.store(text, entries);
+    }
+
+    /// 原文欄の読み仮名表示を切り替える。表示したときに未取得ならメインループが取得する
+    pub fn toggle_furigana(&mut self) {
+        self.furigana.toggle();
+    }
+
+    /// 表示中の原文の読み仮名を取得する必要があるか。統合要約モードの資料には付けない
+    pub fn needs_furigana_lookup(&self) -> bool {
+        self.view_mode == ViewMode::Normal
+            && self.has_training_started()
+            && self.synthesis_sources().is_none()
+            && self.furigana.needs_lookup(&self.original_text)
+    }
+
+    /// 取得した読み仮名付きの文章を原文に結び付けて覚える。失敗したときは再取得を繰り返さない
+    pub fn apply_furigana(&mut self, text: &str, response: Result<String, AppError>) {
+        let annotated = response
+            .and_then(|response| furigana::parse_furigana(&response, text))
+            .ok();
+        self.furigana.store(text, annotated);
+    }
+
+    /// 要約欄に示し、評価で確かめる文字数の目安。出題前は出さない
+    pub fn summary_length_target(&self) -> Option<LengthTarget> {
+        if !self.has_training_started() {
+            return None;
+        }
+        LengthTarget::new(self.length_goal, &self.original_text)
+    }
+
+    /// 原文欄に表示する文章。読み仮名を表示していて取得済みなら、読み仮名付きの文章。
+    /// 語を選んでいる間は、語の位置がずれないよう原文のまま表示する
+    pub fn displayed_original_text(&self) -> &str {
+        if !self.furigana.visible || self.word_selection.is_some() {
+            return &self.original_text;
+        }
+        match self.furigana.annotation(&self.original_text) {
+            Annotation::Ready(annotated) => annotated,
+            Annotation::Pending | Annotation::Failed => &self.original_text,
+        }
+    }
+
+    /// 原文欄で語を選び始める。統合要約モードの資料では選べない
+    pub fn begin_word_selection(&mut self) {
+        if self.synthesis_sources().is_some() {
+            return;
+        }
+        self.word_selection = WordSelection::new(&self.original_text);
+        if self.word_selection.is_some() {
+            self.status_message = STATUS_WORD_SELECTION.to_string();
+        }
+    }
+
+    pub fn end_word_selection(&mut self) {
+        self.word_selection = None;
+        self.status_message = STATUS_NORMAL.to_string();
+    }
+
+    /// 選んでいる語を調べる。手元の辞書にあればその場で、なければメインループがモデルに問い合わせる
+    pub fn lookup_selected_word(&mut self) {
+        let Some(word) = self
+            .word_selection
+            .as_ref()
+            .and_then(WordSelection::current)
+        else {
+            return;
+        };
+        self.word_lookup = Some(match self.dictionary.lookup(&word.text) {
+            Some(entry) => LookupState::Found(entry),
+            None => LookupState::Pending {
+                word: word.text.clone(),
+                sentence: word_lookup::sentence_around(&self.original_text, word),
+            },
+        });
+        self.popup = Some(Popup::WordLookup);
+    }
+
+    /// モデルに問い合わせる語と、その語を含む一文
+    pub fn pending_word_lookup(&self) -> Option<(String, String)> {
+        match &self.word_lookup {
+            Some(LookupState::Pending { word, sentence }) => Some((word.clone(), sentence.clone())),
+            _ => None,
+        }
+    }
+
+    pub fn apply_word_lookup(&mut self, word: &str, response: Result<String, AppError>) {
+        self.word_lookup = Some(
+            response
+                .and_then(|response| word_lookup::parse_lookup(&response, word))
+                .map_or_else(
+                    |_| LookupState::Failed {
+                        word: word.to_string(),
+                    },
+                    LookupState::Found,
+                ),
+        );
+    }
+
+    /// 調べた語を単語帳に加えて保存する
+    pub fn add_lookup_to_vocab(&mut self) {
+        let Some(LookupState::Found(entry)) = self.word_lookup.clone() else {
+            return;
+        };
+        let word = entry.word.clone();
+        self.status_message = if !self.vocab.add(entry, Local::now()) {
+            format!("「{word}」はすでに単語帳にあります。")
+        } else if let Err(e) = self.vocab.save() {
+            format!("警告: 単語帳の保存に失敗しました: {e}")
+        } else {
+            format!(
+                "「{word}」を単語帳に追加しました ({} 語)。",
+                self.vocab.entries.len()
+            )
+        };
+        self.close_word_lookup();
+    }
+
+    pub fn close_word_lookup(&mut self) {
+        self.word_lookup = None;
+        self.popup = None;
+    }
+
+    /// 直前の AI 評価に異議を申し立てるダイアログを開く。模擬試験と自己採点は対象外
+    pub fn begin_appeal(&mut self) {
+        if self.offline.is_some() {
+            self.reject_offline_mode();
+            return;
+        }
+        if self.exam.is_none()
+            && self
+                .last_session
+                .as_ref()
+                .is_some_and(|record| record.evaluation.is_some())
+        {
+            self.appeal = Some(Appeal::new());
+            self.popup = Some(Popup::Appeal);
+        }
+    }
+
+    /// 評価者に送る主張と、異議を申し立てている評価、これまでのやり取り
+    pub fn pending_appeal(&self) -> Option<(SessionRecord, Vec<Exchange>, String)> {
+        let argument = self.appeal.as_ref()?.pending_argument()?;
+        let record = self.last_session.clone()?;
+        Some((
+            record,
+            self.result_tabs.conversation.clone(),
+            argument.to_string(),
+        ))
+    }
+
+    /// 返事は評価結果の会話タブにも残し、ダイアログを閉じたら会話タブが見えるようにしておく
+    pub fn apply_appeal_reply(&mut self, response: Result<String, AppError>) {
+        if let Some(exchange) = self
+            .appeal
+            .as_mut()
+            .and_then(|appeal| appeal.apply_reply(response))
+        {
+            self.result_tabs.conversation.push(exchange);
+            self.result_tabs.select(ResultTab::Conversation);
+        }
+    }
+
+    /// 評価者の返事を読んだ後、記録した合否を逆にする。もう一度押すと評価どおりに戻る
+    pub fn toggle_appeal_override(&mut self) {
+        if self.appeal.is_none() || self.result_tabs.conversation.is_empty() {
+            return;
+        }
+        let Some(record) = self.last_session.as_mut() else {
+            return;
+        };
+        record.passed = !record.passed;
+        let passed = record.passed;
+        self.stats.override_last_result(passed);
+        self.verdict = Verdict::from_passed(passed);
+        self.save_stats();
+        self.status_message = if passed {
+            "異議を認め、合格として記録し直しました。".to_string()
+        } else {
+            "不合格として記録し直しました。".to_string()
+        };
+    }
+
+    pub fn close_appeal(&mut self) {
+        self.appeal = None;
+        self.popup = None;
+    }
+
+    /// 直前のセッションの評価について質問するコーチ画面を開く。別のセッションなら会話を始め直す
+    pub fn enter_coach_view(&mut self) {
+        if self.offline.is_some() {
+            self.reject_offline_mode();
+            return;
+        }
+        let (Some(record), Some(client)) = (self.last_session.as_ref(), self.api_client.as_ref())
+        else {
+            return;
+        };
+        if self
+            .coach
+            .as_ref()
+            .is_none_or(|coach| coach.session != record.timestamp)
+        {
+            client.start_coaching(record);
+            self.coach = Some(CoachChat::new(record.timestamp));
+        }
+        self.view_mode = ViewMode::Coach;
+        self.status_message = STATUS_COACH.to_string();
+    }
+
+    /// コーチに送る質問。返事を待っている間だけ `Some`
+    pub fn pending_coach_question(&self) -> Option<String> {
+        self.coach.as_ref()?.pending.clone()
+    }
+
+    /// 返事を受け取れなかったときは、質問を入力欄に戻して送り直せるようにする
+    pub fn apply_coach_reply(&mut self, response: Result<String, AppError>) {
+        let Some(coach) = self.coach.as_mut() else {
+            return;
+        };
+        let question = coach.pending.take();
+        if let Err(e) = response {
+            if let Some(question) = question {
+                coach.input.set_text(&question);
+            }
+            coach.error = Some(e.to_string());
+        }
+    }
+
+    pub fn stop_editing_scratchpad(&mut self) {
+        self.scratchpad.text_area.focus.set(false);
+        self.status_message = STATUS_NORMAL.to_string();
+    }
+
+    /// 履歴に残すメモ。設定で残さないことにしている場合は `None`
+    pub fn scratchpad_notes_for_history(&self) -> Option<String> {
+        if !self.config.scratchpad.save_to_history {
+            return None;
+        }
+        self.scratchpad.notes()
+    }
+
+    /// カーソルの直前の略語を設定した定型句に置き換える。置き換えたら `true`
+    pub fn expand_abbreviation(&mut self) -> bool {
+        let cursor = self.text_area_state.cursor();
+        let Ok(before) = self
+            .text_area_state
+            .try_str_slice((0, cursor.y)..(cursor.x, cursor.y))
+        else {
+            return false;
+        };
+        let Some((abbreviation, expansion)) =
+            abbreviations::find_expansion(&before, &self.config.abbreviations)
+        else {
+            return false;
+        };
+        let width = u32::try_from(abbreviation.chars().count()).unwrap_or(u32::MAX);
+        let expansion = expansion.to_string();
+        let start = (cursor.x.saturating_sub(width), cursor.y);
+        if self
+            .text_area_state
+            .try_delete_range(start..(cursor.x, cursor.y))
+            .is_err()
+        {
+            return false;
+        }
+        self.text_area_state.insert_str(expansion);
+        true
+    }
+
+    pub fn stop_editing(&mut self) {
+        self.text_area_state.focus.set(false);
+        if self.zen == ZenMode::Peeking {
+            self.zen = ZenMode::Writing;
+        }
+        self.status_message = STATUS_NORMAL.to_string();
+    }
+
+    pub fn begin_training_generation(&mut self, is_next_training: bool) {
+        self.view_mode = ViewMode::Normal;
+        self.training_state = TrainingState::Generating(Instant::now());
+        self.status_message = if is_next_training {
+            STATUS_NEXT_GENERATING
+        } else {
+            STATUS_GENERATING
+        }
+        .to_string();
+    }
+
+    /// 出題する文章を差し替え、文体と出題時刻を記録する
+    fn set_original_text(&mut self, text: String, style: Option<TextStyle>) {
+        self.original_text = text;
+        self.text_style = style;
+        self.start_session_clock();
+        self.submitted_at = None;
+        self.training_state = TrainingState::Ready;
+        self.last_error = None;
+        if let Some(guided) = self.guided.as_mut() {
+            *guided = GuidedSession::new(&self.original_text);
+        }
+        self.offer_keyword_prediction();
+    }
+
+    /// 設定で有効なら、本文の代わりに冒頭の段落だけを見せてキーワードを予想させる。
+    /// 本文の内容を問う 4 択問題や、資料が 2 つある統合要約、時間を計る模擬試験などでは行わない
+    fn offer_keyword_prediction(&mut self) {
+        self.keyword_prediction = None;
+        if !self.config.prereading.keywords
+            || self.popup.is_some()
+            || self.tutorial
+            || self.quiz.is_some()
+            || self.exam.is_some()
+            || self.synthesis.is_some()
+            || self.hot_seat.is_some()
+        {
+            return;
+        }
+        let Some(preview) = guided::split_paragraphs(&self.original_text)
+            .into_iter()
+            .next()
+        else {
+            return;
+        };
+        self.keyword_prediction = Some(KeywordPrediction::new(preview));
+        self.popup = Some(Popup::KeywordPrediction);
+    }
+
+    /// 予想を確定して本文を表示する。読み始めた時刻は本文を表示した時点からにする
+    pub fn confirm_keyword_prediction(&mut self) {
+        if self
+            .keyword_prediction
+            .as_mut()
+            .is_some_and(KeywordPrediction::confirm)
+        {
+            self.popup = None;
+            self.start_session_clock();
+        }
+    }
+
+    pub fn skip_keyword_prediction(&mut self) {
+        self.keyword_prediction = None;
+        self.popup = None;
+        self.start_session_clock();
+    }
+
+    /// 読み書きの時間を今から計り直す。一時停止の記録も消す
+    fn start_session_clock(&mut self) {
+        self.text_shown_at = Some(Local::now());
+        self.pause = SessionPause::default();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.pause.since.is_some()
+    }
+
+    /// 提出前のセッションを一時停止するか再開する。一時停止中は経過時間と持ち時間を止め、原文を隠す
+    pub fn toggle_pause(&mut self) {
+        if let Some(since) = self.pause.since.take() {
+            let paused = Local::now() - since;
+            self.pause.total += paused;
+            // 止めていた時間は、休憩を促すまでの読み続けた時間にも数えない
+            self.reading_since += paused.to_std().unwrap_or_default();
+            self.status_message = STATUS_NORMAL.to_string();
+            return;
+        }
+        if !self.has_training_started()
+            || self.submitted_at.is_some()
+            || self.is_evaluating()
+            || self.is_predicting_keywords()
+        {
+            return;
+        }
+        self.stop_editing();
+        self.pause.since = Some(Local::now());
+        self.status_message = STATUS_PAUSED.to_string();
+    }
+
+    /// 評価者が挙げたキーワードで予想を答え合わせする。予想は 1 回の評価で使い切る
+    pub fn score_keyword_prediction(&mut self, extracted: &[String]) -> Option<KeywordScore> {
+        self.keyword_prediction.take()?.score(extracted)
+    }
+
+    pub fn is_predicting_keywords(&self) -> bool {
+        self.keyword_prediction
+            .as_ref()
+            .is_some_and(KeywordPrediction::is_predicting)
+    }
+
+    /// 提出した要約を原文と比べて測った指標
+    pub fn measure_summary(&self, summary: &str) -> Option<SummaryMetrics> {
+        SummaryMetrics::measure(summary, &self.original_text)
+    }
+
+    /// 要約のうち原文と同じ表現の割合 (%)
+    pub fn verbatim_percent(&self, summary: &str) -> u8 {
+        verbatim::overlap_percent(summary, &self.original_text)
+    }
+
+    /// 原文と同じ表現の割合が目安を超えていれば、評価結果に付ける注意
+    pub fn verbatim_warning(&self, summary: &str) -> Option<String> {
+        let percent = self.verbatim_percent(summary);
+        let max_percent = self.config.verbatim.max_percent();
+        (percent > max_percent).then(|| verbatim::format_warning(percent, max_percent))
+    }
+
+    /// `[verbatim] block` のとき、書き写しが目安を超えた要約は提出させずに入力へ戻す。戻したら `true`
+    pub fn blocks_verbatim_submission(&mut self) -> bool {
+        if !self.config.verbatim.block {
+            return false;
+        }
+        let percent = self.verbatim_percent(&self.text_area_state.value());
+        let max_percent = self.config.verbatim.max_percent();
+        if percent <= max_percent {
+            return false;
+        }
+        self.begin_editing();
+        self.status_message = trf(
+            self.config.locale,
+            "要約の {}% が原文と同じ表現です (目安 {}% 以下)。自分の言葉で書き直してください。",
+            &[&percent, &max_percent],
+        );
+        true
+    }
+
+    pub fn apply_generated_text(&mut self, text: String) {
+        self.set_original_text(text, self.text_style);
+        if let Some(message) = self.hot_seat_turn_message() {
+            self.status_message = message;
+            return;
+        }
+        self.status_message = if self.level_round == Some(LevelRound::Promotion) {
+            STATUS_PROMOTION_EXAM
+        } else {
+            STATUS_NORMAL
+        }
+        .to_string();
+    }
+
+    /// 復習日を過ぎた文章があれば、新しい文章の前にそれらを出題する復習モードを始める
+    pub fn begin_review(&mut self) -> bool {
+        if self.review_queue.due_count(Local::now().date_naive()) == 0 {
+            self.status_message = STATUS_NO_REVIEWS.to_string();
+            return false;
+        }
+        self.begin_free_training();
+        self.review_session = Some(ReviewSession::default());
+        true
+    }
+
+    /// 復習モード中なら、復習日を過ぎた文章を原文にする。なくなったら復習モードを終える
+    pub fn resume_review_text(&mut self) -> bool {
+        let Some(session) = self.review_session.as_mut() else {
+            return false;
+        };
+        let today = Local::now().date_naive();
+        let Some(item) = self.review_queue.next_due(today).cloned() else {
+            self.review_session = None;
+            if let Some(&count) = MENU_OPTIONS.get(self.selected_menu_item) {
+                self.character_count = count;
+            }
+            self.status_message = STATUS_REVIEWS_FINISHED.to_string();
+            return false;
+        };
+        session.current = Some(item.text.clone());
+        self.character_count = item.character_count;
+        self.set_original_text(item.text, None);
+        self.status_message = trf(
+            self.config.locale,
+            "復習 (残り {} 件): 以前不合格だった文章です。'i' で入力します。",
+            &[&self.review_queue.due_count(today)],
+        );
+        true
+    }
+
+    /// 復習中の文章なら次の復習日を決め、そうでなければ不合格の文章を復習に加える
+    pub fn record_review_result(&mut self, passed: bool, scores: Option<&EvaluationScores>) {
+        let today = Local::now().date_naive();
+        let current = self
+            .review_session
+            .as_mut()
+            .and_then(|session| session.current.take());
+        if let Some(text) = current {
+            self.review_queue
+                .grade(&text, review::quality(passed, scores), today);
+        } else if !passed {
+            self.review_queue
+                .add_failed(self.original_text.clone(), self.character_count, today);
+        } else {
+            return;
+        }
+        if let Err(e) = self.review_queue.save() {
+            self.status_message = trf(
+                self.config.locale,
+                "警告: 復習リストの保存に失敗しました: {}",
+                &[&e],
+            );
+        }
+    }
+
+    /// 以前のセッションで使わなかった同じ文字数の文章があれば、それを原文にする
+    pub fn resume_pending_text(&mut self) -> bool {
+        if self.level_round.is_some() || self.daily_challenge.is_some() {
+            return false;
+        }
+        let Some(text) = self
+            .pending
+            .take_for(self.character_count, self.session_started_at)
+        else {
+            return false;
+        };
+        self.set_original_text(text, None);
+        self.status_message = if let Err(e) = self.pending.save() {
+            format!("警告: 保留リストの保存に失敗しました: {e}")
+        } else {
+            STATUS_PENDING_RESUMED.to_string()
+        };
+        true
+    }
+
+    pub fn has_unconsumed_text(&self) -> bool {
+        !self.tutorial
+            && self.has_training_started()
+            && !self.show_evaluation_overlay
+            && !self.is_evaluating()
+            && self.evaluation_text.is_empty()
+    }
+
+    /// 未使用テキストに残せる文章か。次回ふつうの文章として出すので、生成したふつうの文章だけを残す
+    fn can_stash_text(&self) -> bool {
+        let reviewing = self
+            .review_session
+            .as_ref()
+            .is_some_and(|session| session.current.is_some());
+        self.has_unconsumed_text()
+            && self.comparison_base.is_none()
+            && self.challenge.is_none()
+            && self.imported_from.is_none()
+            && self.synthesis.is_none()
+            && self.offline.is_none()
+            && self.daily_challenge.is_none()
+            && self.exam.is_none()
+            && self.quiz.is_none()
+            && !reviewing
+    }
+
+    /// 要約を送信せずに終了する場合、生成済みの文章を未使用テキストとして残す。
+    /// 端末を戻す前に呼ばれるので、保存の失敗は呼び出し側が端末を戻してから知らせる
+    pub fn stash_unconsumed_text(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.can_stash_text() {
+            return Ok(());
+        }
+        self.pending
+            .push(self.original_text.clone(), self.character_count);
+        self.pending.save()
+    }
+
+    pub fn defer_current_text(&mut self) {
+        self.pending
+            .push(self.original_text.clone(), self.character_count);
+        let saved = self.pending.save();
+        self.prepare_next_training();
+        self.status_message = if let Err(e) = saved {
+            format!("警告: 保留リストの保存に失敗しました: {e}")
+        } else {
+            STATUS_DEFERRED.to_string()
+        };
+    }
+
+    /// 生成を待つのをやめてメニューに戻る
+    pub fn cancel_generation(&mut self) {
+        self.training_state = TrainingState::NotStarted;
+        self.view_mode = ViewMode::Menu;
+        self.status_message = STATUS_GENERATION_CANCELLED.to_string();
+    }
+
+    pub fn apply_generation_error(&mut self, error: &AppError) {
+        self.training_state = TrainingState::GenerationFailed;
+        self.show_error(error.notice("文章の生成"));
+    }
+
+    /// エラーの内容と対処方法を重ねて表示する。他のダイアログの表示中はステータス欄だけに出す
+    pub fn show_error(&mut self, notice: ErrorNotice) {
+        if self.popup.is_none() {
+            self.popup = Some(Popup::Error);
+            self.status_message = STATUS_RUNTIME_ERROR.to_string();
+        } else {
+            let locale = self.config.locale;
+            self.status_message = trf(
+                locale,
+                "{}に失敗しました。 {}",
+                &[&tr(locale, &notice.action), &notice.detail],
+            );
+        }
+        self.last_error = Some(notice);
+    }
+
+    /// 文章を生成できていなければメニューに戻り、やり直せるようにする。
+    /// エラーの内容はエラー欄に残す
+    pub fn dismiss_error(&mut self) {
+        self.popup = None;
+        self.return_from_aux_view();
+    }
+
+    pub fn begin_evaluation(&mut self) {
+        self.submitted_at.get_or_insert_with(Local::now);
+        self.evaluation_started_at = Some(Instant::now());
+        self.status_message = STATUS_EVALUATING.to_string();
+    }
+
+    /// 評価はバックグラウンドで進むので、その間は入力の編集や再送信を受け付けない
+    pub fn is_evaluating(&self) -> bool {
+        self.evaluation_started_at.is_some()
+    }
+
+    /// 次に問い合わせる依頼。用語解説・読み仮名・語の意味・異議・コーチ・段落の講評の順に見る
+    pub fn next_side_request(&self) -> Option<SideRequest> {
+        if self.needs_glossary_lookup() {
+            return Some(SideRequest::Glossary {
+                text: self.original_text.clone(),
+            });
+        }
+        if self.needs_furigana_lookup() {
+            return Some(SideRequest::Furigana {
+                text: self.original_text.clone(),
+            });
+        }
+        if let Some((word, sentence)) = self.pending_word_lookup() {
+            return Some(SideRequest::WordLookup { word, sentence });
+        }
+        if let Some((record, exchanges, argument)) = self.pending_appeal() {
+            return Some(SideRequest::Appeal {
+                record: Box::new(record),
+                exchanges,
+                argument,
+            });
+        }
+        if let Some(question) = self.pending_coach_question() {
+            return Some(SideRequest::Coach { question });
+        }
+        let (paragraph, summary) = self.pending_paragraph_review()?;
+        Some(SideRequest::ParagraphReview { paragraph, summary })
+    }
+
+    /// 問い合わせの応答を、依頼した状態に当てはめる。待つ間に閉じた画面や選び直した語には当てはめない
+    pub fn apply_side_response(
+        &mut self,
+        request: SideRequest,
+        response: Result<String, AppError>,
+    ) {
+        self.side_request_started_at = None;
+        match request {
+            SideRequest::Glossary { text } => self.apply_glossary(&text, response),
+            SideRequest::Furigana { text } => self.apply_furigana(&text, response),
+            SideRequest::WordLookup { word, .. } => {
+                if self
+                    .pending_word_lookup()
+                    .is_some_and(|(pending, _)| pending == word)
+                {
+                    self.apply_word_lookup(&word, response);
+                }
+            }
+            SideRequest::Appeal { .. } => self.apply_appeal_reply(response),
+            SideRequest::Coach { .. } => self.apply_coach_reply(response),
+            SideRequest::ParagraphReview { .. } => {
+                if self.pending_paragraph_review().is_some() {
+                    self.apply_paragraph_review(response);
+                }
+            }
+        }
+    }
+
+    /// Esc で取り消せる問い合わせを待っているか
+    pub fn is_waiting_side_request(&self) -> bool {
+        self.side_request_started_at.is_some()
+    }
+
+    /// 出題してからの経過時間。一時停止していた時間は数えず、提出後は提出までの時間で止まる
+    pub fn elapsed_since_shown(&self) -> Option<chrono::Duration> {
+        if self.training_state != TrainingState::Ready {
+            return None;
+        }
+        let shown_at = self.text_shown_at?;
+        let until = self
+            .submitted_at
+            .or(self.pause.since)
+            .unwrap_or_else(Local::now);
+        Some(until - shown_at - self.pause.total)
+    }
+
+    /// 読み書きにかかった秒数。まだ提出していなければ `None`
+    pub fn session_duration_secs(&self) -> Option<u32> {
+        self.submitted_at?;
+        u32::try_from(self.elapsed_since_shown()?.num_seconds()).ok()
+    }
+
+    /// 制限時間付きモードの残り時間。提出後は提出した時点の残りで止まる
+    pub fn time_remaining(&self) -> Option<chrono::Duration> {
+        let limit = chrono::Duration::from_std(self.time_limit?).ok()?;
+        Some(limit - self.elapsed_since_shown()?)
+    }
+
+    /// 提出前に持ち時間を使い切ったか
+    pub fn is_time_up(&self) -> bool {
+        self.submitted_at.is_none()
+            && self
+                .time_remaining()
+                .is_some_and(|remaining| remaining <= chrono::Duration::zero())
+    }
+
+    pub fn time_limit_secs(&self) -> Option<u32> {
+        u32::try_from(self.time_limit?.as_secs()).ok()
+    }
+
+    /// 監査ログに書き込めなかったら、状態表示で知らせる。応答はそのまま使っている
+    pub fn check_audit_log(&mut self) {
+        if let Some(e) = self
+            .api_client
+            .as_ref()
+            .and_then(ApiClient::take_audit_failure)
+        {
+            self.status_message = trf(
+                self.config.locale,
+                "警告: 監査ログの書き込みに失敗しました: {}",
+                &[&e],
+            );
+        }
+    }
+
+    /// API リクエストの再送を待っていれば、その状況
+    pub fn retry_notice(&self) -> Option<RetryNotice> {
+        self.api_client.as_ref().and_then(ApiClient::retry_notice)
+    }
+
+    /// 文章の生成か評価の応答を待っている時間。どちらも待っていなければ `None`
+    pub fn api_wait_elapsed(&self) -> Option<Duration> {
+        let started_at = match self.training_state {
+            TrainingState::Generating(started_at) => started_at,
+            _ => self
+                .evaluation_started_at
+                .or(self.side_request_started_at)?,
+        };
+        Some(started_at.elapsed())
+    }
+
+    pub fn spinner_frame(&self) -> Option<&'static str> {
+        let ticks = self.api_wait_elapsed()?.as_millis() / SPINNER_FRAME_MILLIS;
+        let index = usize::try_from(ticks % SPINNER_FRAMES.len() as u128).unwrap_or(0);
+        SPINNER_FRAMES.get(index).copied()
+    }
+
+    pub fn finish_evaluation(&mut self, text: String, passed: bool) {
+        self.evaluation_started_at = None;
+        self.last_error = None;
+        self.evaluation_text = text;
+        self.verdict = Verdict::from_passed(passed);
+        self.show_evaluation_overlay = true;
+        self.result_tabs.reset_view();
+        // モデルが前置きを書いていても、合否とスコアが先に見えるようにする
+        self.scroll_evaluation_to(VERDICT_MARKER);
+        self.status_message = STATUS_EVALUATED.to_string();
+    }
+
+    /// 評価タブの改善点の一覧へ移動する
+    pub fn jump_to_improvements(&mut self) {
+        if !self.scroll_evaluation_to(IMPROVEMENTS_MARKER) {
+            self.status_message = "この評価には改善点が含まれていません。".to_string();
+        }
+    }
+
+    /// 評価タブを `marker` を含む最初の行までスクロールする。見つからなければ `false`
+    fn scroll_evaluation_to(&mut self, marker: &str) -> bool {
+        let Some(line) = self
+            .evaluation_text
+            .lines()
+            .position(|text| text.contains(marker))
+        else {
+            return false;
+        };
+        let (visible_height, visible_width) = self.evaluation_viewport_size();
+        let total_rows = help::scroll_for_line(&self.evaluation_text, usize::MAX, visible_width);
+        let scroll = help::scroll_for_line(&self.evaluation_text, line, visible_width)
+            .min(total_rows.saturating_sub(visible_height));
+        self.result_tabs.select(ResultTab::Evaluation);
+        self.result_tabs.set_scroll(scroll);
+        true
+    }
+
+    /// 読み続けた時間が設定を超えたら休憩を促す。入力中や他のダイアログ表示中は待つ
+    pub fn check_break_reminder(&mut self) {
+        let reminder = &self.config.break_reminder;
+        if !reminder.enabled
+            || self.view_mode != ViewMode::Normal
+            || self.popup.is_some()
+            || self.text_area_state.focus.get()
+            || self.scratchpad.is_editing()
+            || self.is_paused()
+            || self.reading_since.elapsed() < reminder.interval()
+        {
+            return;
+        }
+        self.popup = Some(Popup::Break {
+            started_at: Instant::now(),
+        });
+    }
+
+    /// 休憩の残り時間。休憩中でなければ `None`
+    pub fn break_remaining(&self) -> Option<Duration> {
+        let Some(Popup::Break { started_at }) = self.popup else {
+            return None;
+        };
+        Some(
+            self.config
+                .break_reminder
+                .duration()
+                .saturating_sub(started_at.elapsed()),
+        )
+    }
+
+    /// カウントダウンが終わった休憩を閉じる
+    pub fn finish_break_if_elapsed(&mut self) {
+        if self.break_remaining() == Some(Duration::ZERO) {
+            self.end_break(false);
+        }
+    }
+
+    pub fn skip_break(&mut self) {
+        self.end_break(true);
+    }
+
+    fn end_break(&mut self, skipped: bool) {
+        let Some(Popup::Break { started_at }) = self.popup else {
+            return;
+        };
+        self.popup = None;
+        self.reading_since = Instant::now();
+        self.stats.breaks.push(BreakRecord {
+            timestamp: Local::now(),
+            duration_secs: started_at.elapsed().as_secs(),
+            skipped,
+        });
+        self.save_stats();
+    }
+
+    /// 統計の保存を予約する。書き込みはバックグラウンドで行う
+    pub fn save_stats(&mut self) {
+        if let Err(e) = self.stats_saver.request(&self.stats) {
+            self.status_message = trf(
+                self.config.locale,
+                "警告: 統計の保存に失敗しました: {}",
+                &[&e],
+            );
+        }
+    }
+
+    /// 1日の目標が設定されていれば、今日の (回数, 目標回数)
+    pub fn daily_goal_progress(&self) -> Option<(usize, usize)> {
+        let goal = self.config.daily_goal.filter(|&goal| goal > 0)?;
+        Some((self.stats.sessions_on(Local::now().date_naive()), goal))
+    }
+
+    /// 今日の目標に届いたら達成日として記録し、ステータスバーで知らせる
+    pub fn check_daily_goal(&mut self) {
+        let Some(goal) = self.config.daily_goal else {
+            return;
+        };
+        if self.stats.record_daily_goal(goal, Local::now()) {
+            self.status_message = trf(
+                self.config.locale,
+                "🎯 今日の目標 ({} 回) を達成しました！",
+                &[&goal],
+            );
+        }
+    }
+
+    /// 自己ベストを更新していればステータスバーで知らせる
+    pub fn check_personal_records(&mut self) {
+        let broken = self.stats.check_personal_records(Local::now());
+        if !broken.is_empty() {
+            let locale = self.config.locale;
+            let labels: Vec<String> = broken.into_iter().map(|kind| kind.label(locale)).collect();
+            let separator = match locale {
+                LabelLanguage::Ja => "、",
+                LabelLanguage::En => ", ",
+            };
+            self.status_message = trf(locale, "🏅 自己ベスト更新: {}", &[&labels.join(separator)]);
+        }
+    }
+
+    /// 新しい段位に上がっていれば、ほかのダイアログを出していないときに祝う
+    pub fn check_rank_up(&mut self) {
+        if let Some(rank) = self.stats.check_rank_up(Local::now())
+            && self.popup.is_none()
+        {
+            self.popup = Some(Popup::RankUp { rank });
+        }
+    }
+
+    /// バックグラウンドでの保存が再試行しても失敗していたら知らせる
+    pub fn check_stats_saved(&mut self) {
+        if let Some(e) = self.stats_saver.take_failure() {
+            self.status_message = trf(
+                self.config.locale,
+                "警告: 統計の保存に失敗しました: {}",
+                &[&e],
+            );
+        }
+    }
+
+    pub fn show_latency_prompt(&mut self) {
+        self.popup = Some(Popup::SlowEvaluation);
+        self.status_message = STATUS_SLOW_EVALUATION.to_string();
+    }
+
+    pub fn keep_waiting(&mut self) {
+        self.popup = None;
+        self.status_message = STATUS_EVALUATING.to_string();
+    }
+
+    pub fn cancel_evaluation(&mut self) {
+        self.evaluation_started_at = None;
+        self.popup = None;
+        self.status_message = STATUS_EVALUATION_CANCELLED.to_string();
+    }
+
+    pub fn begin_self_grade(&mut self) {
+        self.submitted_at.get_or_insert_with(Local::now);
+        self.evaluation_started_at = None;
+        self.popup = Some(Popup::SelfGrade);
+        self.status_message = STATUS_SELF_GRADE.to_string();
+    }
+
+    pub fn cancel_self_grade(&mut self) {
+        self.popup = None;
+        self.status_message = STATUS_NORMAL.to_string();
+    }
+
+    pub fn finish_self_grade(&mut self, passed: bool) {
+        self.popup = None;
+        self.last_error = None;
+        let verdict = if passed { "合格" } else { "不合格" };
+        self.evaluation_text = format!("- 自己採点: {verdict}\n");
+        self.verdict = Verdict::from_passed(passed);
+        self.show_evaluation_overlay = true;
+        self.result_tabs = ResultTabs::default();
+        self.status_message = STATUS_SELF_GRADED.to_string();
+    }
+
+    /// 持ち時間を使い切った要約を不合格として締め切る
+    pub fn finish_time_up(&mut self) {
+        self.stop_editing();
+        self.submitted_at = Some(Local::now());
+        self.popup = None;
+        self.evaluation_text = "- 時間切れ: 不合格\n".to_string();
+        self.verdict = Verdict::Failed;
+        self.show_evaluation_overlay = true;
+        self.result_tabs = ResultTabs::default();
+        self.status_message = STATUS_TIME_UP.to_string();
+    }
+
+    pub fn fail_evaluation_format(&mut self) {
+        self.evaluation_started_at = None;
+        self.evaluation_text = STATUS_INVALID_EVALUATION.to_string();
+        self.verdict = Verdict::Undetermined;
+        self.show_evaluation_overlay = true;
+        self.result_tabs = ResultTabs::default();
+        self.status_message = STATUS_INVALID_EVALUATION.to_string();
+    }
+
+    /// 1 人分の評価に失敗したときは要約を残し、エラーとともに自己採点で記録するかを尋ねる。
+    /// `error` が `None` なら評価結果の形式が不正だった
+    pub fn offer_self_grade_after_failure(&mut self, error: Option<&AppError>) {
+        self.evaluation_started_at = None;
+        self.evaluation_text.clear();
+        self.verdict = Verdict::default();
+        self.show_evaluation_overlay = false;
+        self.result_tabs = ResultTabs::default();
+        let notice = error.map_or_else(
+            || ErrorNotice {
+                category: ErrorCategory::Parse,
+                action: "要約の評価".to_string(),
+                detail: STATUS_INVALID_EVALUATION.to_string(),
+            },
+            |e| e.notice("要約の評価"),
+        );
+        if self.popup.is_some() {
+            self.show_error(notice);
+            return;
+        }
+        self.popup = Some(Popup::EvaluationFailed);
+        self.status_message = STATUS_EVALUATION_FAILED.to_string();
+        self.last_error = Some(notice);
+    }
+
+    /// 自己採点せずに閉じる。要約は残るので、あとから再送信できる
+    pub fn dismiss_evaluation_failure(&mut self) {
+        self.popup = None;
+        self.status_message = STATUS_EVALUATION_RETRYABLE.to_string();
+    }
+
+    /// 評価の依頼に失敗したときは要約を残し、再送信できるようにする
+    pub fn fail_evaluation_request(&mut self, error: &AppError) {
+        self.evaluation_started_at = None;
+        self.evaluation_text.clear();
+        self.verdict = Verdict::default();
+        self.show_evaluation_overlay = false;
+        self.result_tabs = ResultTabs::default();
+        self.show_error(error.notice("要約の評価"));
+    }
+
+    pub fn prepare_next_training(&mut self) {
+        if self.level_round.is_some() {
+            self.begin_level_challenge();
+        }
+        if let Some(hot_seat) = self.hot_seat.as_mut() {
+            hot_seat.reset();
+        }
+        if let Some(session) = self.quiz.as_mut() {
+            *session = QuizSession::default();
+        }
+        if self.exam.as_ref().is_some_and(ExamSession::is_finished) {
+            self.begin_free_training();
+        } else {
+            self.set_exam_round();
+        }
+        // 読み込んだ文章やチュートリアルの文字数ではなく、メニューで選んだ文字数で次を生成する
+        let tutorial_finished = std::mem::take(&mut self.tutorial);
+        if (self.imported_from.take().is_some() || tutorial_finished)
+            && let Some(&count) = MENU_OPTIONS.get(self.selected_menu_item)
+        {
+            self.character_count = count;
+        }
+        self.comparison_base = None;
+        self.challenge = None;
+        self.last_session = None;
+        self.show_evaluation_overlay = false;
+        self.evaluation_text.clear();
+        self.verdict = Verdict::default();
+        self.text_area_state = Self::new_text_area_state();
+        self.scratchpad.clear();
+        self.word_selection = None;
+        self.original_text_scroll = 0;
+        self.result_tabs = ResultTabs::default();
+        self.begin_training_generation(true);
+    }
+
+    pub fn update_terminal_size(&mut self, width: u16, height: u16) {
+        self.terminal_width = width;
+        self.terminal_height = height;
+    }
+
+    pub fn calculate_overlay_area(&self) -> Rect {
+        Self::calculate_overlay_area_for_size(self.terminal_width, self.terminal_height)
+    }
+
+    pub fn calculate_overlay_area_for_size(width: u16, height: u16) -> Rect {
+        let full_area = Rect::new(0, 0, width, height);
+        let max_overlay_width = full_area
+            .width
+            .saturating_sub(OVERLAY_MARGIN.saturating_mul(2));
+        let max_overlay_height = full_area
+            .height
+            .saturating_sub(OVERLAY_MARGIN.saturating_mul(2));
+
+        let overlay_width = full_area
+            .width
+            .saturating_mul(OVERLAY_SIZE_PERCENT)
+            .saturating_div(100)
+            .max(MIN_OVERLAY_WIDTH)
+            .min(max_overlay_width);
+        let overlay_height = full_area
+            .height
+            .saturating_mul(OVERLAY_SIZE_PERCENT)
+            .saturating_div(100)
+            .max(MIN_OVERLAY_HEIGHT)
+            .min(max_overlay_height);
+
+        Rect {
+            x: full_area.x + full_area.width.saturating_sub(overlay_width) / 2,
+            y: full_area.y + full_area.height.saturating_sub(overlay_height) / 2,
+            width: overlay_width,
+            height: overlay_height,
+        }
+    }
+
+    /// エラー欄の高さ。直近のエラーがなければ表示しない
+    pub fn error_line_height(&self) -> u16 {
+        if self.last_error.is_some() {
+            ERROR_LINE_HEIGHT
+        } else {
+            0
+        }
+    }
+
+    /// 原文欄の表示領域。統合要約モードでは上下に分けた資料 1 つ分
+    pub fn original_text_viewport_size(&self) -> (u16, u16) {
+        let original_area = self.pane_areas().original;
+        let mut content_height = original_area.height;
+        if self.glossary.visible {
+            content_height = content_height.saturating_sub(GLOSSARY_FOOTER_HEIGHT);
+        }
+        if self.synthesis_sources().is_some() {
+            content_height /= 2;
+        }
+        let pane_width = original_area.width;
         (
             content_height.saturating_sub(BLOCK_BORDER_SIZE),
             pane_width.saturating_sub(BLOCK_BORDER_SIZE),
         )
     }
 
+    /// 評価結果の本文の表示領域 (タブの行を除く)
     pub fn evaluation_viewport_size(&self) -> (u16, u16) {
-        let overlay_area = self.calculate_overlay_area();
+        let overlay_area = self
+            .pane_areas()
+            .result
+            .unwrap_or_else(|| self.calculate_overlay_area());
         (
-            overlay_area.height.saturating_sub(BLOCK_BORDER_SIZE),
+            overlay_area
+                .height
+                .saturating_sub(BLOCK_BORDER_SIZE + RESULT_TAB_BAR_HEIGHT + VERDICT_BANNER_HEIGHT),
             overlay_area.width.saturating_sub(BLOCK_BORDER_SIZE),
         )
     }
@@ -0,0 +1,195 @@
+use crate::error::AppError;
+use rat_text::text_input::TextInputState;
+use serde::Deserialize;
+use std::fmt::Write;
+
+/// 異議申し立ての 1 往復。評価者が考え直した合否を添える
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Exchange {
+    pub argument: String,
+    pub reply: String,
+    pub passed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AppealStatus {
+    Writing,
+    /// 送った主張への返事を待っている
+    Pending(String),
+    /// 返事を受け取れなかった理由。主張は入力欄に戻してある
+    Failed(String),
+}
+
+/// 評価の直後に、評価者へ主張を送って考え直してもらうダイアログの状態。
+/// やり取りは評価結果の会話タブ (`ResultTabs::conversation`) に残す
+pub struct Appeal {
+    pub input: TextInputState,
+    pub status: AppealStatus,
+}
+
+#[derive(Deserialize)]
+struct AppealResponse {
+    reply: String,
+    overall: String,
+}
+
+impl Appeal {
+    pub fn new() -> Self {
+        let input = TextInputState::new();
+        input.focus.set(true);
+        Self {
+            input,
+            status: AppealStatus::Writing,
+        }
+    }
+
+    /// 入力欄の主張を送る。空のときや返事を待っている間は送らない
+    pub fn submit(&mut self) -> bool {
+        let argument = self.input.text().trim().to_string();
+        if argument.is_empty() || matches!(self.status, AppealStatus::Pending(_)) {
+            return false;
+        }
+        self.input.set_text("");
+        self.status = AppealStatus::Pending(argument);
+        true
+    }
+
+    pub fn pending_argument(&self) -> Option<&str> {
+        match &self.status {
+            AppealStatus::Pending(argument) => Some(argument),
+            _ => None,
+        }
+    }
+
+    /// 返事を読み取れたら、送った主張と合わせた 1 往復を返す
+    pub fn apply_reply(&mut self, response: Result<String, AppError>) -> Option<Exchange> {
+        let AppealStatus::Pending(argument) = &self.status else {
+            return None;
+        };
+        let argument = argument.clone();
+        match response.and_then(|response| parse_reply(&response)) {
+            Ok((reply, passed)) => {
+                self.status = AppealStatus::Writing;
+                Some(Exchange {
+                    argument,
+                    reply,
+                    passed,
+                })
+            }
+            Err(e) => {
+                self.input.set_text(&argument);
+                self.status = AppealStatus::Failed(e.to_string());
+                None
+            }
+        }
+    }
+}
+
+/// これまでのやり取りも渡し、主張を踏まえて評価を考え直させる
+pub fn build_appeal_prompt(
+    original_text: &str,
+    summary_text: &str,
+    evaluation_text: &str,
+    exchanges: &[Exchange],
+    argument: &str,
+) -> String {
+    let mut history = String::new();
+    for exchange in exchanges {
+        let _ = write!(
+            history,
+            "受講者: {}\n評価者: {}\n",
+            exchange.argument, exchange.reply
+        );
+    }
+    format!(
+        r#"
+あなたは以下の「要約文」を評価した評価者です。受講者が評価に異議を申し立てています。
+原文・要約文・評価を見直し、受講者の主張に答えてください。
+
+# 回答ルール
+- 出力は必ず以下の「出力フォーマット」の JSON オブジェクトのみとすること
+- reply には、主張のどこが妥当でどこが妥当でないかを 200 文字以内で書くこと
+- overall は考え直した結果の "合格" または "不合格" のみ。主張に根拠がなければ元の評価を変えないこと
+
+# 出力フォーマット(厳守)
+{{"reply": "...", "overall": "不合格"}}
+
+# 原文
+{original_text}
+
+# 要約文
+{summary_text}
+
+# 評価
+{evaluation_text}
+
+# これまでのやり取り
+{history}
+# 受講者の主張
+{argument}
+"#
+    )
+}
+
+pub fn parse_reply(response: &str) -> Result<(String, bool), AppError> {
+    let (Some(start), Some(end)) = (response.find('{'), response.rfind('}')) else {
+        return Err(AppError::InvalidText(
+            "JSON が含まれていません。".to_string(),
+        ));
+    };
+    let answer: AppealResponse =
+        serde_json::from_str(response.get(start..=end).unwrap_or_default())?;
+    let passed = match answer.overall.trim() {
+        "合格" => true,
+        "不合格" => false,
+        other => {
+            return Err(AppError::InvalidText(format!(
+                "合否を読み取れません: {other}"
+            )));
+        }
+    };
+    if answer.reply.trim().is_empty() {
+        return Err(AppError::InvalidText("返事が空です。".to_string()));
+    }
+    Ok((answer.reply.trim().to_string(), passed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reply_is_recorded_and_a_failed_request_restores_the_argument() {
+        let mut appeal = Appeal::new();
+        assert!(!appeal.submit());
+        appeal.input.set_text(" 要点は押さえています ");
+        assert!(appeal.submit());
+        assert_eq!(appeal.pending_argument(), Some("要点は押さえています"));
+
+        assert_eq!(appeal.apply_reply(Err(AppError::Offline)), None);
+        assert!(matches!(appeal.status, AppealStatus::Failed(_)));
+        assert_eq!(appeal.input.text(), "要点は押さえています");
+
+        assert!(appeal.submit());
+        let exchange = appeal.apply_reply(Ok(
+            r#"考え直しました: {"reply": "確かに要点は含まれています。", "overall": "合格"}"#
+                .to_string(),
+        ));
+        assert_eq!(appeal.status, AppealStatus::Writing);
+        assert_eq!(exchange.as_ref().map(|e| e.passed), Some(true));
+
+        let exchanges: Vec<Exchange> = exchange.into_iter().collect();
+        let prompt = build_appeal_prompt("原文", "要約", "評価", &exchanges, "再度");
+        assert!(
+            prompt.contains("受講者: 要点は押さえています\n評価者: 確かに要点は含まれています。")
+        );
+        assert!(prompt.contains("# 受講者の主張\n再度"));
+    }
+
+    #[test]
+    fn replies_without_a_verdict_are_rejected() {
+        assert!(parse_reply("わかりません").is_err());
+        assert!(parse_reply(r#"{"reply": "…", "overall": "保留"}"#).is_err());
+        assert!(parse_reply(r#"{"reply": " ", "overall": "合格"}"#).is_err());
+    }
+}
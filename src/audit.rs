@@ -0,0 +1,137 @@
+use crate::config::AuditLogConfig;
+use crate::error::AppError;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+const APP_DIR_NAME: &str = "yomitore";
+const AUDIT_DIR_NAME: &str = "audit";
+
+#[derive(Serialize)]
+pub struct AuditEntry<'a> {
+    pub timestamp: DateTime<Local>,
+    pub model: &'a str,
+    pub prompt: &'a str,
+    pub response: Option<&'a str>,
+    pub error: Option<String>,
+}
+
+/// API に送ったプロンプトと生のレスポンスを日付ごとの JSONL に残す
+#[derive(Clone)]
+pub struct AuditLog {
+    dir: PathBuf,
+    /// 最後に書き込めなかった理由。複製どうしで共有し、評価のタスクからメインループに伝える
+    failure: Arc<Mutex<Option<String>>>,
+}
+
+impl AuditLog {
+    /// 無効化されている場合は `None` を返す
+    pub fn from_config(config: &AuditLogConfig) -> Result<Option<Self>, AppError> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let dir = match &config.dir {
+            Some(dir) => PathBuf::from(dir),
+            None => dirs::config_dir()
+                .ok_or(AppError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "設定ディレクトリが見つかりません。",
+                )))?
+                .join(APP_DIR_NAME)
+                .join(AUDIT_DIR_NAME),
+        };
+        fs::create_dir_all(&dir)?;
+        Ok(Some(Self {
+            dir,
+            failure: Arc::default(),
+        }))
+    }
+
+    pub fn record(&self, entry: &AuditEntry) -> Result<(), AppError> {
+        let path = self.file_path_for(entry.timestamp);
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    /// 書き込めなかったことを覚えておく。監査ログは任意なので、応答は捨てずに知らせるだけにする
+    pub fn report_failure(&self, error: &AppError) {
+        if let Ok(mut failure) = self.failure.lock() {
+            *failure = Some(error.to_string());
+        }
+    }
+
+    pub fn take_failure(&self) -> Option<String> {
+        self.failure.lock().ok()?.take()
+    }
+
+    fn file_path_for(&self, timestamp: DateTime<Local>) -> PathBuf {
+        self.dir
+            .join(format!("{}.jsonl", timestamp.format("%Y-%m-%d")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_config_builds_no_audit_log() {
+        let audit_log = AuditLog::from_config(&AuditLogConfig::default());
+        assert!(matches!(audit_log, Ok(None)));
+    }
+
+    #[test]
+    fn audit_files_are_split_by_day() {
+        let audit_log = AuditLog {
+            dir: PathBuf::from("/tmp/yomitore-audit"),
+            failure: Arc::default(),
+        };
+        let timestamp = DateTime::parse_from_rfc3339("2026-07-02T09:30:00+09:00")
+            .map(|t| t.with_timezone(&Local))
+            .unwrap_or_default();
+        let expected = format!("{}.jsonl", timestamp.format("%Y-%m-%d"));
+        assert_eq!(
+            audit_log.file_path_for(timestamp),
+            PathBuf::from("/tmp/yomitore-audit").join(expected)
+        );
+    }
+
+    #[test]
+    fn audit_entry_serializes_prompt_and_response() {
+        let entry = AuditEntry {
+            timestamp: Local::now(),
+            model: "model-x",
+            prompt: "要約してください",
+            response: Some("- 総合評価: 合格"),
+            error: None,
+        };
+        let json = serde_json::to_string(&entry).unwrap_or_default();
+        assert!(json.contains("\"model\":\"model-x\""));
+        assert!(json.contains("\"prompt\":\"要約してください\""));
+        assert!(json.contains("\"response\":\"- 総合評価: 合格\""));
+    }
+
+    #[test]
+    fn write_failures_are_kept_until_taken() {
+        let audit_log = AuditLog {
+            dir: PathBuf::from("/nonexistent/yomitore-audit"),
+            failure: Arc::default(),
+        };
+        let entry = AuditEntry {
+            timestamp: Local::now(),
+            model: "model-x",
+            prompt: "要約してください",
+            response: None,
+            error: None,
+        };
+        if let Err(e) = audit_log.record(&entry) {
+            audit_log.clone().report_failure(&e);
+        }
+        assert!(audit_log.take_failure().is_some());
+        assert_eq!(audit_log.take_failure(), None);
+    }
+}
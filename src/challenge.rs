@@ -0,0 +1,124 @@
+use crate::history;
+use crate::models::{Difficulty, EvaluationScores};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const APP_DIR_NAME: &str = "yomitore";
+const CHALLENGES_DIR_NAME: &str = "challenges";
+const CHALLENGE_FORMAT_VERSION: u32 = 1;
+
+/// 他のユーザーに渡して同じ文章に挑戦してもらうためのファイル。API キーは含めない
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Challenge {
+    pub version: u32,
+    pub created_at: DateTime<Local>,
+    pub character_count: u16,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    pub original_text: String,
+    pub passed: bool,
+    #[serde(default)]
+    pub evaluation: Option<EvaluationScores>,
+}
+
+impl Challenge {
+    pub fn new(
+        character_count: u16,
+        difficulty: Difficulty,
+        original_text: String,
+        passed: bool,
+        evaluation: Option<EvaluationScores>,
+    ) -> Self {
+        Self {
+            version: CHALLENGE_FORMAT_VERSION,
+            created_at: Local::now(),
+            character_count,
+            difficulty,
+            original_text,
+            passed,
+            evaluation,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
+        let challenge: Self = serde_json::from_str(&content)?;
+        if challenge.version > CHALLENGE_FORMAT_VERSION {
+            return Err(
+                "このチャレンジファイルは新しいバージョンの yomitore で作成されています。".into(),
+            );
+        }
+        Ok(challenge)
+    }
+
+    /// 設定ディレクトリの `challenges/` に書き出し、保存先を返す
+    pub fn export(&self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config_dir = dirs::config_dir().ok_or("設定ディレクトリが見つかりません。")?;
+        let dir = config_dir.join(APP_DIR_NAME).join(CHALLENGES_DIR_NAME);
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!(
+            "challenge-{}.json",
+            self.created_at.format("%Y%m%d-%H%M%S")
+        ));
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    pub fn format_comparison(&self, passed: bool, evaluation: Option<&EvaluationScores>) -> String {
+        let score = |evaluation: Option<&EvaluationScores>| {
+            evaluation.map_or(0, |scores| {
+                u16::from(scores.importance)
+                    + u16::from(scores.conciseness)
+                    + u16::from(scores.accuracy)
+            })
+        };
+        let verdict = match (self.passed, score(self.evaluation.as_ref()))
+            .cmp(&(passed, score(evaluation)))
+        {
+            std::cmp::Ordering::Less => "あなたの勝ち",
+            std::cmp::Ordering::Greater => "出題者の勝ち",
+            std::cmp::Ordering::Equal => "引き分け",
+        };
+        format!(
+            "\n# チャレンジ結果\n- 出題者 ({}): {}\n- あなた: {}\n- 判定: {verdict}\n",
+            self.created_at.format("%Y-%m-%d"),
+            history::format_result(self.passed, self.evaluation.as_ref()),
+            history::format_result(passed, evaluation)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn challenge_round_trips_without_secrets() -> Result<(), String> {
+        let challenge = Challenge::new(400, Difficulty::Hard, "原文".to_string(), true, None);
+        let json = serde_json::to_string(&challenge).map_err(|e| e.to_string())?;
+        if json.contains("api_key") {
+            return Err(format!("challenge leaked a secret: {json}"));
+        }
+
+        let loaded: Challenge = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+        if (
+            loaded.character_count,
+            loaded.difficulty,
+            loaded.original_text.as_str(),
+        ) != (400, Difficulty::Hard, "原文")
+        {
+            return Err("challenge did not round-trip".to_string());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn format_comparison_prefers_pass_over_fail() {
+        let challenge = Challenge::new(400, Difficulty::Standard, String::new(), false, None);
+        let text = challenge.format_comparison(true, None);
+        assert!(text.contains("- 出題者 ("));
+        assert!(text.contains("- あなた: 合格\n- 判定: あなたの勝ち"));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::app::MENU_OPTIONS;
+use crate::error::AppError;
+use crate::export::ExportOptions;
+use crate::models::{Difficulty, TextStyle};
+use crate::offline::OFFLINE_FLAG;
+use crate::stats_api::StatsOptions;
+use crate::text_cache::NO_CACHE_FLAG;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+使い方: yomitore [サブコマンド] [--offline] [--no-cache]
+
+サブコマンド:
+  (省略)                          メニューから始める
+  train [--chars N] [--genre G] [--difficulty D]
+                                  メニューを通さずに練習を始める
+                                  N: 400 / 720 / 1440 / 2880
+                                  G: news (新聞記事) / official (公的文書)
+                                  D: easy / standard / hard / expert
+  report                          レポート画面を開く
+  export [--format csv|json] [--out PATH]
+                                  結果を書き出す
+  stats --json [--since YYYY-MM-DD]
+                                  集計した統計を JSON で出力する
+  config set KEY VALUE            config.toml の項目を書き換える (例: config set model openai/gpt-oss-20b)
+  challenge PATH                  チャレンジファイルの文章に挑戦する
+  import PATH                     テキストファイルの文章で練習する
+  --stdin                         標準入力の文章で練習する
+  experiment                      評価プロンプトを比較する
+  help                            この説明を表示する";
+
+/// メニューを通さずに始める練習の条件。省略した項目はメニューの既定値のまま
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TrainOptions {
+    pub character_count: Option<u16>,
+    /// 省略時は文章ごとに文体を選ぶ
+    pub style: Option<TextStyle>,
+    pub difficulty: Option<Difficulty>,
+}
+
+/// コマンドラインで指定した操作
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Menu,
+    Train(TrainOptions),
+    Report,
+    Export(ExportOptions),
+    Stats(StatsOptions),
+    ConfigSet { key: String, value: String },
+    Challenge(PathBuf),
+    Import(PathBuf),
+    Stdin,
+    Experiment,
+    Help,
+}
+
+impl Command {
+    /// 実行ファイル名を除いた引数を解析する。`--offline` と `--no-cache` はどこに書いてもよい
+    pub fn parse(args: &[String]) -> Result<Self, AppError> {
+        let args: Vec<String> = args
+            .iter()
+            .filter(|arg| !matches!(arg.as_str(), OFFLINE_FLAG | NO_CACHE_FLAG))
+            .cloned()
+            .collect();
+        let Some((command, rest)) = args.split_first() else {
+            return Ok(Self::Menu);
+        };
+        let command = match command.as_str() {
+            "train" => Self::Train(TrainOptions::parse(rest)?),
+            "report" => Self::Report,
+            "export" => return Ok(Self::Export(ExportOptions::parse(rest)?)),
+            "stats" => return Ok(Self::Stats(StatsOptions::parse(rest)?)),
+            "config" => match rest {
+                [action, key, value] if action == "set" => Self::ConfigSet {
+                    key: key.clone(),
+                    value: value.clone(),
+                },
+                _ => return Err(usage_error("config set KEY VALUE の形で指定してください。")),
+            },
+            "challenge" => Self::Challenge(path_arg(rest, "チャレンジファイル")?),
+            "import" => Self::Import(path_arg(rest, "読み込むテキストファイル")?),
+            "--stdin" => Self::Stdin,
+            "experiment" => Self::Experiment,
+            "help" | "--help" | "-h" => Self::Help,
+            other => return Err(usage_error(&format!("不明なサブコマンドです: {other}"))),
+        };
+        match command {
+            Self::Train(_) | Self::ConfigSet { .. } | Self::Challenge(_) | Self::Import(_) => {}
+            _ if rest.is_empty() => {}
+            _ => return Err(usage_error(&format!("不明な引数です: {}", rest.join(" ")))),
+        }
+        Ok(command)
+    }
+}
+
+impl TrainOptions {
+    fn parse(args: &[String]) -> Result<Self, AppError> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| usage_error(&format!("{arg} の値を指定してください。")))
+            };
+            match arg.as_str() {
+                "--chars" => {
+                    let value = value()?;
+                    let count = value
+                        .parse()
+                        .ok()
+                        .filter(|count| MENU_OPTIONS.contains(count))
+                        .ok_or_else(|| {
+                            usage_error(&format!(
+                                "--chars は 400 / 720 / 1440 / 2880 のいずれかです: {value}"
+                            ))
+                        })?;
+                    options.character_count = Some(count);
+                }
+                "--genre" => {
+                    let value = value()?;
+                    options.style = Some(match value.as_str() {
+                        "news" => TextStyle::Newspaper,
+                        "official" => TextStyle::Official,
+                        _ => {
+                            return Err(usage_error(&format!(
+                                "--genre は news か official です: {value}"
+                            )));
+                        }
+                    });
+                }
+                "--difficulty" => {
+                    let value = value()?;
+                    options.difficulty = Some(match value.as_str() {
+                        "easy" => Difficulty::Easy,
+                        "standard" => Difficulty::Standard,
+                        "hard" => Difficulty::Hard,
+                        "expert" => Difficulty::Expert,
+                        _ => {
+                            return Err(usage_error(&format!(
+                                "--difficulty は easy / standard / hard / expert のいずれかです: {value}"
+                            )));
+                        }
+                    });
+                }
+                other => return Err(usage_error(&format!("不明な引数です: {other}"))),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn path_arg(args: &[String], what: &str) -> Result<PathBuf, AppError> {
+    match args {
+        [path] => Ok(PathBuf::from(path)),
+        _ => Err(AppError::InvalidConfig(format!(
+            "{what}のパスを指定してください。"
+        ))),
+    }
+}
+
+fn usage_error(message: &str) -> AppError {
+    AppError::InvalidConfig(format!("{message}\n\n{USAGE}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn subcommands_are_parsed_with_global_flags_anywhere() {
+        assert_eq!(Command::parse(&args(&[])).ok(), Some(Command::Menu));
+        assert_eq!(
+            Command::parse(&args(&[
+                "--offline",
+                "train",
+                "--chars",
+                "720",
+                "--genre",
+                "news"
+            ]))
+            .ok(),
+            Some(Command::Train(TrainOptions {
+                character_count: Some(720),
+                style: Some(TextStyle::Newspaper),
+                difficulty: None,
+            }))
+        );
+        assert_eq!(
+            Command::parse(&args(&["config", "set", "model", "openai/gpt-oss-20b"])).ok(),
+            Some(Command::ConfigSet {
+                key: "model".to_string(),
+                value: "openai/gpt-oss-20b".to_string(),
+            })
+        );
+        assert_eq!(
+            Command::parse(&args(&["import", "a.txt", "--no-cache"])).ok(),
+            Some(Command::Import(PathBuf::from("a.txt")))
+        );
+        assert_eq!(
+            Command::parse(&args(&["--stdin"])).ok(),
+            Some(Command::Stdin)
+        );
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        assert!(Command::parse(&args(&["train", "--chars", "500"])).is_err());
+        assert!(Command::parse(&args(&["train", "--genre", "essay"])).is_err());
+        assert!(Command::parse(&args(&["report", "--json"])).is_err());
+        assert!(Command::parse(&args(&["config", "get", "model"])).is_err());
+        assert!(Command::parse(&args(&["challenge"])).is_err());
+        assert!(Command::parse(&args(&["unknown"])).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use std::io::{self, Write};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// コピーに使った方法
+#[derive(Debug, PartialEq, Eq)]
+pub enum CopyMethod {
+    System,
+    Osc52,
+}
+
+/// システムのクリップボードへコピーし、使えない環境 (SSH, tmux など) では
+/// 端末の OSC 52 エスケープシーケンスでコピーする
+#[derive(Default)]
+pub struct Clipboard {
+    // X11 などではクリップボードの所有者が生きている間だけ内容が残るため保持しておく
+    system: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    pub fn copy(&mut self, text: &str) -> io::Result<CopyMethod> {
+        if self.system.is_none() {
+            self.system = arboard::Clipboard::new().ok();
+        }
+        if let Some(system) = self.system.as_mut()
+            && system.set_text(text).is_ok()
+        {
+            return Ok(CopyMethod::System);
+        }
+
+        let in_tmux = std::env::var_os("TMUX").is_some();
+        let mut stdout = io::stdout();
+        stdout.write_all(osc52_sequence(text, in_tmux).as_bytes())?;
+        stdout.flush()?;
+        Ok(CopyMethod::Osc52)
+    }
+}
+
+/// tmux の中では、外側の端末に届くようにパススルーで包む
+fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()));
+    if in_tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| u32::from(chunk.get(i).copied().unwrap_or(0)));
+        let triple = (a << 16) | (b << 8) | c;
+        for (position, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            if position <= chunk.len() {
+                let index = usize::try_from((triple >> shift) & 0x3f).unwrap_or(0);
+                encoded.push(char::from(
+                    BASE64_ALPHABET.get(index).copied().unwrap_or(b'A'),
+                ));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_encode_pads_output() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode("要約".as_bytes()), "6KaB57SE");
+    }
+
+    #[test]
+    fn osc52_sequence_wraps_for_tmux() {
+        assert_eq!(osc52_sequence("foo", false), "\x1b]52;c;Zm9v\x07");
+        assert_eq!(
+            osc52_sequence("foo", true),
+            "\x1bPtmux;\x1b\x1b]52;c;Zm9v\x07\x1b\\"
+        );
+    }
+}
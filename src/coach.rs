@@ -0,0 +1,174 @@
+use chrono::{DateTime, Local};
+use rat_text::text_input::TextInputState;
+use std::sync::{Arc, Mutex};
+
+/// 会話の発言者。`role` の名前は API のものに合わせる
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoachRole {
+    /// 原文・要約・評価を伝える最初の指示。画面には表示しない
+    System,
+    User,
+    Assistant,
+}
+
+impl CoachRole {
+    pub fn api_name(self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::User => "user",
+            Self::Assistant => "assistant",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoachMessage {
+    pub role: CoachRole,
+    pub content: String,
+}
+
+/// コーチとの会話の履歴。`ApiClient` の複製どうしで共有し、質問のたびに全体を送る
+#[derive(Clone, Default)]
+pub struct CoachConversation(Arc<Mutex<Vec<CoachMessage>>>);
+
+impl CoachConversation {
+    /// 履歴を捨て、`context` を最初の指示にして会話を始め直す
+    pub fn restart(&self, context: String) {
+        if let Ok(mut messages) = self.0.lock() {
+            *messages = vec![CoachMessage {
+                role: CoachRole::System,
+                content: context,
+            }];
+        }
+    }
+
+    pub fn push(&self, role: CoachRole, content: String) {
+        if let Ok(mut messages) = self.0.lock() {
+            messages.push(CoachMessage { role, content });
+        }
+    }
+
+    /// 返事を受け取れなかった質問を取り消す
+    pub fn pop_question(&self) {
+        if let Ok(mut messages) = self.0.lock()
+            && messages
+                .last()
+                .is_some_and(|message| message.role == CoachRole::User)
+        {
+            messages.pop();
+        }
+    }
+
+    pub fn messages(&self) -> Vec<CoachMessage> {
+        self.0
+            .lock()
+            .map(|messages| messages.clone())
+            .unwrap_or_default()
+    }
+
+    /// 画面に表示する質問と返事。最初の指示は含めない
+    pub fn transcript(&self) -> Vec<CoachMessage> {
+        self.messages()
+            .into_iter()
+            .filter(|message| message.role != CoachRole::System)
+            .collect()
+    }
+}
+
+/// 評価について質問するコーチ画面の状態。会話の中身は `ApiClient` が持つ
+pub struct CoachChat {
+    pub input: TextInputState,
+    /// 送った質問。返事を待っている間は `Some`
+    pub pending: Option<String>,
+    /// 直前の質問が失敗した理由
+    pub error: Option<String>,
+    /// 会話の末尾から何行さかのぼって表示するか。新しい返事が届くと 0 に戻す
+    pub scroll_from_bottom: u16,
+    /// 会話を始めたセッションの日時。別のセッションで開いたら会話をやり直す
+    pub session: DateTime<Local>,
+}
+
+impl CoachChat {
+    pub fn new(session: DateTime<Local>) -> Self {
+        let input = TextInputState::new();
+        input.focus.set(true);
+        Self {
+            input,
+            pending: None,
+            error: None,
+            scroll_from_bottom: 0,
+            session,
+        }
+    }
+
+    /// 入力欄の質問を送る。空のときや返事を待っている間は送らない
+    pub fn submit(&mut self) -> bool {
+        let question = self.input.text().trim().to_string();
+        if question.is_empty() || self.pending.is_some() {
+            return false;
+        }
+        self.input.set_text("");
+        self.pending = Some(question);
+        self.error = None;
+        self.scroll_from_bottom = 0;
+        true
+    }
+}
+
+/// 会話の最初に渡す指示。評価の理由を受講者に説明する役を与える
+pub fn build_coach_context(
+    original_text: &str,
+    summary_text: &str,
+    evaluation_text: &str,
+) -> String {
+    format!(
+        r"
+あなたは要約トレーニングのコーチです。以下の「原文」「要約文」「評価」について、受講者の質問に日本語で答えてください。
+
+# 回答ルール
+- 評価の理由を、原文や要約文の具体的な箇所を引用しながら説明すること
+- 書き直し方を尋ねられたら、要約文全体を書き直すのではなく、直すべき箇所と方針を示すこと
+- 1 回の回答は 400 文字以内とし、Markdown の見出しや表は使わないこと
+
+# 原文
+{original_text}
+
+# 要約文
+{summary_text}
+
+# 評価
+{evaluation_text}
+"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversation_is_shared_between_clones_and_failed_questions_are_dropped() {
+        let conversation = CoachConversation::default();
+        let shared = conversation.clone();
+        conversation.restart(build_coach_context("原文", "要約", "重要度: 3"));
+        shared.push(CoachRole::User, "なぜ重要度が3なの？".to_string());
+        shared.push(
+            CoachRole::Assistant,
+            "結論が抜けているためです。".to_string(),
+        );
+        shared.push(CoachRole::User, "どう直せばいい？".to_string());
+        shared.pop_question();
+
+        assert_eq!(conversation.messages().len(), 3);
+        let transcript = conversation.transcript();
+        assert_eq!(
+            transcript.iter().map(|m| m.role).collect::<Vec<_>>(),
+            vec![CoachRole::User, CoachRole::Assistant]
+        );
+
+        conversation.pop_question();
+        assert_eq!(conversation.transcript().len(), 2);
+        conversation.restart("別の評価".to_string());
+        assert!(shared.transcript().is_empty());
+    }
+}
@@ -1,48 +1,808 @@
 use crate::error::AppError;
+use crate::pane_layout::DEFAULT_ORIGINAL_PERCENT;
+use crate::strictness::Strictness;
+use crate::theme::ThemeName;
+use chrono::{NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs::{self, File};
-use std::io::Read;
-use std::path::PathBuf;
+use std::io::{Read, Write};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml_edit::{DocumentMut, Item, Table, TableLike, Value};
+
+const DEFAULT_LATENCY_BUDGET_SECS: u64 = 20;
+const DEFAULT_BREAK_INTERVAL_MINS: u64 = 20;
+const DEFAULT_BREAK_DURATION_SECS: u64 = 20;
+const DEFAULT_PREFETCH_QUEUE_SIZE: usize = 2;
+// 720 字でおよそ 5 分
+const DEFAULT_TIMED_SECS_PER_100_CHARS: u64 = 42;
+const DEFAULT_VERBATIM_MAX_PERCENT: u8 = 50;
+const DEFAULT_RUBRIC_PASS_PERCENT: u8 = 60;
+/// 書き換えた `config.toml` の権限
+#[cfg(unix)]
+const CONFIG_FILE_MODE: u32 = 0o600;
 
 #[derive(Serialize, Deserialize, Default)]
-struct Config {
-    api_key: Option<String>,
+pub struct Config {
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub provider: Provider,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub latency_budget_secs: Option<u64>,
+    #[serde(default)]
+    pub read_aloud_cpm: Option<u32>,
+    /// 速読モードで 1 分あたりに表示する文字数
+    #[serde(default)]
+    pub speed_reading_cpm: Option<u32>,
+    /// 1日に行うトレーニングの目標回数
+    #[serde(default)]
+    pub daily_goal: Option<usize>,
+    #[serde(default)]
+    pub adaptive_prompt: bool,
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+    #[serde(default)]
+    pub audit_log: AuditLogConfig,
+    #[serde(default)]
+    pub experiment: ExperimentConfig,
+    #[serde(default)]
+    pub hot_seat: HotSeatConfig,
+    #[serde(default)]
+    pub break_reminder: BreakReminderConfig,
+    #[serde(default)]
+    pub journal: JournalConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub report: ReportConfig,
+    #[serde(default)]
+    pub timed_challenge: TimedChallengeConfig,
+    /// 要約欄で展開する略語と定型句 (`[abbreviations]` セクション)
+    #[serde(default)]
+    pub abbreviations: BTreeMap<String, String>,
+    #[serde(default)]
+    pub scratchpad: ScratchpadConfig,
+    #[serde(default)]
+    pub prereading: PrereadingConfig,
+    #[serde(default)]
+    pub verbatim: VerbatimConfig,
+    #[serde(default)]
+    pub rubric: RubricConfig,
+    /// 画面の表示言語。英語では操作の案内を訳し、文章と評価は日本語のまま表示する
+    #[serde(default)]
+    pub locale: LabelLanguage,
+    #[serde(default)]
+    pub keys: KeysConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    /// 画面の配色。`C` で切り替えた後は、その配色を次回も使う
+    #[serde(default)]
+    pub theme: ThemeName,
+    /// 評価の厳しさ。メニューの `x` で切り替えた後は、その厳しさを次回も使う
+    #[serde(default)]
+    pub strictness: Strictness,
+    #[serde(default)]
+    pub lookup: LookupConfig,
+    #[serde(default)]
+    pub retry: RetryConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub offline: OfflineConfig,
+    #[serde(default)]
+    pub prefetch: PrefetchConfig,
+    #[serde(default)]
+    pub keyring: KeyringConfig,
+    /// 起動時に使う `[[profiles]]` の名前。省略時はトップレベルの接続設定を使う
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub profiles: Vec<ApiProfile>,
 }
 
-fn get_config_path() -> Result<PathBuf, AppError> {
+/// 要約の提出後に次の文章を生成しておく (`[prefetch]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct PrefetchConfig {
+    /// 文字数・難易度の組み合わせごとにためておく件数。0 にすると先読みしない
+    #[serde(default)]
+    pub queue_size: Option<usize>,
+}
+
+impl PrefetchConfig {
+    pub fn queue_size(&self) -> usize {
+        self.queue_size.unwrap_or(DEFAULT_PREFETCH_QUEUE_SIZE)
+    }
+}
+
+/// オフラインで出題する文章 (`[offline]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct OfflineConfig {
+    /// 1 ファイル 1 文章の `.txt` を置いたディレクトリ。既定は設定ディレクトリの `corpus`
+    #[serde(default)]
+    pub corpus_dir: Option<String>,
+}
+
+/// API リクエストの待ち時間の上限 (`[network]` セクション)。
+/// 書かなかった項目は提供元ごとの既定値 (Groq は 60 秒、Ollama は 300 秒、接続は 10 秒) を使う
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct NetworkConfig {
+    /// 1 回のリクエストが応答を返し終えるまでの秒数
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// サーバーに接続できるまでの秒数
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+}
+
+/// 利用上限やサーバーエラーで API リクエストが失敗したときの再送 (`[retry]` セクション)。
+/// 書かなかった項目は既定値 (3 回、1 秒から倍々で最大 16 秒、ジッターあり) を使う
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RetryConfig {
+    /// 最初の送信を含めた送信回数。1 にすると再送しない
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+    #[serde(default)]
+    pub base_delay_ms: Option<u64>,
+    #[serde(default)]
+    pub max_delay_ms: Option<u64>,
+    /// 待ち時間をばらつかせ、同時に送ったリクエストの再送が重ならないようにする
+    #[serde(default = "default_retry_jitter")]
+    pub jitter: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: None,
+            base_delay_ms: None,
+            max_delay_ms: None,
+            jitter: default_retry_jitter(),
+        }
+    }
+}
+
+fn default_retry_jitter() -> bool {
+    true
+}
+
+/// 原文の語を調べるときに使う手元の辞書 (`[lookup]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct LookupConfig {
+    /// `見出し<TAB>読み<TAB>意味` の TSV。載っていない語はモデルに問い合わせる
+    #[serde(default)]
+    pub dictionary: Option<PathBuf>,
+}
+
+/// トレーニング画面の配置 (`[layout]` セクション)
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LayoutConfig {
+    /// 原文欄の幅 (%)。20〜80 に収める。`<` / `>` で変えた後は、その幅を次回も使う
+    #[serde(default = "default_split_percent")]
+    pub split_percent: u16,
+    /// 原文欄と要約欄を左右に並べるか、上下に重ねるか。`L` で切り替えた後は、その並べ方を次回も使う
+    #[serde(default)]
+    pub orientation: PaneOrientation,
+    /// 評価結果を常設の欄ではなく、従来どおり画面中央のオーバーレイで表示する
+    #[serde(default)]
+    pub result_overlay: bool,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            split_percent: default_split_percent(),
+            orientation: PaneOrientation::default(),
+            result_overlay: false,
+        }
+    }
+}
+
+fn default_split_percent() -> u16 {
+    DEFAULT_ORIGINAL_PERCENT
+}
+
+/// 操作に割り当てるキー (`[keys]` セクション)。書かなかった操作は既定のキーを使う
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct KeysConfig {
+    #[serde(default)]
+    pub submit: Option<String>,
+    #[serde(default)]
+    pub edit: Option<String>,
+    #[serde(default)]
+    pub next: Option<String>,
+    #[serde(default)]
+    pub report: Option<String>,
+    #[serde(default)]
+    pub help: Option<String>,
+    #[serde(default)]
+    pub quit: Option<String>,
+    #[serde(default)]
+    pub scroll_up: Option<String>,
+    #[serde(default)]
+    pub scroll_down: Option<String>,
+}
+
+/// 目の休憩を促す間隔と休憩時間 (`[break_reminder]` セクション)
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BreakReminderConfig {
+    #[serde(default = "default_break_reminder_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub interval_mins: Option<u64>,
+    #[serde(default)]
+    pub duration_secs: Option<u64>,
+}
+
+impl Default for BreakReminderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_break_reminder_enabled(),
+            interval_mins: None,
+            duration_secs: None,
+        }
+    }
+}
+
+fn default_break_reminder_enabled() -> bool {
+    true
+}
+
+impl BreakReminderConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_mins(
+            self.interval_mins
+                .unwrap_or(DEFAULT_BREAK_INTERVAL_MINS)
+                .max(1),
+        )
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(
+            self.duration_secs
+                .unwrap_or(DEFAULT_BREAK_DURATION_SECS)
+                .max(1),
+        )
+    }
+}
+
+/// 制限時間付きモードの持ち時間と時間切れの扱い (`[timed_challenge]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct TimedChallengeConfig {
+    #[serde(default)]
+    pub secs_per_100_chars: Option<u64>,
+    #[serde(default)]
+    pub on_timeout: TimeoutAction,
+}
+
+impl TimedChallengeConfig {
+    /// 出題の文字数に応じた持ち時間。最短 1 分
+    pub fn time_limit(&self, character_count: u16) -> Duration {
+        let per_100 = self
+            .secs_per_100_chars
+            .unwrap_or(DEFAULT_TIMED_SECS_PER_100_CHARS);
+        Duration::from_secs((u64::from(character_count) * per_100 / 100).max(60))
+    }
+}
+
+/// 時間切れのときに、書きかけの要約を提出するか不合格として記録するか
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeoutAction {
+    #[default]
+    Submit,
+    Fail,
+}
+
+/// 本文を読む前の練習 (`[prereading]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct PrereadingConfig {
+    /// 冒頭の段落だけを見てキーワードを予想してから本文を読む
+    #[serde(default)]
+    pub keywords: bool,
+}
+
+/// 要約に原文を書き写した部分が多いときの扱い (`[verbatim]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct VerbatimConfig {
+    /// 原文と同じ表現の割合 (%) の目安。これを超えると注意する
+    #[serde(default)]
+    pub max_percent: Option<u8>,
+    /// 目安を超えた要約は提出させない
+    #[serde(default)]
+    pub block: bool,
+}
+
+impl VerbatimConfig {
+    pub fn max_percent(&self) -> u8 {
+        self.max_percent
+            .unwrap_or(DEFAULT_VERBATIM_MAX_PERCENT)
+            .min(100)
+    }
+}
+
+/// 独自の評価基準 (`[rubric]` セクション)。観点がなければ組み込みの基準で評価する
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct RubricConfig {
+    /// `[[rubric.criteria]]` で並べた評価の観点
+    #[serde(default)]
+    pub criteria: Vec<RubricCriterion>,
+    /// 合格に必要な加重スコア (%)
+    #[serde(default)]
+    pub pass_percent: Option<u8>,
+}
+
+impl RubricConfig {
+    pub fn pass_percent(&self) -> u8 {
+        self.pass_percent
+            .unwrap_or(DEFAULT_RUBRIC_PASS_PERCENT)
+            .min(100)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RubricCriterion {
+    pub name: String,
+    /// 評価者に伝える観点の説明
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_rubric_weight")]
+    pub weight: u8,
+}
+
+fn default_rubric_weight() -> u8 {
+    1
+}
+
+/// メモ欄の内容を履歴に残すか (`[scratchpad]` セクション)
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScratchpadConfig {
+    #[serde(default = "default_save_scratchpad_to_history")]
+    pub save_to_history: bool,
+}
+
+impl Default for ScratchpadConfig {
+    fn default() -> Self {
+        Self {
+            save_to_history: default_save_scratchpad_to_history(),
+        }
+    }
+}
+
+fn default_save_scratchpad_to_history() -> bool {
+    true
+}
+
+/// 対戦モードの参加者名 (`[hot_seat]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct HotSeatConfig {
+    #[serde(default)]
+    pub players: Vec<String>,
+}
+
+/// API キーを OS のキーチェーンに保存する設定 (`[keyring]` セクション)。
+/// キーチェーンを使えないときは、これまでどおり `config.toml` に保存する
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct KeyringConfig {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// トップレベルの `provider`・`base_url`・`api_key`・`model` をまとめたプロファイルの名前
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+/// 名前付きの接続設定 (`[[profiles]]`)。書かなかった項目は既定値を使い、トップレベルの値は引き継がない
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ApiProfile {
+    pub name: String,
+    #[serde(default)]
+    pub provider: Provider,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+impl ApiProfile {
+    /// 切り替え画面に表示する、名前と接続先
+    pub fn describe(&self) -> String {
+        let server = match (self.provider, self.base_url.as_deref()) {
+            (_, Some(base_url)) => base_url,
+            (Provider::Groq, None) => "Groq",
+            (Provider::Ollama, None) => "Ollama",
+        };
+        match &self.model {
+            Some(model) => format!("{} ({server}, {model})", self.name),
+            None => format!("{} ({server})", self.name),
+        }
+    }
+}
+
+/// 文章の生成と評価に使う LLM の提供元
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    #[default]
+    Groq,
+    Ollama,
+}
+
+/// `yomitore experiment` で比較する 2 つの評価設定 (`[experiment]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct ExperimentConfig {
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub a: VariantConfig,
+    #[serde(default)]
+    pub b: VariantConfig,
+}
+
+/// 評価に使うモデルとプロンプトテンプレート。`None` は既定値を使う
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct VariantConfig {
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub prompt: Option<String>,
+}
+
+/// API とのやり取りを記録する監査ログの設定 (`[audit_log]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct AuditLogConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub dir: Option<String>,
+}
+
+/// `x` で書き出す学習記録の保存先 (`[journal]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct JournalConfig {
+    #[serde(default)]
+    pub dir: Option<String>,
+}
+
+/// レポートの週の始まりと日付・曜日の表記 (`[report]` セクション)
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReportConfig {
+    #[serde(default)]
+    pub week_start: WeekStart,
+    /// strftime 形式。不正な書式のときは既定の `%m/%d` で表示する
+    #[serde(default = "default_report_date_format")]
+    pub date_format: String,
+    #[serde(default)]
+    pub weekday_labels: LabelLanguage,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            week_start: WeekStart::default(),
+            date_format: default_report_date_format(),
+            weekday_labels: LabelLanguage::default(),
+        }
+    }
+}
+
+const DEFAULT_REPORT_DATE_FORMAT: &str = "%m/%d";
+
+fn default_report_date_format() -> String {
+    DEFAULT_REPORT_DATE_FORMAT.to_string()
+}
+
+impl ReportConfig {
+    pub fn first_weekday(&self) -> Weekday {
+        match self.week_start {
+            WeekStart::Sunday => Weekday::Sun,
+            WeekStart::Monday => Weekday::Mon,
+        }
+    }
+
+    pub fn weekday_label(&self, weekday: Weekday) -> &'static str {
+        let index = weekday.num_days_from_sunday() as usize;
+        let labels = match self.weekday_labels {
+            LabelLanguage::Ja => ["日", "月", "火", "水", "木", "金", "土"],
+            LabelLanguage::En => ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"],
+        };
+        labels.get(index).copied().unwrap_or_default()
+    }
+
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        use std::fmt::Write;
+        let mut formatted = String::new();
+        if write!(formatted, "{}", date.format(&self.date_format)).is_err() {
+            return date.format(DEFAULT_REPORT_DATE_FORMAT).to_string();
+        }
+        formatted
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Sunday,
+    Monday,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum PaneOrientation {
+    /// 原文を左、要約を右に置く
+    #[default]
+    Horizontal,
+    /// 原文を上、要約をその下に置く
+    Vertical,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelLanguage {
+    #[default]
+    Ja,
+    En,
+}
+
+/// 統計・履歴の保存形式と保存先 (`[storage]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct StorageConfig {
+    #[serde(default)]
+    pub backend: StorageBackend,
+    #[serde(default)]
+    pub dir: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Json,
+    Sqlite,
+}
+
+/// API に送る文章から伏せる内容の設定 (`[redaction]` セクション)
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct RedactionConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub emails: bool,
+    #[serde(default)]
+    pub numbers: bool,
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    #[serde(default)]
+    pub replacement: Option<String>,
+}
+
+impl Config {
+    /// トップレベルの接続設定を `default` として先頭に置いた、切り替えられるプロファイルの一覧
+    pub fn api_profiles(&self) -> Vec<ApiProfile> {
+        let default = ApiProfile {
+            name: DEFAULT_PROFILE_NAME.to_string(),
+            provider: self.provider,
+            base_url: self.base_url.clone(),
+            api_key: self.api_key.clone(),
+            model: self.model.clone(),
+        };
+        std::iter::once(default)
+            .chain(self.profiles.iter().cloned())
+            .collect()
+    }
+
+    /// `profile` で指定したプロファイルの `profiles` での位置。存在しない名前はエラー
+    pub fn active_profile_index(&self, profiles: &[ApiProfile]) -> Result<usize, AppError> {
+        let Some(name) = self.profile.as_deref() else {
+            return Ok(0);
+        };
+        profiles
+            .iter()
+            .position(|profile| profile.name == name)
+            .ok_or_else(|| AppError::InvalidConfig(format!("プロファイル {name} がありません。")))
+    }
+
+    /// 接続設定をプロファイルの内容に置き換える
+    pub fn use_profile(&mut self, profile: &ApiProfile) {
+        self.provider = profile.provider;
+        self.base_url.clone_from(&profile.base_url);
+        self.api_key.clone_from(&profile.api_key);
+        self.model.clone_from(&profile.model);
+        self.profile = (profile.name != DEFAULT_PROFILE_NAME).then(|| profile.name.clone());
+    }
+
+    /// 使う API キー。既定のプロファイルでは環境変数 `GROQ_API_KEY` を優先する
+    pub fn resolved_api_key(&self) -> Option<String> {
+        if self.profile.is_none()
+            && let Ok(key) = std::env::var("GROQ_API_KEY")
+        {
+            let key = key.trim();
+            if !key.is_empty() {
+                return Some(key.to_string());
+            }
+        }
+        self.api_key.clone()
+    }
+
+    /// 評価待ちがこの時間を超えたら、待機・キャンセル・自己採点を選べるようにする
+    pub fn latency_budget(&self) -> Duration {
+        Duration::from_secs(
+            self.latency_budget_secs
+                .unwrap_or(DEFAULT_LATENCY_BUDGET_SECS)
+                .max(1),
+        )
+    }
+}
+
+/// `config.toml` や `prompts.toml` を置くディレクトリ (`~/.config/yomitore`)
+pub fn app_config_dir() -> Result<PathBuf, AppError> {
     let config_dir = dirs::config_dir().ok_or(AppError::IoError(std::io::Error::new(
         std::io::ErrorKind::NotFound,
         "設定ディレクトリが見つかりません。",
     )))?;
     let app_config_dir = config_dir.join("yomitore");
     fs::create_dir_all(&app_config_dir)?;
-    Ok(app_config_dir.join("config.toml"))
+    Ok(app_config_dir)
 }
 
-pub fn load_api_key() -> Result<Option<String>, AppError> {
-    if let Ok(key) = std::env::var("GROQ_API_KEY") {
-        let key = key.trim();
-        if !key.is_empty() {
-            return Ok(Some(key.to_string()));
-        }
-    }
+fn get_config_path() -> Result<PathBuf, AppError> {
+    Ok(app_config_dir()?.join("config.toml"))
+}
 
+pub fn load_config() -> Result<Config, AppError> {
     let Ok(config_path) = get_config_path() else {
-        return Ok(None);
+        return Ok(Config::default());
     };
 
     if !config_path.exists() {
-        return Ok(None);
+        return Ok(Config::default());
     }
 
     let mut file = File::open(config_path)?;
     let mut contents = String::new();
     file.read_to_string(&mut contents)?;
 
-    let config: Config = toml::from_str(&contents)
-        .map_err(|_| AppError::IoError(std::io::Error::other("設定の解析に失敗しました。")))?;
+    toml::from_str(&contents)
+        .map_err(|_| AppError::IoError(std::io::Error::other("設定の解析に失敗しました。")))
+}
+
+/// `config.toml` のプロファイルの `api_key` を書き換える。`default` ならトップレベルの `api_key` を
+/// 書き換える。ほかの設定はそのまま残す
+pub fn save_api_key(key: &str, profile: &str) -> Result<(), AppError> {
+    edit_config(|contents| with_api_key(contents, key, profile))
+}
+
+/// `yomitore config set` で `config.toml` の `key` (`retry.max_attempts` のように `.` でセクションを区切る) を
+/// 書き換える。`value` は TOML の値として読めなければ文字列として書く
+pub fn set_config_value(key: &str, value: &str) -> Result<(), AppError> {
+    edit_config(|contents| with_value(contents, key, value))
+}
 
-    Ok(config.api_key)
+/// キーチェーンに移したプロファイルの `api_key` を `config.toml` から消す
+pub fn remove_api_keys(profiles: &[String]) -> Result<(), AppError> {
+    edit_config(|contents| without_api_keys(contents, profiles))
+}
+
+/// 起動時に使うプロファイルとして `config.toml` の `profile` に書き込む
+pub fn save_active_profile(profile: Option<&str>) -> Result<(), AppError> {
+    edit_config(|contents| with_active_profile(contents, profile))
+}
+
+fn edit_config(edit: impl FnOnce(&str) -> Result<String, AppError>) -> Result<(), AppError> {
+    let config_path = get_config_path()?;
+    let contents = if config_path.exists() {
+        fs::read_to_string(&config_path)?
+    } else {
+        String::new()
+    };
+    write_private(&config_path, &edit(&contents)?)
+}
+
+/// API キーを書くことがあるので、持ち主だけが読み書きできるファイルとして書き込む
+fn write_private(path: &Path, contents: &str) -> Result<(), AppError> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(CONFIG_FILE_MODE);
+    let mut file = options.open(path)?;
+    // 以前に作られたファイルは権限が広いままなので、書き込む前に狭める
+    #[cfg(unix)]
+    file.set_permissions(fs::Permissions::from_mode(CONFIG_FILE_MODE))?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+/// コメントや書式を残したまま書き換えられるように読み込む
+fn parse_document(contents: &str) -> Result<DocumentMut, AppError> {
+    contents
+        .parse::<DocumentMut>()
+        .map_err(|_| AppError::IoError(std::io::Error::other("設定の解析に失敗しました。")))
+}
+
+/// `name` のプロファイルを書いた表。`default` はトップレベル
+fn profile_table<'a>(document: &'a mut DocumentMut, name: &str) -> Option<&'a mut Table> {
+    if name == DEFAULT_PROFILE_NAME {
+        return Some(document.as_table_mut());
+    }
+    document
+        .get_mut("profiles")
+        .and_then(Item::as_array_of_tables_mut)?
+        .iter_mut()
+        .find(|entry| entry.get("name").and_then(Item::as_str) == Some(name))
+}
+
+/// `key` に `value` を書く。すでにある値は、行末のコメントなどの装飾を引き継ぐ
+fn set_value(table: &mut dyn TableLike, key: &str, mut value: Value) {
+    if let Some(existing) = table.get_mut(key).and_then(Item::as_value_mut) {
+        *value.decor_mut() = existing.decor().clone();
+        *existing = value;
+    } else {
+        table.insert(key, Item::Value(value));
+    }
+}
+
+fn with_api_key(contents: &str, key: &str, profile: &str) -> Result<String, AppError> {
+    let mut document = parse_document(contents)?;
+    let table = profile_table(&mut document, profile)
+        .ok_or_else(|| AppError::InvalidConfig(format!("プロファイル {profile} がありません。")))?;
+    set_value(table, "api_key", Value::from(key));
+    Ok(document.to_string())
+}
+
+fn without_api_keys(contents: &str, profiles: &[String]) -> Result<String, AppError> {
+    let mut document = parse_document(contents)?;
+    for profile in profiles {
+        if let Some(entry) = profile_table(&mut document, profile) {
+            entry.remove("api_key");
+        }
+    }
+    Ok(document.to_string())
+}
+
+fn with_value(contents: &str, key: &str, value: &str) -> Result<String, AppError> {
+    let mut document = parse_document(contents)?;
+    let (sections, name) = match key.rsplit_once('.') {
+        Some((sections, name)) => (sections.split('.').collect(), name),
+        None => (Vec::new(), key),
+    };
+    let mut target: &mut dyn TableLike = document.as_table_mut();
+    for section in sections {
+        target = target
+            .entry(section)
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| {
+                AppError::InvalidConfig(format!("{section} はセクションではありません。"))
+            })?;
+    }
+    let value = value
+        .parse::<Value>()
+        .unwrap_or_else(|_| Value::from(value));
+    set_value(target, name, value);
+    let contents = document.to_string();
+    toml::from_str::<Config>(&contents).map_err(|e| {
+        AppError::InvalidConfig(format!("{key} に設定できない値です: {}", e.message()))
+    })?;
+    Ok(contents)
+}
+
+fn with_active_profile(contents: &str, profile: Option<&str>) -> Result<String, AppError> {
+    let mut document = parse_document(contents)?;
+    match profile {
+        Some(name) => set_value(document.as_table_mut(), "profile", Value::from(name)),
+        None => {
+            document.remove("profile");
+        }
+    }
+    Ok(document.to_string())
 }
 
 #[cfg(test)]
@@ -53,6 +813,7 @@ mod tests {
     fn test_config_serialization() {
         let config = Config {
             api_key: Some("test_key".to_string()),
+            ..Config::default()
         };
         let toml = toml::to_string(&config).unwrap_or_default();
         assert!(toml.contains("api_key = \"test_key\""));
@@ -65,12 +826,217 @@ mod tests {
         assert_eq!(config.api_key, Some("secret_key".to_string()));
     }
 
+    #[test]
+    fn test_api_key_is_saved_without_dropping_other_settings() {
+        let contents = "# 接続先\napi_key = \"old\" # 個人用\nmodel = \"openai/gpt-oss-20b\"\n\n[retry]\nmax_attempts = 5\n";
+        let saved = with_api_key(contents, "gsk_new", DEFAULT_PROFILE_NAME).unwrap_or_default();
+        let config: Config = toml::from_str(&saved).unwrap_or_default();
+        assert_eq!(config.api_key.as_deref(), Some("gsk_new"));
+        assert_eq!(config.model.as_deref(), Some("openai/gpt-oss-20b"));
+        assert_eq!(saved, contents.replace("\"old\"", "\"gsk_new\""));
+        assert!(
+            with_api_key("", "gsk_new", DEFAULT_PROFILE_NAME)
+                .is_ok_and(|saved| saved.contains("gsk_new"))
+        );
+    }
+
+    #[test]
+    fn test_profiles_switch_connection_settings() {
+        let toml_str = r#"
+api_key = "gsk_personal"
+profile = "work"
+
+[[profiles]]
+name = "work"
+base_url = "https://api.openai.com/v1"
+api_key = "sk-work"
+model = "gpt-4o-mini"
+
+[[profiles]]
+name = "local"
+provider = "ollama"
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap_or_default();
+        let profiles = config.api_profiles();
+        assert_eq!(profiles.len(), 3);
+        let index = config.active_profile_index(&profiles).unwrap_or_default();
+        assert_eq!(index, 1);
+
+        if let Some(work) = profiles.get(index) {
+            config.use_profile(work);
+        }
+        assert_eq!(config.resolved_api_key().as_deref(), Some("sk-work"));
+        assert_eq!(config.model.as_deref(), Some("gpt-4o-mini"));
+        if let Some(local) = profiles.get(2) {
+            config.use_profile(local);
+            assert_eq!(local.describe(), "local (Ollama)");
+        }
+        assert_eq!(config.provider, Provider::Ollama);
+        assert_eq!(config.api_key, None);
+        assert_eq!(config.profile.as_deref(), Some("local"));
+
+        config.profile = Some("missing".to_string());
+        assert!(config.active_profile_index(&profiles).is_err());
+
+        let saved = with_api_key(toml_str, "sk-new", "work").unwrap_or_default();
+        let saved = with_active_profile(&saved, None).unwrap_or_default();
+        let config: Config = toml::from_str(&saved).unwrap_or_default();
+        assert_eq!(config.profile, None);
+        assert_eq!(config.api_key.as_deref(), Some("gsk_personal"));
+        assert_eq!(
+            config
+                .profiles
+                .first()
+                .and_then(|work| work.api_key.as_deref()),
+            Some("sk-new")
+        );
+    }
+
+    #[test]
+    fn test_config_values_are_set_from_the_command_line() {
+        let saved = with_value("api_key = \"gsk\"\n", "model", "openai/gpt-oss-20b")
+            .and_then(|saved| with_value(&saved, "retry.max_attempts", "5"))
+            .unwrap_or_default();
+        let config: Config = toml::from_str(&saved).unwrap_or_default();
+        assert_eq!(config.api_key.as_deref(), Some("gsk"));
+        assert_eq!(config.model.as_deref(), Some("openai/gpt-oss-20b"));
+        assert!(saved.contains("max_attempts = 5"));
+        assert!(with_value("", "latency_budget_secs", "soon").is_err());
+        assert!(with_value("model = \"a\"\n", "model.name", "b").is_err());
+    }
+
+    #[test]
+    fn test_api_keys_are_removed_after_moving_to_the_keyring() {
+        let toml_str = "api_key = \"gsk_personal\"\n\n[keyring]\nenabled = true\n\n[[profiles]]\nname = \"work\"\napi_key = \"sk-work\"\n";
+        let profiles = [
+            DEFAULT_PROFILE_NAME.to_string(),
+            "work".to_string(),
+            "missing".to_string(),
+        ];
+        let saved = without_api_keys(toml_str, &profiles).unwrap_or_default();
+        let config: Config = toml::from_str(&saved).unwrap_or_default();
+        assert!(config.keyring.enabled);
+        assert_eq!(config.api_key, None);
+        assert_eq!(
+            config
+                .profiles
+                .first()
+                .map(|work| (work.name.as_str(), work.api_key.clone())),
+            Some(("work", None))
+        );
+    }
+
     #[test]
     fn test_config_default() {
         let config = Config::default();
         assert!(config.api_key.is_none());
     }
 
+    #[test]
+    fn test_redaction_section_deserialization() {
+        let toml_str = "[redaction]\nenabled = true\nemails = true\npatterns = [\"山田\"]";
+        let config: Config = toml::from_str(toml_str).unwrap_or_default();
+        assert!(config.redaction.enabled);
+        assert!(config.redaction.emails);
+        assert!(!config.redaction.numbers);
+        assert_eq!(config.redaction.patterns, vec!["山田".to_string()]);
+    }
+
+    #[test]
+    fn test_report_section_deserialization() {
+        let toml_str =
+            "[report]\nweek_start = \"monday\"\ndate_format = \"%-d %b\"\nweekday_labels = \"en\"";
+        let config: Config = toml::from_str(toml_str).unwrap_or_default();
+        assert_eq!(config.report.first_weekday(), Weekday::Mon);
+        assert_eq!(config.report.weekday_label(Weekday::Sat), "Sa");
+        let date = NaiveDate::from_ymd_opt(2026, 7, 2).unwrap_or_default();
+        assert_eq!(config.report.format_date(date), "2 Jul");
+
+        let broken = ReportConfig {
+            date_format: "%Q".to_string(),
+            ..ReportConfig::default()
+        };
+        assert_eq!(broken.format_date(date), "07/02");
+        assert_eq!(ReportConfig::default().weekday_label(Weekday::Sun), "日");
+    }
+
+    #[test]
+    fn test_provider_deserialization() {
+        let config = Config::default();
+        assert_eq!(config.provider, Provider::Groq);
+
+        let toml_str = "provider = \"ollama\"\nbase_url = \"http://192.168.0.10:11434\"";
+        let config: Config = toml::from_str(toml_str).unwrap_or_default();
+        assert_eq!(config.provider, Provider::Ollama);
+        assert_eq!(
+            config.base_url.as_deref(),
+            Some("http://192.168.0.10:11434")
+        );
+    }
+
+    #[test]
+    fn test_model_deserialization() {
+        let config: Config = toml::from_str("model = \"openai/gpt-oss-20b\"").unwrap_or_default();
+        assert_eq!(config.model.as_deref(), Some("openai/gpt-oss-20b"));
+        assert!(Config::default().model.is_none());
+    }
+
+    #[test]
+    fn test_break_reminder_defaults_and_overrides() {
+        let config = Config::default();
+        assert!(config.break_reminder.enabled);
+        assert_eq!(config.break_reminder.interval(), Duration::from_mins(20));
+        assert_eq!(config.break_reminder.duration(), Duration::from_secs(20));
+
+        let toml_str = "[break_reminder]\ninterval_mins = 40\nduration_secs = 30";
+        let config: Config = toml::from_str(toml_str).unwrap_or_default();
+        assert!(config.break_reminder.enabled);
+        assert_eq!(config.break_reminder.interval(), Duration::from_mins(40));
+        assert_eq!(config.break_reminder.duration(), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_network_overrides() {
+        let config = Config::default();
+        assert_eq!(config.network.timeout_secs, None);
+
+        let toml_str = "[network]\ntimeout_secs = 120\nconnect_timeout_secs = 5";
+        let config: Config = toml::from_str(toml_str).unwrap_or_default();
+        assert_eq!(config.network.timeout_secs, Some(120));
+        assert_eq!(config.network.connect_timeout_secs, Some(5));
+    }
+
+    #[test]
+    fn test_timed_challenge_defaults_and_overrides() {
+        let config = Config::default();
+        assert_eq!(
+            config.timed_challenge.time_limit(720),
+            Duration::from_secs(302)
+        );
+        assert_eq!(
+            config.timed_challenge.time_limit(100),
+            Duration::from_mins(1)
+        );
+        assert_eq!(config.timed_challenge.on_timeout, TimeoutAction::Submit);
+
+        let toml_str = "[timed_challenge]\nsecs_per_100_chars = 30\non_timeout = \"fail\"";
+        let config: Config = toml::from_str(toml_str).unwrap_or_default();
+        assert_eq!(
+            config.timed_challenge.time_limit(400),
+            Duration::from_mins(2)
+        );
+        assert_eq!(config.timed_challenge.on_timeout, TimeoutAction::Fail);
+    }
+
+    #[test]
+    fn test_latency_budget_defaults_and_overrides() {
+        let config = Config::default();
+        assert_eq!(config.latency_budget(), Duration::from_secs(20));
+
+        let config: Config = toml::from_str("latency_budget_secs = 5").unwrap_or_default();
+        assert_eq!(config.latency_budget(), Duration::from_secs(5));
+    }
+
     #[test]
     fn test_api_key_loading_priority() {
         use std::env;
@@ -81,7 +1047,7 @@ mod tests {
         unsafe {
             env::set_var(env_var_name, "env_key");
         }
-        let result = load_api_key().unwrap_or_default();
+        let result = Config::default().resolved_api_key();
         assert_eq!(result, Some("env_key".to_string()));
 
         // Restore env var
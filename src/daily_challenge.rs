@@ -0,0 +1,112 @@
+use crate::models::TextStyle;
+use chrono::{Datelike, NaiveDate};
+
+/// 日付から選ぶお題の話題。順番を変えると過去の日のお題も変わるので、増やすときは末尾に足す
+const TOPICS: [&str; 16] = [
+    "地域の公共交通の維持",
+    "食品ロスの削減",
+    "働き方改革と長時間労働",
+    "再生可能エネルギーの普及",
+    "少子化と子育て支援",
+    "高齢者の見守りと地域福祉",
+    "自然災害への備え",
+    "インターネット上の誤情報",
+    "学校教育のデジタル化",
+    "観光客の増加と地域の暮らし",
+    "空き家の活用",
+    "海洋プラスチックごみ",
+    "医療現場の人手不足",
+    "農業の担い手確保",
+    "生成 AI の利用と規制",
+    "水道などのインフラ老朽化",
+];
+const STYLES: [TextStyle; 2] = [TextStyle::Official, TextStyle::Newspaper];
+const LENGTHS: [u16; 3] = [400, 720, 1440];
+
+/// その日の日付だけで決まる「今日のお題」。同じ日ならどの環境でも同じ話題・文体・文字数になる
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DailyChallenge {
+    pub date: NaiveDate,
+    pub topic: &'static str,
+    pub style: TextStyle,
+    pub character_count: u16,
+}
+
+/// 日付の通し番号をかき混ぜた値。乱数ライブラリの実装に左右されないよう自前で計算する
+fn day_seed(date: NaiveDate) -> u64 {
+    let mut seed = u64::try_from(date.num_days_from_ce()).unwrap_or_default();
+    seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    seed = (seed ^ (seed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    seed = (seed ^ (seed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    seed ^ (seed >> 31)
+}
+
+fn pick<T: Copy>(items: &[T], seed: u64) -> Option<T> {
+    let len = u64::try_from(items.len()).ok()?;
+    items.get(usize::try_from(seed % len).ok()?).copied()
+}
+
+impl DailyChallenge {
+    pub fn for_date(date: NaiveDate) -> Self {
+        let seed = day_seed(date);
+        Self {
+            date,
+            topic: pick(&TOPICS, seed).unwrap_or_default(),
+            style: pick(&STYLES, seed >> 16).unwrap_or(TextStyle::Newspaper),
+            character_count: pick(&LENGTHS, seed >> 32).unwrap_or(720),
+        }
+    }
+
+    /// 全員が同じ条件で練習できるよう、難易度や苦手対策の指示は加えない
+    pub fn prompt(&self) -> String {
+        format!(
+            "「{}」を話題に、{}{}文字程度で生成してください。",
+            self.topic,
+            self.style.prompt(),
+            self.character_count
+        )
+        .repeat(2)
+    }
+
+    /// メニューに表示するお題の説明
+    pub fn describe(&self) -> String {
+        format!(
+            "{} / {} / {} 文字",
+            self.topic,
+            self.style.label(),
+            self.character_count
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap_or_default()
+    }
+
+    #[test]
+    fn challenge_is_fixed_for_a_date() {
+        let challenge = DailyChallenge::for_date(date(18));
+        assert_eq!(challenge, DailyChallenge::for_date(date(18)));
+        assert!(TOPICS.contains(&challenge.topic));
+        assert!(LENGTHS.contains(&challenge.character_count));
+        assert!(challenge.prompt().contains(challenge.topic));
+        assert!(
+            challenge
+                .describe()
+                .ends_with(&format!("{} 文字", challenge.character_count))
+        );
+    }
+
+    #[test]
+    fn challenge_changes_across_days() {
+        let topics: Vec<&str> = (1..=14)
+            .map(|day| DailyChallenge::for_date(date(day)).topic)
+            .collect();
+        let first = topics.first();
+        assert!(topics.iter().any(|topic| Some(topic) != first));
+    }
+}
@@ -0,0 +1,153 @@
+/// 差分の 1 区間が、どちらの文章にあるか
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// 両方にある
+    Same,
+    /// 自分の要約にだけある
+    Added,
+    /// 模範解答にだけある (書き漏らした語)
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffSegment {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// 文字の種類。同じ種類が続く間を 1 語とみなす
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum CharClass {
+    Kanji,
+    Hiragana,
+    Katakana,
+    Alphanumeric,
+    Other,
+}
+
+fn char_class(c: char) -> CharClass {
+    match c {
+        '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '々' => CharClass::Kanji,
+        '\u{3041}'..='\u{309F}' => CharClass::Hiragana,
+        '\u{30A0}'..='\u{30FF}' | '\u{FF66}'..='\u{FF9F}' => CharClass::Katakana,
+        c if c.is_alphanumeric() => CharClass::Alphanumeric,
+        _ => CharClass::Other,
+    }
+}
+
+/// 分かち書きのない日本語でも比べられるよう、文字の種類の切れ目で語に分ける。
+/// 記号や空白は 1 文字ずつにする
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<CharClass> = None;
+    for (index, c) in text.char_indices() {
+        let class = char_class(c);
+        if previous.is_some_and(|previous| previous != class || class == CharClass::Other) {
+            tokens.push(text.get(start..index).unwrap_or_default());
+            start = index;
+        }
+        previous = Some(class);
+    }
+    if start < text.len() {
+        tokens.push(text.get(start..).unwrap_or_default());
+    }
+    tokens
+}
+
+/// 要約と模範解答を語単位で比べる。最長共通部分列で対応を取り、同じ種類の区間はまとめる
+pub fn diff_words(summary: &str, reference: &str) -> Vec<DiffSegment> {
+    let ours = tokenize(summary);
+    let theirs = tokenize(reference);
+    // lengths[i][j]: ours[i..] と theirs[j..] の最長共通部分列の長さ
+    let mut lengths = vec![vec![0usize; theirs.len() + 1]; ours.len() + 1];
+    for (i, our) in ours.iter().enumerate().rev() {
+        for (j, their) in theirs.iter().enumerate().rev() {
+            let value = if our == their {
+                lcs_at(&lengths, i + 1, j + 1) + 1
+            } else {
+                lcs_at(&lengths, i + 1, j).max(lcs_at(&lengths, i, j + 1))
+            };
+            if let Some(cell) = lengths.get_mut(i).and_then(|row| row.get_mut(j)) {
+                *cell = value;
+            }
+        }
+    }
+
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < ours.len() || j < theirs.len() {
+        let (kind, token) = match (ours.get(i), theirs.get(j)) {
+            (Some(our), Some(their)) if our == their => {
+                i += 1;
+                j += 1;
+                (DiffKind::Same, *our)
+            }
+            (Some(our), Some(_)) if lcs_at(&lengths, i + 1, j) >= lcs_at(&lengths, i, j + 1) => {
+                i += 1;
+                (DiffKind::Added, *our)
+            }
+            (Some(our), None) => {
+                i += 1;
+                (DiffKind::Added, *our)
+            }
+            (_, Some(their)) => {
+                j += 1;
+                (DiffKind::Missing, *their)
+            }
+            (None, None) => break,
+        };
+        match segments.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(token),
+            _ => segments.push(DiffSegment {
+                kind,
+                text: token.to_string(),
+            }),
+        }
+    }
+    segments
+}
+
+fn lcs_at(lengths: &[Vec<usize>], i: usize, j: usize) -> usize {
+    lengths
+        .get(i)
+        .and_then(|row| row.get(j))
+        .copied()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(kind: DiffKind, text: &str) -> DiffSegment {
+        DiffSegment {
+            kind,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn tokens_split_at_character_class_boundaries() {
+        assert_eq!(
+            tokenize("政府はAI規制を、2026年に"),
+            vec!["政府", "は", "AI", "規制", "を", "、", "2026", "年", "に"]
+        );
+    }
+
+    #[test]
+    fn diff_marks_added_and_missing_words() {
+        assert_eq!(
+            diff_words("政府は規制を強化した。", "政府は新たな規制を導入した。"),
+            vec![
+                segment(DiffKind::Same, "政府は"),
+                segment(DiffKind::Missing, "新たな"),
+                segment(DiffKind::Same, "規制を"),
+                segment(DiffKind::Added, "強化"),
+                segment(DiffKind::Missing, "導入"),
+                segment(DiffKind::Same, "した。"),
+            ]
+        );
+        assert!(diff_words("", "").is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::error::AppError;
+use crate::storage;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// 書きかけの要約を保存する間隔
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 評価に出す前の原文と書きかけの要約 (`session.json`)。異常終了しても次の起動で続きから書ける
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SessionDraft {
+    pub original_text: String,
+    pub summary: String,
+    pub character_count: u16,
+    pub saved_at: DateTime<Local>,
+}
+
+impl SessionDraft {
+    /// 保存されている下書き。なければ `None`
+    pub fn load() -> Result<Option<Self>, AppError> {
+        Ok(
+            storage::load_json::<Option<Self>>(storage::global()?, storage::SESSION_DOCUMENT)?
+                .flatten(),
+        )
+    }
+
+    fn save(draft: Option<&Self>) -> Result<(), AppError> {
+        storage::save_json(storage::global()?, storage::SESSION_DOCUMENT, &draft)
+    }
+
+    fn is_same_work(&self, other: &Self) -> bool {
+        self.original_text == other.original_text && self.summary == other.summary
+    }
+}
+
+/// 起動してから最後に書き込んだ内容
+#[derive(Default)]
+enum Written {
+    /// まだ書き込んでいない。前回のセッションの下書きが残っていることがある
+    #[default]
+    Nothing,
+    Cleared,
+    Draft(SessionDraft),
+}
+
+/// 一定の間隔で下書きを保存する。内容が変わったときだけ書き込む
+#[derive(Default)]
+pub struct DraftAutosaver {
+    last_check: Option<Instant>,
+    written: Written,
+}
+
+impl DraftAutosaver {
+    /// 前回の確認から間隔が空いていれば `true` を返し、確認した時刻を進める
+    pub fn is_due(&mut self, now: Instant) -> bool {
+        if self
+            .last_check
+            .is_some_and(|last| now.duration_since(last) < AUTOSAVE_INTERVAL)
+        {
+            return false;
+        }
+        self.last_check = Some(now);
+        true
+    }
+
+    /// 前回書き込んだ内容から変わっているか。起動直後に下書きがなければ、
+    /// 前回のセッションの下書きを残すため書き込まない
+    fn is_changed(&self, draft: Option<&SessionDraft>) -> bool {
+        match (&self.written, draft) {
+            (Written::Nothing | Written::Cleared, None) => false,
+            (Written::Draft(written), Some(draft)) => !written.is_same_work(draft),
+            _ => true,
+        }
+    }
+
+    /// 下書きが変わっていれば保存する。下書きがなくなっていれば消す
+    pub fn update(&mut self, draft: Option<SessionDraft>) -> Result<(), AppError> {
+        if !self.is_changed(draft.as_ref()) {
+            return Ok(());
+        }
+        SessionDraft::save(draft.as_ref())?;
+        self.written = draft.map_or(Written::Cleared, Written::Draft);
+        Ok(())
+    }
+
+    /// 保存されている下書きを消す
+    pub fn clear(&mut self) -> Result<(), AppError> {
+        SessionDraft::save(None)?;
+        self.written = Written::Cleared;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(summary: &str) -> SessionDraft {
+        SessionDraft {
+            original_text: "原文です。".to_string(),
+            summary: summary.to_string(),
+            character_count: 400,
+            saved_at: Local::now(),
+        }
+    }
+
+    #[test]
+    fn autosave_runs_at_intervals() {
+        let mut autosaver = DraftAutosaver::default();
+        let start = Instant::now();
+        assert!(autosaver.is_due(start));
+        assert!(!autosaver.is_due(start + Duration::from_secs(1)));
+        assert!(autosaver.is_due(start + AUTOSAVE_INTERVAL));
+    }
+
+    #[test]
+    fn only_changed_drafts_are_written() {
+        let mut autosaver = DraftAutosaver::default();
+        assert!(!autosaver.is_changed(None));
+        assert!(autosaver.is_changed(Some(&draft("要約"))));
+
+        autosaver.written = Written::Draft(draft("要約"));
+        assert!(!autosaver.is_changed(Some(&draft("要約"))));
+        assert!(autosaver.is_changed(Some(&draft("要約の続き"))));
+        assert!(autosaver.is_changed(None));
+
+        autosaver.written = Written::Cleared;
+        assert!(!autosaver.is_changed(None));
+        let json = serde_json::to_string(&None::<SessionDraft>).unwrap_or_default();
+        assert_eq!(
+            serde_json::from_str::<Option<SessionDraft>>(&json).ok(),
+            Some(None)
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use reqwest::StatusCode;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -11,11 +12,141 @@ pub enum AppError {
     #[error("API キーが無効です。")]
     InvalidApiKey,
 
+    #[error("API の利用上限に達しました。")]
+    RateLimited,
+
     #[error("I/O エラー: {0}")]
     IoError(#[from] std::io::Error),
 
     #[error("API レスポンスに choices が含まれていません。")]
     NoChoicesInResponse,
+
+    #[error("ローカル LLM サーバー ({0}) に接続できません。")]
+    BackendUnavailable(String),
+
+    #[error("問題の形式が不正です: {0}")]
+    InvalidQuiz(String),
+
+    #[error("文章を読み込めません: {0}")]
+    InvalidText(String),
+
+    #[error("クリップボードを読み込めません: {0}")]
+    Clipboard(String),
+
+    #[error("設定が不正です: {0}")]
+    InvalidConfig(String),
+
+    #[error("データベースの操作に失敗しました: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("保存先を開けません: {0}")]
+    Storage(String),
+
+    #[error("オフラインでは使えません。")]
+    Offline,
+
+    #[error("キーチェーンを使えません: {0}")]
+    Keyring(String),
+
+    #[error("応答を待つのをやめました。")]
+    Cancelled,
+}
+
+/// 利用者に見せるときのエラーの分類。分類ごとに対処方法を案内する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    Network,
+    Auth,
+    Quota,
+    Parse,
+    Storage,
+    Keyring,
+    Input,
+}
+
+impl ErrorCategory {
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Network => "通信エラー / Network error",
+            Self::Auth => "認証エラー / Authentication error",
+            Self::Quota => "利用上限 / Quota exceeded",
+            Self::Parse => "応答の解析エラー / Unexpected response",
+            Self::Storage => "保存エラー / Storage error",
+            Self::Keyring => "キーチェーンのエラー / Keychain error",
+            Self::Input => "入力エラー / Invalid input",
+        }
+    }
+
+    pub fn remediation(self) -> &'static str {
+        match self {
+            Self::Network => {
+                "インターネット接続を確認し、しばらくしてから再試行してください。ローカル LLM を使っている場合はサーバーが起動しているか確認してください。"
+            }
+            Self::Auth => {
+                "API キーが有効か確認してください。メニュー画面の 'a' でキーを設定し直せます。環境変数 GROQ_API_KEY を使っている場合はその値を、config.toml の [[profiles]] で接続先を分けている場合は 'P' で選んだプロファイルのキーを確認してください。"
+            }
+            Self::Quota => {
+                "しばらく待ってから再試行してください。続く場合はプランの利用上限を確認してください。"
+            }
+            Self::Parse => {
+                "もう一度試してください。続く場合はメニュー画面の 'm' で別のモデルを選んでください。"
+            }
+            Self::Storage => {
+                "設定ディレクトリ (~/.config/yomitore など) の書き込み権限と空き容量を確認してください。"
+            }
+            Self::Keyring => {
+                "OS のキーチェーンを使えるか確認してください (Linux などは secret-tool と Secret Service、macOS はキーチェーンアクセス)。使えない場合は config.toml の [keyring] を enabled = false にすると、キーを config.toml に保存します。"
+            }
+            Self::Input => "入力した内容や設定ファイル (config.toml) を見直してください。",
+        }
+    }
+}
+
+impl AppError {
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::ApiError(e) => match e.status() {
+                Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => ErrorCategory::Auth,
+                Some(StatusCode::TOO_MANY_REQUESTS) => ErrorCategory::Quota,
+                _ if e.is_decode() => ErrorCategory::Parse,
+                _ => ErrorCategory::Network,
+            },
+            Self::InvalidApiKey => ErrorCategory::Auth,
+            Self::RateLimited => ErrorCategory::Quota,
+            Self::ResponseParseError(_) | Self::NoChoicesInResponse | Self::InvalidQuiz(_) => {
+                ErrorCategory::Parse
+            }
+            Self::BackendUnavailable(_) | Self::Offline | Self::Cancelled => ErrorCategory::Network,
+            Self::IoError(_) | Self::Database(_) | Self::Storage(_) => ErrorCategory::Storage,
+            Self::Keyring(_) => ErrorCategory::Keyring,
+            Self::InvalidText(_) | Self::Clipboard(_) | Self::InvalidConfig(_) => {
+                ErrorCategory::Input
+            }
+        }
+    }
+
+    /// エラー表示用にまとめる。`action` は失敗した操作 (「文章の生成」など)
+    pub fn notice(&self, action: &str) -> ErrorNotice {
+        ErrorNotice {
+            category: self.category(),
+            action: action.to_string(),
+            detail: self.to_string(),
+        }
+    }
+}
+
+/// エラー表示に使う内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorNotice {
+    pub category: ErrorCategory,
+    pub action: String,
+    pub detail: String,
+}
+
+impl ErrorNotice {
+    pub fn summary(&self) -> String {
+        format!("{}に失敗しました。", self.action)
+    }
 }
 
 #[cfg(test)]
@@ -30,4 +161,22 @@ mod tests {
             "API レスポンスに choices が含まれていません。"
         );
     }
+
+    #[test]
+    fn test_error_categories_and_notice() {
+        assert_eq!(AppError::RateLimited.category(), ErrorCategory::Quota);
+        assert_eq!(
+            AppError::BackendUnavailable("http://localhost:11434".to_string()).category(),
+            ErrorCategory::Network
+        );
+        assert_eq!(
+            AppError::IoError(std::io::Error::other("disk full")).category(),
+            ErrorCategory::Storage
+        );
+        let notice = AppError::InvalidApiKey.notice("文章の生成");
+        assert_eq!(notice.category, ErrorCategory::Auth);
+        assert!(notice.category.remediation().contains("'a'"));
+        assert_eq!(notice.summary(), "文章の生成に失敗しました。");
+        assert_eq!(notice.detail, "API キーが無効です。");
+    }
 }
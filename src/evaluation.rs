@@ -1,9 +1,36 @@
+use crate::models::EvaluationScores;
+use serde::Deserialize;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OverallEvaluation {
     Pass,
     Fail,
 }
 
+/// 評価結果の上部に表示する判定。モデルの文章ではなく解析した結果から決める
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verdict {
+    Passed,
+    Failed,
+    /// 評価の形式が不正で合否を読み取れなかった
+    #[default]
+    Undetermined,
+}
+
+impl Verdict {
+    pub fn from_passed(passed: bool) -> Self {
+        if passed { Self::Passed } else { Self::Failed }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Passed => "合格",
+            Self::Failed => "不合格",
+            Self::Undetermined => "判定不能",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EvaluationResult {
     pub appropriate: bool,
@@ -16,35 +43,55 @@ pub struct EvaluationResult {
     pub overall: OverallEvaluation,
 }
 
+impl EvaluationResult {
+    pub fn passed(&self) -> bool {
+        matches!(self.overall, OverallEvaluation::Pass)
+    }
+
+    pub fn into_scores(self) -> EvaluationScores {
+        let overall_passed = self.passed();
+        EvaluationScores {
+            appropriate: self.appropriate,
+            importance: self.importance,
+            conciseness: self.conciseness,
+            accuracy: self.accuracy,
+            improvement1: self.improvement1,
+            improvement2: self.improvement2,
+            improvement3: self.improvement3,
+            overall_passed,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseEvaluationError {
     DuplicateField(&'static str),
     MissingField(&'static str),
     InvalidValue(&'static str, String),
+    InvalidJson(String),
 }
 
 const BULLET_PREFIXES: [char; 5] = ['-', '・', '•', '−', '*'];
+const MAX_SCORE: u8 = 5;
+const SCORE_LABEL_WIDTH: usize = 7;
 
 pub fn build_evaluation_prompt(original_text: &str, summary_text: &str) -> String {
     format!(
-        r"
+        r#"
 以下の「原文」と「要約文」を比較し、要約として適切か評価してください。
 
 # 評価ルール
-- 出力は必ず以下の「出力フォーマット」のみ使用すること
+- 出力は必ず以下の「出力フォーマット」の JSON オブジェクトのみとすること
 - 数値は 1〜5 の整数のみ
-- 余計な文章や注釈は禁止
-- Markdown 記法は禁止
+- improvements には改善点をちょうど 3 つ入れること
+- model_answer には、要約文と同じくらいの長さの模範解答を書くこと
+- key_points には原文の要点を 3〜5 つ挙げ、covered に要約文がその要点を含むかを入れること
+- keywords には原文の内容を最もよく表す語を 3 つ、原文中の表記のまま入れること
+- overall は "合格" または "不合格" のみ
+- JSON 以外の文章や注釈、コードブロックは禁止
 
 # 出力フォーマット(厳守)
-- 適切な要約か: はい/いいえ
-- 重要情報の抽出: [1-5]
-- 簡潔性: [1-5]
-- 正確性: [1-5]
-- 改善点1: ...
-- 改善点2: ...
-- 改善点3: ...
-- 総合評価: 合格/不合格
+{{"appropriate": true, "importance": 4, "conciseness": 3, "accuracy": 5, "improvements": ["...", "...", "..."], "model_answer": "...", "key_points": [{{"point": "...", "covered": true}}], "keywords": ["...", "...", "..."], "overall": "合格"}}
 
 # 採点基準
 - 5: 非常に優れている
@@ -56,11 +103,128 @@ pub fn build_evaluation_prompt(original_text: &str, summary_text: &str) -> Strin
 
 # 要約文
 {summary_text}
-"
+"#
     )
 }
 
+/// `{original}` と `{summary}` を差し込んで評価プロンプトを組み立てる
+pub fn fill_evaluation_template(template: &str, original_text: &str, summary_text: &str) -> String {
+    template
+        .replace("{original}", original_text)
+        .replace("{summary}", summary_text)
+}
+
+/// JSON 形式の評価を優先して解析し、JSON でなければ従来の行形式として解析する
 pub fn parse_evaluation(evaluation: &str) -> Result<EvaluationResult, ParseEvaluationError> {
+    if let (Some(start), Some(end)) = (evaluation.find('{'), evaluation.rfind('}'))
+        && start < end
+    {
+        // 行形式の応答にも波括弧が混じることがあるので、JSON として読めなければ行形式で読み直す
+        return match parse_json_evaluation(evaluation.get(start..=end).unwrap_or_default()) {
+            Err(json_error @ ParseEvaluationError::InvalidJson(_)) => {
+                parse_line_evaluation(evaluation).map_err(|_| json_error)
+            }
+            result => result,
+        };
+    }
+    parse_line_evaluation(evaluation)
+}
+
+/// 採点には使わない、評価結果のタブに表示する補足
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct EvaluationExtras {
+    pub model_answer: String,
+    pub key_points: Vec<KeyPoint>,
+    /// 評価者が挙げた原文のキーワード。キーワード予想の答え合わせに使う
+    pub keywords: Vec<String>,
+    /// 文字数の条件を付けて評価したときの、評価者による判定
+    pub length_met: Option<bool>,
+}
+
+/// 原文の要点と、要約文がそれを含むか
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct KeyPoint {
+    pub point: String,
+    #[serde(default)]
+    pub covered: bool,
+}
+
+#[derive(Deserialize)]
+struct JsonEvaluationExtras {
+    #[serde(default)]
+    model_answer: String,
+    #[serde(default)]
+    key_points: Vec<KeyPoint>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    length_met: Option<bool>,
+}
+
+/// 模範解答と要点を取り出す。行形式や古いプロンプトの応答では空になる
+pub fn parse_evaluation_extras(evaluation: &str) -> EvaluationExtras {
+    let (Some(start), Some(end)) = (evaluation.find('{'), evaluation.rfind('}')) else {
+        return EvaluationExtras::default();
+    };
+    let Ok(parsed) = serde_json::from_str::<JsonEvaluationExtras>(
+        evaluation.get(start..=end).unwrap_or_default(),
+    ) else {
+        return EvaluationExtras::default();
+    };
+    EvaluationExtras {
+        model_answer: parsed.model_answer.trim().to_string(),
+        key_points: parsed
+            .key_points
+            .into_iter()
+            .filter(|key_point| !key_point.point.trim().is_empty())
+            .collect(),
+        keywords: parsed
+            .keywords
+            .iter()
+            .map(|keyword| keyword.trim().to_string())
+            .filter(|keyword| !keyword.is_empty())
+            .collect(),
+        length_met: parsed.length_met,
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonEvaluation {
+    appropriate: bool,
+    importance: u8,
+    conciseness: u8,
+    accuracy: u8,
+    improvements: Vec<String>,
+    overall: String,
+}
+
+fn parse_json_evaluation(json: &str) -> Result<EvaluationResult, ParseEvaluationError> {
+    let parsed: JsonEvaluation =
+        serde_json::from_str(json).map_err(|e| ParseEvaluationError::InvalidJson(e.to_string()))?;
+    let mut improvements = parsed.improvements.into_iter();
+    let mut next_improvement = || improvements.next().unwrap_or_default();
+
+    Ok(EvaluationResult {
+        appropriate: parsed.appropriate,
+        importance: validate_score("重要情報の抽出", parsed.importance)?,
+        conciseness: validate_score("簡潔性", parsed.conciseness)?,
+        accuracy: validate_score("正確性", parsed.accuracy)?,
+        improvement1: next_improvement(),
+        improvement2: next_improvement(),
+        improvement3: next_improvement(),
+        overall: parse_overall("総合評価", parsed.overall.trim())?,
+    })
+}
+
+fn validate_score(field: &'static str, score: u8) -> Result<u8, ParseEvaluationError> {
+    if (1..=5).contains(&score) {
+        Ok(score)
+    } else {
+        Err(ParseEvaluationError::InvalidValue(field, score.to_string()))
+    }
+}
+
+fn parse_line_evaluation(evaluation: &str) -> Result<EvaluationResult, ParseEvaluationError> {
     let mut fields = EvaluationFields::default();
 
     for line in evaluation.lines() {
@@ -79,7 +243,19 @@ pub fn parse_evaluation(evaluation: &str) -> Result<EvaluationResult, ParseEvalu
     fields.build()
 }
 
+/// 評価画面に表示するスコア表
 pub fn format_evaluation_display(parsed: &EvaluationResult) -> String {
+    let score_table = [
+        format_score_row("重要情報の抽出", parsed.importance),
+        format_score_row("簡潔性", parsed.conciseness),
+        format_score_row("正確性", parsed.accuracy),
+    ]
+    .join("\n");
+    format_evaluation_with_table(parsed, &score_table)
+}
+
+/// スコア表を差し替えて評価画面の表示を組み立てる。`[rubric]` の観点で評価したときに使う
+pub fn format_evaluation_with_table(parsed: &EvaluationResult, score_table: &str) -> String {
     let appropriate = if parsed.appropriate {
         "はい"
     } else {
@@ -91,15 +267,20 @@ pub fn format_evaluation_display(parsed: &EvaluationResult) -> String {
     };
 
     format!(
-        "- 適切な要約か: {}\n- 重要情報の抽出: {}\n- 簡潔性: {}\n- 正確性: {}\n- 改善点1: {}\n- 改善点2: {}\n- 改善点3: {}\n- 総合評価: {}\n",
-        appropriate,
-        parsed.importance,
-        parsed.conciseness,
-        parsed.accuracy,
-        parsed.improvement1,
-        parsed.improvement2,
-        parsed.improvement3,
-        overall
+        "総合評価: {overall}\n適切な要約か: {appropriate}\n\n{score_table}\n\n改善点\n1. {}\n2. {}\n3. {}\n",
+        parsed.improvement1, parsed.improvement2, parsed.improvement3,
+    )
+}
+
+pub fn format_score_row(label: &str, score: u8) -> String {
+    // 全角スペースで埋めて、項目名の幅を揃える
+    let padding = SCORE_LABEL_WIDTH.saturating_sub(label.chars().count());
+    let filled = usize::from(score.min(MAX_SCORE));
+    format!(
+        "{label}{} {}{} {score}/{MAX_SCORE}",
+        "\u{3000}".repeat(padding),
+        "■".repeat(filled),
+        "□".repeat(usize::from(MAX_SCORE) - filled),
     )
 }
 
@@ -335,6 +516,27 @@ mod tests {
         assert!(matches!(parsed.overall, OverallEvaluation::Pass));
     }
 
+    #[test]
+    fn verdict_follows_parsed_result() -> Result<(), String> {
+        let parsed = parse_evaluation(FAIL_RESPONSE).map_err(|e| format!("{e:?}"))?;
+        if Verdict::from_passed(parsed.passed()) != Verdict::Failed {
+            return Err("failed response was not judged as failed".to_string());
+        }
+        if Verdict::from_passed(true).label() != "合格" {
+            return Err(format!(
+                "unexpected label: {}",
+                Verdict::from_passed(true).label()
+            ));
+        }
+        if Verdict::default().label() != "判定不能" {
+            return Err(format!(
+                "unexpected default label: {}",
+                Verdict::default().label()
+            ));
+        }
+        Ok(())
+    }
+
     #[test]
     fn parse_evaluation_rejects_broken_response() {
         assert!(parse_evaluation(BROKEN_RESPONSE).is_err());
@@ -415,12 +617,81 @@ mod tests {
             overall: OverallEvaluation::Pass,
         };
         let formatted = format_evaluation_display(&result);
-        assert!(formatted.contains("適切な要約か: はい"));
-        assert!(formatted.contains("重要情報の抽出: 5"));
-        assert!(formatted.contains("簡潔性: 3"));
-        assert!(formatted.contains("正確性: 4"));
-        assert!(formatted.contains("改善点1: imp1"));
-        assert!(formatted.contains("総合評価: 合格"));
+        assert!(formatted.starts_with("総合評価: 合格\n適切な要約か: はい\n"));
+        assert!(formatted.contains("重要情報の抽出 ■■■■■ 5/5"));
+        assert!(formatted.contains("簡潔性\u{3000}\u{3000}\u{3000}\u{3000} ■■■□□ 3/5"));
+        assert!(formatted.contains("正確性\u{3000}\u{3000}\u{3000}\u{3000} ■■■■□ 4/5"));
+        assert!(formatted.contains("1. imp1\n2. imp2\n3. imp3"));
+    }
+
+    #[test]
+    fn parse_evaluation_accepts_json_response() {
+        let response = r#"```json
+{"appropriate": false, "importance": 2, "conciseness": 4, "accuracy": 3,
+ "improvements": ["a", "b", "c"], "overall": "不合格"}
+```"#;
+        let parsed = parse_evaluation(response);
+        assert_eq!(
+            parsed,
+            Ok(EvaluationResult {
+                appropriate: false,
+                importance: 2,
+                conciseness: 4,
+                accuracy: 3,
+                improvement1: "a".to_string(),
+                improvement2: "b".to_string(),
+                improvement3: "c".to_string(),
+                overall: OverallEvaluation::Fail,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_evaluation_rejects_invalid_json_scores() {
+        let response = r#"{"appropriate": true, "importance": 9, "conciseness": 4, "accuracy": 3, "improvements": [], "overall": "合格"}"#;
+        assert_eq!(
+            parse_evaluation(response),
+            Err(ParseEvaluationError::InvalidValue(
+                "重要情報の抽出",
+                "9".to_string()
+            ))
+        );
+        assert!(matches!(
+            parse_evaluation("{\"appropriate\": true}"),
+            Err(ParseEvaluationError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn parse_evaluation_falls_back_to_lines_when_braces_are_not_json() {
+        let response = PASS_RESPONSE.replace("改善点1: なし", "改善点1: {背景} の説明を補う");
+        let parsed = parse_evaluation(&response).map(|parsed| parsed.improvement1);
+        assert_eq!(parsed, Ok("{背景} の説明を補う".to_string()));
+    }
+
+    #[test]
+    fn parse_evaluation_extras_reads_model_answer_and_key_points() {
+        let response = r#"{"appropriate": true, "importance": 4, "conciseness": 4, "accuracy": 4, "improvements": ["a", "b", "c"], "model_answer": " 模範の要約。 ", "key_points": [{"point": "要点1", "covered": true}, {"point": "要点2"}, {"point": " "}], "keywords": [" 少子化 ", ""], "overall": "合格"}"#;
+        let extras = parse_evaluation_extras(response);
+        assert_eq!(extras.model_answer, "模範の要約。");
+        assert_eq!(extras.keywords, ["少子化"]);
+        assert_eq!(
+            extras.key_points,
+            [
+                KeyPoint {
+                    point: "要点1".to_string(),
+                    covered: true,
+                },
+                KeyPoint {
+                    point: "要点2".to_string(),
+                    covered: false,
+                },
+            ]
+        );
+        assert_eq!(
+            parse_evaluation_extras(PASS_RESPONSE),
+            EvaluationExtras::default()
+        );
     }
 
     #[test]
@@ -430,6 +701,12 @@ mod tests {
         assert!(prompt.contains("# 要約文\n要約"));
     }
 
+    #[test]
+    fn fill_evaluation_template_replaces_placeholders() {
+        let prompt = fill_evaluation_template("原文={original}\n要約={summary}", "A", "B");
+        assert_eq!(prompt, "原文=A\n要約=B");
+    }
+
     #[test]
     fn fail_response_parses_as_fail() {
         let parsed = parse_evaluation(FAIL_RESPONSE).unwrap_or(EvaluationResult {
@@ -0,0 +1,120 @@
+use crate::api_client::ApiClient;
+use crate::error::AppError;
+use crate::summary_length::LengthTarget;
+use futures_util::future::join_all;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+/// 評価結果を受け取ったときに、どの提出に対する結果かを判別するための情報
+pub enum EvaluationJob {
+    Single {
+        summary: String,
+    },
+    /// 統合要約モード。原文は 2 つの資料をつなげたもの
+    Synthesis {
+        summary: String,
+    },
+    HotSeat {
+        players: [String; 2],
+    },
+}
+
+type Responses = Vec<Result<String, AppError>>;
+
+struct RunningEvaluation {
+    job: EvaluationJob,
+    handle: JoinHandle<()>,
+    deadline: Instant,
+}
+
+/// 評価 API の呼び出しを tokio タスクで実行し、結果をチャネル経由でメインループに返す。
+/// 評価中も画面の描画とキー入力を止めないようにするため
+pub struct EvaluationRunner {
+    sender: UnboundedSender<Responses>,
+    receiver: UnboundedReceiver<Responses>,
+    running: Option<RunningEvaluation>,
+}
+
+impl Default for EvaluationRunner {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            sender,
+            receiver,
+            running: None,
+        }
+    }
+}
+
+impl EvaluationRunner {
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// `summaries` の各要約を並行して評価する。結果は同じ順序で返る。
+    /// `length` は要約の長さの条件で、統合要約モードでは使わない
+    pub fn start(
+        &mut self,
+        client: ApiClient,
+        original_text: String,
+        summaries: Vec<String>,
+        job: EvaluationJob,
+        budget: Duration,
+        length: Option<LengthTarget>,
+    ) {
+        self.cancel();
+        let sender = self.sender.clone();
+        let synthesis = matches!(job, EvaluationJob::Synthesis { .. });
+        let handle = tokio::spawn(async move {
+            let responses = if synthesis {
+                let requests = summaries
+                    .iter()
+                    .map(|summary| client.evaluate_synthesis(&original_text, summary));
+                join_all(requests).await
+            } else {
+                let requests = summaries
+                    .iter()
+                    .map(|summary| client.evaluate_summary(&original_text, summary, length));
+                join_all(requests).await
+            };
+            // 受信側が先に破棄されていれば結果は不要なので無視する
+            let _ = sender.send(responses);
+        });
+        self.running = Some(RunningEvaluation {
+            job,
+            handle,
+            deadline: Instant::now() + budget,
+        });
+    }
+
+    /// 1人分の評価が予算時間を超えているか。対戦モードでは選択肢を出さない
+    pub fn is_over_budget(&self) -> bool {
+        self.running.as_ref().is_some_and(|running| {
+            matches!(
+                running.job,
+                EvaluationJob::Single { .. } | EvaluationJob::Synthesis { .. }
+            ) && Instant::now() >= running.deadline
+        })
+    }
+
+    pub fn extend_deadline(&mut self, budget: Duration) {
+        if let Some(running) = self.running.as_mut() {
+            running.deadline = Instant::now() + budget;
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        if let Some(running) = self.running.take() {
+            running.handle.abort();
+        }
+        while self.receiver.try_recv().is_ok() {}
+    }
+
+    /// 評価が終わっていれば、その提出内容と結果を返す
+    pub fn try_finish(&mut self) -> Option<(EvaluationJob, Responses)> {
+        let responses = self.receiver.try_recv().ok()?;
+        let running = self.running.take()?;
+        Some((running.job, responses))
+    }
+}
@@ -1,31 +1,55 @@
-use crate::app::{App, MENU_OPTIONS, ViewMode};
+use crate::app::{App, MENU_OPTIONS, Popup, ViewMode};
 use crate::error::AppError;
+use crate::pane_layout::ZenMode;
+use crate::reports::ReportTab;
+use crate::result_tabs::ResultTab;
+use crate::romaji;
 use rat_text::event::HandleEvent;
 use ratatui::{
     crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
     widgets::{Paragraph, Wrap},
 };
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const EVENT_POLL_INTERVAL_MS: u64 = 100;
+/// コーチ画面で PageUp/PageDown を押したときにスクロールする行数
+const COACH_PAGE_LINES: u16 = 10;
 
 pub enum AppAction {
+    CancelRequest,
+    CancelSideRequest,
+    DeferTraining,
     Evaluate,
+    KeepWaiting,
     NextTraining,
+    OpenModelSelect,
+    QuizAnswered,
+    RequestSelfGrade,
+    SelfGrade(bool),
     StartTraining,
+    SubmitApiKey,
+    SwitchProfile,
 }
 
 pub fn handle_events(app: &mut App) -> Result<Option<AppAction>, AppError> {
     if event::poll(Duration::from_millis(EVENT_POLL_INTERVAL_MS))? {
         let ev = event::read()?;
+        if let Event::Resize(width, height) = ev {
+            app.update_terminal_size(width, height);
+            clamp_original_text_scroll(app);
+            return Ok(None);
+        }
         if let Event::Key(key) = ev {
             if key.kind != KeyEventKind::Press {
                 return Ok(None);
             }
+            if key.code == KeyCode::Esc && app.is_waiting_side_request() {
+                return Ok(Some(AppAction::CancelSideRequest));
+            }
 
             match app.view_mode {
                 ViewMode::Menu => return Ok(handle_menu_events(app, key)),
-                ViewMode::Report => {
+                ViewMode::Report | ViewMode::BadgeGallery => {
                     handle_report_events(app, key);
                     return Ok(None);
                 }
@@ -33,10 +57,60 @@ pub fn handle_events(app: &mut App) -> Result<Option<AppAction>, AppError> {
                     handle_help_events(app, key);
                     return Ok(None);
                 }
+                ViewMode::ModelSelect => {
+                    handle_model_select_events(app, key);
+                    return Ok(None);
+                }
+                ViewMode::ReadAloud => {
+                    handle_read_aloud_events(app, key);
+                    return Ok(None);
+                }
+                ViewMode::SpeedReading => {
+                    handle_speed_reading_events(app, key);
+                    return Ok(None);
+                }
+                ViewMode::FileBrowser => {
+                    handle_file_browser_events(app, key);
+                    return Ok(None);
+                }
+                ViewMode::History => {
+                    handle_history_events(app, key);
+                    return Ok(None);
+                }
+                ViewMode::ApiKeySetup => return Ok(handle_api_key_setup_events(app, &ev, key)),
+                ViewMode::ProfileSelect => return Ok(handle_profile_select_events(app, key)),
+                ViewMode::Coach => {
+                    handle_coach_events(app, &ev, key);
+                    return Ok(None);
+                }
                 ViewMode::Normal => {
+                    if app.popup.is_some() {
+                        return Ok(handle_popup_events(app, &ev, key));
+                    }
                     if app.text_area_state.focus.get() {
                         return Ok(handle_editing_events(app, &ev, key));
                     }
+                    if app.scratchpad.is_editing() {
+                        if key.code == KeyCode::Esc {
+                            app.stop_editing_scratchpad();
+                        } else {
+                            let _ = app
+                                .scratchpad
+                                .text_area
+                                .handle(&ev, rat_text::event::Regular);
+                        }
+                        return Ok(None);
+                    }
+                    if app.word_selection.is_some() {
+                        handle_word_selection_events(app, key);
+                        return Ok(None);
+                    }
+                    if app.is_quiz_answering() && is_quiz_key(key) {
+                        return Ok(handle_quiz_events(app, key));
+                    }
+                    if key.code == KeyCode::Esc && app.is_evaluating() {
+                        return Ok(Some(AppAction::CancelRequest));
+                    }
                     return Ok(handle_normal_mode_events(app, key));
                 }
             }
@@ -45,7 +119,94 @@ pub fn handle_events(app: &mut App) -> Result<Option<AppAction>, AppError> {
     Ok(None)
 }
 
+/// 通常画面に重ねたダイアログの操作
+fn handle_popup_events(app: &mut App, ev: &Event, key: event::KeyEvent) -> Option<AppAction> {
+    match app.popup {
+        Some(Popup::SlowEvaluation) => return handle_latency_prompt_events(key),
+        Some(Popup::SelfGrade) => return handle_self_grade_events(app, key),
+        Some(Popup::EvaluationFailed) => return handle_evaluation_failed_events(app, key),
+        Some(Popup::Break { .. }) => {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Char('s')) {
+                app.skip_break();
+            }
+        }
+        Some(Popup::WordLookup) => handle_word_lookup_events(app, key),
+        Some(Popup::Appeal) => handle_appeal_events(app, ev, key),
+        Some(Popup::KeywordPrediction) => match key.code {
+            KeyCode::Enter => app.confirm_keyword_prediction(),
+            KeyCode::Esc => app.skip_keyword_prediction(),
+            _ => {
+                if let Some(prediction) = app.keyword_prediction.as_mut() {
+                    let _ = prediction.input.handle(ev, rat_text::event::Regular);
+                }
+            }
+        },
+        Some(Popup::Error | Popup::RankUp { .. }) => match key.code {
+            KeyCode::Enter | KeyCode::Esc => app.dismiss_error(),
+            _ if app.keys.quit.matches(key) => app.should_quit = true,
+            _ => {}
+        },
+        None => {}
+    }
+    None
+}
+
+/// 返事を待っている間は入力を受け付けない。Tab は返事を受け取ってから使える
+fn handle_appeal_events(app: &mut App, ev: &Event, key: event::KeyEvent) {
+    let Some(appeal) = app.appeal.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.close_appeal(),
+        KeyCode::Tab => app.toggle_appeal_override(),
+        _ if appeal.pending_argument().is_some() => {}
+        KeyCode::Enter => {
+            appeal.submit();
+        }
+        _ => {
+            let _ = appeal.input.handle(ev, rat_text::event::Regular);
+        }
+    }
+}
+
+/// 会話のスクロールは末尾からの行数で持つ。上限は描画時に会話の長さで抑える
+fn handle_coach_events(app: &mut App, ev: &Event, key: event::KeyEvent) {
+    let Some(coach) = app.coach.as_mut() else {
+        app.return_from_aux_view();
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.return_from_aux_view(),
+        KeyCode::Up => coach.scroll_from_bottom = coach.scroll_from_bottom.saturating_add(1),
+        KeyCode::Down => coach.scroll_from_bottom = coach.scroll_from_bottom.saturating_sub(1),
+        KeyCode::PageUp => {
+            coach.scroll_from_bottom = coach.scroll_from_bottom.saturating_add(COACH_PAGE_LINES);
+        }
+        KeyCode::PageDown => {
+            coach.scroll_from_bottom = coach.scroll_from_bottom.saturating_sub(COACH_PAGE_LINES);
+        }
+        _ if coach.pending.is_some() => {}
+        KeyCode::Enter => {
+            coach.submit();
+        }
+        _ => {
+            let _ = coach.input.handle(ev, rat_text::event::Regular);
+        }
+    }
+}
+
+// 条件を満たさないキーを後ろの腕に回さないよう、match ガードではなく腕の中の if で判定する
+#[expect(clippy::collapsible_match)]
 fn handle_menu_events(app: &mut App, key: event::KeyEvent) -> Option<AppAction> {
+    if app.recovered_draft.is_some() {
+        handle_draft_recovery_events(app, key);
+        return None;
+    }
+    if app.flashback.is_some() {
+        handle_flashback_events(app, key);
+        return None;
+    }
+
     match key.code {
         KeyCode::Up | KeyCode::Char('k') => {
             if app.selected_menu_item > 0 {
@@ -64,18 +225,58 @@ fn handle_menu_events(app: &mut App, key: event::KeyEvent) -> Option<AppAction>
             }
         }
         KeyCode::Enter => {
-            if let Some(&count) = MENU_OPTIONS.get(app.selected_menu_item) {
-                app.character_count = count;
-            }
+            app.begin_free_training();
+            return Some(AppAction::StartTraining);
+        }
+        KeyCode::Char('a') => app.open_api_key_setup(None),
+        KeyCode::Char('P') => app.enter_profile_select(),
+        // 対戦・問題・統合要約・段階練習・今日のお題・モデル選択は API がないと使えない。
+        // 4 択問題だけは、ライブラリに残した問題があれば解き直せる
+        KeyCode::Char(mode @ ('2' | 'c' | 's' | 'u' | 'o' | 'm'))
+            if app.offline.is_some() && (mode != 'c' || !app.has_library_quiz()) =>
+        {
+            app.reject_offline_mode();
+        }
+        KeyCode::Char(mode) if begin_training_mode(app, mode) => {
+            return Some(AppAction::StartTraining);
+        }
+        KeyCode::Char('v') if app.begin_review() => {
             return Some(AppAction::StartTraining);
         }
-        KeyCode::Char('r') => {
+        KeyCode::Char('m') => {
+            return Some(AppAction::OpenModelSelect);
+        }
+        KeyCode::Char('d') => {
+            app.selected_difficulty = app.selected_difficulty.next();
+        }
+        KeyCode::Char('g') => {
+            app.cycle_length_goal();
+        }
+        KeyCode::Char('x') => {
+            app.cycle_strictness();
+        }
+        KeyCode::Char('f') => {
+            app.open_file_browser();
+        }
+        KeyCode::Char('p') => {
+            app.paste_clipboard_text();
+        }
+        KeyCode::Char('b') => {
+            app.start_library_text();
+        }
+        _ if app.keys.report.matches(key) => {
             app.enter_report_view();
         }
-        KeyCode::Char('h') => {
+        KeyCode::Char('H') => {
+            app.enter_history_view();
+        }
+        KeyCode::Char('C') => {
+            app.cycle_theme();
+        }
+        _ if app.keys.help.matches(key) => {
             app.enter_help_view();
         }
-        KeyCode::Char('q') => {
+        _ if app.keys.quit.matches(key) => {
             app.should_quit = true;
         }
         _ => {}
@@ -83,55 +284,415 @@ fn handle_menu_events(app: &mut App, key: event::KeyEvent) -> Option<AppAction>
     None
 }
 
+/// メニューのキーに対応する練習のモードを始める。始めたら `true`
+fn begin_training_mode(app: &mut App, key: char) -> bool {
+    match key {
+        '2' => app.begin_hot_seat(),
+        'l' => app.begin_level_challenge(),
+        't' => app.begin_timed_challenge(),
+        'c' => app.begin_quiz(),
+        'e' => app.begin_exam(),
+        's' => app.begin_synthesis(),
+        'u' => app.begin_guided(),
+        'o' => app.begin_daily_challenge(),
+        _ => return false,
+    }
+    true
+}
+
+fn handle_model_select_events(app: &mut App, key: event::KeyEvent) {
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.selected_model_item = app.selected_model_item.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j')
+            if app.selected_model_item + 1 < app.available_models.len() =>
+        {
+            app.selected_model_item += 1;
+        }
+        KeyCode::Enter => app.select_model(),
+        KeyCode::Esc | KeyCode::Char('m') => app.return_from_aux_view(),
+        _ if app.keys.quit.matches(key) => app.should_quit = true,
+        _ => {}
+    }
+}
+
+/// 入力欄はキーを伏せて表示する。`q` などもキーの一部として入力する
+fn handle_api_key_setup_events(
+    app: &mut App,
+    ev: &Event,
+    key: event::KeyEvent,
+) -> Option<AppAction> {
+    let setup = app.api_key_setup.as_mut()?;
+    if setup.checking {
+        return None;
+    }
+    match key.code {
+        KeyCode::Enter => return Some(AppAction::SubmitApiKey),
+        KeyCode::Esc => app.close_api_key_setup(),
+        _ => {
+            let _ = setup.input.handle(ev, rat_text::event::Regular);
+        }
+    }
+    None
+}
+
+fn handle_profile_select_events(app: &mut App, key: event::KeyEvent) -> Option<AppAction> {
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.selected_profile_item = app.selected_profile_item.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j')
+            if app.selected_profile_item + 1 < app.profiles.len() =>
+        {
+            app.selected_profile_item += 1;
+        }
+        KeyCode::Enter => return Some(AppAction::SwitchProfile),
+        KeyCode::Esc | KeyCode::Char('P') => app.return_from_aux_view(),
+        _ if app.keys.quit.matches(key) => app.should_quit = true,
+        _ => {}
+    }
+    None
+}
+
+fn handle_history_events(app: &mut App, key: event::KeyEvent) {
+    let Some(browser) = app.history_browser.as_mut() else {
+        return;
+    };
+    let detail_open = browser.is_detail_open();
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if detail_open => {
+            browser.detail_scroll = browser.detail_scroll.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') if detail_open => {
+            browser.detail_scroll = browser.detail_scroll.saturating_add(1);
+        }
+        KeyCode::Up | KeyCode::Char('k') => browser.select_previous(),
+        KeyCode::Down | KeyCode::Char('j') => browser.select_next(),
+        KeyCode::Enter if !detail_open => app.open_history_detail(),
+        KeyCode::Char('R') => app.retry_history_selection(),
+        KeyCode::Esc | KeyCode::Char('H') => app.back_from_history(),
+        _ if app.keys.quit.matches(key) => app.should_quit = true,
+        _ => {}
+    }
+}
+
+fn handle_file_browser_events(app: &mut App, key: event::KeyEvent) {
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            if let Some(browser) = app.file_browser.as_mut() {
+                browser.select_previous();
+            }
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            if let Some(browser) = app.file_browser.as_mut() {
+                browser.select_next();
+            }
+        }
+        KeyCode::Enter => app.open_selected_file(),
+        KeyCode::Backspace | KeyCode::Left => app.browse_parent_dir(),
+        KeyCode::Esc => app.close_file_browser(),
+        _ if app.keys.quit.matches(key) => app.should_quit = true,
+        _ => {}
+    }
+}
+
+fn handle_read_aloud_events(app: &mut App, key: event::KeyEvent) {
+    match key.code {
+        KeyCode::Char(' ') => app.advance_read_aloud(),
+        KeyCode::Esc => app.exit_read_aloud(),
+        _ if app.keys.quit.matches(key) => app.should_quit = true,
+        _ => {}
+    }
+}
+
+fn handle_speed_reading_events(app: &mut App, key: event::KeyEvent) {
+    let Some(session) = app.speed_reading.as_mut() else {
+        app.exit_speed_reading();
+        return;
+    };
+    match key.code {
+        KeyCode::Char(' ') => session.toggle_pause(Instant::now()),
+        KeyCode::Char('+' | '=') => session.adjust_pace(true),
+        KeyCode::Char('-') => session.adjust_pace(false),
+        KeyCode::Esc => app.exit_speed_reading(),
+        _ if app.keys.quit.matches(key) => app.should_quit = true,
+        _ => {}
+    }
+}
+
+fn handle_latency_prompt_events(key: event::KeyEvent) -> Option<AppAction> {
+    match key.code {
+        KeyCode::Char('w') => Some(AppAction::KeepWaiting),
+        KeyCode::Char('c') | KeyCode::Esc => Some(AppAction::CancelRequest),
+        KeyCode::Char('s') => Some(AppAction::RequestSelfGrade),
+        _ => None,
+    }
+}
+
+/// 語の意味のダイアログ。`a` で単語帳に追加する
+fn handle_word_lookup_events(app: &mut App, key: event::KeyEvent) {
+    match key.code {
+        KeyCode::Char('a') => app.add_lookup_to_vocab(),
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('d') => app.close_word_lookup(),
+        _ => {}
+    }
+}
+
+/// 評価に失敗したときのダイアログ。y/n で自己採点として記録し、Esc/Enter で閉じる
+fn handle_evaluation_failed_events(app: &mut App, key: event::KeyEvent) -> Option<AppAction> {
+    match key.code {
+        KeyCode::Char('y') => return Some(AppAction::SelfGrade(true)),
+        KeyCode::Char('n') => return Some(AppAction::SelfGrade(false)),
+        KeyCode::Esc | KeyCode::Enter => app.dismiss_evaluation_failure(),
+        _ if app.keys.quit.matches(key) => app.should_quit = true,
+        _ => {}
+    }
+    None
+}
+
+fn handle_self_grade_events(app: &mut App, key: event::KeyEvent) -> Option<AppAction> {
+    match key.code {
+        KeyCode::Char('y') => Some(AppAction::SelfGrade(true)),
+        KeyCode::Char('n') => Some(AppAction::SelfGrade(false)),
+        KeyCode::Esc => {
+            app.cancel_self_grade();
+            None
+        }
+        _ => None,
+    }
+}
+
+fn handle_flashback_events(app: &mut App, key: event::KeyEvent) {
+    match key.code {
+        KeyCode::Enter => app.accept_flashback(),
+        KeyCode::Esc | KeyCode::Char('x') => app.dismiss_flashback(),
+        _ if app.keys.quit.matches(key) => app.should_quit = true,
+        _ => {}
+    }
+}
+
+fn handle_draft_recovery_events(app: &mut App, key: event::KeyEvent) {
+    match key.code {
+        KeyCode::Enter | KeyCode::Char('y') => app.restore_draft(),
+        KeyCode::Esc | KeyCode::Char('n') => app.discard_draft(),
+        _ if app.keys.quit.matches(key) => app.should_quit = true,
+        _ => {}
+    }
+}
+
 fn handle_editing_events(app: &mut App, ev: &Event, key: event::KeyEvent) -> Option<AppAction> {
-    if key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL) {
+    if handle_zen_peek_events(app, key) {
+        return None;
+    }
+    if key.code == KeyCode::Char('j') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        app.toggle_kana_input();
+        return None;
+    }
+    if app.kana_input.enabled && handle_kana_input_events(app, key) {
+        return None;
+    }
+    if app.keys.submit.matches(key) {
         if !app.text_area_state.value().trim().is_empty() {
             app.stop_editing();
             return Some(AppAction::Evaluate);
         }
     } else if key.code == KeyCode::Esc {
         app.stop_editing();
+    } else if matches!(key.code, KeyCode::Char(' ') | KeyCode::Tab)
+        && !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        && app.expand_abbreviation()
+    {
+        // 略語を展開したときは区切りの空白を入力しない
     } else {
         let _ = app.text_area_state.handle(ev, rat_text::event::Regular);
     }
     None
 }
 
+/// 集中モードで原文をのぞくキー。のぞいている間は ↑/↓ でスクロールし、ほかのキーで要約欄に戻る。処理した場合は `true`
+fn handle_zen_peek_events(app: &mut App, key: event::KeyEvent) -> bool {
+    let peek_key = key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL);
+    match app.zen {
+        ZenMode::Peeking => match key.code {
+            KeyCode::Down => scroll_training_view(app, key, true),
+            KeyCode::Up => scroll_training_view(app, key, false),
+            _ => app.toggle_zen_peek(),
+        },
+        ZenMode::Writing if peek_key => app.toggle_zen_peek(),
+        _ => return false,
+    }
+    true
+}
+
+/// かな入力モードのキー操作。処理した場合は `true` を返し、通常の入力には渡さない
+fn handle_kana_input_events(app: &mut App, key: event::KeyEvent) -> bool {
+    let plain = !key
+        .modifiers
+        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+    let composing = app.kana_input.is_composing();
+    match key.code {
+        KeyCode::Char(c) if plain && romaji::is_romaji_char(c) => {
+            if let Some(text) = app.kana_input.push_char(c) {
+                app.text_area_state.insert_str(text);
+            }
+        }
+        KeyCode::Char(' ') if composing => app.kana_input.convert(),
+        KeyCode::Down | KeyCode::Tab if app.kana_input.is_selecting() => {
+            app.kana_input.convert();
+        }
+        KeyCode::Up | KeyCode::BackTab if app.kana_input.is_selecting() => {
+            app.kana_input.previous_candidate();
+        }
+        KeyCode::Enter if composing => app.commit_kana_input(),
+        KeyCode::Backspace if composing => app.kana_input.backspace(),
+        KeyCode::Esc if app.kana_input.is_selecting() => app.kana_input.cancel_candidates(),
+        KeyCode::Esc if composing => app.kana_input.discard(),
+        _ => {
+            // 数字や Ctrl+S など、変換に使わないキーは確定してから通常どおり処理する
+            app.commit_kana_input();
+            return false;
+        }
+    }
+    true
+}
+
 fn handle_report_events(app: &mut App, key: event::KeyEvent) {
+    if app.view_mode == ViewMode::BadgeGallery {
+        handle_badge_gallery_events(app, key);
+        return;
+    }
     match key.code {
-        KeyCode::Char('r') => {
+        _ if app.keys.report.matches(key) => {
             app.return_from_aux_view();
         }
-        KeyCode::Char('q') => {
+        KeyCode::Char('n') => app.toggle_score_scale(),
+        KeyCode::Char('b') => app.enter_badge_gallery(),
+        KeyCode::Tab => app.select_report_tab(app.report_tab.next()),
+        KeyCode::Char(number @ '1'..='5') => {
+            if let Some(tab) = ReportTab::from_number(number) {
+                app.select_report_tab(tab);
+            }
+        }
+        KeyCode::Down => app.report_scroll = app.report_scroll.saturating_add(1),
+        KeyCode::Up => app.report_scroll = app.report_scroll.saturating_sub(1),
+        _ if app.keys.scroll_down.matches(key) => {
+            app.report_scroll = app.report_scroll.saturating_add(1);
+        }
+        _ if app.keys.scroll_up.matches(key) => {
+            app.report_scroll = app.report_scroll.saturating_sub(1);
+        }
+        _ if app.keys.quit.matches(key) => {
             app.should_quit = true;
         }
         _ => {}
     }
 }
 
-fn handle_help_events(app: &mut App, key: event::KeyEvent) {
+fn handle_badge_gallery_events(app: &mut App, key: event::KeyEvent) {
     match key.code {
-        KeyCode::Char('h') => {
+        _ if app.keys.report.matches(key) => {
             app.return_from_aux_view();
-            app.help_scroll = 0;
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        KeyCode::Char('b') | KeyCode::Esc => app.enter_report_view(),
+        KeyCode::Down => app.badge_gallery_scroll = app.badge_gallery_scroll.saturating_add(1),
+        KeyCode::Up => app.badge_gallery_scroll = app.badge_gallery_scroll.saturating_sub(1),
+        _ if app.keys.scroll_down.matches(key) => {
+            app.badge_gallery_scroll = app.badge_gallery_scroll.saturating_add(1);
+        }
+        _ if app.keys.scroll_up.matches(key) => {
+            app.badge_gallery_scroll = app.badge_gallery_scroll.saturating_sub(1);
+        }
+        _ if app.keys.quit.matches(key) => {
+            app.should_quit = true;
+        }
+        _ => {}
+    }
+}
+
+fn handle_help_events(app: &mut App, key: event::KeyEvent) {
+    if app.help_search.editing {
+        match key.code {
+            KeyCode::Enter => app.confirm_help_search(),
+            KeyCode::Esc => app.cancel_help_search(),
+            KeyCode::Backspace => app.edit_help_search(None),
+            KeyCode::Char(c) => app.edit_help_search(Some(c)),
+            _ => {}
+        }
+        return;
+    }
+    if app.help_toc.is_some() {
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => app.move_help_toc(true),
+            KeyCode::Up | KeyCode::Char('k') => app.move_help_toc(false),
+            KeyCode::Enter => app.jump_to_help_toc_selection(),
+            KeyCode::Esc | KeyCode::Char('t') => app.toggle_help_toc(),
+            _ if app.keys.quit.matches(key) => app.should_quit = true,
+            _ => {}
+        }
+        return;
+    }
+    match key.code {
+        _ if app.keys.help.matches(key) => {
+            app.leave_help_view();
+        }
+        KeyCode::Down => app.help_scroll = app.help_scroll.saturating_add(1),
+        KeyCode::Up => app.help_scroll = app.help_scroll.saturating_sub(1),
+        _ if app.keys.scroll_down.matches(key) => {
             app.help_scroll = app.help_scroll.saturating_add(1);
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        _ if app.keys.scroll_up.matches(key) => {
             app.help_scroll = app.help_scroll.saturating_sub(1);
         }
-        KeyCode::Char('q') => {
+        KeyCode::Char('/') => app.begin_help_search(),
+        KeyCode::Char('n') => app.move_help_match(true),
+        KeyCode::Char('N') => app.move_help_match(false),
+        KeyCode::Char('t') => app.toggle_help_toc(),
+        KeyCode::Char('T') => {
+            app.leave_help_view();
+            app.start_tutorial();
+        }
+        KeyCode::Char(']') => app.jump_help_section(true),
+        KeyCode::Char('[') => app.jump_help_section(false),
+        _ if app.keys.quit.matches(key) => {
             app.should_quit = true;
         }
         _ => {}
     }
 }
 
+/// 4 択問題の選択と解答に使うキー。ほかのキーは通常の操作に回す
+fn is_quiz_key(key: event::KeyEvent) -> bool {
+    !key.modifiers.contains(KeyModifiers::SHIFT)
+        && matches!(
+            key.code,
+            KeyCode::Down | KeyCode::Up | KeyCode::Enter | KeyCode::Char('j' | 'k')
+        )
+}
+
+fn handle_quiz_events(app: &mut App, key: event::KeyEvent) -> Option<AppAction> {
+    let session = app.quiz.as_mut()?;
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => session.select_next(),
+        KeyCode::Up | KeyCode::Char('k') => session.select_previous(),
+        KeyCode::Enter => return session.confirm().then_some(AppAction::QuizAnswered),
+        _ => {}
+    }
+    None
+}
+
+#[expect(clippy::collapsible_match)]
 fn handle_normal_mode_events(app: &mut App, key: event::KeyEvent) -> Option<AppAction> {
+    if app.is_paused() {
+        handle_paused_events(app, key);
+        return None;
+    }
     match key.code {
-        KeyCode::Char('i') | KeyCode::Enter => {
-            if !app.show_evaluation_overlay {
+        _ if app.show_evaluation_overlay && handle_evaluation_overlay_events(app, key) => {}
+        _ if key.code == KeyCode::Enter || app.keys.edit.matches(key) => {
+            if !app.show_evaluation_overlay && !app.is_evaluating() && app.quiz.is_none() {
                 app.begin_editing();
             }
         }
@@ -139,52 +700,231 @@ fn handle_normal_mode_events(app: &mut App, key: event::KeyEvent) -> Option<AppA
             if !app.evaluation_text.is_empty() {
                 app.show_evaluation_overlay = !app.show_evaluation_overlay;
                 if app.show_evaluation_overlay {
-                    app.evaluation_overlay_scroll = 0;
+                    app.result_tabs.reset_view();
                 }
             }
         }
-        KeyCode::Char('n') => {
+        _ if app.keys.next.matches(key) => {
             if app.show_evaluation_overlay {
                 app.show_evaluation_overlay = false;
                 return Some(AppAction::NextTraining);
             }
         }
-        KeyCode::Char('r') => {
+        KeyCode::Char('p') if !app.is_evaluating() && app.exam.is_none() => {
+            app.paste_clipboard_text();
+        }
+        KeyCode::Char('s') if app.has_training_started() => {
+            app.open_scratchpad();
+        }
+        KeyCode::Char('S') if app.has_training_started() => {
+            app.toggle_scratchpad();
+        }
+        KeyCode::Char('a') if app.has_training_started() => {
+            app.begin_read_aloud();
+        }
+        KeyCode::Char('v') if app.has_training_started() => {
+            app.begin_speed_reading();
+        }
+        KeyCode::Char('z') => app.toggle_zen_mode(),
+        KeyCode::Char('P') => app.toggle_pause(),
+        KeyCode::Char('g') if app.has_training_started() => {
+            app.toggle_glossary();
+        }
+        KeyCode::Char('w') if app.has_training_started() => {
+            app.begin_word_selection();
+            scroll_to_selected_word(app);
+        }
+        KeyCode::Char('f') if app.has_training_started() => {
+            app.toggle_furigana();
+            clamp_original_text_scroll(app);
+        }
+        KeyCode::Char(c @ ('<' | '>')) => {
+            app.resize_panes(c == '>');
+            clamp_original_text_scroll(app);
+        }
+        KeyCode::Char('L') => {
+            app.toggle_pane_orientation();
+            clamp_original_text_scroll(app);
+        }
+        KeyCode::Char('d') if app.has_unconsumed_text() && app.exam.is_none() => {
+            return Some(AppAction::DeferTraining);
+        }
+        _ if app.keys.report.matches(key) => {
             app.enter_report_view();
         }
-        KeyCode::Char('h') => {
+        KeyCode::Char('H') => {
+            app.enter_history_view();
+        }
+        KeyCode::Char('C') => {
+            app.cycle_theme();
+        }
+        _ if app.keys.help.matches(key) => {
             app.enter_help_view();
         }
-        KeyCode::Char('q') => {
+        _ if app.keys.quit.matches(key) => {
             app.should_quit = true;
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.show_evaluation_overlay && key.modifiers.contains(KeyModifiers::SHIFT) {
-                let (visible_height, visible_width) = app.evaluation_viewport_size();
-                let max_scroll =
-                    calculate_max_scroll(&app.evaluation_text, visible_height, visible_width);
-                app.evaluation_overlay_scroll = app
-                    .evaluation_overlay_scroll
-                    .saturating_add(1)
-                    .min(max_scroll);
-            } else {
-                let (visible_height, visible_width) = app.original_text_viewport_size();
-                let max_scroll =
-                    calculate_max_scroll(&app.original_text, visible_height, visible_width);
-                app.original_text_scroll =
-                    app.original_text_scroll.saturating_add(1).min(max_scroll);
+        KeyCode::Down => scroll_training_view(app, key, true),
+        KeyCode::Up => scroll_training_view(app, key, false),
+        _ if app.keys.scroll_down.matches(key) => scroll_training_view(app, key, true),
+        _ if app.keys.scroll_up.matches(key) => scroll_training_view(app, key, false),
+        _ => {}
+    }
+    None
+}
+
+/// 一時停止中は再開と終了のほかは受け付けない
+fn handle_paused_events(app: &mut App, key: event::KeyEvent) {
+    match key.code {
+        KeyCode::Char('P') => app.toggle_pause(),
+        _ if app.keys.quit.matches(key) => app.should_quit = true,
+        _ => {}
+    }
+}
+
+/// 評価結果のタブを前後に動かすキーなら、次のタブへ進むか (`true`) を返す
+fn result_tab_step(key: event::KeyEvent) -> Option<bool> {
+    match key.code {
+        KeyCode::Right | KeyCode::Char('l') => Some(true),
+        KeyCode::Left | KeyCode::Char('h') => Some(false),
+        _ => None,
+    }
+}
+
+/// 評価結果を表示している間だけ使えるキー。使ったキーなら `true`
+fn handle_evaluation_overlay_events(app: &mut App, key: event::KeyEvent) -> bool {
+    // タブの移動はヘルプのキー (既定は `h`) より優先する
+    if let Some(forward) = result_tab_step(key) {
+        if forward {
+            app.result_tabs.select_next();
+        } else {
+            app.result_tabs.select_previous();
+        }
+        return true;
+    }
+    match key.code {
+        _ if app.keys.help.matches(key) => return false,
+        KeyCode::Char('R') => app.retry_last_failed(),
+        KeyCode::Char(number @ '1'..='4') => {
+            if let Some(tab) = ResultTab::from_number(number) {
+                app.result_tabs.select(tab);
             }
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.show_evaluation_overlay && key.modifiers.contains(KeyModifiers::SHIFT) {
-                app.evaluation_overlay_scroll = app.evaluation_overlay_scroll.saturating_sub(1);
-            } else {
-                app.original_text_scroll = app.original_text_scroll.saturating_sub(1);
+        KeyCode::Char('i') => app.jump_to_improvements(),
+        KeyCode::Char('y') => app.copy_evaluation(),
+        KeyCode::Char('a') if app.last_session.is_some() => app.begin_appeal(),
+        KeyCode::Char('t') if app.last_session.is_some() => app.enter_coach_view(),
+        KeyCode::Char('c') if app.last_session.is_some() => app.export_challenge(),
+        KeyCode::Char('x') if app.last_session.is_some() => app.export_journal(),
+        _ => return false,
+    }
+    true
+}
+
+/// API の応答を待っている間に Esc が押されたか。ほかのキーは読み捨てる
+pub fn cancel_requested() -> Result<bool, AppError> {
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && key.code == KeyCode::Esc
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// 評価結果の表示中は Shift 付きで評価を、それ以外は原文をスクロールする
+fn scroll_training_view(app: &mut App, key: event::KeyEvent, down: bool) {
+    let evaluation = app.show_evaluation_overlay && key.modifiers.contains(KeyModifiers::SHIFT);
+    match (evaluation, down) {
+        (true, true) => {
+            let (visible_height, visible_width) = app.evaluation_viewport_size();
+            let max_scroll =
+                calculate_max_scroll(&app.result_tab_text(), visible_height, visible_width);
+            app.result_tabs.scroll_down(max_scroll);
+        }
+        (true, false) => app.result_tabs.scroll_up(),
+        (false, true) => {
+            let max_scroll = original_text_max_scroll(app);
+            app.original_text_scroll = app.original_text_scroll.saturating_add(1).min(max_scroll);
+        }
+        (false, false) => {
+            app.original_text_scroll = app.original_text_scroll.saturating_sub(1);
+        }
+    }
+}
+
+/// 原文欄で語を選んでいる間は、←/→ で語を移り、`d` で調べる
+fn handle_word_selection_events(app: &mut App, key: event::KeyEvent) {
+    match key.code {
+        KeyCode::Left | KeyCode::Right | KeyCode::Char('h' | 'l') => {
+            if let Some(selection) = app.word_selection.as_mut() {
+                selection.move_by(matches!(key.code, KeyCode::Right | KeyCode::Char('l')));
             }
+            scroll_to_selected_word(app);
         }
+        KeyCode::Char('d') => app.lookup_selected_word(),
+        KeyCode::Esc | KeyCode::Char('w') => app.end_word_selection(),
+        KeyCode::Down => scroll_training_view(app, key, true),
+        KeyCode::Up => scroll_training_view(app, key, false),
+        _ if app.keys.scroll_down.matches(key) => scroll_training_view(app, key, true),
+        _ if app.keys.scroll_up.matches(key) => scroll_training_view(app, key, false),
         _ => {}
     }
-    None
+}
+
+/// 選んでいる語が原文欄に見えるようにスクロールする
+fn scroll_to_selected_word(app: &mut App) {
+    let Some(start) = app
+        .word_selection
+        .as_ref()
+        .and_then(|selection| selection.current())
+        .map(|word| word.start)
+    else {
+        return;
+    };
+    let (visible_height, visible_width) = app.original_text_viewport_size();
+    if visible_width == 0 || visible_height == 0 {
+        return;
+    }
+    let before = app.original_text.get(..start).unwrap_or_default();
+    let line = Paragraph::new(format!("{before}_"))
+        .wrap(Wrap { trim: false })
+        .line_count(visible_width);
+    let line = u16::try_from(line.saturating_sub(1)).unwrap_or(u16::MAX);
+    if line < app.original_text_scroll {
+        app.original_text_scroll = line;
+    } else if line >= app.original_text_scroll.saturating_add(visible_height) {
+        app.original_text_scroll = line.saturating_sub(visible_height - 1);
+    }
+}
+
+/// 原文欄の大きさが変わったら、折り返しの行数に合わせてスクロール位置を収め直す
+fn clamp_original_text_scroll(app: &mut App) {
+    app.original_text_scroll = app.original_text_scroll.min(original_text_max_scroll(app));
+}
+
+/// 統合要約モードでは 2 つの資料を一緒にスクロールし、長い方の末尾まで進める
+fn original_text_max_scroll(app: &App) -> u16 {
+    let (visible_height, visible_width) = app.original_text_viewport_size();
+    if let Some(guided) = app
+        .guided
+        .as_ref()
+        .filter(|guided| !guided.paragraphs.is_empty())
+    {
+        return calculate_max_scroll(&guided.outline(), visible_height, visible_width);
+    }
+    app.synthesis_sources().map_or_else(
+        || calculate_max_scroll(app.displayed_original_text(), visible_height, visible_width),
+        |sources| {
+            sources
+                .iter()
+                .map(|source| calculate_max_scroll(source, visible_height, visible_width))
+                .max()
+                .unwrap_or(0)
+        },
+    )
 }
 
 fn calculate_max_scroll(text: &str, visible_height: u16, visible_width: u16) -> u16 {
@@ -198,7 +938,24 @@ fn calculate_max_scroll(text: &str, visible_height: u16, visible_width: u16) ->
 
 #[cfg(test)]
 mod tests {
-    use super::calculate_max_scroll;
+    use super::{calculate_max_scroll, result_tab_step};
+    use crate::keybindings::KeyBindings;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn h_moves_result_tabs_even_with_the_default_help_key() {
+        let h = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE);
+        assert!(KeyBindings::default().help.matches(h));
+        assert_eq!(result_tab_step(h), Some(false));
+        assert_eq!(
+            result_tab_step(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE)),
+            Some(true)
+        );
+        assert_eq!(
+            result_tab_step(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE)),
+            None
+        );
+    }
 
     #[test]
     fn calculate_max_scroll_uses_inner_height_without_extra_border_adjustment() {
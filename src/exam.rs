@@ -0,0 +1,149 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// 模擬試験で出題する文字数。短い順に 1 問ずつ出す
+pub const EXAM_LENGTHS: [u16; 3] = [400, 720, 1440];
+/// 1 問の満点 (3 観点 × 5 点)
+const ROUND_MAX_POINTS: u32 = 15;
+/// 試験全体の合格点 (100 点満点)
+pub const EXAM_PASS_SCORE: u8 = 60;
+
+/// 模擬試験の 1 問分の結果
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExamRound {
+    pub character_count: u16,
+    pub passed: bool,
+    /// AI 評価の 3 観点の合計点。評価を受けなかった (時間切れで不合格など) 場合は `None`
+    pub points: Option<u8>,
+    pub duration_secs: Option<u32>,
+    pub timed_out: bool,
+}
+
+/// 統計に残す模擬試験 1 回分の記録
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ExamRecord {
+    pub timestamp: DateTime<Local>,
+    /// 100 点満点
+    pub score: u8,
+    pub passed: bool,
+    pub rounds: Vec<ExamRound>,
+}
+
+/// 受験中の模擬試験
+#[derive(Default)]
+pub struct ExamSession {
+    pub rounds: Vec<ExamRound>,
+}
+
+impl ExamSession {
+    /// 次に出題する文字数。全問終えていれば `None`
+    pub fn current_length(&self) -> Option<u16> {
+        EXAM_LENGTHS.get(self.rounds.len()).copied()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current_length().is_none()
+    }
+
+    /// 何問目か (1 始まり)
+    pub fn round_number(&self) -> usize {
+        (self.rounds.len() + 1).min(EXAM_LENGTHS.len())
+    }
+
+    pub fn record(&mut self, round: ExamRound) {
+        if !self.is_finished() {
+            self.rounds.push(round);
+        }
+    }
+
+    /// 全問の得点の合計を 100 点満点に換算する。評価を受けなかった問は 0 点
+    pub fn score(&self) -> u8 {
+        let points: u32 = self
+            .rounds
+            .iter()
+            .map(|round| u32::from(round.points.unwrap_or(0)).min(ROUND_MAX_POINTS))
+            .sum();
+        let max_points = ROUND_MAX_POINTS * u32::try_from(EXAM_LENGTHS.len()).unwrap_or(u32::MAX);
+        u8::try_from(points * 100 / max_points).unwrap_or(100)
+    }
+
+    pub fn to_record(&self, now: DateTime<Local>) -> ExamRecord {
+        let score = self.score();
+        ExamRecord {
+            timestamp: now,
+            score,
+            passed: score >= EXAM_PASS_SCORE,
+            rounds: self.rounds.clone(),
+        }
+    }
+}
+
+/// 試験の最後に表示する成績表
+pub fn format_report(record: &ExamRecord) -> String {
+    let verdict = if record.passed { "合格" } else { "不合格" };
+    let mut text = format!(
+        "# 模擬試験の結果\n- 得点: {}/100 ({verdict}、合格点 {EXAM_PASS_SCORE})\n\n",
+        record.score
+    );
+    for (index, round) in record.rounds.iter().enumerate() {
+        let points = round.points.map_or_else(
+            || "-".to_string(),
+            |points| format!("{points}/{ROUND_MAX_POINTS}"),
+        );
+        let duration = round.duration_secs.map_or_else(String::new, |secs| {
+            format!(" / {}:{:02}", secs / 60, secs % 60)
+        });
+        let time_up = if round.timed_out {
+            " (時間切れ)"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            text,
+            "- 第{}問 {} 文字: {} / {points}{duration}{time_up}",
+            index + 1,
+            round.character_count,
+            if round.passed { "合格" } else { "不合格" },
+        );
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(character_count: u16, points: Option<u8>) -> ExamRound {
+        ExamRound {
+            character_count,
+            passed: points.is_some_and(|points| points >= 9),
+            points,
+            duration_secs: Some(125),
+            timed_out: points.is_none(),
+        }
+    }
+
+    #[test]
+    fn exam_runs_lengths_in_order_and_scores_out_of_100() {
+        let mut session = ExamSession::default();
+        assert_eq!(session.current_length(), Some(400));
+        session.record(round(400, Some(15)));
+        session.record(round(720, Some(12)));
+        assert_eq!(
+            (session.round_number(), session.current_length()),
+            (3, Some(1440))
+        );
+        session.record(round(1440, None));
+        assert!(session.is_finished());
+        session.record(round(2880, Some(15)));
+        assert_eq!(session.rounds.len(), 3);
+
+        let record = session.to_record(Local::now());
+        assert_eq!(record.score, 60);
+        assert!(record.passed);
+        let report = format_report(&record);
+        assert!(report.contains("得点: 60/100 (合格"));
+        assert!(report.contains("第3問 1440 文字: 不合格 / - / 2:05 (時間切れ)"));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::api_client::ApiClient;
+use crate::config::{ExperimentConfig, VariantConfig};
+use crate::error::AppError;
+use crate::evaluation::{EvaluationResult, OverallEvaluation, parse_evaluation};
+use crate::history::SessionRecord;
+use chrono::Local;
+use std::fs;
+use std::path::PathBuf;
+
+const APP_DIR_NAME: &str = "yomitore";
+const EXPERIMENTS_DIR_NAME: &str = "experiments";
+const DEFAULT_PAIR_LIMIT: usize = 20;
+
+/// 同じ (原文, 要約) を 2 つの評価設定で採点した結果の一致率
+#[derive(Debug, Default, PartialEq)]
+pub struct AgreementReport {
+    pub total: usize,
+    pub compared: usize,
+    pub failed_a: usize,
+    pub failed_b: usize,
+    pub verdict_agreements: usize,
+    pub importance_agreements: usize,
+    pub conciseness_agreements: usize,
+    pub accuracy_agreements: usize,
+    pub score_abs_diff_sum: u32,
+}
+
+impl AgreementReport {
+    pub fn add_pair(&mut self, a: Option<&EvaluationResult>, b: Option<&EvaluationResult>) {
+        self.total += 1;
+        match (a, b) {
+            (Some(a), Some(b)) => {
+                self.compared += 1;
+                if a.overall == b.overall {
+                    self.verdict_agreements += 1;
+                }
+                for (left, right, agreements) in [
+                    (a.importance, b.importance, &mut self.importance_agreements),
+                    (
+                        a.conciseness,
+                        b.conciseness,
+                        &mut self.conciseness_agreements,
+                    ),
+                    (a.accuracy, b.accuracy, &mut self.accuracy_agreements),
+                ] {
+                    if left == right {
+                        *agreements += 1;
+                    }
+                    self.score_abs_diff_sum += u32::from(left.abs_diff(right));
+                }
+            }
+            (a, b) => {
+                if a.is_none() {
+                    self.failed_a += 1;
+                }
+                if b.is_none() {
+                    self.failed_b += 1;
+                }
+            }
+        }
+    }
+
+    fn rate(&self, count: usize) -> f64 {
+        if self.compared == 0 {
+            return 0.0;
+        }
+        f64::from(u32::try_from(count).unwrap_or(u32::MAX) * 100)
+            / f64::from(u32::try_from(self.compared).unwrap_or(u32::MAX))
+    }
+
+    fn mean_abs_diff(&self) -> f64 {
+        if self.compared == 0 {
+            return 0.0;
+        }
+        f64::from(self.score_abs_diff_sum)
+            / f64::from(u32::try_from(self.compared * 3).unwrap_or(u32::MAX))
+    }
+
+    pub fn to_markdown(&self, a: &VariantConfig, b: &VariantConfig) -> String {
+        format!(
+            "# 評価プロンプト比較レポート ({})\n\n\
+             | 項目 | 値 |\n|---|---|\n\
+             | A | {} |\n| B | {} |\n\
+             | 対象ペア数 | {} |\n| 比較できたペア数 | {} |\n\
+             | A の解析失敗 | {} |\n| B の解析失敗 | {} |\n\
+             | 合否の一致率 | {:.1}% |\n\
+             | 重要情報の一致率 | {:.1}% |\n\
+             | 簡潔性の一致率 | {:.1}% |\n\
+             | 正確性の一致率 | {:.1}% |\n\
+             | スコア差の平均 | {:.2} |\n",
+            Local::now().format("%Y-%m-%d %H:%M"),
+            describe_variant(a),
+            describe_variant(b),
+            self.total,
+            self.compared,
+            self.failed_a,
+            self.failed_b,
+            self.rate(self.verdict_agreements),
+            self.rate(self.importance_agreements),
+            self.rate(self.conciseness_agreements),
+            self.rate(self.accuracy_agreements),
+            self.mean_abs_diff(),
+        )
+    }
+}
+
+fn describe_variant(variant: &VariantConfig) -> String {
+    format!(
+        "モデル: {} / プロンプト: {}",
+        variant.model.as_deref().unwrap_or("既定"),
+        if variant.prompt.is_some() {
+            "カスタム"
+        } else {
+            "既定"
+        }
+    )
+}
+
+fn verdict_label(result: Option<&EvaluationResult>) -> &'static str {
+    match result.map(|r| &r.overall) {
+        Some(OverallEvaluation::Pass) => "合格",
+        Some(OverallEvaluation::Fail) => "不合格",
+        None => "解析失敗",
+    }
+}
+
+async fn evaluate_variant(
+    client: &ApiClient,
+    variant: &VariantConfig,
+    record: &SessionRecord,
+) -> Option<EvaluationResult> {
+    let response = client
+        .evaluate_summary_with(
+            variant.model.as_deref(),
+            variant.prompt.as_deref(),
+            &record.original_text,
+            &record.summary,
+            None,
+        )
+        .await
+        .ok()?;
+    parse_evaluation(&response).ok()
+}
+
+/// 履歴の新しい順に最大 `limit` 件のペアを 2 つの設定で評価し、レポートを保存する
+pub async fn run_from_history(
+    client: &ApiClient,
+    config: &ExperimentConfig,
+    records: &[SessionRecord],
+) -> Result<PathBuf, AppError> {
+    let limit = config.limit.unwrap_or(DEFAULT_PAIR_LIMIT);
+    let mut report = AgreementReport::default();
+
+    for (index, record) in records
+        .iter()
+        .rev()
+        .filter(|record| !record.summary.trim().is_empty())
+        .take(limit)
+        .enumerate()
+    {
+        let a = evaluate_variant(client, &config.a, record).await;
+        let b = evaluate_variant(client, &config.b, record).await;
+        println!(
+            "[{}/{limit}] {}: A={} B={}",
+            index + 1,
+            record.timestamp.format("%Y-%m-%d %H:%M"),
+            verdict_label(a.as_ref()),
+            verdict_label(b.as_ref())
+        );
+        report.add_pair(a.as_ref(), b.as_ref());
+    }
+
+    let dir = dirs::config_dir()
+        .ok_or(AppError::IoError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "設定ディレクトリが見つかりません。",
+        )))?
+        .join(APP_DIR_NAME)
+        .join(EXPERIMENTS_DIR_NAME);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.md", Local::now().format("%Y%m%d-%H%M%S")));
+    let markdown = report.to_markdown(&config.a, &config.b);
+    fs::write(&path, &markdown)?;
+    println!("\n{markdown}");
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(overall: OverallEvaluation, scores: (u8, u8, u8)) -> EvaluationResult {
+        EvaluationResult {
+            appropriate: matches!(overall, OverallEvaluation::Pass),
+            importance: scores.0,
+            conciseness: scores.1,
+            accuracy: scores.2,
+            improvement1: String::new(),
+            improvement2: String::new(),
+            improvement3: String::new(),
+            overall,
+        }
+    }
+
+    #[test]
+    fn agreement_report_counts_matches_and_failures() {
+        let mut report = AgreementReport::default();
+        let pass = result(OverallEvaluation::Pass, (4, 4, 4));
+        let pass_lower = result(OverallEvaluation::Pass, (4, 3, 2));
+        let fail = result(OverallEvaluation::Fail, (2, 2, 2));
+
+        report.add_pair(Some(&pass), Some(&pass_lower));
+        report.add_pair(Some(&pass), Some(&fail));
+        report.add_pair(None, Some(&fail));
+
+        assert_eq!(report.total, 3);
+        assert_eq!(report.compared, 2);
+        assert_eq!(report.failed_a, 1);
+        assert_eq!(report.failed_b, 0);
+        assert_eq!(report.verdict_agreements, 1);
+        assert_eq!(report.importance_agreements, 1);
+        assert_eq!(report.conciseness_agreements, 0);
+        assert_eq!(report.score_abs_diff_sum, 9);
+        assert!((report.rate(report.verdict_agreements) - 50.0).abs() < f64::EPSILON);
+        assert!((report.mean_abs_diff() - 1.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn markdown_report_describes_variants() {
+        let report = AgreementReport::default();
+        let a = VariantConfig {
+            model: Some("model-a".to_string()),
+            prompt: None,
+        };
+        let b = VariantConfig {
+            model: None,
+            prompt: Some("{original}{summary}".to_string()),
+        };
+        let markdown = report.to_markdown(&a, &b);
+        assert!(markdown.contains("| A | モデル: model-a / プロンプト: 既定 |"));
+        assert!(markdown.contains("| B | モデル: 既定 / プロンプト: カスタム |"));
+        assert!(markdown.contains("| 合否の一致率 | 0.0% |"));
+    }
+}
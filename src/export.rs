@@ -0,0 +1,284 @@
+use crate::error::AppError;
+use crate::models::{Difficulty, TextStyle, TrainingResult};
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+const CSV_HEADER: &str = "timestamp,passed,self_graded,difficulty,character_count,genre,importance,conciseness,accuracy,retry_of,duration_secs,cpm,text_level,daily_challenge,overridden";
+// 表計算ソフトが UTF-8 として開けるように先頭に付ける
+const UTF8_BOM: &str = "\u{feff}";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// `yomitore export` の引数
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    /// 省略時は標準出力に書き出す
+    pub out: Option<PathBuf>,
+}
+
+impl ExportOptions {
+    /// `--format csv|json` と `--out <path>` を解析する。
+    /// `--format` を省略したときは出力先の拡張子から決め、それもなければ CSV にする
+    pub fn parse(args: &[String]) -> Result<Self, AppError> {
+        let mut format = None;
+        let mut out = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => {
+                    let value = args.next().ok_or_else(|| {
+                        AppError::InvalidConfig(
+                            "--format には csv か json を指定してください。".to_string(),
+                        )
+                    })?;
+                    format = Some(ExportFormat::parse(value).ok_or_else(|| {
+                        AppError::InvalidConfig(format!("未対応の形式です: {value}"))
+                    })?);
+                }
+                "--out" => {
+                    let value = args.next().ok_or_else(|| {
+                        AppError::InvalidConfig(
+                            "--out には出力先のパスを指定してください。".to_string(),
+                        )
+                    })?;
+                    out = Some(PathBuf::from(value));
+                }
+                other => {
+                    return Err(AppError::InvalidConfig(format!("不明な引数です: {other}")));
+                }
+            }
+        }
+        let format = format
+            .or_else(|| {
+                out.as_ref()
+                    .and_then(|path| path.extension())
+                    .and_then(|ext| ext.to_str())
+                    .and_then(ExportFormat::parse)
+            })
+            .unwrap_or(ExportFormat::Csv);
+        Ok(Self { format, out })
+    }
+}
+
+/// 書き出す 1 件分。スコアは AI 評価を受けた結果のみ
+#[derive(Serialize)]
+struct ExportRow {
+    timestamp: String,
+    passed: bool,
+    self_graded: bool,
+    difficulty: Option<&'static str>,
+    character_count: Option<u16>,
+    genre: Option<&'static str>,
+    importance: Option<u8>,
+    conciseness: Option<u8>,
+    accuracy: Option<u8>,
+    retry_of: Option<String>,
+    duration_secs: Option<u32>,
+    cpm: Option<u32>,
+    text_level: Option<&'static str>,
+    daily_challenge: Option<String>,
+    overridden: bool,
+}
+
+impl ExportRow {
+    fn from_result(result: &TrainingResult) -> Self {
+        let evaluation = result.evaluation.as_ref();
+        Self {
+            timestamp: result.timestamp.to_rfc3339(),
+            passed: result.passed,
+            self_graded: result.self_graded,
+            difficulty: result.difficulty.map(Difficulty::label),
+            character_count: result.character_count,
+            genre: result.genre.map(TextStyle::label),
+            importance: evaluation.map(|scores| scores.importance),
+            conciseness: evaluation.map(|scores| scores.conciseness),
+            accuracy: evaluation.map(|scores| scores.accuracy),
+            retry_of: result.retry_of.map(|original| original.to_rfc3339()),
+            duration_secs: result.duration_secs,
+            cpm: result.cpm,
+            text_level: result
+                .text_features
+                .map(|features| features.jlpt_level.label()),
+            daily_challenge: result.daily_challenge.map(|date| date.to_string()),
+            overridden: result.overridden_from.is_some(),
+        }
+    }
+
+    fn to_csv_line(&self) -> String {
+        let fields = [
+            self.timestamp.clone(),
+            self.passed.to_string(),
+            self.self_graded.to_string(),
+            self.difficulty.unwrap_or_default().to_string(),
+            optional(self.character_count),
+            self.genre.unwrap_or_default().to_string(),
+            optional(self.importance),
+            optional(self.conciseness),
+            optional(self.accuracy),
+            self.retry_of.clone().unwrap_or_default(),
+            optional(self.duration_secs),
+            optional(self.cpm),
+            self.text_level.unwrap_or_default().to_string(),
+            self.daily_challenge.clone().unwrap_or_default(),
+            self.overridden.to_string(),
+        ];
+        fields
+            .iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// 統計の結果一覧を指定の形式で書き出す
+pub fn export_results(results: &[TrainingResult], options: &ExportOptions) -> Result<(), AppError> {
+    let rows: Vec<ExportRow> = results.iter().map(ExportRow::from_result).collect();
+    let content = match options.format {
+        ExportFormat::Csv => format_csv(&rows),
+        ExportFormat::Json => serde_json::to_string_pretty(&rows)? + "\n",
+    };
+    if let Some(path) = &options.out {
+        fs::write(path, content)?;
+    } else {
+        let mut stdout = io::stdout();
+        stdout.write_all(content.as_bytes())?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+fn format_csv(rows: &[ExportRow]) -> String {
+    let mut csv = format!("{UTF8_BOM}{CSV_HEADER}\n");
+    for row in rows {
+        csv.push_str(&row.to_csv_line());
+        csv.push('\n');
+    }
+    csv
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// カンマ・引用符・改行を含む値だけを引用符で囲む
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EvaluationScores;
+    use crate::text_difficulty;
+    use chrono::{Local, TimeZone};
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_string()).collect()
+    }
+
+    #[test]
+    fn parse_options_infers_format_from_extension() {
+        let options = ExportOptions::parse(&args(&["--out", "stats.JSON"]));
+        assert!(matches!(
+            options,
+            Ok(ExportOptions {
+                format: ExportFormat::Json,
+                ..
+            })
+        ));
+        let options = ExportOptions::parse(&args(&["--format", "csv", "--out", "stats.json"]));
+        assert!(matches!(
+            options,
+            Ok(ExportOptions {
+                format: ExportFormat::Csv,
+                ..
+            })
+        ));
+        assert_eq!(
+            ExportOptions::parse(&[]).ok(),
+            Some(ExportOptions {
+                format: ExportFormat::Csv,
+                out: None,
+            })
+        );
+        assert!(ExportOptions::parse(&args(&["--format", "xlsx"])).is_err());
+        assert!(ExportOptions::parse(&args(&["--out"])).is_err());
+    }
+
+    #[test]
+    fn csv_contains_scores_and_blank_optional_fields() {
+        let timestamp = Local
+            .with_ymd_and_hms(2026, 1, 2, 9, 30, 0)
+            .single()
+            .unwrap_or_default();
+        let results = [
+            TrainingResult {
+                timestamp,
+                passed: true,
+                evaluation: Some(EvaluationScores {
+                    appropriate: true,
+                    importance: 4,
+                    conciseness: 3,
+                    accuracy: 5,
+                    improvement1: String::new(),
+                    improvement2: String::new(),
+                    improvement3: String::new(),
+                    overall_passed: true,
+                }),
+                difficulty: Some(Difficulty::Hard),
+                character_count: Some(720),
+                genre: Some(TextStyle::Newspaper),
+                duration_secs: Some(240),
+                cpm: Some(180),
+                text_features: text_difficulty::analyze("きょうは はれです。"),
+                daily_challenge: chrono::NaiveDate::from_ymd_opt(2026, 1, 2),
+                ..TrainingResult::default()
+            },
+            TrainingResult {
+                timestamp,
+                self_graded: true,
+                ..TrainingResult::default()
+            },
+        ];
+        let rows: Vec<ExportRow> = results.iter().map(ExportRow::from_result).collect();
+        let csv = format_csv(&rows);
+        let lines: Vec<&str> = csv.lines().collect();
+        let stamp = timestamp.to_rfc3339();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines.get(1).copied(),
+            Some(
+                format!(
+                    "{stamp},true,false,難しい,720,新聞記事,4,3,5,,240,180,N5,2026-01-02,false"
+                )
+                .as_str()
+            )
+        );
+        assert_eq!(
+            lines.get(2).copied(),
+            Some(format!("{stamp},false,true,,,,,,,,,,,,false").as_str())
+        );
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,127 @@
+use crate::error::AppError;
+use std::collections::HashMap;
+
+pub fn build_furigana_prompt(text: &str) -> String {
+    format!(
+        "次の文章の漢字を含む語すべてに、直後の半角括弧でひらがなの読みを付けてください（例: 漢字(かんじ)）。\
+         読み以外は文章を一字も変えず、前置きや説明を付けずに文章だけを出力してください。\n\n# 文章\n{text}"
+    )
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(c, 'ぁ'..='ゖ' | 'ァ'..='ヺ' | 'ー')
+}
+
+/// `漢字(かんじ)` の読みの部分を取り除く。かな以外を含む括弧は原文の一部として残す
+pub fn strip_ruby(annotated: &str) -> String {
+    let mut plain = String::with_capacity(annotated.len());
+    let mut rest = annotated;
+    while let Some(open) = rest.find('(') {
+        let (before, after) = rest.split_at(open);
+        plain.push_str(before);
+        let reading = after
+            .get(1..)
+            .and_then(|inner| inner.find(')').map(|close| (inner, close)))
+            .filter(|(inner, close)| {
+                let reading = inner.get(..*close).unwrap_or_default();
+                !reading.is_empty() && reading.chars().all(is_kana)
+            });
+        if let Some((inner, close)) = reading {
+            rest = inner.get(close + 1..).unwrap_or_default();
+        } else {
+            plain.push('(');
+            rest = after.get(1..).unwrap_or_default();
+        }
+    }
+    plain.push_str(rest);
+    plain
+}
+
+/// 応答を読み仮名付きの文章として受け取る。読みを除いた文章が原文と違う (書き換えられた) 場合はエラー
+pub fn parse_furigana(response: &str, original: &str) -> Result<String, AppError> {
+    let annotated = response.trim();
+    let without_spaces = |text: &str| -> String { text.split_whitespace().collect() };
+    if annotated.is_empty() || without_spaces(&strip_ruby(annotated)) != without_spaces(original) {
+        return Err(AppError::InvalidText(
+            "読み仮名付きの文章が原文と一致しません。".to_string(),
+        ));
+    }
+    Ok(annotated.to_string())
+}
+
+/// ある原文の読み仮名の取得状況
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Annotation<'a> {
+    Pending,
+    Failed,
+    Ready(&'a str),
+}
+
+/// 原文欄の読み仮名表示。原文ごとに覚えておき、このセッションの間は API を呼び直さない
+#[derive(Default)]
+pub struct Furigana {
+    pub visible: bool,
+    /// 取得に失敗した原文は `None` を入れ、表示し直すまで再取得しない
+    cache: HashMap<String, Option<String>>,
+}
+
+impl Furigana {
+    /// 表示を切り替える。表示し直すときは取得に失敗した原文をもう一度調べる
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        if self.visible {
+            self.cache.retain(|_, annotated| annotated.is_some());
+        }
+    }
+
+    pub fn annotation(&self, text: &str) -> Annotation<'_> {
+        match self.cache.get(text) {
+            None => Annotation::Pending,
+            Some(None) => Annotation::Failed,
+            Some(Some(annotated)) => Annotation::Ready(annotated),
+        }
+    }
+
+    /// 表示中で、まだ調べていない原文か
+    pub fn needs_lookup(&self, text: &str) -> bool {
+        self.visible && !text.trim().is_empty() && !self.cache.contains_key(text)
+    }
+
+    pub fn store(&mut self, text: &str, annotated: Option<String>) {
+        self.cache.insert(text.to_string(), annotated);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readings_are_stripped_and_checked_against_the_original() {
+        let original = "市は図書館の開館時間（平日）を延長する。";
+        let annotated = "市(し)は図書館(としょかん)の開館(かいかん)時間(じかん)（平日(へいじつ)）を延長(えんちょう)する。";
+        assert_eq!(strip_ruby(annotated), original);
+        assert_eq!(strip_ruby("A(1)と(か)"), "A(1)と");
+        assert!(parse_furigana(&format!("\n{annotated}\n"), original).is_ok());
+        assert!(parse_furigana("市(し)は美術館(びじゅつかん)の", original).is_err());
+    }
+
+    #[test]
+    fn annotations_are_cached_per_text() {
+        let mut furigana = Furigana::default();
+        assert!(!furigana.needs_lookup("原文"));
+        furigana.toggle();
+        assert!(furigana.needs_lookup("原文"));
+        furigana.store("原文", Some("原文(げんぶん)".to_string()));
+        assert_eq!(
+            furigana.annotation("原文"),
+            Annotation::Ready("原文(げんぶん)")
+        );
+        assert_eq!(furigana.annotation("未取得"), Annotation::Pending);
+        furigana.store("別文", None);
+        furigana.toggle();
+        furigana.toggle();
+        assert!(!furigana.needs_lookup("原文"));
+        assert!(furigana.needs_lookup("別文"));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::error::AppError;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// 解説する用語の数。原文欄の下に 1 行ずつ表示する
+pub const GLOSSARY_TERM_COUNT: u16 = 3;
+
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GlossaryEntry {
+    pub term: String,
+    pub gloss: String,
+}
+
+#[derive(Deserialize)]
+struct GlossaryResponse {
+    terms: Vec<GlossaryEntry>,
+}
+
+pub fn build_glossary_prompt(text: &str) -> String {
+    format!(
+        "次の文章から、読み手がつまずきやすい難しい用語を{GLOSSARY_TERM_COUNT}つ選び、それぞれを30文字以内の1文で説明してください。\
+         次の JSON だけを出力してください。{{\"terms\": [{{\"term\": \"用語\", \"gloss\": \"説明\"}}]}}\n\n# 文章\n{text}"
+    )
+}
+
+/// 応答から用語と説明を読み取る。空の項目は除き、多すぎる分は切り捨てる
+pub fn parse_glossary(response: &str) -> Result<Vec<GlossaryEntry>, AppError> {
+    let (Some(start), Some(end)) = (response.find('{'), response.rfind('}')) else {
+        return Err(AppError::InvalidQuiz(
+            "JSON が含まれていません。".to_string(),
+        ));
+    };
+    let parsed: GlossaryResponse =
+        serde_json::from_str(response.get(start..=end).unwrap_or_default())?;
+    let entries: Vec<GlossaryEntry> = parsed
+        .terms
+        .into_iter()
+        .map(|entry| GlossaryEntry {
+            term: entry.term.trim().to_string(),
+            gloss: entry.gloss.trim().to_string(),
+        })
+        .filter(|entry| !entry.term.is_empty() && !entry.gloss.is_empty())
+        .take(usize::from(GLOSSARY_TERM_COUNT))
+        .collect();
+    if entries.is_empty() {
+        return Err(AppError::InvalidQuiz("用語がありません。".to_string()));
+    }
+    Ok(entries)
+}
+
+/// 原文欄の下に出す用語の解説。原文ごとに覚えておき、同じ原文では API を呼び直さない
+#[derive(Default)]
+pub struct Glossary {
+    pub visible: bool,
+    /// 取得に失敗した原文は空の一覧を入れ、表示し直すまで再取得しない
+    cache: HashMap<String, Vec<GlossaryEntry>>,
+}
+
+impl Glossary {
+    /// 表示を切り替える。表示し直すときは取得に失敗した原文をもう一度調べる
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        if self.visible {
+            self.cache.retain(|_, entries| !entries.is_empty());
+        }
+    }
+
+    pub fn entries(&self, text: &str) -> Option<&[GlossaryEntry]> {
+        self.cache.get(text).map(Vec::as_slice)
+    }
+
+    /// 表示中で、まだ調べていない原文か
+    pub fn needs_lookup(&self, text: &str) -> bool {
+        self.visible && !text.trim().is_empty() && !self.cache.contains_key(text)
+    }
+
+    pub fn store(&mut self, text: &str, entries: Vec<GlossaryEntry>) {
+        self.cache.insert(text.to_string(), entries);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glossary_keeps_three_non_empty_terms() {
+        let response = r#"{"terms": [{"term": " 緩和 ", "gloss": "ゆるめること。"}, {"term": "", "gloss": "空"}, {"term": "是正", "gloss": "正すこと。"}, {"term": "逼迫", "gloss": "差し迫ること。"}, {"term": "漸進", "gloss": "少しずつ進むこと。"}]}"#;
+        let entries = parse_glossary(response).unwrap_or_default();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.term.as_str())
+                .collect::<Vec<_>>(),
+            vec!["緩和", "是正", "逼迫"]
+        );
+        assert!(parse_glossary(r#"{"terms": []}"#).is_err());
+    }
+
+    #[test]
+    fn failed_lookups_are_retried_after_showing_again() {
+        let mut glossary = Glossary::default();
+        assert!(!glossary.needs_lookup("原文"));
+        glossary.toggle();
+        assert!(glossary.needs_lookup("原文") && !glossary.needs_lookup(" "));
+
+        glossary.store("原文", Vec::new());
+        assert!(!glossary.needs_lookup("原文"));
+        glossary.toggle();
+        glossary.toggle();
+        assert!(glossary.needs_lookup("原文"));
+    }
+}
@@ -0,0 +1,264 @@
+use crate::error::AppError;
+use serde::Deserialize;
+
+/// 改行のない文章を区切るときの段落の数の目安
+const TARGET_PARAGRAPHS: usize = 4;
+const SENTENCE_ENDINGS: [char; 3] = ['。', '！', '？'];
+
+/// 段落ごとの要約の進み具合。段落をすべて要約し終えると全体の要約に進む
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GuidedStep {
+    /// `index` 番目の段落を要約している
+    Paragraph(usize),
+    /// `index` 番目の段落の要約を送り、講評を待っている
+    Reviewing { index: usize, summary: String },
+    /// 段落ごとの要約をもとに、全体の要約を書いている
+    Final,
+}
+
+/// 段落の要約と、それへの講評
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParagraphReview {
+    pub summary: String,
+    /// 段落の要点を押さえているか
+    pub captured: bool,
+    pub feedback: String,
+}
+
+/// 段落ごとに要約してから全体をまとめる、初心者向けの段階練習の状態
+#[derive(Debug)]
+pub struct GuidedSession {
+    pub paragraphs: Vec<String>,
+    pub reviews: Vec<ParagraphReview>,
+    pub step: GuidedStep,
+}
+
+#[derive(Deserialize)]
+struct ReviewResponse {
+    captured: bool,
+    feedback: String,
+}
+
+impl GuidedSession {
+    /// 原文を段落に分けて、最初の段落から始める
+    pub fn new(original_text: &str) -> Self {
+        let paragraphs = split_paragraphs(original_text);
+        let step = if paragraphs.is_empty() {
+            GuidedStep::Final
+        } else {
+            GuidedStep::Paragraph(0)
+        };
+        Self {
+            paragraphs,
+            reviews: Vec::new(),
+            step,
+        }
+    }
+
+    /// 要約している段落の番号。全体の要約に進んだら `None`
+    pub fn current_paragraph(&self) -> Option<usize> {
+        match &self.step {
+            GuidedStep::Paragraph(index) | GuidedStep::Reviewing { index, .. } => Some(*index),
+            GuidedStep::Final => None,
+        }
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.step == GuidedStep::Final
+    }
+
+    /// 段落の要約を送る。送れたら講評を頼む段落と要約を返す
+    pub fn submit(&mut self, summary: &str) -> Option<(String, String)> {
+        let GuidedStep::Paragraph(index) = self.step else {
+            return None;
+        };
+        let paragraph = self.paragraphs.get(index)?.clone();
+        let summary = summary.trim().to_string();
+        self.step = GuidedStep::Reviewing {
+            index,
+            summary: summary.clone(),
+        };
+        Some((paragraph, summary))
+    }
+
+    /// 講評を待っている段落と要約
+    pub fn pending_review(&self) -> Option<(&str, &str)> {
+        let GuidedStep::Reviewing { index, summary } = &self.step else {
+            return None;
+        };
+        Some((self.paragraphs.get(*index)?, summary))
+    }
+
+    /// 講評を記録して次の段落へ進む。最後の段落なら全体の要約に進む
+    pub fn record_review(&mut self, captured: bool, feedback: String) {
+        let GuidedStep::Reviewing { index, summary } =
+            std::mem::replace(&mut self.step, GuidedStep::Final)
+        else {
+            return;
+        };
+        self.reviews.push(ParagraphReview {
+            summary,
+            captured,
+            feedback,
+        });
+        if index + 1 < self.paragraphs.len() {
+            self.step = GuidedStep::Paragraph(index + 1);
+        }
+    }
+
+    /// 講評を受け取れなかったときは同じ段落に戻し、送った要約を返す
+    pub fn cancel_review(&mut self) -> Option<String> {
+        let GuidedStep::Reviewing { index, summary } = &self.step else {
+            return None;
+        };
+        let summary = summary.clone();
+        self.step = GuidedStep::Paragraph(*index);
+        Some(summary)
+    }
+
+    /// 原文欄に表示する段落・要約・講評を、表示と同じ行の並びでつなげたもの。スクロールの上限を求めるのに使う
+    pub fn outline(&self) -> String {
+        let total = self.paragraphs.len();
+        self.paragraphs
+            .iter()
+            .enumerate()
+            .map(|(index, paragraph)| {
+                let block = format!("【段落 {}/{total}】\n{paragraph}", index + 1);
+                match self.reviews.get(index) {
+                    Some(review) => {
+                        format!("{block}\n→ 要約: {}\n✔ {}", review.summary, review.feedback)
+                    }
+                    None => block,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// 全体の要約の下書き。段落ごとの要約をつなげたもの
+    pub fn final_draft(&self) -> String {
+        self.reviews
+            .iter()
+            .map(|review| review.summary.as_str())
+            .collect::<Vec<_>>()
+            .join("")
+    }
+}
+
+/// 空行や改行で段落に分ける。改行のない文章は、文の切れ目でおよそ `TARGET_PARAGRAPHS` 個に分ける
+pub fn split_paragraphs(text: &str) -> Vec<String> {
+    let lines: Vec<String> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(ToString::to_string)
+        .collect();
+    if lines.len() != 1 {
+        return lines;
+    }
+    let text = lines.concat();
+    let chunk_chars = (text.chars().count() / TARGET_PARAGRAPHS).max(1);
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    for sentence in text.split_inclusive(SENTENCE_ENDINGS) {
+        current.push_str(sentence);
+        if current.chars().count() >= chunk_chars {
+            paragraphs.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.trim().is_empty() {
+        match paragraphs.last_mut() {
+            // 文の途中で終わる短い残りは、前の段落に含める
+            Some(last) if !current.ends_with(SENTENCE_ENDINGS) => last.push_str(&current),
+            _ => paragraphs.push(current),
+        }
+    }
+    paragraphs
+}
+
+/// 1 つの段落とその要約だけを見て、要点を押さえているかを短く講評させる
+pub fn build_review_prompt(paragraph: &str, summary: &str) -> String {
+    format!(
+        r#"
+あなたは要約の練習をしている初心者のコーチです。以下の「段落」を受講者が要約しました。
+要約が段落の要点を押さえているかを判定し、短く講評してください。
+
+# 回答ルール
+- 出力は必ず以下の「出力フォーマット」の JSON オブジェクトのみとすること
+- captured は段落の要点を押さえていれば true、押さえていなければ false
+- feedback には、よい点か足りない点を 1 つ、100 文字以内で書くこと
+
+# 出力フォーマット(厳守)
+{{"captured": true, "feedback": "..."}}
+
+# 段落
+{paragraph}
+
+# 要約
+{summary}
+"#
+    )
+}
+
+pub fn parse_review(response: &str) -> Result<(bool, String), AppError> {
+    let (Some(start), Some(end)) = (response.find('{'), response.rfind('}')) else {
+        return Err(AppError::InvalidText(
+            "JSON が含まれていません。".to_string(),
+        ));
+    };
+    let review: ReviewResponse =
+        serde_json::from_str(response.get(start..=end).unwrap_or_default())?;
+    let feedback = review.feedback.trim();
+    if feedback.is_empty() {
+        return Err(AppError::InvalidText("講評が空です。".to_string()));
+    }
+    Ok((review.captured, feedback.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_without_line_breaks_is_split_at_sentence_ends() {
+        assert_eq!(
+            split_paragraphs("一段落目。\n\n 二段落目。\n"),
+            vec!["一段落目。", "二段落目。"]
+        );
+        let paragraphs = split_paragraphs("あいう。えお。かきく。けこ。さしす。せ");
+        assert_eq!(
+            paragraphs,
+            vec!["あいう。", "えお。かきく。", "けこ。さしす。せ"]
+        );
+        assert!(split_paragraphs(" \n").is_empty());
+    }
+
+    #[test]
+    fn session_advances_paragraph_by_paragraph_to_the_final_summary() {
+        let mut session = GuidedSession::new("一段落目。\n二段落目。");
+        assert_eq!(session.current_paragraph(), Some(0));
+        assert_eq!(
+            session.submit(" 要約1 "),
+            Some(("一段落目。".to_string(), "要約1".to_string()))
+        );
+        assert_eq!(session.submit("二重送信"), None);
+
+        assert_eq!(session.cancel_review(), Some("要約1".to_string()));
+        assert_eq!(session.step, GuidedStep::Paragraph(0));
+
+        session.submit("要約1");
+        let review =
+            parse_review(r#"講評: {"captured": true, "feedback": "要点を押さえています。"}"#);
+        let feedback = "要点を押さえています。".to_string();
+        assert_eq!(review.ok(), Some((true, feedback.clone())));
+        session.record_review(true, feedback.clone());
+        assert_eq!(session.current_paragraph(), Some(1));
+
+        session.submit("要約2");
+        assert_eq!(session.pending_review(), Some(("二段落目。", "要約2")));
+        session.record_review(false, feedback);
+        assert!(session.is_final());
+        assert_eq!(session.reviews.iter().filter(|r| r.captured).count(), 1);
+        assert_eq!(session.final_draft(), "要約1要約2");
+    }
+}
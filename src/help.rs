@@ -1 +1,171 @@
+use ratatui::widgets::{Paragraph, Wrap};
+
 pub const HELP_CONTENT: &str = include_str!("../docs/HELP.md");
+
+const CODE_FENCE: &str = "```";
+
+/// ヘルプの見出し (`## 使い方` など)。`line` は本文の行番号 (0 始まり)
+#[derive(Debug, PartialEq, Eq)]
+pub struct HelpHeading {
+    pub level: usize,
+    pub title: String,
+    pub line: usize,
+}
+
+/// Markdown の見出しを取り出す。コードブロック内の `#` は見出しとみなさない
+pub fn parse_headings(content: &str) -> Vec<HelpHeading> {
+    let mut in_code_block = false;
+    let mut headings = Vec::new();
+    for (line, text) in content.lines().enumerate() {
+        if text.trim_start().starts_with(CODE_FENCE) {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        let level = text.chars().take_while(|&c| c == '#').count();
+        let Some(title) = text.get(level..).and_then(|rest| rest.strip_prefix(' ')) else {
+            continue;
+        };
+        if level > 0 {
+            headings.push(HelpHeading {
+                level,
+                title: title.trim().to_string(),
+                line,
+            });
+        }
+    }
+    headings
+}
+
+/// 検索語を含む行の行番号 (英字の大文字・小文字は区別しない)
+pub fn find_matches(content: &str, query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query = query.to_lowercase();
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, text)| text.to_lowercase().contains(&query))
+        .map(|(line, _)| line)
+        .collect()
+}
+
+/// 本文の `line` 行目を先頭に表示するためのスクロール量 (折り返し後の行数)
+pub fn scroll_for_line(content: &str, line: usize, width: u16) -> u16 {
+    if width == 0 {
+        return 0;
+    }
+    let rows: usize = content
+        .lines()
+        .take(line)
+        .map(|text| {
+            Paragraph::new(text)
+                .wrap(Wrap { trim: false })
+                .line_count(width)
+        })
+        .sum();
+    u16::try_from(rows).unwrap_or(u16::MAX)
+}
+
+/// ヘルプ画面の `/` 検索の状態
+#[derive(Default)]
+pub struct HelpSearch {
+    pub query: String,
+    pub editing: bool,
+    pub matches: Vec<usize>,
+    pub current: usize,
+}
+
+impl HelpSearch {
+    pub fn begin(&mut self) {
+        *self = Self {
+            editing: true,
+            ..Self::default()
+        };
+    }
+
+    /// 入力した検索語で検索し、最初に一致した行を返す
+    pub fn confirm(&mut self, content: &str) -> Option<usize> {
+        self.editing = false;
+        self.matches = find_matches(content, &self.query);
+        self.current = 0;
+        self.current_line()
+    }
+
+    pub fn current_line(&self) -> Option<usize> {
+        self.matches.get(self.current).copied()
+    }
+
+    pub fn next(&mut self) -> Option<usize> {
+        if self.matches.is_empty() {
+            return None;
+        }
+        self.current = (self.current + 1) % self.matches.len();
+        self.current_line()
+    }
+
+    pub fn previous(&mut self) -> Option<usize> {
+        if self.matches.is_empty() {
+            return None;
+        }
+        self.current = self
+            .current
+            .checked_sub(1)
+            .unwrap_or(self.matches.len() - 1);
+        self.current_line()
+    }
+
+    /// ステータス欄に表示する検索の状況
+    pub fn status(&self) -> String {
+        if self.editing {
+            return format!("/{}", self.query);
+        }
+        if self.matches.is_empty() {
+            return format!("「{}」は見つかりませんでした。", self.query);
+        }
+        format!(
+            "「{}」: {}/{} 件 (n/N: 次/前の一致)",
+            self.query,
+            self.current + 1,
+            self.matches.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str =
+        "# タイトル\n本文\n## 使い方\n```sh\n# コメント\n```\n### API キー\n#タグ\napi を設定";
+
+    #[test]
+    fn parse_headings_skips_code_blocks() {
+        let headings = parse_headings(CONTENT);
+        let summary: Vec<(usize, &str, usize)> = headings
+            .iter()
+            .map(|heading| (heading.level, heading.title.as_str(), heading.line))
+            .collect();
+        assert_eq!(
+            summary,
+            [(1, "タイトル", 0), (2, "使い方", 2), (3, "API キー", 6)]
+        );
+        assert!(!parse_headings(HELP_CONTENT).is_empty());
+    }
+
+    #[test]
+    fn search_cycles_through_case_insensitive_matches() {
+        let mut search = HelpSearch::default();
+        search.begin();
+        search.query = "API".to_string();
+        assert_eq!(search.confirm(CONTENT), Some(6));
+        assert_eq!(search.next(), Some(8));
+        assert_eq!(search.next(), Some(6));
+        assert_eq!(search.previous(), Some(8));
+        assert_eq!(search.status(), "「API」: 2/2 件 (n/N: 次/前の一致)");
+        assert_eq!(scroll_for_line("12345\nab\nc", 2, 2), 4);
+    }
+}
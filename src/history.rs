@@ -0,0 +1,281 @@
+use crate::models::EvaluationScores;
+use crate::storage;
+use chrono::{DateTime, Local, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SessionRecord {
+    pub timestamp: DateTime<Local>,
+    pub character_count: u16,
+    pub original_text: String,
+    pub summary: String,
+    pub evaluation_text: String,
+    pub passed: bool,
+    #[serde(default)]
+    pub evaluation: Option<EvaluationScores>,
+    #[serde(default)]
+    pub started_at: Option<DateTime<Local>>,
+    /// 再挑戦の場合、最初に挑戦したセッションの日時
+    #[serde(default)]
+    pub retry_of: Option<DateTime<Local>>,
+    /// 読みながら書いたメモ。書かなかったか、履歴に残さない設定なら `None`
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// 履歴画面の状態。記録は新しい順に並べる
+pub struct HistoryBrowser {
+    pub records: Vec<SessionRecord>,
+    pub selected: usize,
+    opened: Option<usize>,
+    pub detail_scroll: u16,
+}
+
+impl HistoryBrowser {
+    pub fn new(mut records: Vec<SessionRecord>) -> Self {
+        records.reverse();
+        Self {
+            records,
+            selected: 0,
+            opened: None,
+            detail_scroll: 0,
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.records.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn open_selected(&mut self) -> bool {
+        if self.selected >= self.records.len() {
+            return false;
+        }
+        self.opened = Some(self.selected);
+        self.detail_scroll = 0;
+        true
+    }
+
+    pub fn close_detail(&mut self) {
+        self.opened = None;
+    }
+
+    pub fn is_detail_open(&self) -> bool {
+        self.opened.is_some()
+    }
+
+    pub fn opened_record(&self) -> Option<&SessionRecord> {
+        self.opened.and_then(|index| self.records.get(index))
+    }
+}
+
+/// 一覧に表示する1行 (日時・文字数・結果・要約の冒頭)
+pub fn format_list_line(record: &SessionRecord) -> String {
+    const SUMMARY_PREVIEW_CHARS: usize = 30;
+    let preview: String = record
+        .summary
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(SUMMARY_PREVIEW_CHARS)
+        .collect();
+    format!(
+        "{}  {:>4}字  {:<3}  {preview}",
+        record.timestamp.format("%Y-%m-%d %H:%M"),
+        record.character_count,
+        if record.passed { "合格" } else { "不合格" },
+    )
+}
+
+/// 所要時間 (出題から評価まで) の表示。出題時刻がない古い記録は `None`
+pub fn format_duration(record: &SessionRecord) -> Option<String> {
+    let started_at = record.started_at?;
+    let seconds = (record.timestamp - started_at).num_seconds().max(0);
+    Some(format!("{}分{:02}秒", seconds / 60, seconds % 60))
+}
+
+/// 壊れた記録は読み飛ばし、読めた記録だけを古い順に返す
+pub fn load_records() -> Result<Vec<SessionRecord>, Box<dyn std::error::Error>> {
+    Ok(storage::query_json(
+        storage::global()?,
+        storage::HISTORY_COLLECTION,
+        None,
+    )?)
+}
+
+pub fn append_record(record: &SessionRecord) -> Result<(), Box<dyn std::error::Error>> {
+    storage::append_json(storage::global()?, storage::HISTORY_COLLECTION, record)?;
+    Ok(())
+}
+
+/// ちょうど1年前、なければ1ヶ月前の同じ日の記録を探す
+pub fn find_flashback(records: &[SessionRecord], today: NaiveDate) -> Option<&SessionRecord> {
+    [Months::new(12), Months::new(1)]
+        .into_iter()
+        .filter_map(|months| today.checked_sub_months(months))
+        .find_map(|date| {
+            records
+                .iter()
+                .rev()
+                .find(|record| record.timestamp.date_naive() == date)
+        })
+}
+
+pub fn flashback_label(record: &SessionRecord, today: NaiveDate) -> &'static str {
+    if today.checked_sub_months(Months::new(12)) == Some(record.timestamp.date_naive()) {
+        "1年前の今日"
+    } else {
+        "1ヶ月前の今日"
+    }
+}
+
+pub fn format_result(passed: bool, evaluation: Option<&EvaluationScores>) -> String {
+    let verdict = if passed { "合格" } else { "不合格" };
+    match evaluation {
+        Some(scores) => format!(
+            "{verdict} (重要情報 {} / 簡潔性 {} / 正確性 {})",
+            scores.importance, scores.conciseness, scores.accuracy
+        ),
+        None => verdict.to_string(),
+    }
+}
+
+pub fn format_comparison(
+    base: &SessionRecord,
+    passed: bool,
+    evaluation: Option<&EvaluationScores>,
+) -> String {
+    format!(
+        "\n# 前回との比較\n- 前回 ({}): {}\n- 今回: {}\n",
+        base.timestamp.format("%Y-%m-%d"),
+        format_result(base.passed, base.evaluation.as_ref()),
+        format_result(passed, evaluation)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn record_on(year: i32, month: u32, day: u32, summary: &str) -> Option<SessionRecord> {
+        let timestamp = Local
+            .with_ymd_and_hms(year, month, day, 12, 0, 0)
+            .single()?;
+        Some(SessionRecord {
+            timestamp,
+            character_count: 400,
+            original_text: "原文".to_string(),
+            summary: summary.to_string(),
+            evaluation_text: String::new(),
+            passed: false,
+            evaluation: None,
+            started_at: None,
+            retry_of: None,
+            notes: None,
+        })
+    }
+
+    #[test]
+    fn find_flashback_prefers_year_ago_over_month_ago() -> Result<(), String> {
+        let records: Vec<SessionRecord> = [
+            record_on(2025, 7, 2, "year"),
+            record_on(2026, 6, 2, "month"),
+            record_on(2026, 6, 3, "other"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let today = NaiveDate::from_ymd_opt(2026, 7, 2).ok_or("invalid date")?;
+
+        let found = find_flashback(&records, today).map(|r| r.summary.as_str());
+        if found != Some("year") {
+            return Err(format!("unexpected flashback: {found:?}"));
+        }
+
+        let month_only: Vec<SessionRecord> = records.into_iter().skip(1).collect();
+        let found = find_flashback(&month_only, today).ok_or("no flashback was found")?;
+        if found.summary != "month" {
+            return Err(format!("unexpected flashback: {}", found.summary));
+        }
+        if flashback_label(found, today) != "1ヶ月前の今日" {
+            return Err(format!(
+                "unexpected label: {}",
+                flashback_label(found, today)
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn find_flashback_returns_none_without_matching_day() {
+        let records: Vec<SessionRecord> = record_on(2026, 6, 10, "x").into_iter().collect();
+        let today = NaiveDate::from_ymd_opt(2026, 7, 2).unwrap_or_default();
+        assert!(find_flashback(&records, today).is_none());
+    }
+
+    #[test]
+    fn history_browser_lists_newest_first() {
+        let records: Vec<SessionRecord> =
+            [record_on(2026, 6, 1, "old"), record_on(2026, 6, 2, "new")]
+                .into_iter()
+                .flatten()
+                .collect();
+        let mut browser = HistoryBrowser::new(records);
+        assert_eq!(
+            browser.records.first().map(|r| r.summary.as_str()),
+            Some("new")
+        );
+
+        browser.select_next();
+        browser.select_next();
+        assert!(browser.open_selected());
+        assert_eq!(
+            browser.opened_record().map(|r| r.summary.as_str()),
+            Some("old")
+        );
+        browser.close_detail();
+        assert!(!browser.is_detail_open());
+    }
+
+    #[test]
+    fn format_duration_needs_start_time() -> Result<(), String> {
+        let mut record = record_on(2026, 6, 2, "x").ok_or("invalid date")?;
+        if let Some(duration) = format_duration(&record) {
+            return Err(format!("duration without start time: {duration}"));
+        }
+        record.started_at = Some(record.timestamp - chrono::Duration::seconds(125));
+        let duration = format_duration(&record);
+        if duration.as_deref() != Some("2分05秒") {
+            return Err(format!("unexpected duration: {duration:?}"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn stored_records_skip_broken_lines() -> Result<(), String> {
+        let record = record_on(2026, 6, 2, "ok").ok_or("invalid date")?;
+        let dir = std::env::temp_dir().join(format!("yomitore-history-{}", std::process::id()));
+        let storage = storage::JsonStorage::open(dir.clone()).map_err(|e| e.to_string())?;
+        let line = serde_json::to_string(&record).map_err(|e| e.to_string())?;
+        std::fs::write(
+            dir.join("history.jsonl"),
+            format!("{line}\nnot json\n\n{line}\n"),
+        )
+        .map_err(|e| e.to_string())?;
+        let records: Vec<SessionRecord> =
+            storage::query_json(&storage, storage::HISTORY_COLLECTION, None)
+                .map_err(|e| e.to_string())?;
+        let _ = std::fs::remove_dir_all(&dir);
+        if records.len() != 2 {
+            return Err(format!("expected two records, got {}", records.len()));
+        }
+        Ok(())
+    }
+}
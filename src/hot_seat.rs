@@ -0,0 +1,130 @@
+use crate::config::HotSeatConfig;
+use crate::history;
+use crate::models::HeadToHeadResult;
+
+const DEFAULT_PLAYER_NAMES: [&str; 2] = ["プレイヤー1", "プレイヤー2"];
+
+/// 1台の端末で2人が交代で同じ文章を要約する対戦モードの進行状態
+pub struct HotSeat {
+    pub players: [String; 2],
+    first_summary: Option<String>,
+}
+
+impl HotSeat {
+    pub fn new(config: &HotSeatConfig) -> Self {
+        let [first, second] = DEFAULT_PLAYER_NAMES;
+        Self {
+            players: [
+                player_name(config, 0, first),
+                player_name(config, 1, second),
+            ],
+            first_summary: None,
+        }
+    }
+
+    pub fn current_player(&self) -> &str {
+        let [first, second] = &self.players;
+        if self.first_summary.is_some() {
+            second
+        } else {
+            first
+        }
+    }
+
+    /// 次の提出が2人目のもので、両方の要約がそろうか
+    pub fn is_last_turn(&self) -> bool {
+        self.first_summary.is_some()
+    }
+
+    /// 要約を提出する。2人目の提出で両方の要約を返し、次のラウンドに備えて状態を戻す
+    pub fn submit(&mut self, summary: String) -> Option<(String, String)> {
+        if let Some(first) = self.first_summary.take() {
+            return Some((first, summary));
+        }
+        self.first_summary = Some(summary);
+        None
+    }
+
+    pub fn reset(&mut self) {
+        self.first_summary = None;
+    }
+}
+
+fn player_name(config: &HotSeatConfig, index: usize, default: &str) -> String {
+    config
+        .players
+        .get(index)
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .unwrap_or(default)
+        .to_string()
+}
+
+/// 2人分の評価詳細と勝敗をまとめた評価画面用のテキスト
+pub fn format_head_to_head(result: &HeadToHeadResult, details: [&str; 2]) -> String {
+    let mut sections: Vec<String> = result
+        .participants
+        .iter()
+        .zip(details)
+        .map(|(participant, detail)| format!("# {}\n{detail}", participant.name))
+        .collect();
+
+    let mut summary = vec!["# 対戦結果".to_string()];
+    summary.extend(result.participants.iter().map(|participant| {
+        format!(
+            "- {}: {}",
+            participant.name,
+            history::format_result(participant.passed, participant.evaluation.as_ref())
+        )
+    }));
+    let verdict = match result.winner() {
+        Some(winner) => format!("{} の勝ち", winner.name),
+        None => "引き分け".to_string(),
+    };
+    summary.push(format!("- 判定: {verdict}\n"));
+    sections.push(summary.join("\n"));
+    sections.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ParticipantResult;
+
+    #[test]
+    fn hot_seat_alternates_turns_and_returns_both_summaries() {
+        let mut hot_seat = HotSeat::new(&HotSeatConfig {
+            players: vec!["たろう".to_string(), " ".to_string()],
+        });
+        assert_eq!(hot_seat.current_player(), "たろう");
+        assert!(!hot_seat.is_last_turn());
+
+        assert_eq!(hot_seat.submit("一人目".to_string()), None);
+        assert_eq!(hot_seat.current_player(), "プレイヤー2");
+        assert!(hot_seat.is_last_turn());
+
+        let summaries = hot_seat.submit("二人目".to_string());
+        assert_eq!(
+            summaries,
+            Some(("一人目".to_string(), "二人目".to_string()))
+        );
+        assert_eq!(hot_seat.current_player(), "たろう");
+        assert!(!hot_seat.is_last_turn());
+    }
+
+    #[test]
+    fn format_head_to_head_names_winner() {
+        let participant = |name: &str, passed: bool| ParticipantResult {
+            name: name.to_string(),
+            passed,
+            evaluation: None,
+        };
+        let result = HeadToHeadResult {
+            timestamp: chrono::Local::now(),
+            participants: [participant("A", false), participant("B", true)],
+        };
+        let text = format_head_to_head(&result, ["詳細A", "詳細B"]);
+        assert!(text.starts_with("# A\n詳細A\n# B\n詳細B\n# 対戦結果\n"));
+        assert!(text.contains("- A: 不合格\n- B: 合格\n- 判定: B の勝ち"));
+    }
+}
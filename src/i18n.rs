@@ -0,0 +1,970 @@
+use crate::config::LabelLanguage;
+use std::fmt::Display;
+
+/// 画面の日本語の文字列をキーにした英語の対訳。書式付きの文字列は `{}` の数と順序を揃える
+const EN: &[(&str, &str)] = &[
+    // ステータスバー
+    (
+        "文字数を選び、開始してください。",
+        "Choose a text length and start.",
+    ),
+    (
+        "通常モードです。'i' で入力します。",
+        "Normal mode. Press 'i' to write.",
+    ),
+    (
+        "入力モードです。Esc で戻ります。",
+        "Editing. Press Esc to go back.",
+    ),
+    (
+        "メモを入力しています。Esc で戻ります。メモは評価に送られません。",
+        "Writing notes. Press Esc to go back. Notes are not sent for evaluation.",
+    ),
+    (
+        "レポート表示中です。'Tab' か '1'〜'5' でタブを、'n' で素点とモデル補正を切り替え、'j'/'k' でスクロール、'b' でバッジ一覧、'r' で閉じます。",
+        "Showing the report. Press 'Tab' or '1'-'5' to switch tabs, 'n' to switch raw and model-normalized scores, 'j'/'k' to scroll, 'b' for all badges, 'r' to close.",
+    ),
+    (
+        "バッジ一覧を表示中です。'j'/'k' でスクロール、'b' でレポートに戻り、'r' で閉じます。",
+        "Showing all badges. Press 'j'/'k' to scroll, 'b' to go back to the report, 'r' to close.",
+    ),
+    (
+        "ヘルプ表示中です。'/' で検索、't' で目次、'h' で閉じます。",
+        "Showing help. '/' to search, 't' for contents, 'h' to close.",
+    ),
+    (
+        "文章を生成しています... (Esc: キャンセル)",
+        "Generating a text... (Esc: cancel)",
+    ),
+    (
+        "次の文章を生成しています... (Esc: キャンセル)",
+        "Generating the next text... (Esc: cancel)",
+    ),
+    (
+        "要約を評価しています... (Esc: キャンセル)",
+        "Evaluating your summary... (Esc: cancel)",
+    ),
+    (
+        "評価が完了しました。'e' で切替、'n' で次へ進みます。",
+        "Evaluation finished. 'e' to toggle, 'n' for the next text.",
+    ),
+    (
+        "評価結果の形式が不正です。",
+        "The evaluation result is malformed.",
+    ),
+    (
+        "エラーが発生しました。Enter で閉じます。",
+        "An error occurred. Press Enter to close.",
+    ),
+    (
+        "文章を後回しにしました。次の文章を生成しています... (Esc: キャンセル)",
+        "Text deferred. Generating the next text... (Esc: cancel)",
+    ),
+    (
+        "今日復習する文章はありません。",
+        "No texts to review today.",
+    ),
+    (
+        "今日の復習は終わりました。新しい文章を生成します。",
+        "Today's reviews are done. Generating a new text.",
+    ),
+    (
+        "未使用テキストから出題しました。'i' で入力します。",
+        "Serving an unused text. Press 'i' to write.",
+    ),
+    (
+        "評価に時間がかかっています。w: 待つ / c: キャンセル / s: 自己採点",
+        "The evaluation is taking a while. w: wait / c: cancel / s: self-grade",
+    ),
+    ("配色: {}", "Theme: {}"),
+    (
+        "警告: 下書きの保存に失敗しました: {}",
+        "Warning: could not save the draft: {}",
+    ),
+    (
+        "警告: 下書きの削除に失敗しました: {}",
+        "Warning: could not delete the draft: {}",
+    ),
+    (
+        "原文欄の幅を {}% にしました。",
+        "Set the original pane to {}% of the width.",
+    ),
+    (
+        "履歴の読み込みに失敗しました: {}",
+        "Could not load the history: {}",
+    ),
+    (
+        "警告: 履歴の保存に失敗しました: {}",
+        "Warning: could not save the history: {}",
+    ),
+    (
+        "モデル一覧の取得に失敗しました: {}",
+        "Could not fetch the model list: {}",
+    ),
+    (
+        "ディレクトリを開けません: {}",
+        "Cannot open the directory: {}",
+    ),
+    (
+        "ライブラリに {} 文字前後の文章がありません。",
+        "The library has no texts of about {} characters.",
+    ),
+    (
+        "警告: ライブラリの保存に失敗しました: {}",
+        "Warning: could not save the library: {}",
+    ),
+    (
+        "{} (ライブラリに同じ文章があります)",
+        "{} (the library already has this text)",
+    ),
+    (
+        "{} (ライブラリに {} 件追加)",
+        "{} ({} added to the library)",
+    ),
+    (
+        "チャレンジファイルを保存しました: {}",
+        "Saved the challenge file: {}",
+    ),
+    (
+        "チャレンジファイルの保存に失敗しました: {}",
+        "Could not save the challenge file: {}",
+    ),
+    ("学習記録を保存しました: {}", "Saved the study journal: {}"),
+    (
+        "学習記録の保存に失敗しました: {}",
+        "Could not save the study journal: {}",
+    ),
+    (
+        "復習 (残り {} 件): 以前不合格だった文章です。'i' で入力します。",
+        "Review ({} left): a text you failed before. Press 'i' to write.",
+    ),
+    (
+        "警告: 復習リストの保存に失敗しました: {}",
+        "Warning: could not save the review list: {}",
+    ),
+    (
+        "要約の {}% が原文と同じ表現です (目安 {}% 以下)。自分の言葉で書き直してください。",
+        "{}% of the summary copies the original (aim for {}% or less). Rewrite it in your own words.",
+    ),
+    ("{}に失敗しました。 {}", "{} failed. {}"),
+    ("文章の生成", "Text generation"),
+    ("要約の評価", "Summary evaluation"),
+    ("段落の講評", "Paragraph feedback"),
+    (
+        "警告: 監査ログの書き込みに失敗しました: {}",
+        "Warning: could not write the audit log: {}",
+    ),
+    (
+        "警告: 統計の保存に失敗しました: {}",
+        "Warning: could not save the stats: {}",
+    ),
+    (
+        "🎯 今日の目標 ({} 回) を達成しました！",
+        "🎯 Reached today's goal ({} sessions)!",
+    ),
+    ("🏅 自己ベスト更新: {}", "🏅 New personal best: {}"),
+    ("{} 文字の最速合格", "fastest pass at {} chars"),
+    ("最長の合格", "longest pass"),
+    ("最高得点", "highest score"),
+    ("1日の最多回数", "most sessions in a day"),
+    (
+        "評価をキャンセルしました。Ctrl+S で再送信できます。",
+        "Evaluation cancelled. Press Ctrl+S to resubmit.",
+    ),
+    (
+        "自己採点: y で合格、n で不合格、Esc で戻ります。",
+        "Self-grade: y for pass, n for fail, Esc to go back.",
+    ),
+    (
+        "自己採点を記録しました。'n' で次へ進みます。",
+        "Self-grade recorded. Press 'n' for the next text.",
+    ),
+    (
+        "↑/↓ or j/k で選択、Enter で解答します。",
+        "↑/↓ or j/k to choose, Enter to answer.",
+    ),
+    (
+        "ライブラリに残した問題から出題しました。↑/↓ or j/k で選択、Enter で解答します。",
+        "Served questions saved in the library. ↑/↓ or j/k to choose, Enter to answer.",
+    ),
+    (
+        "2 つの資料を読み、両方の内容を 1 つの要約にまとめてください。'i' で入力します。",
+        "Read both sources and combine them into one summary. Press 'i' to write.",
+    ),
+    (
+        "採点しました。'n' で次へ進みます。",
+        "Graded. Press 'n' for the next text.",
+    ),
+    (
+        "提出しました。'n' で次の問題へ進みます。",
+        "Submitted. Press 'n' for the next question.",
+    ),
+    (
+        "模擬試験が終わりました。'n' で通常の練習に戻ります。",
+        "The mock exam is over. Press 'n' to return to practice.",
+    ),
+    (
+        "時間切れのため不合格として記録しました。'n' で次へ進みます。",
+        "Time is up; recorded as failed. Press 'n' for the next text.",
+    ),
+    (
+        "声に出して読み、段落を読み終えたら Space を押します。Esc で戻ります。",
+        "Read aloud and press Space after each paragraph. Esc to go back.",
+    ),
+    (
+        "音読が終わりました。Esc で戻ります。",
+        "Reading aloud finished. Press Esc to go back.",
+    ),
+    (
+        "集中モードです。入力中は要約欄だけを表示し、Ctrl+P で原文をのぞけます。",
+        "Zen mode on. While editing only the summary is shown; Ctrl+P peeks at the original.",
+    ),
+    ("集中モードを終了しました。", "Zen mode off."),
+    (
+        "一時停止中です。'P' で再開します。",
+        "Paused. Press 'P' to resume.",
+    ),
+    (
+        "一時停止中は原文を隠しています。'P' で再開します。",
+        "The original is hidden while paused. Press 'P' to resume.",
+    ),
+    (
+        "表示された部分だけを読みます。Space: 一時停止, +/-: ペース, Esc: 戻る",
+        "Read only the part on screen. Space: pause, +/-: pace, Esc: back",
+    ),
+    (
+        "最後まで表示しました。Esc で戻って要約を書きます。",
+        "Reached the end. Press Esc to go back and write the summary.",
+    ),
+    (
+        "チャレンジに挑戦します。'i' で入力します。",
+        "Starting the challenge. Press 'i' to write.",
+    ),
+    (
+        "昇級試験です。'i' で入力します。",
+        "Promotion exam. Press 'i' to write.",
+    ),
+    (
+        "モデル一覧を取得しています...",
+        "Fetching the model list...",
+    ),
+    (
+        "j/k で選択、Enter で決定、Esc で戻ります。",
+        "j/k to choose, Enter to select, Esc to go back.",
+    ),
+    (
+        "かな入力モードです。Space で変換、Enter で確定、Ctrl+J で切り替えます。",
+        "Kana input. Space to convert, Enter to confirm, Ctrl+J to toggle.",
+    ),
+    (
+        "j/k で選択、Enter で開く、Backspace で上の階層、Esc で戻ります。",
+        "j/k to choose, Enter to open, Backspace for the parent folder, Esc to go back.",
+    ),
+    (
+        "読み込んだ文章で練習します。'i' で入力します。",
+        "Practicing with the loaded text. Press 'i' to write.",
+    ),
+    (
+        "ライブラリの文章で練習します。'i' で入力します。",
+        "Practicing with a library text. Press 'i' to write.",
+    ),
+    (
+        "j/k で選択、Enter で開く、R で再挑戦、Esc で戻ります。",
+        "j/k to choose, Enter to open, R to retry, Esc to go back.",
+    ),
+    (
+        "過去のセッションを表示しています (読み取り専用)。R で再挑戦、Esc で一覧に戻ります。",
+        "Viewing a past session (read-only). R to retry, Esc to return to the list.",
+    ),
+    (
+        "不合格でした。'R' で同じ文章に再挑戦、'n' で次へ進みます。",
+        "Failed. 'R' to retry the same text, 'n' for the next text.",
+    ),
+    (
+        "同じ文章に再挑戦します。'i' で入力します。",
+        "Retrying the same text. Press 'i' to write.",
+    ),
+    (
+        "以前の文章に再挑戦します。'i' で入力します。",
+        "Retrying an earlier text. Press 'i' to write.",
+    ),
+    (
+        "{}: レポート | {}: ヘルプ | {}: 終了",
+        "{}: report | {}: help | {}: quit",
+    ),
+    ("残り {}", "{} left"),
+    // ヘッダーと各欄のタイトル
+    (
+        "yomitore: 読解力トレーニング",
+        "yomitore: Reading Comprehension Training",
+    ),
+    ("昇級試験", "Promotion exam"),
+    ("{}日連続", "{}-day streak"),
+    (
+        "原文 (↑/↓ or j/k: スクロール)",
+        "Original (↑/↓ or j/k: scroll)",
+    ),
+    (
+        "原文 | {} (↑/↓ or j/k: スクロール)",
+        "Original | {} (↑/↓ or j/k: scroll)",
+    ),
+    ("用語を調べています…", "Looking up terms…"),
+    (
+        "用語の解説を取得できませんでした。g を 2 回押すと再取得します。",
+        "Could not fetch the glossary. Press g twice to retry.",
+    ),
+    ("用語 (g: 表示切り替え)", "Glossary (g: toggle)"),
+    ("あなた", "you"),
+    ("{} さん", "{}"),
+    (
+        "{}の要約{} (i:入力モード Esc:通常モード Ctrl+S:送信 Ctrl+J:かな入力)",
+        "Summary by {}{} (i: edit, Esc: normal, Ctrl+S: submit, Ctrl+J: kana input)",
+    ),
+    (
+        "メモ (s:入力 Esc:通常モード S:隠す) 評価には送られません",
+        "Notes (s: edit, Esc: normal, S: hide) not sent for evaluation",
+    ),
+    ("4択問題", "Multiple choice"),
+    (
+        "4択問題 {}/{} (↑/↓ or j/k: 選択 Enter: 解答)",
+        "Multiple choice {}/{} (↑/↓ or j/k: choose, Enter: answer)",
+    ),
+    (
+        "{}/{} 問正解 ('e' で結果を表示)",
+        "{}/{} correct ('e' to show the result)",
+    ),
+    (
+        "変換 (Space: 次の候補 Enter: 確定 Esc: 取消)",
+        "Convert (Space: next candidate, Enter: confirm, Esc: cancel)",
+    ),
+    // 評価結果
+    ("{} (重み {})", "{} (weight {})"),
+    (
+        "加重スコア: {}% (合格ライン {}%)",
+        "Weighted score: {}% (pass mark {}%)",
+    ),
+    (
+        " 評価結果 (e: 閉じる, 1-4 or h/l: タブ, Shift+↑/↓ or Shift+j/k: スクロール, i: 改善点, n: 次の問題, c: チャレンジ書き出し, x: 記録を保存, y: コピー, a: 異議, t: コーチ) ",
+        " Result (e: close, 1-4 or h/l: tabs, Shift+↑/↓ or Shift+j/k: scroll, i: improvements, n: next, c: export challenge, x: save journal, y: copy, a: appeal, t: coach) ",
+    ),
+    (
+        " 評価結果 (e: 操作をやめる, 1-4: タブ, n: 次の問題) ",
+        " Result (e: unfocus, 1-4: tabs, n: next) ",
+    ),
+    (" 評価結果 (e: 操作する) ", " Result (e: focus) "),
+    (" 評価結果 ", " Result "),
+    ("読み仮名を取得中…", "Fetching readings…"),
+    (
+        "読み仮名を取得できませんでした (f を 2 回押すと再取得)",
+        "Could not fetch readings (press f twice to retry)",
+    ),
+    ("読み仮名 (f: 切り替え)", "Readings (f: toggle)"),
+    (
+        "←/→ or h/l で語を選び、d で調べます。Esc で戻ります。",
+        "Pick a word with ←/→ or h/l and press d to look it up. Esc to go back.",
+    ),
+    ("調べています…", "Looking it up…"),
+    (
+        "意味を調べられませんでした。",
+        "Could not look up the meaning.",
+    ),
+    ("読み: {}", "Reading: {}"),
+    ("意味: {}", "Meaning: {}"),
+    ("a: 単語帳に追加", "a: add to vocabulary"),
+    (" 現在 {} / 目標 {}±{} 文字 ", " {} / target {}±{} chars "),
+    (
+        "要約を提出すると、ここに評価結果が表示されます。",
+        "Submit your summary to see the evaluation here.",
+    ),
+    ("評価", "Evaluation"),
+    ("模範解答", "Model answer"),
+    ("キーポイント", "Key points"),
+    ("会話", "Conversation"),
+    ("合格", "Passed"),
+    ("不合格", "Failed"),
+    ("判定不能", "Undetermined"),
+    // ダイアログ
+    (
+        " 評価に時間がかかっています ",
+        " The evaluation is taking a while ",
+    ),
+    ("w: このまま待つ", "w: keep waiting"),
+    ("c: キャンセル", "c: cancel"),
+    ("s: 自己採点に切り替える", "s: switch to self-grading"),
+    (" 自己採点 ", " Self-grade "),
+    (
+        "原文と見比べて、要約の出来を判定してください。",
+        "Compare with the original and judge your summary.",
+    ),
+    ("y: 合格", "y: pass"),
+    ("n: 不合格", "n: fail"),
+    ("Esc: 戻る", "Esc: back"),
+    (" ひと休み ", " Take a break "),
+    (
+        "画面から目を離し、遠くを眺めて肩の力を抜きましょう。",
+        "Look away from the screen, gaze into the distance and relax your shoulders.",
+    ),
+    ("残り {} 秒", "{} s left"),
+    ("({} 秒経過)", "({} s elapsed)"),
+    (" (Esc: 取り消し)", " (Esc: cancel)"),
+    ("Esc: スキップ", "Esc: skip"),
+    (" 評価への異議 ", " Appeal the evaluation "),
+    (
+        "評価に納得できない理由を書いて Enter で送ると、評価者が考え直して答えます。",
+        "Write why you disagree and press Enter; the evaluator will reconsider and reply.",
+    ),
+    ("あなた: ", "You: "),
+    ("評価者: ", "Evaluator: "),
+    ("→ 考え直した結果: {}", "→ Reconsidered: {}"),
+    ("評価者に問い合わせています…", "Asking the evaluator…"),
+    ("返事を受け取れませんでした: {}", "No reply received: {}"),
+    (
+        "記録: {} (異議により変更)",
+        "Recorded: {} (changed by appeal)",
+    ),
+    ("記録: {}", "Recorded: {}"),
+    ("Enter: 送る / Esc: 閉じる", "Enter: send / Esc: close"),
+    (
+        "Enter: 送る / Tab: 記録した合否を逆にする / Esc: 閉じる",
+        "Enter: send / Tab: flip the recorded result / Esc: close",
+    ),
+    (" 🎉 昇段 ", " 🎉 Rank up "),
+    ("{} に昇段しました！", "You reached {}!"),
+    ("合格 {} 回 / 平均 {} 点", "{} passes / average {}"),
+    (
+        "次の{}: 合格 {} 回・平均 {} 点",
+        "Next, {}: {} passes and an average of {}",
+    ),
+    ("最高の段位です", "This is the highest rank"),
+    ("対処方法", "How to fix"),
+    ("Enter/Esc: 閉じる", "Enter/Esc: close"),
+    // メニュー
+    ("文字数を選択してください", "Choose a text length"),
+    ("{} 文字", "{} chars"),
+    (
+        "レベル: {} (l: レベル挑戦)",
+        "Level: {} (l: level challenge)",
+    ),
+    (
+        "レベル: {} (l: レベル挑戦 / 昇級試験まであと {} 回合格)",
+        "Level: {} (l: level challenge / {} more passes to the promotion exam)",
+    ),
+    ("難易度: {} (d: 変更)", "Difficulty: {} (d: change)"),
+    ("要約の長さ: {} (g: 変更)", "Summary length: {} (g: change)"),
+    ("評価の厳しさ: {} (x: 変更)", "Strictness: {} (x: change)"),
+    ("甘口", "Lenient"),
+    ("中辛", "Standard"),
+    ("辛口", "Strict"),
+    (
+        " 前回の書きかけの要約があります (Enter/y: 復元, Esc/n: 破棄) ",
+        " Unfinished summary from last session (Enter/y: restore, Esc/n: discard) ",
+    ),
+    ("あなたの要約", "Your summary"),
+    (
+        "文章の生成をキャンセルしました。",
+        "Text generation cancelled.",
+    ),
+    (
+        "オフラインで起動しました。手元の文章から出題し、評価は自己採点で行います。",
+        "Started offline. Texts come from the local corpus and you grade your own summary.",
+    ),
+    (
+        "手元の文章から出題しました。'i' で入力し、Ctrl+S で自己採点します。",
+        "Picked a local text. Press 'i' to write, then Ctrl+S to grade yourself.",
+    ),
+    (
+        "評価に失敗しました。y/n で自己採点して記録するか、Esc で閉じてください。",
+        "Evaluation failed. Press y/n to record a self-grade, or Esc to close.",
+    ),
+    (
+        "要約はそのまま残っています。Ctrl+S で再送信できます。",
+        "Your summary is kept. Press Ctrl+S to submit again.",
+    ),
+    (
+        "自己採点で記録する (y: 合格 / n: 不合格) / Esc: 閉じて再送信を待つ",
+        "Record a self-grade (y: pass / n: fail) / Esc: close and resubmit later",
+    ),
+    (
+        "このモードはオフラインでは使えません。",
+        "This mode is not available offline.",
+    ),
+    (
+        "オフラインのため対戦の評価はできません。2人分の要約はそのまま残っています。",
+        "Head-to-head summaries cannot be evaluated offline. Both summaries are kept.",
+    ),
+    (
+        "API の応答が得られないため、キャッシュした未出題の文章から出題しました。'i' で入力します。",
+        "The API did not respond, so an unused cached text was served. Press 'i' to type.",
+    ),
+    (
+        "オフライン: 手元の {} 件の文章から出題し、自己採点します",
+        "Offline: {} local texts, self-graded",
+    ),
+    (
+        "オフライン: 手元の {} 件の文章から出題し、自己採点します (a: API キーを設定)",
+        "Offline: {} local texts, self-graded (a: set API key)",
+    ),
+    (
+        "API キーを貼り付け、Enter で確かめて保存します。Esc で戻ります。",
+        "Paste your API key and press Enter to check and save it. Press Esc to go back.",
+    ),
+    (
+        "API キーを確かめています... (Esc: 中止)",
+        "Checking the API key... (Esc: cancel)",
+    ),
+    (
+        "API キーを保存しました。文字数を選び、開始してください。",
+        "API key saved. Choose a text length and start.",
+    ),
+    (
+        "Ollama を使うときは API キーは要りません。",
+        "No API key is needed with Ollama.",
+    ),
+    (
+        "評価について質問できます。Enter で送信、↑/↓ でスクロール、Esc で戻ります。",
+        "Ask about the evaluation. Enter: send, ↑/↓: scroll, Esc: back.",
+    ),
+    (
+        " コーチ (Enter: 送信, ↑/↓: スクロール, Esc: 戻る) ",
+        " Coach (Enter: send, ↑/↓: scroll, Esc: back) ",
+    ),
+    (
+        "評価について気になることを質問してください。例: なぜ重要度が3なの？",
+        "Ask anything about the evaluation, e.g. \"Why is importance only 3?\"",
+    ),
+    ("コーチ: ", "Coach: "),
+    (
+        "キーワードを予想すると本文が表示されます。",
+        "The full text appears after you predict the keywords.",
+    ),
+    (
+        "冒頭を読んで、本文のキーワードを {} つ予想してください（読点か空白で区切ります）。",
+        "Read the opening and predict {} keywords of the text (separate them with commas or spaces).",
+    ),
+    (
+        "Enter: 予想して本文へ, Esc: 予想せずに本文へ",
+        "Enter: predict and read, Esc: read without predicting",
+    ),
+    (" キーワード予想 ", " Keyword prediction "),
+    ("考えています…", "Thinking…"),
+    (
+        "API キーの設定 (Enter: 確かめて保存, Esc: 戻る)",
+        "API key (Enter: check and save, Esc: back)",
+    ),
+    (
+        "Groq の API キー (https://console.groq.com/keys で発行) を貼り付けてください。確かめたキーは config.toml に保存します。",
+        "Paste your Groq API key (issued at https://console.groq.com/keys). Once checked, it is saved to config.toml.",
+    ),
+    ("API キーを貼り付けてください。", "Paste your API key."),
+    (
+        "空白や改行が含まれています。キーだけを貼り付けてください。",
+        "The key contains spaces or line breaks. Paste only the key.",
+    ),
+    (
+        "Groq の API キーは gsk_ で始まります。このまま確かめることもできます。",
+        "Groq API keys start with gsk_. You can still check this one.",
+    ),
+    (
+        "Enter で API に接続して確かめ、設定に保存します。",
+        "Press Enter to check the key with the API and save it.",
+    ),
+    ("{} 文字 / 末尾 {}", "{} characters / ends with {}"),
+    ("確かめられませんでした: {}", "Could not verify: {}"),
+    (
+        "API キーを OS のキーチェーンに保存しました。文字数を選び、開始してください。",
+        "API key saved to the OS keychain. Choose a text length and start.",
+    ),
+    (
+        "config.toml に [[profiles]] を書くと、接続先を切り替えられます。",
+        "Add [[profiles]] to config.toml to switch between connections.",
+    ),
+    (
+        "接続先のプロファイルを選択してください (Enter: 接続, Esc: 戻る)",
+        "Choose a connection profile (Enter: connect, Esc: back)",
+    ),
+    ("プロファイル: {} (P: 切り替え)", "Profile: {} (P: switch)"),
+    (
+        "{} に接続しています... (Esc: 中止)",
+        "Connecting to {}... (Esc: abort)",
+    ),
+    (
+        "プロファイル {} に切り替えました。",
+        "Switched to profile {}.",
+    ),
+    (
+        "プロファイル {} に切り替えましたが、設定に保存できませんでした: {}",
+        "Switched to profile {}, but could not save it to the config: {}",
+    ),
+    ("{} に接続できません: {}", "Cannot connect to {}: {}"),
+    (
+        "再送しています ({}/{}): {}。{} 秒後に送ります",
+        "Retrying ({}/{}): {}. Sending again in {} s",
+    ),
+    ("原文", "Original"),
+    (
+        "前回の書きかけの要約を復元しました。'i' で続きを入力します。",
+        "Restored your unfinished summary. Press 'i' to continue writing.",
+    ),
+    (
+        "制限時間付き: {} 文字を {} 以内 (t: 開始)",
+        "Timed: {} chars within {} (t: start)",
+    ),
+    (
+        "4択問題: {} 文字の本文と {} 問 (c: 開始)",
+        "Multiple choice: a {}-char passage and {} questions (c: start)",
+    ),
+    (
+        "模擬試験: {} 文字の {} 問、制限時間付き (e: 開始)",
+        "Mock exam: {} chars, {} questions, timed (e: start)",
+    ),
+    (
+        "統合要約: 約 {} 文字の資料 2 つを 1 つに要約 (s: 開始)",
+        "Synthesis: combine two sources of about {} chars into one summary (s: start)",
+    ),
+    (
+        "段階練習: {} 文字の文章を段落ごとに要約してからまとめる (u: 開始)",
+        "Guided: summarize a {}-char text paragraph by paragraph, then combine (u: start)",
+    ),
+    ("【段落 {}/{}】", "[Paragraph {}/{}]"),
+    ("→ 要約: {}", "→ Summary: {}"),
+    ("段落 {}/{} ", "you, paragraph {}/{}"),
+    ("全体", "you, overall"),
+    (
+        "段落の要約を講評しています...",
+        "Reviewing the paragraph summary...",
+    ),
+    (
+        "講評を原文欄に表示しました。次の段落を要約してください。",
+        "Feedback is shown in the source pane. Summarize the next paragraph.",
+    ),
+    (
+        "段落ごとの要約を入力欄に並べました。全体の要約にまとめて Ctrl+S で送信します。",
+        "Your paragraph summaries are in the input. Combine them into one summary and press Ctrl+S.",
+    ),
+    (
+        "今日のお題: {} (o: 挑戦)",
+        "Today's challenge: {} (o: start)",
+    ),
+    ("挑戦済み", "done"),
+    ("モデル: {} (m: 変更)", "Model: {} (m: change)"),
+    (
+        "苦手対策: {} を重点的に出題します",
+        "Weakness focus: practicing {}",
+    ),
+    (
+        "復習: {} 件 (v: 新しい文章の前に復習する)",
+        "Reviews: {} due (v: review before new texts)",
+    ),
+    (
+        "ライブラリ: {} 文字前後 {} 件 / 全 {} 件 (b: ライブラリから出題)",
+        "Library: around {} chars {} / {} total (b: practice from the library)",
+    ),
+    (
+        "未使用テキスト: {} 件 (API を使わずに出題します)",
+        "Unused texts: {} (served without the API)",
+    ),
+    // そのほかの画面
+    ("利用できるモデルがありません。", "No models are available."),
+    (
+        "モデルを選択してください (Enter: 決定, Esc: 戻る)",
+        "Choose a model (Enter: select, Esc: back)",
+    ),
+    ("まだ履歴がありません。", "No history yet."),
+    (
+        "履歴 ({} 件) (Enter: 開く, Esc: 戻る)",
+        "History ({}) (Enter: open, Esc: back)",
+    ),
+    (
+        "テキストファイルを選択してください: {}",
+        "Choose a text file: {}",
+    ),
+    ("音読の結果 (Esc: 戻る)", "Reading aloud result (Esc: back)"),
+    (
+        "音読 (段落 {}/{}, Space: 次の段落, Esc: 戻る)",
+        "Reading aloud (paragraph {}/{}, Space: next paragraph, Esc: back)",
+    ),
+    ("目標ペース", "Target pace"),
+    ("文字数: {} 文字", "Length: {} chars"),
+    (
+        "文字数: {} / 目標 {}±{} 文字 ({})",
+        "Length: {} / target {}±{} chars ({})",
+    ),
+    ("条件を満たしています", "within the target"),
+    ("条件を満たしていません", "outside the target"),
+    ("所要時間: {} 秒 (目標 {} 秒)", "Time: {} s (target {} s)"),
+    ("ペース: {} 文字/分", "Pace: {} chars/min"),
+    ("経過 {} 秒 / 目標 {} 秒", "Elapsed {} s / target {} s"),
+    (
+        " {} (Enter: 再挑戦, Esc: 閉じる) ",
+        " {} (Enter: retry, Esc: close) ",
+    ),
+    ("1年前の今日", "On this day a year ago"),
+    ("1ヶ月前の今日", "On this day a month ago"),
+    ("{} / {} 文字", "{} / {} chars"),
+    ("結果: {}", "Result: {}"),
+    ("{} / {}字 / {}", "{} / {} chars / {}"),
+    (" / 所要 {}", " / took {}"),
+    ("--- メモ ---", "--- Notes ---"),
+    ("原文 | {}", "Original | {}"),
+    (
+        "評価 (j/k: スクロール, Esc: 一覧へ)",
+        "Evaluation (j/k: scroll, Esc: back to list)",
+    ),
+    (
+        "速読 (一時停止中, Space: 再開, Esc: 戻る)",
+        "Speed reading (paused, Space: resume, Esc: back)",
+    ),
+    (
+        "速読 ({} 文字/分, Space: 一時停止, +/-: ペース, Esc: 戻る)",
+        "Speed reading ({} chars/min, Space: pause, +/-: pace, Esc: back)",
+    ),
+    ("最後まで表示しました。", "Reached the end of the text."),
+    ("進み具合", "Progress"),
+    (
+        "原文をのぞいています  ↑/↓: スクロール  ほかのキー: 要約欄に戻る",
+        "Peeking at the original  Up/Down: scroll  Any other key: back to the summary",
+    ),
+    (
+        "集中モード  Ctrl+P: 原文をのぞく  Esc: 入力を終える",
+        "Zen mode  Ctrl+P: peek at the original  Esc: stop editing",
+    ),
+    (" チュートリアル ({}/{}) ", " Tutorial ({}/{}) "),
+    (
+        "左の原文を読みましょう。{} で入力を始めます",
+        "Read the original text on the left. Press {} to start writing",
+    ),
+    (
+        "右の欄に、原文の要点を短くまとめて書きましょう",
+        "Write a short summary of the key points in the right pane",
+    ),
+    (
+        "書き終えたら {} で提出します。Esc で入力を中断できます",
+        "When you are done, press {} to submit. Esc pauses writing",
+    ),
+    (
+        "評価結果です。1-4 でタブを切り替え、{} で本番のトレーニングを始めます",
+        "This is the evaluation. Use 1-4 to switch tabs and {} to start real training",
+    ),
+    (
+        "チュートリアルです。画面の案内に沿って進めてください。",
+        "Tutorial. Follow the hints on screen.",
+    ),
+    (
+        "ヘルプ (↑/↓ or j/k: スクロール, /: 検索, t: 目次, [/]: 前/次の見出し, T: チュートリアル, h: 閉じる)",
+        "Help (↑/↓ or j/k: scroll, /: search, t: contents, [/]: previous/next heading, T: tutorial, h: close)",
+    ),
+    (
+        "目次 (j/k: 選択, Enter: 移動, Esc: 閉じる)",
+        "Contents (j/k: choose, Enter: jump, Esc: close)",
+    ),
+    // レポート
+    (
+        "レポート {} (Tab/1〜5: 切り替え, j/k: スクロール, n: 素点/補正, r: 閉じる)",
+        "Report {} (Tab/1-5: switch, j/k: scroll, n: raw/normalized, r: close)",
+    ),
+    ("バッジ", "Badges"),
+    (
+        "バッジ一覧 {}/{} (j/k: スクロール, b: レポートに戻る, r: 閉じる)",
+        "All badges {}/{} (j/k: scroll, b: back to report, r: close)",
+    ),
+    ("ヒートマップ", "Heatmap"),
+    ("週次", "Weekly"),
+    ("推移", "Trend"),
+    ("スコア", "Scores"),
+    ("記録なし", "No records"),
+    ("バディ (レベル {})", "Buddy (level {})"),
+    ("180日 (過去180日)", "180 days (past 180 days)"),
+    (
+        "推移 (直近90日, 7日間ごとの合格率と回数)",
+        "Trend (last 90 days, 7-day pass rate and sessions)",
+    ),
+    ("合格率 (%)", "Pass rate (%)"),
+    ("週ごと (過去{}週)", "By week (past {} weeks)"),
+    ("ジャンルごと (直近180日)", "By genre (last 180 days)"),
+    (
+        "文字数とジャンル別の合格率",
+        "Pass rate by length and genre",
+    ),
+    ("回数", "Sessions"),
+    ("過去1年 (52週)", "Past year (52 weeks)"),
+    (
+        "過去1年: {} 回 (練習した日 {} 日, 1日の最多 {} 回)",
+        "Past year: {} sessions ({} active days, at most {} in a day)",
+    ),
+    ("週次 (過去4週)", "Weekly (past 4 weeks)"),
+    ("難易度別の合格率", "Pass rate by difficulty"),
+    (
+        "文章の推定レベル別の合格率",
+        "Pass rate by estimated text level",
+    ),
+    ("対戦成績", "Head-to-head results"),
+    ("🥋 段位: ", "🥋 Rank: "),
+    (
+        " (次の{}まで: 合格 {}/{} 回, 平均 {}/{} 点)",
+        " (to {}: {}/{} passes, average {}/{})",
+    ),
+    (" (合格 {} 回, 平均 {} 点)", " ({} passes, average {})"),
+    ("  最後の昇段: {}", "  Last promotion: {}"),
+    ("📅 継続日数: ", "📅 Daily streak: "),
+    ("{} 日", "{} days"),
+    (" (最長 {} 日)", " (longest {} days)"),
+    ("🔥 連続正解: ", "🔥 Pass streak: "),
+    ("{} 回", "{} times"),
+    ("🗓 今日のお題: ", "🗓 Daily challenges: "),
+    ("🎯 目標達成: ", "🎯 Goals met: "),
+    ("✨ 累積正解: ", "✨ Total passes: "),
+    ("📅 継続: ", "📅 Streaks: "),
+    ("🏆 チャレンジ: ", "🏆 Challenges: "),
+    ("経験値: {}/{}", "EXP: {}/{}"),
+    ("獲得: {}", "Earned: {}"),
+    ("評価スコア (直近180日)", "Scores (last 180 days)"),
+    (" {} (n: 切り替え)", " {} (n: switch)"),
+    ("素点", "Raw"),
+    ("モデル補正", "Model-normalized"),
+    ("評価スコア: なし", "Scores: none"),
+    ("件数: {}", "Count: {}"),
+    (
+        "重要情報: 平均 {} / 中央値 {}",
+        "Key points: average {} / median {}",
+    ),
+    (
+        "簡潔性: 平均 {} / 中央値 {}",
+        "Conciseness: average {} / median {}",
+    ),
+    (
+        "正確性: 平均 {} / 中央値 {}",
+        "Accuracy: average {} / median {}",
+    ),
+    ("再挑戦: {} 回 (うち合格 {} 回)", "Retries: {} ({} passed)"),
+    ("読み込んだ文章", "Imported texts"),
+    ("2880字超", "Over 2880 chars"),
+    ("{}字", "{} chars"),
+    (
+        "  重要情報 {} / 簡潔性 {} / 正確性 {}",
+        "  Key points {} / Conciseness {} / Accuracy {}",
+    ),
+    (" ({}件)", " ({} sessions)"),
+    ("重要情報", "Key points "),
+    ("簡潔性　", "Conciseness"),
+    ("正確性　", "Accuracy   "),
+    (" {} (中央値 {})", " {} (median {})"),
+    (
+        "最速合格 ({} 文字): {} ({})",
+        "Fastest pass ({} chars): {} ({})",
+    ),
+    ("最長の合格: {} 文字 ({})", "Longest pass: {} chars ({})"),
+    ("最高得点: {}/{} ({})", "Highest score: {}/{} ({})"),
+    (
+        "1日の最多回数: {} 回 ({})",
+        "Most sessions in a day: {} ({})",
+    ),
+    (
+        "記録 (🏅 自己ベスト更新 {} 回)",
+        "Records (🏅 {} personal bests)",
+    ),
+    (
+        "直近: {} 点 ({}) {}  最高: {} 点  受験 {} 回",
+        "Latest: {} ({}) {}  Best: {}  Taken {} times",
+    ),
+    ("推移: {}", "Trend: {}"),
+    (
+        "模擬試験 (100 点満点、合格点 {})",
+        "Mock exam (out of 100, pass mark {})",
+    ),
+    (
+        "読み書きの速さ (字/分) 最新 {} / 平均 {} (直近{}回)",
+        "Reading and writing speed (chars/min) latest {} / average {} (last {})",
+    ),
+    ("キーワード予想", "Keyword predictions"),
+    (
+        "的中率: {}% ({} / {} 語、{} 回)",
+        "Hit rate: {}% ({} / {} words, {} sessions)",
+    ),
+    (
+        "{}: 最新 {}{} / 平均 {}{}",
+        "{}: latest {}{} / average {}{}",
+    ),
+    ("圧縮率", "Compression"),
+    ("文の数", "Sentences"),
+    ("漢字の割合", "Kanji ratio"),
+    ("原文と同じ表現", "Copied from the original"),
+    ("要約の指標 (直近{}回)", "Summary metrics (last {})"),
+    ("凡例: ", "Legend: "),
+    (" なし  ", " none  "),
+    (" 全不正解  ", " all failed  "),
+    (" 混在  ", " mixed  "),
+    (" 良  ", " good  "),
+    (" 優  ", " great  "),
+    (" 秀  ", " excellent  "),
+    (" 目標達成", " goal met"),
+    ("凡例: 少 ", "Legend: fewer "),
+    (" 多", " more"),
+    (" 正解  ", " passed  "),
+    (" 不正解", " failed"),
+];
+
+/// `text` を `locale` の言語にする。日本語はそのまま返し、対訳のない文字列
+/// (生成した文章や組み立てたメッセージ) も日本語のまま表示する
+pub fn tr(locale: LabelLanguage, text: &str) -> &str {
+    match locale {
+        LabelLanguage::Ja => text,
+        LabelLanguage::En => EN
+            .iter()
+            .find(|(ja, _)| *ja == text)
+            .map_or(text, |(_, en)| en),
+    }
+}
+
+/// 書式を訳してから `{}` を前から順に `args` で置き換える
+pub fn trf(locale: LabelLanguage, template: &str, args: &[&dyn Display]) -> String {
+    let mut parts = tr(locale, template).split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (part, arg) in parts.zip(
+        args.iter()
+            .map(ToString::to_string)
+            .chain(std::iter::repeat(String::new())),
+    ) {
+        text.push_str(&arg);
+        text.push_str(part);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app;
+
+    #[test]
+    fn strings_are_translated_only_for_english() {
+        assert_eq!(
+            tr(LabelLanguage::Ja, app::STATUS_NORMAL),
+            app::STATUS_NORMAL
+        );
+        assert_eq!(
+            tr(LabelLanguage::En, app::STATUS_NORMAL),
+            "Normal mode. Press 'i' to write."
+        );
+        assert_eq!(tr(LabelLanguage::En, "生成した文章"), "生成した文章");
+        assert_eq!(
+            trf(
+                LabelLanguage::En,
+                "{}/{} 問正解 ('e' で結果を表示)",
+                &[&2, &3]
+            ),
+            "2/3 correct ('e' to show the result)"
+        );
+        assert_eq!(trf(LabelLanguage::Ja, "{} 文字", &[&" 720"]), " 720 文字");
+    }
+
+    #[test]
+    fn translations_keep_placeholders_and_keys_are_unique() {
+        for (index, (ja, en)) in EN.iter().enumerate() {
+            assert_eq!(ja.matches("{}").count(), en.matches("{}").count(), "{ja}");
+            assert!(
+                EN.iter().skip(index + 1).all(|(other, _)| other != ja),
+                "{ja}"
+            );
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::config::JournalConfig;
+use crate::error::AppError;
+use crate::history::SessionRecord;
+use crate::result_tabs::{ResultTab, ResultTabs};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+const APP_DIR_NAME: &str = "yomitore";
+const JOURNAL_DIR_NAME: &str = "journal";
+
+/// 1 回分のセッションを Markdown の学習記録として書き出す。
+/// Obsidian などで扱えるよう、日時や合否はフロントマターに入れる
+pub fn export_session(
+    config: &JournalConfig,
+    record: &SessionRecord,
+    tabs: &ResultTabs,
+) -> Result<PathBuf, AppError> {
+    let dir = match &config.dir {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::config_dir()
+            .ok_or(AppError::IoError(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "設定ディレクトリが見つかりません。",
+            )))?
+            .join(APP_DIR_NAME)
+            .join(JOURNAL_DIR_NAME),
+    };
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "yomitore-{}.md",
+        record.timestamp.format("%Y-%m-%d-%H%M%S")
+    ));
+    fs::write(&path, format_markdown(record, tabs))?;
+    Ok(path)
+}
+
+fn format_markdown(record: &SessionRecord, tabs: &ResultTabs) -> String {
+    let verdict = if record.passed { "合格" } else { "不合格" };
+    let mut markdown = String::from("---\n");
+    let _ = writeln!(markdown, "date: {}", record.timestamp.to_rfc3339());
+    let _ = writeln!(markdown, "result: {verdict}");
+    let _ = writeln!(markdown, "characters: {}", record.character_count);
+    if let Some(scores) = &record.evaluation {
+        let _ = writeln!(markdown, "importance: {}", scores.importance);
+        let _ = writeln!(markdown, "conciseness: {}", scores.conciseness);
+        let _ = writeln!(markdown, "accuracy: {}", scores.accuracy);
+    }
+    markdown.push_str("tags: [yomitore]\n---\n\n");
+
+    let _ = writeln!(
+        markdown,
+        "# 要約トレーニング {} ({verdict})\n",
+        record.timestamp.format("%Y-%m-%d %H:%M")
+    );
+    let _ = writeln!(markdown, "## 原文\n\n{}\n", record.original_text.trim());
+    let _ = writeln!(markdown, "## 私の要約\n\n{}\n", record.summary.trim());
+    let _ = writeln!(markdown, "## 評価\n\n{}\n", record.evaluation_text.trim());
+    if !tabs.model_answer.is_empty() {
+        let _ = writeln!(markdown, "## 模範解答\n\n{}\n", tabs.model_answer);
+    }
+    if !tabs.key_points.is_empty() {
+        markdown.push_str("## キーポイント\n\n");
+        for key_point in &tabs.key_points {
+            let mark = if key_point.covered { 'x' } else { ' ' };
+            let _ = writeln!(markdown, "- [{mark}] {}", key_point.point);
+        }
+        markdown.push('\n');
+    }
+    if !tabs.conversation.is_empty() {
+        let _ = writeln!(
+            markdown,
+            "## 評価への異議\n\n{}",
+            tabs.text(ResultTab::Conversation)
+        );
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluation::{EvaluationExtras, KeyPoint};
+    use chrono::{Local, TimeZone};
+
+    fn record() -> SessionRecord {
+        SessionRecord {
+            timestamp: Local
+                .with_ymd_and_hms(2026, 3, 4, 21, 5, 9)
+                .single()
+                .unwrap_or_default(),
+            character_count: 400,
+            original_text: "原文です。\n".to_string(),
+            summary: "要約です。".to_string(),
+            evaluation_text: "総合評価: 合格\n".to_string(),
+            passed: true,
+            evaluation: None,
+            started_at: None,
+            retry_of: None,
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn markdown_contains_front_matter_and_checklist() {
+        let mut tabs = ResultTabs::default();
+        tabs.set_extras(EvaluationExtras {
+            model_answer: "模範です。".to_string(),
+            key_points: vec![
+                KeyPoint {
+                    point: "要点A".to_string(),
+                    covered: true,
+                },
+                KeyPoint {
+                    point: "要点B".to_string(),
+                    covered: false,
+                },
+            ],
+            keywords: Vec::new(),
+            length_met: None,
+        });
+        let markdown = format_markdown(&record(), &tabs);
+        assert!(markdown.starts_with("---\ndate: 2026-03-04T21:05:09"));
+        assert!(markdown.contains("result: 合格\ncharacters: 400\ntags: [yomitore]\n---\n"));
+        assert!(markdown.contains("# 要約トレーニング 2026-03-04 21:05 (合格)"));
+        assert!(markdown.contains("## 原文\n\n原文です。\n\n## 私の要約\n\n要約です。\n"));
+        assert!(markdown.contains("## 模範解答\n\n模範です。\n"));
+        assert!(markdown.contains("- [x] 要点A\n- [ ] 要点B\n"));
+    }
+
+    #[test]
+    fn export_session_writes_to_configured_dir() {
+        let dir = std::env::temp_dir().join(format!("yomitore-journal-{}", std::process::id()));
+        let config = JournalConfig {
+            dir: Some(dir.to_string_lossy().into_owned()),
+        };
+        let path = export_session(&config, &record(), &ResultTabs::default());
+        assert_eq!(path.ok(), Some(dir.join("yomitore-2026-03-04-210509.md")));
+        let written = fs::read_to_string(dir.join("yomitore-2026-03-04-210509.md"));
+        assert!(written.is_ok_and(|markdown| !markdown.contains("## 模範解答")));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
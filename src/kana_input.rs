@@ -0,0 +1,162 @@
+use crate::romaji;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// 同梱の簡易辞書 (読み<TAB>候補1/候補2/...)
+static KANJI_DICTIONARY: LazyLock<HashMap<&'static str, Vec<&'static str>>> = LazyLock::new(|| {
+    include_str!("../assets/kanji_dict.txt")
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(reading, candidates)| (reading, candidates.split('/').collect()))
+        .collect()
+});
+
+/// 読みに対する変換候補。辞書の候補の後にひらがな・カタカナを並べる
+pub fn candidates_for(reading: &str) -> Vec<String> {
+    let mut candidates: Vec<String> = KANJI_DICTIONARY
+        .get(reading)
+        .map(|words| words.iter().map(|word| (*word).to_string()).collect())
+        .unwrap_or_default();
+    for fallback in [reading.to_string(), romaji::to_katakana(reading)] {
+        if !candidates.contains(&fallback) {
+            candidates.push(fallback);
+        }
+    }
+    candidates
+}
+
+/// IME が使えない環境向けの、要約欄に組み込んだローマ字かな漢字変換
+#[derive(Default)]
+pub struct KanaInput {
+    pub enabled: bool,
+    romaji: String,
+    kana: String,
+    pub candidates: Vec<String>,
+    pub selected: usize,
+}
+
+impl KanaInput {
+    /// 入力方式を切り替える。変換中の文字列があれば確定して返す
+    pub fn toggle(&mut self) -> Option<String> {
+        let committed = self.is_composing().then(|| self.commit());
+        self.enabled = !self.enabled;
+        committed
+    }
+
+    pub fn is_composing(&self) -> bool {
+        !self.romaji.is_empty() || !self.kana.is_empty()
+    }
+
+    pub fn is_selecting(&self) -> bool {
+        !self.candidates.is_empty()
+    }
+
+    /// 未確定の文字列 (変換済みのかなと、綴り途中のローマ字)
+    pub fn preedit(&self) -> String {
+        format!("{}{}", self.kana, self.romaji)
+    }
+
+    /// ローマ字を 1 文字入力する。候補の選択中なら、選択中の候補を確定して返す
+    pub fn push_char(&mut self, c: char) -> Option<String> {
+        let committed = self.is_selecting().then(|| self.commit());
+        self.romaji.push(c);
+        let (kana, rest) = romaji::to_hiragana(&self.romaji);
+        self.kana.push_str(&kana);
+        self.romaji = rest;
+        committed
+    }
+
+    pub fn backspace(&mut self) {
+        if self.is_selecting() {
+            self.cancel_candidates();
+        } else if self.romaji.pop().is_none() {
+            self.kana.pop();
+        }
+    }
+
+    /// 変換候補を表示する。表示中なら次の候補に進む
+    pub fn convert(&mut self) {
+        if self.is_selecting() {
+            self.selected = (self.selected + 1) % self.candidates.len();
+            return;
+        }
+        self.kana = romaji::flush(&self.preedit());
+        self.romaji.clear();
+        self.candidates = candidates_for(&self.kana);
+        self.selected = 0;
+    }
+
+    pub fn previous_candidate(&mut self) {
+        if self.is_selecting() {
+            self.selected = self
+                .selected
+                .checked_sub(1)
+                .unwrap_or(self.candidates.len() - 1);
+        }
+    }
+
+    pub fn cancel_candidates(&mut self) {
+        self.candidates.clear();
+        self.selected = 0;
+    }
+
+    /// 変換中の文字列を破棄する
+    pub fn discard(&mut self) {
+        self.romaji.clear();
+        self.kana.clear();
+        self.cancel_candidates();
+    }
+
+    /// 選択中の候補 (候補がなければかな) を確定して返す
+    pub fn commit(&mut self) -> String {
+        let text = self
+            .candidates
+            .get(self.selected)
+            .cloned()
+            .unwrap_or_else(|| romaji::flush(&self.preedit()));
+        self.discard();
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_reading_with_bundled_dictionary() {
+        let mut input = KanaInput {
+            enabled: true,
+            ..KanaInput::default()
+        };
+        for c in "youyaku".chars() {
+            assert_eq!(input.push_char(c), None);
+        }
+        assert_eq!(input.preedit(), "ようやく");
+
+        input.convert();
+        assert_eq!(
+            input.candidates,
+            ["要約", "漸く", "ようやく", "ヨウヤク"].map(String::from)
+        );
+        input.previous_candidate();
+        assert_eq!(input.selected, 3);
+        input.convert();
+
+        assert_eq!(input.push_char('w'), Some("要約".to_string()));
+        assert_eq!(input.preedit(), "w");
+        input.backspace();
+        assert!(!input.is_composing());
+    }
+
+    #[test]
+    fn commits_kana_when_reading_is_unknown() {
+        let mut input = KanaInput::default();
+        for c in "desun".chars() {
+            input.push_char(c);
+        }
+        assert_eq!(input.commit(), "ですん");
+        assert!(!input.is_composing());
+    }
+}
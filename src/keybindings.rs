@@ -0,0 +1,375 @@
+use crate::config::KeysConfig;
+use crate::error::AppError;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// `ctrl+s` や `f2` のように書いた 1 つのキー。Shift は大文字で書く
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    const fn plain(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    const fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    /// `ctrl+` / `alt+` に続けて、1 文字か `enter` `tab` `space` `esc` `up` `down` `pageup` `pagedown` `f1`〜`f12` を書く
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text.trim();
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if lower.starts_with("ctrl+") {
+                modifiers |= KeyModifiers::CONTROL;
+            } else if lower.starts_with("alt+") {
+                modifiers |= KeyModifiers::ALT;
+            } else {
+                break;
+            }
+            rest = rest.split_once('+').map_or("", |(_, key)| key);
+        }
+        let code = match rest.to_ascii_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "space" => KeyCode::Char(' '),
+            "esc" => KeyCode::Esc,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            name => match (
+                name.strip_prefix('f'),
+                rest.chars().collect::<Vec<_>>().as_slice(),
+            ) {
+                (_, [c]) if !modifiers.is_empty() => KeyCode::Char(c.to_ascii_lowercase()),
+                (_, [c]) => KeyCode::Char(*c),
+                (Some(number), _) => {
+                    KeyCode::F(number.parse().ok().filter(|n| (1..=12).contains(n))?)
+                }
+                _ => return None,
+            },
+        };
+        Some(Self { code, modifiers })
+    }
+
+    /// Shift の有無は文字の大小で区別するので、Ctrl と Alt だけを比べる
+    pub fn matches(self, key: KeyEvent) -> bool {
+        key.code == self.code
+            && key
+                .modifiers
+                .intersection(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                == self.modifiers
+    }
+
+    /// 画面の案内に出す表記 (`Ctrl+S` など)
+    pub fn label(self) -> String {
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        match self.code {
+            KeyCode::Char(' ') => label.push_str("Space"),
+            KeyCode::Char(c) if self.modifiers.is_empty() => label.push(c),
+            KeyCode::Char(c) => label.push(c.to_ascii_uppercase()),
+            code => label.push_str(&code.to_string()),
+        }
+        label
+    }
+}
+
+/// 画面の名前と、その画面で固定の操作に使う文字キー
+type FixedKeys = (&'static str, &'static str);
+
+const MENU_KEYS: FixedKeys = ("メニュー", "aP2ltcesuovmdgxfpbHCjk");
+const TRAINING_KEYS: FixedKeys = ("学習画面", "epsSavzPgwfLdHC<>");
+const RESULT_KEYS: FixedKeys = ("評価結果", "R1234hliyatcx");
+const VIEWER_KEYS: FixedKeys = ("レポートやヘルプ", "nbNtT/[]12345");
+
+/// `[keys]` セクションで変えられる操作のキー。矢印キーや Enter などの補助的なキーは固定のまま使える
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBindings {
+    pub submit: KeyBinding,
+    pub edit: KeyBinding,
+    pub next: KeyBinding,
+    pub report: KeyBinding,
+    pub help: KeyBinding,
+    pub quit: KeyBinding,
+    pub scroll_up: KeyBinding,
+    pub scroll_down: KeyBinding,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            submit: KeyBinding::ctrl('s'),
+            edit: KeyBinding::plain('i'),
+            next: KeyBinding::plain('n'),
+            report: KeyBinding::plain('r'),
+            help: KeyBinding::plain('h'),
+            quit: KeyBinding::plain('q'),
+            scroll_up: KeyBinding::plain('k'),
+            scroll_down: KeyBinding::plain('j'),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// 書いていない操作は既定のキーにする。読めないキーや、2 つの操作に同じキーを割り当てた場合はエラー
+    pub fn from_config(config: &KeysConfig) -> Result<Self, AppError> {
+        let defaults = Self::default();
+        let resolve = |name: &str, value: Option<&String>, default: KeyBinding| {
+            value.map_or(Ok(default), |text| {
+                KeyBinding::parse(text).ok_or_else(|| {
+                    AppError::InvalidConfig(format!("[keys] {name} のキー `{text}` が不正です。"))
+                })
+            })
+        };
+        let bindings = Self {
+            submit: resolve("submit", config.submit.as_ref(), defaults.submit)?,
+            edit: resolve("edit", config.edit.as_ref(), defaults.edit)?,
+            next: resolve("next", config.next.as_ref(), defaults.next)?,
+            report: resolve("report", config.report.as_ref(), defaults.report)?,
+            help: resolve("help", config.help.as_ref(), defaults.help)?,
+            quit: resolve("quit", config.quit.as_ref(), defaults.quit)?,
+            scroll_up: resolve("scroll_up", config.scroll_up.as_ref(), defaults.scroll_up)?,
+            scroll_down: resolve(
+                "scroll_down",
+                config.scroll_down.as_ref(),
+                defaults.scroll_down,
+            )?,
+        };
+
+        let named = bindings.named();
+        for (index, (name, binding)) in named.iter().enumerate() {
+            if let Some((other, _)) = named
+                .iter()
+                .skip(index + 1)
+                .find(|(_, other)| other == binding)
+            {
+                return Err(AppError::InvalidConfig(format!(
+                    "[keys] {name} と {other} に同じキー `{}` が割り当てられています。",
+                    binding.label()
+                )));
+            }
+        }
+        bindings.check_fixed_keys()?;
+        Ok(bindings)
+    }
+
+    /// 操作を受け付ける画面で固定の操作に使っているキーを割り当てていないか確かめる。
+    /// 評価結果の表示中は `h` / `l` のタブの移動が help より優先するので、help は評価結果と重なってよい
+    fn check_fixed_keys(&self) -> Result<(), AppError> {
+        // 修飾キーのない文字や Enter は入力に、Ctrl+J と Ctrl+P はかな入力と集中モードに使う
+        let typed = self.submit.modifiers.is_empty()
+            && matches!(
+                self.submit.code,
+                KeyCode::Char(_) | KeyCode::Enter | KeyCode::Tab | KeyCode::Esc
+            );
+        if typed || [KeyBinding::ctrl('j'), KeyBinding::ctrl('p')].contains(&self.submit) {
+            return Err(AppError::InvalidConfig(format!(
+                "[keys] submit のキー `{}` は要約の入力に使われているため割り当てられません。",
+                self.submit.label()
+            )));
+        }
+        let checks: [(&str, KeyBinding, &[FixedKeys]); 7] = [
+            ("edit", self.edit, &[TRAINING_KEYS]),
+            ("next", self.next, &[TRAINING_KEYS, RESULT_KEYS]),
+            (
+                "report",
+                self.report,
+                &[MENU_KEYS, TRAINING_KEYS, RESULT_KEYS],
+            ),
+            ("help", self.help, &[MENU_KEYS, TRAINING_KEYS]),
+            (
+                "quit",
+                self.quit,
+                &[MENU_KEYS, TRAINING_KEYS, RESULT_KEYS, VIEWER_KEYS],
+            ),
+            ("scroll_up", self.scroll_up, &[TRAINING_KEYS, VIEWER_KEYS]),
+            (
+                "scroll_down",
+                self.scroll_down,
+                &[TRAINING_KEYS, VIEWER_KEYS],
+            ),
+        ];
+        for (name, binding, screens) in checks {
+            if let Some((screen, _)) = screens
+                .iter()
+                .find(|(_, keys)| keys.chars().any(|c| binding == KeyBinding::plain(c)))
+            {
+                return Err(AppError::InvalidConfig(format!(
+                    "[keys] {name} のキー `{}` は{screen}の操作に使われているため割り当てられません。",
+                    binding.label()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn named(&self) -> [(&'static str, KeyBinding); 8] {
+        [
+            ("submit", self.submit),
+            ("edit", self.edit),
+            ("next", self.next),
+            ("report", self.report),
+            ("help", self.help),
+            ("quit", self.quit),
+            ("scroll_up", self.scroll_up),
+            ("scroll_down", self.scroll_down),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_parsed_and_matched_with_modifiers() {
+        let submit = KeyBinding::parse("Ctrl+Enter");
+        assert!(submit.is_some_and(|binding| binding.matches(KeyEvent::new(
+            KeyCode::Enter,
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT
+        ))));
+        assert!(!submit.is_some_and(|binding| {
+            binding.matches(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+        }));
+        assert_eq!(
+            KeyBinding::parse("ctrl+S")
+                .map(KeyBinding::label)
+                .as_deref(),
+            Some("Ctrl+S")
+        );
+        assert_eq!(
+            KeyBinding::parse("R").map(KeyBinding::label).as_deref(),
+            Some("R")
+        );
+        assert_eq!(
+            KeyBinding::parse("f5").map(KeyBinding::label).as_deref(),
+            Some("F5")
+        );
+        assert_eq!(KeyBinding::parse("f13"), None);
+        assert_eq!(KeyBinding::parse("ctrl+"), None);
+    }
+
+    #[test]
+    fn config_overrides_defaults_and_rejects_conflicts() {
+        let config = KeysConfig {
+            submit: Some("ctrl+d".to_string()),
+            ..KeysConfig::default()
+        };
+        let bindings = KeyBindings::from_config(&config).unwrap_or_default();
+        assert_eq!(bindings.submit.label(), "Ctrl+D");
+        assert_eq!(bindings.quit, KeyBindings::default().quit);
+
+        let conflict = KeysConfig {
+            help: Some("q".to_string()),
+            ..KeysConfig::default()
+        };
+        assert!(
+            KeyBindings::from_config(&conflict)
+                .is_err_and(|e| e.to_string().contains("help と quit"))
+        );
+        let invalid = KeysConfig {
+            next: Some("ctrl+alt".to_string()),
+            ..KeysConfig::default()
+        };
+        assert!(KeyBindings::from_config(&invalid).is_err());
+    }
+
+    #[test]
+    fn global_actions_cannot_take_fixed_menu_keys() {
+        for key in ["a", "P", "v", "H"] {
+            let config = KeysConfig {
+                report: Some(key.to_string()),
+                ..KeysConfig::default()
+            };
+            assert!(
+                KeyBindings::from_config(&config)
+                    .is_err_and(|e| e.to_string().contains("report のキー"))
+            );
+        }
+        let quit = KeysConfig {
+            quit: Some("x".to_string()),
+            ..KeysConfig::default()
+        };
+        assert!(KeyBindings::from_config(&quit).is_err());
+        let help_in_results = KeysConfig {
+            help: Some("y".to_string()),
+            ..KeysConfig::default()
+        };
+        assert!(KeyBindings::from_config(&help_in_results).is_ok());
+        let modified = KeysConfig {
+            help: Some("ctrl+a".to_string()),
+            ..KeysConfig::default()
+        };
+        assert!(KeyBindings::from_config(&modified).is_ok());
+    }
+
+    #[test]
+    fn every_action_is_checked_against_fixed_keys() {
+        let rejected = |config: KeysConfig, name: &str| {
+            KeyBindings::from_config(&config)
+                .is_err_and(|e| e.to_string().contains(&format!("{name} のキー")))
+        };
+        for submit in ["a", "enter", "ctrl+j"] {
+            assert!(rejected(
+                KeysConfig {
+                    submit: Some(submit.to_string()),
+                    ..KeysConfig::default()
+                },
+                "submit"
+            ));
+        }
+        assert!(rejected(
+            KeysConfig {
+                edit: Some("e".to_string()),
+                ..KeysConfig::default()
+            },
+            "edit"
+        ));
+        assert!(rejected(
+            KeysConfig {
+                next: Some("y".to_string()),
+                ..KeysConfig::default()
+            },
+            "next"
+        ));
+        assert!(rejected(
+            KeysConfig {
+                scroll_up: Some("S".to_string()),
+                ..KeysConfig::default()
+            },
+            "scroll_up"
+        ));
+        assert!(rejected(
+            KeysConfig {
+                scroll_down: Some("n".to_string()),
+                next: Some("ctrl+n".to_string()),
+                ..KeysConfig::default()
+            },
+            "scroll_down"
+        ));
+        // メニューでは scroll を使わないので、メニューの j/k と重なってもよい
+        let swapped = KeysConfig {
+            scroll_up: Some("j".to_string()),
+            scroll_down: Some("k".to_string()),
+            ..KeysConfig::default()
+        };
+        assert!(KeyBindings::from_config(&swapped).is_ok());
+    }
+}
@@ -0,0 +1,152 @@
+use crate::error::AppError;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// キーチェーンの項目を見分けるサービス名。アカウント名にはプロファイルの名前を使う
+const SERVICE: &str = "yomitore";
+
+/// OS のキーチェーンを操作するコマンド。Linux などは Secret Service の `secret-tool`、
+/// macOS は `security` を使う。ほかの OS ではキーチェーンを使わず設定ファイルに保存する
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Backend {
+    SecretTool,
+    MacSecurity,
+}
+
+impl Backend {
+    fn current() -> Option<Self> {
+        if cfg!(target_os = "macos") {
+            Some(Self::MacSecurity)
+        } else if cfg!(unix) {
+            Some(Self::SecretTool)
+        } else {
+            None
+        }
+    }
+
+    fn lookup_command(self, account: &str) -> (&'static str, Vec<String>) {
+        match self {
+            Self::SecretTool => (
+                "secret-tool",
+                args(&["lookup", "service", SERVICE, "account", account]),
+            ),
+            Self::MacSecurity => (
+                "security",
+                args(&["find-generic-password", "-s", SERVICE, "-a", account, "-w"]),
+            ),
+        }
+    }
+
+    /// 保存するコマンドと、標準入力に渡す内容。キーは `ps` で見えないよう引数には渡さない。
+    /// `security` は `-i` で標準入力からコマンドを読ませ、そのコマンドの中にキーを書く
+    fn store_command(self, account: &str, key: &str) -> (&'static str, Vec<String>, String) {
+        match self {
+            Self::SecretTool => (
+                "secret-tool",
+                args(&[
+                    "store",
+                    &format!("--label=yomitore API key ({account})"),
+                    "service",
+                    SERVICE,
+                    "account",
+                    account,
+                ]),
+                key.to_string(),
+            ),
+            Self::MacSecurity => (
+                "security",
+                args(&["-i"]),
+                format!(
+                    "add-generic-password -U -s {} -a {} -w {}\n",
+                    quote(SERVICE),
+                    quote(account),
+                    quote(key)
+                ),
+            ),
+        }
+    }
+}
+
+/// `security -i` が 1 つの引数として読めるよう、二重引用符で囲んでエスケープする
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(ToString::to_string).collect()
+}
+
+fn unavailable(detail: impl std::fmt::Display) -> AppError {
+    AppError::Keyring(detail.to_string())
+}
+
+/// `account` の API キーを読む。項目がなければ `None`
+pub fn read(account: &str) -> Result<Option<String>, AppError> {
+    let backend = Backend::current().ok_or_else(|| unavailable("この OS には対応していません"))?;
+    let (program, args) = backend.lookup_command(account);
+    let output = Command::new(program)
+        .args(args)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| unavailable(format!("{program}: {e}")))?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let key = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((!key.is_empty()).then_some(key))
+}
+
+/// `account` の API キーを保存する。同じ項目があれば置き換える
+pub fn store(account: &str, key: &str) -> Result<(), AppError> {
+    let backend = Backend::current().ok_or_else(|| unavailable("この OS には対応していません"))?;
+    let (program, args, input) = backend.store_command(account, key);
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| unavailable(format!("{program}: {e}")))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    if child.wait()?.success() {
+        Ok(())
+    } else {
+        Err(unavailable(format!("{program} が保存に失敗しました")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_passed_on_stdin_and_never_as_arguments() {
+        let (program, args, input) = Backend::SecretTool.store_command("work", "sk-secret");
+        assert_eq!(program, "secret-tool");
+        assert!(args.iter().all(|arg| !arg.contains("sk-secret")));
+        assert_eq!(input, "sk-secret");
+
+        let (program, args, input) = Backend::MacSecurity.store_command("work", "sk-\"secret");
+        assert_eq!((program, args), ("security", vec!["-i".to_string()]));
+        assert_eq!(
+            input,
+            "add-generic-password -U -s \"yomitore\" -a \"work\" -w \"sk-\\\"secret\"\n"
+        );
+
+        let (program, args) = Backend::MacSecurity.lookup_command("default");
+        assert_eq!(program, "security");
+        assert_eq!(
+            args,
+            [
+                "find-generic-password",
+                "-s",
+                "yomitore",
+                "-a",
+                "default",
+                "-w"
+            ]
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use rat_text::text_input::TextInputState;
+
+/// 予想するキーワードの数
+pub const KEYWORD_COUNT: usize = 3;
+const SEPARATORS: [char; 6] = [',', '、', '，', '・', ' ', '　'];
+
+/// 本文を読む前に冒頭だけを見て、キーワードを予想する練習の状態
+pub struct KeywordPrediction {
+    /// 予想の手がかりに見せる冒頭の段落
+    pub preview: String,
+    pub input: TextInputState,
+    /// 確定した予想。入力中は空
+    pub predicted: Vec<String>,
+}
+
+/// 予想したキーワードのうち、評価者が挙げたキーワードと一致した数
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeywordScore {
+    pub hits: u8,
+    pub total: u8,
+    pub predicted: Vec<String>,
+    pub extracted: Vec<String>,
+}
+
+impl KeywordPrediction {
+    pub fn new(preview: String) -> Self {
+        let input = TextInputState::new();
+        input.focus.set(true);
+        Self {
+            preview,
+            input,
+            predicted: Vec::new(),
+        }
+    }
+
+    pub fn is_predicting(&self) -> bool {
+        self.predicted.is_empty()
+    }
+
+    /// 入力欄の予想を確定する。キーワードが 1 つもなければ確定しない
+    pub fn confirm(&mut self) -> bool {
+        self.predicted = parse_keywords(self.input.text());
+        !self.predicted.is_empty()
+    }
+
+    /// 評価者が挙げたキーワードと照らし合わせる。どちらかが空なら採点しない
+    pub fn score(&self, extracted: &[String]) -> Option<KeywordScore> {
+        if self.predicted.is_empty() || extracted.is_empty() {
+            return None;
+        }
+        let hits = self
+            .predicted
+            .iter()
+            .filter(|keyword| matches_any(keyword, extracted))
+            .count();
+        Some(KeywordScore {
+            hits: u8::try_from(hits).unwrap_or(u8::MAX),
+            total: u8::try_from(self.predicted.len()).unwrap_or(u8::MAX),
+            predicted: self.predicted.clone(),
+            extracted: extracted.to_vec(),
+        })
+    }
+}
+
+impl KeywordScore {
+    /// 評価結果の末尾に付ける、予想と評価者のキーワードの比較
+    pub fn format(&self) -> String {
+        format!(
+            "\n\n# キーワード予想\n- 予想: {}\n- 評価者: {}\n- 的中: {} / {}\n",
+            self.predicted.join("、"),
+            self.extracted.join("、"),
+            self.hits,
+            self.total
+        )
+    }
+}
+
+/// 読点・カンマ・空白で区切り、重複を除いて先頭から `KEYWORD_COUNT` 個まで取り出す
+pub fn parse_keywords(input: &str) -> Vec<String> {
+    let mut keywords: Vec<String> = Vec::new();
+    for keyword in input.split(SEPARATORS).map(str::trim) {
+        if !keyword.is_empty() && !keywords.iter().any(|k| k == keyword) {
+            keywords.push(keyword.to_string());
+        }
+    }
+    keywords.truncate(KEYWORD_COUNT);
+    keywords
+}
+
+/// 「少子化」と「少子化対策」のように、どちらかがもう一方を含めば一致とみなす
+fn matches_any(keyword: &str, extracted: &[String]) -> bool {
+    extracted
+        .iter()
+        .any(|other| other.contains(keyword) || keyword.contains(other.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_are_split_deduplicated_and_capped() {
+        assert_eq!(
+            parse_keywords("少子化、 財政,少子化　地方・人口"),
+            vec!["少子化", "財政", "地方"]
+        );
+        assert!(parse_keywords(" 、 ").is_empty());
+    }
+
+    #[test]
+    fn partial_matches_count_as_hits() {
+        let mut prediction = KeywordPrediction::new("冒頭".to_string());
+        assert!(prediction.is_predicting());
+        assert!(!prediction.confirm());
+        prediction.input.set_text("少子化 税金 人口減少");
+        assert!(prediction.confirm());
+
+        let extracted = [
+            "少子化対策".to_string(),
+            "人口".to_string(),
+            "地方".to_string(),
+        ];
+        let score = prediction.score(&extracted);
+        assert_eq!(score.as_ref().map(|s| (s.hits, s.total)), Some((2, 3)));
+        assert_eq!(prediction.score(&[]), None);
+        assert!(
+            score
+                .map(|s| s.format())
+                .is_some_and(|text| text.contains("- 的中: 2 / 3"))
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::models::Difficulty;
+
+/// 昇級試験を受けられるようになるまでに必要な、現在のレベルでの合格回数
+pub const PROMOTION_INTERVAL: usize = 5;
+
+pub struct ReadingLevel {
+    pub name: &'static str,
+    pub character_count: u16,
+    pub difficulty: Difficulty,
+}
+
+/// 文字数と難易度を組み合わせた段位。昇級試験に合格すると次に進む
+pub const READING_LEVELS: [ReadingLevel; 7] = [
+    ReadingLevel {
+        name: "入門",
+        character_count: 400,
+        difficulty: Difficulty::Easy,
+    },
+    ReadingLevel {
+        name: "初級",
+        character_count: 720,
+        difficulty: Difficulty::Easy,
+    },
+    ReadingLevel {
+        name: "中級",
+        character_count: 720,
+        difficulty: Difficulty::Standard,
+    },
+    ReadingLevel {
+        name: "中上級",
+        character_count: 1440,
+        difficulty: Difficulty::Standard,
+    },
+    ReadingLevel {
+        name: "上級",
+        character_count: 1440,
+        difficulty: Difficulty::Hard,
+    },
+    ReadingLevel {
+        name: "超上級",
+        character_count: 2880,
+        difficulty: Difficulty::Hard,
+    },
+    ReadingLevel {
+        name: "達人",
+        character_count: 2880,
+        difficulty: Difficulty::Expert,
+    },
+];
+
+/// レベル挑戦中の1問が練習か昇級試験か
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum LevelRound {
+    Practice,
+    Promotion,
+}
+
+pub fn level_at(index: usize) -> &'static ReadingLevel {
+    READING_LEVELS
+        .get(index)
+        .or(READING_LEVELS.last())
+        .unwrap_or(&READING_LEVELS[0])
+}
+
+pub fn is_top_level(index: usize) -> bool {
+    index + 1 >= READING_LEVELS.len()
+}
+
+pub fn describe_level(index: usize) -> String {
+    let level = level_at(index);
+    format!(
+        "Lv.{} {} ({}字・{})",
+        index + 1,
+        level.name,
+        level.character_count,
+        level.difficulty.label()
+    )
+}
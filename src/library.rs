@@ -0,0 +1,311 @@
+use crate::app::MENU_OPTIONS;
+use crate::models::Difficulty;
+use crate::quiz::{Quiz, QuizQuestion};
+use crate::storage;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// これより短い段落のまとまりは要約の題材にならないので取り込まない
+const MIN_PASSAGE_CHARS: usize = 200;
+/// 文字の 3-gram の重なりがこの割合以上なら、ほぼ同じ文章とみなす
+const DUPLICATE_SIMILARITY: f64 = 0.8;
+const SHINGLE_SIZE: usize = 3;
+
+/// ライブラリに取り込んだ文章。`bucket` はメニューの文字数のうち最も近いもの
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LibraryText {
+    pub text: String,
+    pub character_count: u16,
+    pub bucket: u16,
+    pub added_at: DateTime<Local>,
+    #[serde(default)]
+    pub used_count: u32,
+    /// 4 択問題モードでこの文章から作った問題。API を使わずに解き直せる
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiz: Option<LibraryQuiz>,
+}
+
+/// 文章に付けて残す 4 択問題と、作ったときの難易度
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LibraryQuiz {
+    pub difficulty: Difficulty,
+    pub questions: Vec<QuizQuestion>,
+}
+
+/// 読み込んだ文章をためておき、メニューで選んだ文字数に合わせて出題する
+#[derive(Serialize, Deserialize, Default)]
+pub struct Library {
+    pub texts: Vec<LibraryText>,
+}
+
+/// 取り込んだ件数と、重複として取り込まなかった件数
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub duplicates: usize,
+}
+
+impl Library {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(storage::load_json(storage::global()?, storage::LIBRARY_DOCUMENT)?.unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        storage::save_json(storage::global()?, storage::LIBRARY_DOCUMENT, self)?;
+        Ok(())
+    }
+
+    /// 空白を整え、長い文章は段落の区切りで最長のメニュー文字数以下に分けて取り込む。
+    /// すでにある文章とほぼ同じものは取り込まない
+    pub fn import(&mut self, text: &str, now: DateTime<Local>) -> ImportSummary {
+        let mut summary = ImportSummary::default();
+        for passage in split_passages(&normalize_whitespace(text)) {
+            let passage_shingles = shingles(&passage);
+            if self.texts.iter().any(|existing| {
+                similarity(&passage_shingles, &shingles(&existing.text)) >= DUPLICATE_SIMILARITY
+            }) {
+                summary.duplicates += 1;
+                continue;
+            }
+            let character_count = u16::try_from(passage.chars().count()).unwrap_or(u16::MAX);
+            self.texts.push(LibraryText {
+                text: passage,
+                character_count,
+                bucket: bucket_for(character_count),
+                added_at: now,
+                used_count: 0,
+                quiz: None,
+            });
+            summary.added += 1;
+        }
+        summary
+    }
+
+    /// `bucket` の文章のうち、出題した回数が最も少なく古いものを選んで出題済みにする
+    pub fn take_for(&mut self, bucket: u16) -> Option<String> {
+        let item = self
+            .texts
+            .iter_mut()
+            .filter(|item| item.bucket == bucket)
+            .min_by_key(|item| (item.used_count, item.added_at))?;
+        item.used_count += 1;
+        Some(item.text.clone())
+    }
+
+    /// 生成して出題した 4 択問題を本文ごと残す。ほぼ同じ文章があれば、その文章の問題を差し替える
+    pub fn add_quiz(&mut self, quiz: &Quiz, difficulty: Difficulty, now: DateTime<Local>) {
+        let passage = normalize_whitespace(&quiz.passage);
+        let library_quiz = LibraryQuiz {
+            difficulty,
+            questions: quiz.questions.clone(),
+        };
+        let passage_shingles = shingles(&passage);
+        if let Some(existing) = self.texts.iter_mut().find(|existing| {
+            similarity(&passage_shingles, &shingles(&existing.text)) >= DUPLICATE_SIMILARITY
+        }) {
+            existing.used_count += 1;
+            existing.quiz = Some(library_quiz);
+            return;
+        }
+        let character_count = u16::try_from(passage.chars().count()).unwrap_or(u16::MAX);
+        self.texts.push(LibraryText {
+            text: passage,
+            character_count,
+            bucket: bucket_for(character_count),
+            added_at: now,
+            used_count: 1,
+            quiz: Some(library_quiz),
+        });
+    }
+
+    pub fn has_quiz_for(&self, bucket: u16, difficulty: Difficulty) -> bool {
+        self.texts
+            .iter()
+            .any(|item| Self::has_quiz(item, bucket, difficulty))
+    }
+
+    /// `bucket` と `difficulty` の問題が付いた文章のうち、出題した回数が最も少なく古いものを選んで出題済みにする
+    pub fn take_quiz_for(&mut self, bucket: u16, difficulty: Difficulty) -> Option<Quiz> {
+        let item = self
+            .texts
+            .iter_mut()
+            .filter(|item| Self::has_quiz(item, bucket, difficulty))
+            .min_by_key(|item| (item.used_count, item.added_at))?;
+        item.used_count += 1;
+        Some(Quiz {
+            passage: item.text.clone(),
+            questions: item.quiz.as_ref()?.questions.clone(),
+        })
+    }
+
+    fn has_quiz(item: &LibraryText, bucket: u16, difficulty: Difficulty) -> bool {
+        item.bucket == bucket
+            && item
+                .quiz
+                .as_ref()
+                .is_some_and(|quiz| quiz.difficulty == difficulty)
+    }
+
+    pub fn count_for(&self, bucket: u16) -> usize {
+        self.texts
+            .iter()
+            .filter(|item| item.bucket == bucket)
+            .count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+}
+
+/// 行内の空白 (全角を含む) の連続を 1 つにまとめ、空行の連続を段落の区切り 1 つにする
+pub fn normalize_whitespace(text: &str) -> String {
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join("\n"));
+                current.clear();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join("\n"));
+    }
+    paragraphs.join("\n\n")
+}
+
+/// 段落を順に詰め、最長のメニュー文字数を超える手前で区切る。短すぎるまとまりは捨てる
+fn split_passages(text: &str) -> Vec<String> {
+    let max_chars = usize::from(MENU_OPTIONS.iter().copied().max().unwrap_or(u16::MAX));
+    let mut passages = Vec::new();
+    let mut current = String::new();
+    for paragraph in text.split("\n\n") {
+        let length = current.chars().count() + paragraph.chars().count();
+        if !current.is_empty() && length > max_chars {
+            passages.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(paragraph);
+    }
+    passages.push(current);
+    passages.retain(|passage| passage.chars().count() >= MIN_PASSAGE_CHARS);
+    passages
+}
+
+/// メニューの文字数のうち、`character_count` に最も近いもの
+pub fn bucket_for(character_count: u16) -> u16 {
+    MENU_OPTIONS
+        .iter()
+        .copied()
+        .min_by_key(|&option| option.abs_diff(character_count))
+        .unwrap_or(character_count)
+}
+
+/// 空白を除いた文字の 3-gram の集合
+fn shingles(text: &str) -> HashSet<[char; SHINGLE_SIZE]> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    chars
+        .windows(SHINGLE_SIZE)
+        .filter_map(|window| <[char; SHINGLE_SIZE]>::try_from(window).ok())
+        .collect()
+}
+
+/// 2 つの集合の Jaccard 係数
+fn similarity(a: &HashSet<[char; SHINGLE_SIZE]>, b: &HashSet<[char; SHINGLE_SIZE]>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    let shared = u32::try_from(a.intersection(b).count()).unwrap_or(u32::MAX);
+    f64::from(shared) / f64::from(u32::try_from(union).unwrap_or(u32::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passage(seed: &str, chars: usize) -> String {
+        seed.chars().cycle().take(chars).collect()
+    }
+
+    #[test]
+    fn near_duplicates_are_skipped_and_texts_are_bucketed() {
+        let mut library = Library::default();
+        let text = passage(
+            "吾輩は猫である。名前はまだ無い。どこで生れたかとんと見当がつかぬ。",
+            700,
+        );
+        assert_eq!(
+            library.import(&text, Local::now()),
+            ImportSummary {
+                added: 1,
+                duplicates: 0
+            }
+        );
+        // 空白の違いや数文字の違いは同じ文章とみなす
+        let spaced = format!("  {}　\n\n\n{}", &text, "追記");
+        assert_eq!(library.import(&spaced, Local::now()).duplicates, 1);
+
+        let other = passage(
+            "春はあけぼの。やうやう白くなりゆく山ぎは、少しあかりて。",
+            1500,
+        );
+        assert_eq!(library.import(&other, Local::now()).added, 1);
+        assert_eq!(library.count_for(720), 1);
+        assert_eq!(library.count_for(1440), 1);
+        assert!(library.take_for(400).is_none());
+        assert_eq!(library.take_for(720).map(|t| t.chars().count()), Some(700));
+        assert_eq!(library.texts.first().map(|t| t.used_count), Some(1));
+    }
+
+    #[test]
+    fn long_texts_are_split_at_paragraphs() {
+        let paragraphs: Vec<String> = (0..4)
+            .map(|i| passage(&format!("第{i}段落の文章です。"), 1000))
+            .collect();
+        let passages = split_passages(&normalize_whitespace(&paragraphs.join("\n \n")));
+        let lengths: Vec<usize> = passages.iter().map(|p| p.chars().count()).collect();
+        assert_eq!(lengths, vec![2002, 2002]);
+        assert_eq!(normalize_whitespace("a  b\t c\n\n\n d "), "a b c\n\nd");
+    }
+
+    #[test]
+    fn generated_quizzes_are_kept_with_their_passage() -> Result<(), String> {
+        let mut library = Library::default();
+        let quiz = Quiz {
+            passage: passage("川の水は海へ流れ、雲になって山に雨を降らせる。", 400),
+            questions: vec![QuizQuestion {
+                question: "水はどこへ流れるか".to_string(),
+                choices: ["海", "山", "空", "森"].map(String::from).to_vec(),
+                answer: 0,
+            }],
+        };
+        library.add_quiz(&quiz, Difficulty::default(), Local::now());
+        library.add_quiz(&quiz, Difficulty::default(), Local::now());
+        if library.len() != 1 {
+            return Err(format!("expected one text, got {}", library.len()));
+        }
+        if library.has_quiz_for(720, Difficulty::default()) {
+            return Err("quiz should only be served for its own bucket".to_string());
+        }
+
+        let served = library
+            .take_quiz_for(400, Difficulty::default())
+            .ok_or("stored quiz should be served")?;
+        if served != quiz {
+            return Err(format!("unexpected quiz: {served:?}"));
+        }
+        if library.texts.first().map(|t| t.used_count) != Some(3) {
+            return Err("serving a quiz should count as a use".to_string());
+        }
+        Ok(())
+    }
+}
@@ -1,133 +1,933 @@
+mod abbreviations;
 mod api_client;
+mod api_key_setup;
 mod app;
+mod appeal;
+mod audit;
+mod challenge;
+mod cli;
+mod clipboard;
+mod coach;
 mod config;
+mod daily_challenge;
+mod diff;
+mod draft;
 mod error;
 mod evaluation;
+mod evaluation_task;
 mod events;
+mod exam;
+mod experiment;
+mod export;
+mod furigana;
+mod glossary;
+mod guided;
 mod help;
+mod history;
+mod hot_seat;
+mod i18n;
+mod journal;
+mod kana_input;
+mod keybindings;
+mod keyring;
+mod keyword_prediction;
+mod levels;
+mod library;
+mod migration;
 mod models;
+mod offline;
+mod pane_layout;
+mod pending;
+mod prefetch;
+mod prompts;
+mod quiz;
+mod ranks;
+mod read_aloud;
+mod records;
+mod redact;
 mod reports;
+mod result_tabs;
+mod retry;
+mod review;
+mod romaji;
+mod rubric;
+mod score_normalization;
+mod scratchpad;
+mod side_request;
+mod speed_reading;
 mod stats;
 mod stats_analysis;
+mod stats_api;
+mod stats_sync;
+mod storage;
+mod strictness;
+mod summary_length;
+mod summary_metrics;
+mod synthesis;
+mod text_cache;
+mod text_difficulty;
+mod text_import;
+mod theme;
 mod tui;
+mod tutorial;
 mod ui;
+mod verbatim;
+mod word_lookup;
 
 use crate::{
     api_client::ApiClient,
-    app::App,
+    app::{App, Popup, STATUS_HOT_SEAT_OFFLINE},
+    audit::AuditLog,
+    challenge::Challenge,
+    cli::Command,
+    config::{Config, LabelLanguage, NetworkConfig, Provider, TimeoutAction},
     error::AppError,
-    evaluation::{OverallEvaluation, format_evaluation_display, parse_evaluation},
+    evaluation::{
+        EvaluationResult, format_evaluation_display, format_evaluation_with_table,
+        parse_evaluation, parse_evaluation_extras,
+    },
+    evaluation_task::{EvaluationJob, EvaluationRunner},
     events::AppAction,
-    models::EvaluationScores,
+    history::SessionRecord,
+    i18n::{tr, trf},
+    keybindings::KeyBindings,
+    models::{EvaluationScores, HeadToHeadResult, ParticipantResult},
+    offline::OfflineCorpus,
+    redact::Redactor,
+    retry::RetryPolicy,
+    side_request::SideRequestRunner,
+    summary_length::LengthTarget,
+    text_cache::CacheKey,
+    text_import::TextSource,
+    word_lookup::Dictionary,
 };
+use chrono::Local;
+use std::time::{Duration, Instant};
+
+/// 文章の生成を待つ間、画面を描き直して Esc を確かめる間隔
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 #[tokio::main]
 async fn main() -> Result<(), AppError> {
+    let command = Command::parse(&std::env::args().skip(1).collect::<Vec<_>>())?;
     let mut app = App::default();
 
-    let api_client = authenticate().await?;
-    app.api_client = Some(api_client);
+    // 書き出し・集計・設定の変更は API を使わないので認証の前に行う
+    if run_offline_command(&app, &command)? {
+        return Ok(());
+    }
+
+    app.keys = KeyBindings::from_config(&app.config.keys)?;
+    app.dictionary = Dictionary::from_config(&app.config.lookup)?;
+    app.load_keyring_keys();
+    app.apply_configured_profile()?;
+    let is_experiment = command == Command::Experiment;
+    let api_client = match connect(&mut app, is_experiment).await? {
+        Some(client) => Some(configure_client(&app, client)?),
+        None => None,
+    };
+
+    if is_experiment && let Some(api_client) = &api_client {
+        let records = history::load_records().unwrap_or_default();
+        let path =
+            experiment::run_from_history(api_client, &app.config.experiment, &records).await?;
+        println!("レポートを保存しました: {}", path.display());
+        return Ok(());
+    }
+
+    app.api_client = api_client;
+
+    let starts_training = start_from_args(&mut app, &command)?;
+    if !starts_training && app.should_start_tutorial() {
+        app.start_tutorial();
+    }
 
     let mut tui = tui::init()?;
+    if starts_training {
+        handle_start_training(&mut app, &mut tui).await?;
+    }
+
+    let mut runner = EvaluationRunner::default();
+    let mut side_requests = SideRequestRunner::default();
 
     while !app.should_quit {
+        tick(&mut app, &mut runner, &mut side_requests);
         tui.draw(|frame| ui::render(&mut app, frame))?;
 
         if let Some(action) = events::handle_events(&mut app)? {
             match action {
                 AppAction::StartTraining => handle_start_training(&mut app, &mut tui).await?,
-                AppAction::Evaluate => handle_evaluate(&mut app, &mut tui).await?,
+                AppAction::Evaluate if app.tutorial => handle_tutorial_submit(&mut app),
+                AppAction::Evaluate if runner.is_running() => {}
+                AppAction::Evaluate if app.blocks_verbatim_submission() => {}
+                AppAction::Evaluate if app.is_guided_paragraph_step() => {
+                    app.submit_guided_paragraph();
+                }
+                AppAction::Evaluate if app.hot_seat.is_some() => {
+                    handle_hot_seat_submit(&mut app, &mut runner);
+                }
+                AppAction::Evaluate => handle_evaluate(&mut app, &mut runner),
                 AppAction::NextTraining => handle_next_training(&mut app, &mut tui).await?,
+                AppAction::DeferTraining => handle_defer_training(&mut app, &mut tui).await?,
+                AppAction::SelfGrade(passed) => handle_self_grade(&mut app, passed),
+                AppAction::QuizAnswered => handle_quiz_answered(&mut app),
+                AppAction::OpenModelSelect => handle_open_model_select(&mut app, &mut tui).await?,
+                AppAction::SubmitApiKey => handle_submit_api_key(&mut app, &mut tui).await?,
+                AppAction::SwitchProfile => handle_switch_profile(&mut app, &mut tui).await?,
+                AppAction::KeepWaiting => {
+                    app.keep_waiting();
+                    runner.extend_deadline(app.config.latency_budget());
+                }
+                AppAction::CancelRequest => {
+                    runner.cancel();
+                    app.cancel_evaluation();
+                }
+                AppAction::CancelSideRequest => {
+                    if let Some(request) = side_requests.cancel() {
+                        app.apply_side_response(request, Err(AppError::Cancelled));
+                    }
+                }
+                AppAction::RequestSelfGrade => {
+                    runner.cancel();
+                    app.begin_self_grade();
+                }
             }
         }
     }
 
+    let stashed = app.stash_unconsumed_text();
+    // 正常に終了したときは、書きかけの原文を未使用テキストに残すので下書きは要らない
+    if app.recovered_draft.is_none() {
+        app.clear_draft();
+    }
     tui::restore()?;
+    if let Err(e) = stashed {
+        eprintln!("未使用テキストの保存に失敗しました: {e}");
+    }
+    // 保存待ちの統計を書き終えてから終了する
+    app.stats_saver.flush().await;
+    if let Some(e) = app.stats_saver.take_failure() {
+        eprintln!("統計の保存に失敗しました: {e}");
+    }
     Ok(())
 }
 
-async fn generate_text_for_training(app: &mut App) {
-    if let Some(client) = &app.api_client {
-        match client.generate_text(&app.generate_text_prompt()).await {
-            Ok(text) => app.apply_generated_text(text),
-            Err(e) => app.apply_generation_error(&e),
+/// 描画の前に、時間の経過で進む状態とバックグラウンドの問い合わせを確かめる
+fn tick(app: &mut App, runner: &mut EvaluationRunner, side_requests: &mut SideRequestRunner) {
+    if runner.is_over_budget() && app.popup.is_none() {
+        app.show_latency_prompt();
+    }
+    app.check_break_reminder();
+    app.tick_speed_reading();
+    app.finish_break_if_elapsed();
+    app.check_stats_saved();
+    app.autosave_draft();
+    prefetch_next_text(app);
+    if app.is_time_up() {
+        handle_time_up(app, runner);
+    }
+    if let Some((job, responses)) = runner.try_finish() {
+        handle_evaluation_finished(app, job, responses);
+    }
+    if let Some((request, response)) = side_requests.try_finish() {
+        app.apply_side_response(request, response);
+    }
+    app.check_audit_log();
+    if !runner.is_running() && !side_requests.is_running() {
+        start_side_request(app, side_requests);
+    }
+}
+
+/// 応答を待った結果
+enum Waited<T> {
+    Finished(T),
+    /// Esc で待つのをやめた
+    Cancelled,
+    /// `give_up_after` を過ぎても応答がなかった
+    Slow,
+}
+
+/// 応答を待つ間も画面を描き直し、Esc が押されたか `give_up_after` を過ぎたら待つのをやめる。
+/// 待つのをやめた `request` はその場で破棄され、通信も打ち切られる
+async fn wait_cancellable<T>(
+    app: &mut App,
+    tui: &mut tui::Tui,
+    request: impl Future<Output = T>,
+    give_up_after: Option<Duration>,
+) -> Result<Waited<T>, AppError> {
+    let deadline = give_up_after.map(|limit| Instant::now() + limit);
+    tokio::pin!(request);
+    loop {
+        tokio::select! {
+            response = &mut request => return Ok(Waited::Finished(response)),
+            () = tokio::time::sleep(CANCEL_POLL_INTERVAL) => {
+                tui.draw(|frame| ui::render(app, frame))?;
+                if events::cancel_requested()? {
+                    return Ok(Waited::Cancelled);
+                }
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    return Ok(Waited::Slow);
+                }
+            }
+        }
+    }
+}
+
+async fn generate_text_for_training(app: &mut App, tui: &mut tui::Tui) -> Result<(), AppError> {
+    if app.quiz.is_some() {
+        return generate_quiz_for_training(app, tui).await;
+    }
+    if app.synthesis.is_some() {
+        return generate_synthesis_for_training(app, tui).await;
+    }
+    if app.offline.is_some() {
+        app.start_offline_text();
+        return Ok(());
+    }
+    if app.resume_review_text() || app.resume_pending_text() || app.use_prefetched_text() {
+        return Ok(());
+    }
+    let prompt = app.generate_text_prompt();
+    let Some(client) = app.api_client.clone() else {
+        return Ok(());
+    };
+    // キャッシュに未出題の文章があれば、latency_budget_secs を過ぎたところでそちらを出す
+    let give_up_after = app
+        .has_unseen_cached_text()
+        .then(|| app.config.latency_budget());
+    match wait_cancellable(app, tui, client.generate_text(&prompt), give_up_after).await? {
+        Waited::Finished(Ok(text)) => {
+            app.cache_generated_text(&text);
+            app.apply_generated_text(text);
         }
+        Waited::Finished(Err(e))
+            if retry::retry_reason(&e).is_some() && app.serve_cached_text(&prompt) => {}
+        Waited::Finished(Err(e)) => app.apply_generation_error(&e),
+        Waited::Slow if app.serve_cached_text(&prompt) => {}
+        Waited::Slow | Waited::Cancelled => app.cancel_generation(),
     }
+    Ok(())
+}
+
+/// 要約を提出した後、次の文章をバックグラウンドで生成しておく。生成した文章は未出題としてキャッシュにも残す
+fn prefetch_next_text(app: &mut App) {
+    for (key, prefetched) in app.prefetcher.poll() {
+        let cache_key = CacheKey {
+            character_count: key.character_count,
+            style: prefetched.style,
+            difficulty: key.difficulty,
+        };
+        app.cache_text(cache_key, &prefetched.text, false);
+    }
+    let Some(client) = app.api_client.clone() else {
+        return;
+    };
+    if let Some((key, prompt, style)) = app.prefetch_request() {
+        app.prefetcher.start(client, key, prompt, style);
+    }
+}
+
+/// 用語解説・読み仮名・語の意味・異議・コーチ・段落の講評の依頼があれば、バックグラウンドで問い合わせる。
+/// 待つ間は状態表示に経過時間が出て、Esc で取り消せる
+fn start_side_request(app: &mut App, side_requests: &mut SideRequestRunner) {
+    let Some(request) = app.next_side_request() else {
+        return;
+    };
+    let Some(client) = app.api_client.clone() else {
+        app.apply_side_response(request, Err(AppError::Offline));
+        return;
+    };
+    app.side_request_started_at = Some(Instant::now());
+    side_requests.start(client, request);
+}
+
+async fn generate_quiz_for_training(app: &mut App, tui: &mut tui::Tui) -> Result<(), AppError> {
+    let prompt = app.quiz_prompt();
+    let Some(client) = app.api_client.clone() else {
+        // オフラインでは、ライブラリに残した問題だけを出題する
+        app.serve_library_quiz(&prompt);
+        return Ok(());
+    };
+    // ライブラリに同じ条件の問題があれば、latency_budget_secs を過ぎたところでそちらを出す
+    let give_up_after = app.has_library_quiz().then(|| app.config.latency_budget());
+    match wait_cancellable(app, tui, client.generate_quiz(&prompt), give_up_after).await? {
+        Waited::Finished(Ok(response)) => app.apply_generated_quiz(&response),
+        Waited::Finished(Err(e))
+            if retry::retry_reason(&e).is_some() && app.serve_library_quiz(&prompt) => {}
+        Waited::Finished(Err(e)) => app.apply_generation_error(&e),
+        Waited::Slow if app.serve_library_quiz(&prompt) => {}
+        Waited::Slow | Waited::Cancelled => app.cancel_generation(),
+    }
+    Ok(())
+}
+
+async fn generate_synthesis_for_training(
+    app: &mut App,
+    tui: &mut tui::Tui,
+) -> Result<(), AppError> {
+    let prompt = app.synthesis_prompt();
+    let Some(client) = app.api_client.clone() else {
+        return Ok(());
+    };
+    match wait_cancellable(app, tui, client.generate_synthesis_sources(&prompt), None).await? {
+        Waited::Finished(Ok(response)) => app.apply_generated_sources(&response),
+        Waited::Finished(Err(e)) => app.apply_generation_error(&e),
+        Waited::Cancelled | Waited::Slow => app.cancel_generation(),
+    }
+    Ok(())
 }
 
 async fn handle_start_training(app: &mut App, tui: &mut tui::Tui) -> Result<(), AppError> {
     app.begin_training_generation(false);
     tui.draw(|frame| ui::render(app, frame))?;
 
-    generate_text_for_training(app).await;
-    Ok(())
+    generate_text_for_training(app, tui).await
 }
 
-async fn handle_evaluate(app: &mut App, tui: &mut tui::Tui) -> Result<(), AppError> {
+/// サブコマンドで指定した画面や文章から始める。
+/// `train` で文章の生成から始める場合は `true` を返す
+fn start_from_args(app: &mut App, command: &Command) -> Result<bool, AppError> {
+    match command {
+        Command::Train(options) => {
+            app.begin_cli_training(options);
+            // 下書きを復元できるときは、メニューで復元するかを先に選んでもらう
+            return Ok(app.recovered_draft.is_none());
+        }
+        Command::Report => app.enter_report_view(),
+        Command::Challenge(path) => {
+            let challenge = Challenge::load(path)
+                .map_err(|e| AppError::InvalidConfig(format!("{}: {e}", path.display())))?;
+            app.start_challenge(challenge);
+        }
+        Command::Import(path) => {
+            let text = text_import::load_text(path)?;
+            app.start_imported_text(text, TextSource::File(path.clone()));
+        }
+        // 標準入力から読んだ文章で始める場合は、文章の生成を行わない
+        Command::Stdin => {
+            let text = text_import::read_stdin()?;
+            app.start_imported_text(text, TextSource::Stdin);
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// API を使わないサブコマンドを実行する。実行した場合は `true` を返す
+fn run_offline_command(app: &App, command: &Command) -> Result<bool, AppError> {
+    match command {
+        Command::Export(options) => {
+            export::export_results(&app.stats.results, options)?;
+            if let Some(path) = &options.out {
+                println!("統計を書き出しました: {}", path.display());
+            }
+        }
+        Command::Stats(options) => stats_api::print_summary(&app.stats, options)?,
+        Command::ConfigSet { key, value } => {
+            config::set_config_value(key, value)?;
+            println!("{key} を設定しました。");
+        }
+        Command::Help => println!("{}", cli::USAGE),
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn handle_evaluate(app: &mut App, runner: &mut EvaluationRunner) {
+    let Some(client) = app.api_client.clone() else {
+        // オフラインでは評価に出さず、合否を自分で付ける
+        app.begin_self_grade();
+        return;
+    };
     app.begin_evaluation();
-    tui.draw(|frame| ui::render(app, frame))?;
 
-    let Some(client) = &app.api_client else {
-        return Ok(());
+    let summary = app.text_area_state.value().clone();
+    let job = if app.synthesis.is_some() {
+        EvaluationJob::Synthesis {
+            summary: summary.clone(),
+        }
+    } else {
+        EvaluationJob::Single {
+            summary: summary.clone(),
+        }
     };
+    runner.start(
+        client.with_strictness(app.strictness),
+        app.original_text.clone(),
+        vec![summary],
+        job,
+        app.config.latency_budget(),
+        app.summary_length_target(),
+    );
+}
+
+/// チュートリアルでは API を呼ばず、固定の評価を表示する。統計と履歴には残さない
+fn handle_tutorial_submit(app: &mut App) {
+    let summary = app.text_area_state.value().clone();
+    let Ok(parsed) = parse_evaluation(tutorial::MOCK_EVALUATION) else {
+        return;
+    };
+    app.finish_evaluation(format_evaluation_display(&parsed), parsed.passed());
+    app.result_tabs
+        .set_extras(parse_evaluation_extras(tutorial::MOCK_EVALUATION));
+    app.result_tabs.set_summary(&summary);
+}
+
+/// 制限時間付きモードで持ち時間を使い切ったら、設定に従って提出するか不合格として記録する
+fn handle_time_up(app: &mut App, runner: &mut EvaluationRunner) {
+    app.stop_editing();
+    let summary = app.text_area_state.value().clone();
+    if app.config.timed_challenge.on_timeout == TimeoutAction::Submit
+        && !summary.trim().is_empty()
+        && app.api_client.is_some()
+    {
+        handle_evaluate(app, runner);
+        return;
+    }
+    app.finish_time_up();
+    record_session(app, summary, false, None, None);
+    app.stats.mark_last_result_as_time_up();
+    app.save_stats();
+}
 
+/// 対戦モードで要約を提出する。2人目の提出で両方をまとめて評価に回す
+fn handle_hot_seat_submit(app: &mut App, runner: &mut EvaluationRunner) {
     let summary = app.text_area_state.value().clone();
+    let client = app.api_client.clone();
+    let Some(hot_seat) = app.hot_seat.as_mut() else {
+        return;
+    };
+    // 評価に出せないときは、1人目の要約も2人目の入力欄もそのまま残す
+    if client.is_none() && hot_seat.is_last_turn() {
+        app.status_message = STATUS_HOT_SEAT_OFFLINE.to_string();
+        return;
+    }
+    let players = hot_seat.players.clone();
+    let Some((first, second)) = hot_seat.submit(summary) else {
+        app.pass_turn();
+        return;
+    };
+    let Some(client) = client else {
+        return;
+    };
+    app.begin_evaluation();
+
+    runner.start(
+        client.with_strictness(app.strictness),
+        app.original_text.clone(),
+        vec![first, second],
+        EvaluationJob::HotSeat { players },
+        app.config.latency_budget(),
+        app.summary_length_target(),
+    );
+}
+
+fn handle_evaluation_finished(
+    app: &mut App,
+    job: EvaluationJob,
+    responses: Vec<Result<String, AppError>>,
+) {
+    if app.popup == Some(Popup::SlowEvaluation) {
+        app.popup = None;
+    }
+    match job {
+        EvaluationJob::Single { summary } => finish_single(app, summary, responses, false),
+        EvaluationJob::Synthesis { summary } => finish_single(app, summary, responses, true),
+        EvaluationJob::HotSeat { players } => finish_hot_seat(app, players, responses),
+    }
+}
 
-    match client.evaluate_summary(&app.original_text, &summary).await {
+/// 1人分の評価結果を表示して記録する。統合要約では資料ごとの反映度も合否に含める
+fn finish_single(
+    app: &mut App,
+    summary: String,
+    responses: Vec<Result<String, AppError>>,
+    synthesis: bool,
+) {
+    let Some(response) = responses.into_iter().next() else {
+        return;
+    };
+    match response {
         Ok(evaluation) => match parse_evaluation(&evaluation) {
-            Ok(parsed) => {
-                let evaluation_passed = matches!(parsed.overall, OverallEvaluation::Pass);
-                let evaluation_text = format_evaluation_display(&parsed);
-                let scores = EvaluationScores {
-                    appropriate: parsed.appropriate,
-                    importance: parsed.importance,
-                    conciseness: parsed.conciseness,
-                    accuracy: parsed.accuracy,
-                    improvement1: parsed.improvement1,
-                    improvement2: parsed.improvement2,
-                    improvement3: parsed.improvement3,
-                    overall_passed: evaluation_passed,
-                };
+            Ok(mut parsed) => {
+                let mut evaluation_text = judge_evaluation(app, &evaluation, &mut parsed);
+                let mut evaluation_passed = parsed.passed();
+                if let Some(coverage) = synthesis
+                    .then(|| synthesis::parse_source_coverage(&evaluation))
+                    .flatten()
+                {
+                    evaluation_text.push_str(&synthesis::format_coverage(coverage));
+                    evaluation_passed &= synthesis::covers_both(coverage);
+                }
+                let scores = parsed.into_scores();
+                let extras = parse_evaluation_extras(&evaluation);
+                let keyword_score = app.score_keyword_prediction(&extras.keywords);
+                if let Some(score) = &keyword_score {
+                    evaluation_text.push_str(&score.format());
+                }
+                if let Some(metrics) = app.measure_summary(&summary) {
+                    evaluation_text.push_str(&metrics.format());
+                }
+                if let Some(warning) = app.verbatim_warning(&summary) {
+                    evaluation_text.push_str(&warning);
+                }
+                // 評価者が答えなかった (カスタムテンプレートなど) ときは手元で数える
+                let length_met = app.summary_length_target().map(|target| {
+                    let met = extras.length_met.unwrap_or_else(|| target.is_met(&summary));
+                    evaluation_text.push_str(&format_length_check(
+                        &summary,
+                        target,
+                        met,
+                        app.config.locale,
+                    ));
+                    met
+                });
 
                 app.finish_evaluation(evaluation_text, evaluation_passed);
-
-                app.stats
-                    .add_result_with_evaluation(evaluation_passed, Some(scores));
-                if let Err(e) = app.stats.save() {
-                    app.status_message = format!("警告: 統計の保存に失敗しました: {e}");
-                    eprintln!("統計の保存に失敗しました: {e}");
+                app.result_tabs.set_extras(extras);
+                app.result_tabs.set_summary(&summary);
+                record_session(app, summary, evaluation_passed, Some(scores), length_met);
+                if let Some(score) = keyword_score {
+                    app.stats.mark_last_result_keywords(score.hits, score.total);
+                    app.save_stats();
                 }
             }
-            Err(_) => app.fail_evaluation_format(),
+            Err(_) => app.offer_self_grade_after_failure(None),
         },
-        Err(e) => app.fail_evaluation_request(&e),
+        Err(e) => app.offer_self_grade_after_failure(Some(&e)),
     }
-    Ok(())
+}
+
+/// 評価の厳しさと `[rubric]` の合格ラインで合否を決め、評価画面の表示を組み立てる
+fn judge_evaluation(app: &App, evaluation: &str, parsed: &mut EvaluationResult) -> String {
+    app.strictness.adjust(parsed);
+    match rubric::score(&app.config.rubric, evaluation) {
+        Some(mut scores) => {
+            scores.pass_percent = app.strictness.rubric_pass_percent(scores.pass_percent);
+            scores.apply(parsed);
+            format_evaluation_with_table(parsed, &scores.format_table(app.config.locale))
+        }
+        None => format_evaluation_display(parsed),
+    }
+}
+
+/// 2人分の評価結果から勝敗を決めて記録する
+fn finish_hot_seat(app: &mut App, players: [String; 2], responses: Vec<Result<String, AppError>>) {
+    let mut participants = Vec::with_capacity(2);
+    let mut details = Vec::with_capacity(2);
+    for (name, response) in players.into_iter().zip(responses) {
+        let evaluation = match response {
+            Ok(evaluation) => evaluation,
+            Err(e) => {
+                app.fail_evaluation_request(&e);
+                return;
+            }
+        };
+        let Ok(mut parsed) = parse_evaluation(&evaluation) else {
+            app.fail_evaluation_format();
+            return;
+        };
+        details.push(judge_evaluation(app, &evaluation, &mut parsed));
+        participants.push(ParticipantResult {
+            name,
+            passed: parsed.passed(),
+            evaluation: Some(parsed.into_scores()),
+        });
+    }
+    let (Ok(participants), [first_detail, second_detail]) = (
+        <[ParticipantResult; 2]>::try_from(participants),
+        details.as_slice(),
+    ) else {
+        return;
+    };
+
+    let result = HeadToHeadResult {
+        timestamp: Local::now(),
+        participants,
+    };
+    let text = hot_seat::format_head_to_head(&result, [first_detail, second_detail]);
+    let any_passed = result.participants.iter().any(|p| p.passed);
+    app.finish_evaluation(text, any_passed);
+
+    app.stats.add_head_to_head_result(result);
+    app.save_stats();
+}
+
+/// 4 択問題は API を使わずに採点し、要約と同じく統計と履歴に記録する
+fn handle_quiz_answered(app: &mut App) {
+    let Some((passed, correct, total, answers)) = app.finish_quiz() else {
+        return;
+    };
+    record_session(app, answers, passed, None, None);
+    app.stats.mark_last_result_as_quiz(correct, total);
+    app.save_stats();
+}
+
+fn handle_self_grade(app: &mut App, passed: bool) {
+    app.finish_self_grade(passed);
+    let summary = app.text_area_state.value().clone();
+    let length_met = app
+        .summary_length_target()
+        .map(|target| target.is_met(&summary));
+    record_session(app, summary, passed, None, length_met);
+}
+
+/// 評価結果の末尾に付ける文字数の条件の判定
+fn format_length_check(
+    summary: &str,
+    target: LengthTarget,
+    met: bool,
+    locale: LabelLanguage,
+) -> String {
+    let condition = if met {
+        "条件を満たしています"
+    } else {
+        "条件を満たしていません"
+    };
+    let line = trf(
+        locale,
+        "文字数: {} / 目標 {}±{} 文字 ({})",
+        &[
+            &summary_length::count_chars(summary),
+            &target.chars,
+            &target.tolerance,
+            &tr(locale, condition),
+        ],
+    );
+    format!("\n\n{line}")
+}
+
+/// 評価結果を統計と履歴に保存する。`scores` が `None` の場合は自己採点として記録する。
+/// `length_met` は要約の長さの条件を満たしたか (確かめなかった場合は `None`)
+fn record_session(
+    app: &mut App,
+    summary: String,
+    passed: bool,
+    scores: Option<EvaluationScores>,
+    length_met: Option<bool>,
+) {
+    // 4 択問題の解答は要約ではないので、指標を測らない
+    let metrics = app
+        .quiz
+        .is_none()
+        .then(|| app.measure_summary(&summary))
+        .flatten();
+    let retry_of = app.comparison_base.take().map(|base| {
+        let comparison = history::format_comparison(&base, passed, scores.as_ref());
+        app.evaluation_text.push_str(&comparison);
+        base.retry_of.unwrap_or(base.timestamp)
+    });
+    if let Some(challenge) = app.challenge.take() {
+        let comparison = challenge.format_comparison(passed, scores.as_ref());
+        app.evaluation_text.push_str(&comparison);
+    }
+    // 自己採点はレベルの判定に使わない
+    if scores.is_some() {
+        app.apply_level_result(passed);
+    }
+
+    let record = SessionRecord {
+        timestamp: Local::now(),
+        character_count: app.character_count,
+        original_text: app.original_text.clone(),
+        summary,
+        evaluation_text: app.evaluation_text.clone(),
+        passed,
+        evaluation: scores.clone(),
+        started_at: app.text_shown_at,
+        retry_of,
+        notes: app.scratchpad_notes_for_history(),
+    };
+
+    app.record_review_result(passed, scores.as_ref());
+    match scores {
+        Some(scores) => {
+            app.stats
+                .add_result_with_evaluation(passed, Some(scores), app.difficulty);
+            if let Some(model) = app.current_model().map(str::to_string) {
+                app.stats.set_last_result_model(model);
+            }
+        }
+        None => app.stats.add_self_graded_result(passed, app.difficulty),
+    }
+    app.stats
+        .describe_last_result(app.character_count, app.text_style);
+    app.stats.analyze_last_result_text(&app.original_text);
+    if let Some(secs) = app.session_duration_secs() {
+        app.stats
+            .time_last_result(secs, app.original_text.chars().count());
+    }
+    if let Some(limit) = app.time_limit_secs() {
+        let timed_out = app
+            .session_duration_secs()
+            .is_some_and(|secs| secs >= limit);
+        app.stats.mark_last_result_as_timed(limit, timed_out);
+    }
+    if let Some(original) = retry_of {
+        app.stats.mark_last_result_as_retry(original);
+    }
+    if let Some(met) = length_met {
+        app.stats.mark_last_result_length(met);
+    }
+    if let Some(metrics) = metrics {
+        app.stats.mark_last_result_metrics(metrics);
+    }
+    if let Some(challenge) = app.daily_challenge {
+        app.stats
+            .mark_last_result_as_daily_challenge(challenge.date);
+    }
+    app.check_daily_goal();
+    app.check_personal_records();
+    app.check_rank_up();
+    if let Some(exam) = app.record_exam_round() {
+        app.stats.add_exam_record(exam);
+    }
+    app.save_stats();
+    if let Err(e) = history::append_record(&record) {
+        app.status_message = trf(
+            app.config.locale,
+            "警告: 履歴の保存に失敗しました: {}",
+            &[&e],
+        );
+    }
+    app.complete_session(record);
 }
 
 async fn handle_next_training(app: &mut App, tui: &mut tui::Tui) -> Result<(), AppError> {
     app.prepare_next_training();
     tui.draw(|frame| ui::render(app, frame))?;
 
-    generate_text_for_training(app).await;
+    generate_text_for_training(app, tui).await
+}
+
+async fn handle_open_model_select(app: &mut App, tui: &mut tui::Tui) -> Result<(), AppError> {
+    let Some(client) = app.api_client.clone() else {
+        return Ok(());
+    };
+    app.begin_loading_models();
+    tui.draw(|frame| ui::render(app, frame))?;
+
+    match client.list_models().await {
+        Ok(models) => app.enter_model_select_view(models),
+        Err(e) => app.apply_model_list_error(&e),
+    }
     Ok(())
 }
 
-async fn authenticate() -> Result<ApiClient, AppError> {
-    if let Some(key) = config::load_api_key()?
-        && let Some(client) = authenticate_with_key(&key).await
-    {
-        return Ok(client);
+async fn handle_defer_training(app: &mut App, tui: &mut tui::Tui) -> Result<(), AppError> {
+    app.defer_current_text();
+    tui.draw(|frame| ui::render(app, frame))?;
+
+    generate_text_for_training(app, tui).await
+}
+
+/// 認証したクライアント。`--offline` を付けたか認証に失敗した場合は `None` を返し、
+/// オフラインで練習する。API キーがないか無効なら、キーを入力する画面から始める。
+/// 実験は API がないと行えないので、認証の失敗をそのまま返す
+async fn connect(app: &mut App, is_experiment: bool) -> Result<Option<ApiClient>, AppError> {
+    if offline::is_forced() && !is_experiment {
+        app.enter_offline_mode(OfflineCorpus::load(&app.config.offline)?, None);
+        return Ok(None);
+    }
+    match authenticate(&app.config).await {
+        Ok(client) => Ok(Some(client)),
+        Err(e) if is_experiment => Err(e),
+        Err(e) => {
+            app.enter_offline_mode(OfflineCorpus::load(&app.config.offline)?, Some(&e));
+            if matches!(e, AppError::InvalidApiKey) {
+                app.open_api_key_setup(Some(&e));
+            }
+            Ok(None)
+        }
     }
-    Err(AppError::InvalidApiKey)
 }
 
-async fn authenticate_with_key(key: &str) -> Option<ApiClient> {
-    if key.is_empty() {
-        return None;
+/// 設定に合わせて、モデル・伏せ字・監査ログ・評価プロンプト・再送の方針を付ける
+fn configure_client(app: &App, client: ApiClient) -> Result<ApiClient, AppError> {
+    Ok(client
+        .with_model(app.config.model.clone())
+        .with_redactor(Redactor::from_config(&app.config.redaction)?)
+        .with_audit_log(AuditLog::from_config(&app.config.audit_log)?)
+        .with_evaluation_template(app.prompts.evaluation.clone())
+        .with_rubric(app.config.rubric.clone())
+        .with_retry_policy(RetryPolicy::from_config(&app.config.retry)))
+}
+
+/// API キーの設定画面で入力したキーを確かめ、使えれば保存して接続する
+async fn handle_submit_api_key(app: &mut App, tui: &mut tui::Tui) -> Result<(), AppError> {
+    let Some(key) = app.begin_api_key_check() else {
+        return Ok(());
+    };
+    let config = &app.config;
+    let client = client_for(
+        config.provider,
+        config.base_url.as_deref(),
+        Some(&key),
+        &config.network,
+    )?;
+    match wait_cancellable(app, tui, client.validate_credentials(), None).await? {
+        Waited::Finished(Ok(())) => {
+            let client = configure_client(app, client)?;
+            app.apply_api_key(client, key);
+        }
+        Waited::Finished(Err(e)) => app.reject_api_key(&e),
+        Waited::Cancelled | Waited::Slow => app.cancel_api_key_check(),
     }
+    Ok(())
+}
 
-    let client = ApiClient::new(key.to_string());
-    client.validate_credentials().await.ok()?;
-    Some(client)
+/// 切り替え画面で選んだプロファイルに接続し、つながれば切り替える
+async fn handle_switch_profile(app: &mut App, tui: &mut tui::Tui) -> Result<(), AppError> {
+    let Some((index, profile)) = app.begin_profile_switch() else {
+        return Ok(());
+    };
+    let client = match client_for(
+        profile.provider,
+        profile.base_url.as_deref(),
+        profile.api_key.as_deref(),
+        &app.config.network,
+    ) {
+        Ok(client) => client,
+        Err(e) => {
+            app.reject_profile(&profile, &e);
+            return Ok(());
+        }
+    };
+    match wait_cancellable(app, tui, client.validate_credentials(), None).await? {
+        Waited::Finished(Ok(())) => {
+            let client = configure_client(app, client)?.with_model(profile.model.clone());
+            app.apply_profile(index, client);
+        }
+        Waited::Finished(Err(e)) => app.reject_profile(&profile, &e),
+        Waited::Cancelled | Waited::Slow => app.cancel_profile_switch(),
+    }
+    Ok(())
+}
+
+async fn authenticate(config: &Config) -> Result<ApiClient, AppError> {
+    let client = client_for(
+        config.provider,
+        config.base_url.as_deref(),
+        config.resolved_api_key().as_deref(),
+        &config.network,
+    )?;
+    client.validate_credentials().await?;
+    Ok(client)
+}
+
+/// 接続先に合わせたクライアント。Ollama 以外で API キーがなければ `InvalidApiKey`
+fn client_for(
+    provider: Provider,
+    base_url: Option<&str>,
+    key: Option<&str>,
+    network: &NetworkConfig,
+) -> Result<ApiClient, AppError> {
+    let client = match provider {
+        Provider::Ollama => ApiClient::ollama()?,
+        Provider::Groq => match key.filter(|key| !key.is_empty()) {
+            Some(key) => ApiClient::new(key.to_string())?,
+            None => return Err(AppError::InvalidApiKey),
+        },
+    };
+    client.with_base_url(base_url).with_timeouts(network)
 }
@@ -0,0 +1,187 @@
+use crate::error::AppError;
+use crate::storage::{self, Storage};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// 統計・履歴の保存形式の版数。保存形式を変えたら上げ、`migrate` に変換を追加する
+pub const SCHEMA_VERSION: u32 = 2;
+/// 版数を記録する前のデータは版 1 とみなす
+const UNVERSIONED: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct SchemaMeta {
+    schema_version: u32,
+}
+
+/// 移行の記録。`migrations` に 1 件ずつ残す
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MigrationRecord {
+    pub timestamp: DateTime<Local>,
+    pub from: u32,
+    pub to: u32,
+    /// 移行前のデータを書き出したディレクトリ
+    pub backup: String,
+}
+
+/// 保存されているデータが古い版なら、`backup_root` の下に日時付きで書き出してから版を上げる。
+/// 新しいリリースで保存したデータ (版が大きい) には触れない
+pub fn migrate(
+    storage: &dyn Storage,
+    backup_root: &Path,
+    now: DateTime<Local>,
+) -> Result<Option<MigrationRecord>, AppError> {
+    let from = storage::load_json::<SchemaMeta>(storage, storage::META_DOCUMENT)?
+        .map_or(UNVERSIONED, |meta| meta.schema_version);
+    if from >= SCHEMA_VERSION {
+        return Ok(None);
+    }
+    // 初めて起動したときは、移すものがないので版数だけ記録する
+    if !has_data(storage)? {
+        save_version(storage)?;
+        return Ok(None);
+    }
+
+    let backup = backup_root.join(format!("{}-v{from}", now.format("%Y%m%d-%H%M%S")));
+    write_backup(storage, &backup)?;
+    // 版 2 までの変更は項目の追加だけなので、読み込み時の既定値で足りる
+    save_version(storage)?;
+    let record = MigrationRecord {
+        timestamp: now,
+        from,
+        to: SCHEMA_VERSION,
+        backup: backup.display().to_string(),
+    };
+    storage::append_json(storage, storage::MIGRATION_COLLECTION, &record)?;
+    Ok(Some(record))
+}
+
+fn has_data(storage: &dyn Storage) -> Result<bool, AppError> {
+    for key in storage::DOCUMENTS {
+        if storage.load(key)?.is_some() {
+            return Ok(true);
+        }
+    }
+    for collection in storage::COLLECTIONS {
+        if !storage.query(collection, Some(1))?.is_empty() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn save_version(storage: &dyn Storage) -> Result<(), AppError> {
+    storage::save_json(
+        storage,
+        storage::META_DOCUMENT,
+        &SchemaMeta {
+            schema_version: SCHEMA_VERSION,
+        },
+    )
+}
+
+/// 保存先の形式によらず、JSON の保存形式 (`<key>.json` と `<collection>.jsonl`) で書き出す。
+/// 古いリリースに戻すときは、このファイルを設定ディレクトリに戻せばよい
+fn write_backup(storage: &dyn Storage, dir: &Path) -> Result<(), AppError> {
+    fs::create_dir_all(dir)?;
+    for key in storage::DOCUMENTS {
+        if let Some(content) = storage.load(key)? {
+            fs::write(dir.join(format!("{key}.json")), content)?;
+        }
+    }
+    for collection in storage::COLLECTIONS {
+        let records = storage.query(collection, None)?;
+        if !records.is_empty() {
+            fs::write(
+                dir.join(format!("{collection}.jsonl")),
+                records.join("\n") + "\n",
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::JsonStorage;
+    use chrono::TimeZone;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("yomitore-migration-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn old_data_is_backed_up_once_and_logged() -> Result<(), String> {
+        let dir = temp_dir("old");
+        let storage = JsonStorage::open(dir.join("data")).map_err(|e| e.to_string())?;
+        storage
+            .save(storage::STATS_DOCUMENT, "{\"results\":[]}")
+            .map_err(|e| e.to_string())?;
+        storage
+            .append(storage::HISTORY_COLLECTION, "{}")
+            .map_err(|e| e.to_string())?;
+        let now = Local
+            .with_ymd_and_hms(2026, 7, 1, 9, 30, 0)
+            .single()
+            .ok_or("invalid date")?;
+
+        let record = migrate(&storage, &dir.join("backups"), now)
+            .map_err(|e| e.to_string())?
+            .ok_or("old data was not migrated")?;
+        if (record.from, record.to) != (1, 2) {
+            return Err(format!(
+                "unexpected migration: {} -> {}",
+                record.from, record.to
+            ));
+        }
+        let backup = dir.join("backups").join("20260701-093000-v1");
+        let stats = fs::read_to_string(backup.join("stats.json")).map_err(|e| e.to_string())?;
+        if stats != "{\"results\":[]}" {
+            return Err(format!("unexpected backup of stats: {stats}"));
+        }
+        if !backup.join("history.jsonl").exists() {
+            return Err("history was not backed up".to_string());
+        }
+        let log = storage
+            .query(storage::MIGRATION_COLLECTION, None)
+            .map_err(|e| e.to_string())?;
+        if log.len() != 1 {
+            return Err(format!("expected one migration record, got {}", log.len()));
+        }
+
+        if !matches!(migrate(&storage, &dir.join("backups"), now), Ok(None)) {
+            return Err("migrated data was migrated again".to_string());
+        }
+        let _ = fs::remove_dir_all(&dir);
+        Ok(())
+    }
+
+    #[test]
+    fn fresh_install_only_records_version() -> Result<(), String> {
+        let dir = temp_dir("fresh");
+        let storage = JsonStorage::open(dir.join("data")).map_err(|e| e.to_string())?;
+        if !matches!(
+            migrate(&storage, &dir.join("backups"), Local::now()),
+            Ok(None)
+        ) {
+            return Err("fresh install was migrated".to_string());
+        }
+        if dir.join("backups").exists() {
+            return Err("fresh install was backed up".to_string());
+        }
+        if storage
+            .load(storage::META_DOCUMENT)
+            .map_err(|e| e.to_string())?
+            .is_none()
+        {
+            return Err("version was not recorded".to_string());
+        }
+        let _ = fs::remove_dir_all(&dir);
+        Ok(())
+    }
+}
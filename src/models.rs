@@ -1,18 +1,121 @@
-use chrono::{DateTime, Local};
+use crate::summary_metrics::SummaryMetrics;
+use crate::text_difficulty::{JlptLevel, TextFeatures};
+use chrono::{DateTime, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct TrainingResult {
     pub timestamp: DateTime<Local>,
     pub passed: bool,
     #[serde(default)]
     pub evaluation: Option<EvaluationScores>,
+    #[serde(default)]
+    pub self_graded: bool,
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    /// 再挑戦の場合、最初に挑戦したセッションの日時
+    #[serde(default)]
+    pub retry_of: Option<DateTime<Local>>,
+    /// 出題の文字数 (記録していなかった古い結果では `None`)
+    #[serde(default)]
+    pub character_count: Option<u16>,
+    /// 出題の文体。読み込んだ文章では `None`
+    #[serde(default)]
+    pub genre: Option<TextStyle>,
+    /// 文章を表示してから提出するまでの秒数 (読む時間と書く時間の合計)
+    #[serde(default)]
+    pub duration_secs: Option<u32>,
+    /// 1 分あたりに読み書きした原文の文字数
+    #[serde(default)]
+    pub cpm: Option<u32>,
+    /// 制限時間付きモードの持ち時間 (秒)。通常の練習では `None`
+    #[serde(default)]
+    pub time_limit_secs: Option<u32>,
+    /// 持ち時間を使い切った結果
+    #[serde(default)]
+    pub timed_out: bool,
+    /// 4 択問題モードの正解数と問題数
+    #[serde(default)]
+    pub quiz_score: Option<(u8, u8)>,
+    /// 原文を分析した難しさ。メニューで選んだ難易度とは別に記録する
+    #[serde(default)]
+    pub text_features: Option<TextFeatures>,
+    /// 評価したモデル。自己採点や、記録していなかった古い結果では `None`
+    #[serde(default)]
+    pub evaluator_model: Option<String>,
+    /// 今日のお題として出題された場合、そのお題の日付
+    #[serde(default)]
+    pub daily_challenge: Option<NaiveDate>,
+    /// 要約の長さの条件を満たしたか。条件を確かめなかった結果では `None`
+    #[serde(default)]
+    pub length_met: Option<bool>,
+    /// 異議申し立ての後に合否を書き換えた結果なら、評価が付けた元の合否
+    #[serde(default)]
+    pub overridden_from: Option<bool>,
+    /// 本文を読む前に予想したキーワードの的中数と予想の数。予想しなかった結果では `None`
+    #[serde(default)]
+    pub keyword_hits: Option<(u8, u8)>,
+    /// 要約を手元で測った指標。4 択問題や、記録していなかった古い結果では `None`
+    #[serde(default)]
+    pub summary_metrics: Option<SummaryMetrics>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum BadgeType {
     ConsecutiveStreak(usize),   // 連続正解数 (5, 10, 15, ...)
     CumulativeMilestone(usize), // 累積正解数 (5, 10, 15, ...)
+    DailyStreak(usize),         // 連続練習日数 (3, 7, 14, ...)
+    DailyGoal(usize),           // 1日の目標を達成した日数 (1, 7, 30, ...)
+    PersonalBest(usize),        // 自己ベストを更新した回数 (1, 2, 3, ...)
+    EarlyBird,                  // 朝 7 時より前に練習した
+    Marathon,                   // 1 日に 10 回練習した
+    GenreMaster(TextStyle),     // 1 つの文体で 20 回合格した
+    Comeback,                   // 5 回続けて不合格の直後に合格した
+}
+
+impl BadgeType {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::ConsecutiveStreak(_) => "🔥",   // Fire for streak
+            Self::CumulativeMilestone(_) => "⭐", // Star for milestone
+            Self::DailyStreak(_) => "📅",         // Calendar for daily habit
+            Self::DailyGoal(_) => "🎯",           // Target for daily goal
+            Self::PersonalBest(_) => "🏅",        // Medal for personal best
+            Self::EarlyBird => "🌅",
+            Self::Marathon => "🏃",
+            Self::GenreMaster(_) => "🎓",
+            Self::Comeback => "💪",
+        }
+    }
+
+    pub fn display_text(&self) -> String {
+        match self {
+            Self::ConsecutiveStreak(n) => format!("{n}連"),
+            Self::CumulativeMilestone(n) => format!("累積{n}"),
+            Self::DailyStreak(n) => format!("{n}日"),
+            Self::DailyGoal(n) => format!("目標{n}日"),
+            Self::PersonalBest(n) => format!("自己ベスト{n}"),
+            Self::EarlyBird => "早起き".to_string(),
+            Self::Marathon => "マラソン".to_string(),
+            Self::GenreMaster(style) => format!("{}マスター", style.label()),
+            Self::Comeback => "復活".to_string(),
+        }
+    }
+
+    /// バッジ一覧で、まだ獲得していないバッジに添える獲得の条件
+    pub fn unlock_condition(&self) -> String {
+        match self {
+            Self::ConsecutiveStreak(n) => format!("{n} 回続けて合格する"),
+            Self::CumulativeMilestone(n) => format!("合格を {n} 回積み重ねる"),
+            Self::DailyStreak(n) => format!("{n} 日続けて練習する"),
+            Self::DailyGoal(n) => format!("1日の目標を {n} 日達成する"),
+            Self::PersonalBest(n) => format!("自己ベストを {n} 回更新する"),
+            Self::EarlyBird => "朝 7 時より前に練習する".to_string(),
+            Self::Marathon => "1 日に 10 回練習する".to_string(),
+            Self::GenreMaster(style) => format!("{}で 20 回合格する", style.label()),
+            Self::Comeback => "5 回続けて不合格の直後に合格する".to_string(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -24,18 +127,12 @@ pub struct Badge {
 impl Badge {
     /// Get the emoji icon for this badge
     pub fn get_icon(&self) -> &str {
-        match &self.badge_type {
-            BadgeType::ConsecutiveStreak(_) => "🔥",   // Fire for streak
-            BadgeType::CumulativeMilestone(_) => "⭐", // Star for milestone
-        }
+        self.badge_type.icon()
     }
 
     /// Get the display text for this badge
     pub fn get_display_text(&self) -> String {
-        match &self.badge_type {
-            BadgeType::ConsecutiveStreak(n) => format!("{n}連"),
-            BadgeType::CumulativeMilestone(n) => format!("累積{n}"),
-        }
+        self.badge_type.display_text()
     }
 }
 
@@ -69,7 +166,7 @@ pub struct EvaluationScoreStats {
     pub median: f32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct EvaluationSummary {
     pub count: usize,
     pub importance: Option<EvaluationScoreStats>,
@@ -81,6 +178,8 @@ pub struct EvaluationSummary {
 pub struct DailyStats {
     pub correct: usize,
     pub incorrect: usize,
+    /// その日に1日の目標を達成したか
+    pub goal_met: bool,
 }
 
 impl DailyStats {
@@ -89,9 +188,266 @@ impl DailyStats {
     }
 }
 
+/// ある日までの数日間 (移動窓) の練習回数と合格率。レポートの推移グラフに使う
+#[derive(Clone, Debug, PartialEq)]
+pub struct RollingTrend {
+    pub date: NaiveDate,
+    pub sessions: usize,
+    /// 窓の中に結果がなければ `None`
+    pub pass_rate: Option<f64>,
+}
+
+/// 最近の評価で点数が伸びていない観点
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weakness {
+    Importance,
+    Conciseness,
+    Accuracy,
+}
+
+impl Weakness {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Importance => "重要情報の抽出",
+            Self::Conciseness => "簡潔性",
+            Self::Accuracy => "正確性",
+        }
+    }
+
+    /// 苦手を練習できる文章にするため生成プロンプトに付け加える指示
+    pub fn prompt_hint(self) -> &'static str {
+        match self {
+            Self::Importance => {
+                "主旨と細部を取り違えやすいよう、目立つ具体例や細部の記述の中に要点が埋もれている構成にしてください。"
+            }
+            Self::Conciseness => {
+                "補足説明や言い換え、具体例を多めに含め、要点を短く絞り込む練習になる構成にしてください。"
+            }
+            Self::Accuracy => {
+                "数値や固有名詞、条件や因果関係を複数含め、正確に読み取らないと誤りやすい構成にしてください。"
+            }
+        }
+    }
+}
+
+/// 難易度ごとの合格・不合格の件数
+#[derive(Clone, Debug, PartialEq)]
+pub struct DifficultyStats {
+    pub difficulty: Difficulty,
+    pub correct: usize,
+    pub incorrect: usize,
+}
+
+impl DifficultyStats {
+    pub fn total(&self) -> usize {
+        self.correct + self.incorrect
+    }
+
+    pub fn pass_rate(&self) -> f64 {
+        let total = u32::try_from(self.total()).unwrap_or(u32::MAX);
+        if total == 0 {
+            return 0.0;
+        }
+        f64::from(u32::try_from(self.correct).unwrap_or(u32::MAX)) * 100.0 / f64::from(total)
+    }
+}
+
+/// 原文から推定したレベルごとの合格数と、文章の特徴の平均
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextLevelStats {
+    pub level: JlptLevel,
+    pub correct: usize,
+    pub incorrect: usize,
+    pub average_kanji_percent: u8,
+    pub average_sentence_chars: u16,
+}
+
+impl TextLevelStats {
+    pub fn total(&self) -> usize {
+        self.correct + self.incorrect
+    }
+
+    pub fn pass_rate(&self) -> f64 {
+        let total = u32::try_from(self.total()).unwrap_or(u32::MAX);
+        if total == 0 {
+            return 0.0;
+        }
+        f64::from(u32::try_from(self.correct).unwrap_or(u32::MAX)) * 100.0 / f64::from(total)
+    }
+}
+
+/// 出題の文字数と文体の組み合わせごとの合格数と、評価の 3 観点の集計
+#[derive(Clone, Debug)]
+pub struct LengthGenreStats {
+    /// メニューの文字数のうち、出題の文字数以上で最も短いもの。最も長い選択肢を超える読み込んだ文章は `None`
+    pub length: Option<u16>,
+    pub genre: Option<TextStyle>,
+    pub correct: usize,
+    pub incorrect: usize,
+    pub scores: EvaluationSummary,
+}
+
+impl LengthGenreStats {
+    pub fn total(&self) -> usize {
+        self.correct + self.incorrect
+    }
+
+    pub fn pass_rate(&self) -> f64 {
+        let total = u32::try_from(self.total()).unwrap_or(u32::MAX);
+        if total == 0 {
+            return 0.0;
+        }
+        f64::from(u32::try_from(self.correct).unwrap_or(u32::MAX)) * 100.0 / f64::from(total)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct WeeklyStats {
-    pub week_number: usize,
+    /// 週の始まりの日 (設定した曜日)
+    pub start_date: NaiveDate,
     pub correct: usize,
     pub incorrect: usize,
 }
+
+/// 生成する文章の難易度
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Standard,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 4] = [Self::Easy, Self::Standard, Self::Hard, Self::Expert];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Easy => "やさしい",
+            Self::Standard => "標準",
+            Self::Hard => "難しい",
+            Self::Expert => "最難関",
+        }
+    }
+
+    /// 日本語能力試験 (JLPT) の目安。最難関は N1 を超えるため `None`
+    pub fn jlpt_level(self) -> Option<&'static str> {
+        match self {
+            Self::Easy => Some("N3"),
+            Self::Standard => Some("N2"),
+            Self::Hard => Some("N1"),
+            Self::Expert => None,
+        }
+    }
+
+    /// メニューなどに表示する「やさしい (N3)」形式の名前
+    pub fn display_name(self) -> String {
+        match self.jlpt_level() {
+            Some(jlpt) => format!("{} ({jlpt})", self.label()),
+            None => self.label().to_string(),
+        }
+    }
+
+    /// メニューで `d` を押したときの次の難易度
+    pub fn next(self) -> Self {
+        match self {
+            Self::Easy => Self::Standard,
+            Self::Standard => Self::Hard,
+            Self::Hard => Self::Expert,
+            Self::Expert => Self::Easy,
+        }
+    }
+
+    /// 生成プロンプトに付け加える語彙・構成の指示
+    pub fn prompt_hint(self) -> &'static str {
+        match self {
+            Self::Easy => "日本語能力試験 N3 程度の平易な語彙と短い文を中心に、",
+            Self::Standard => "",
+            Self::Hard => "日本語能力試験 N1 程度の語彙を使い、専門用語や長い複文を適度に含め、",
+            Self::Expert => "専門用語や抽象的な概念を多く含み、論点が複数絡み合う構成で、",
+        }
+    }
+}
+
+/// 生成する文章の文体
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextStyle {
+    Official,
+    Newspaper,
+}
+
+impl TextStyle {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Official => "公的文書",
+            Self::Newspaper => "新聞記事",
+        }
+    }
+
+    pub fn prompt(self) -> &'static str {
+        match self {
+            Self::Official => {
+                "日本の公的文書（省庁や自治体が発行する通知や報告書）の文体で、感情表現や口語表現を避け、形式的かつ客観的な文章を"
+            }
+            Self::Newspaper => {
+                "日本の新聞記事の本文として、事実関係を中心に客観的かつ簡潔な文体で文章を"
+            }
+        }
+    }
+}
+
+/// 対戦モードでの参加者1人分の結果
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ParticipantResult {
+    pub name: String,
+    pub passed: bool,
+    #[serde(default)]
+    pub evaluation: Option<EvaluationScores>,
+}
+
+impl ParticipantResult {
+    pub fn total_score(&self) -> u16 {
+        self.evaluation.as_ref().map_or(0, |scores| {
+            u16::from(scores.importance)
+                + u16::from(scores.conciseness)
+                + u16::from(scores.accuracy)
+        })
+    }
+}
+
+/// 同じ文章を2人で要約した1回分の対戦結果
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HeadToHeadResult {
+    pub timestamp: DateTime<Local>,
+    pub participants: [ParticipantResult; 2],
+}
+
+impl HeadToHeadResult {
+    /// 合否が優先、同じならスコア合計で勝者を決める。引き分けは `None`
+    pub fn winner(&self) -> Option<&ParticipantResult> {
+        let [a, b] = &self.participants;
+        let key = |p: &ParticipantResult| (p.passed, p.total_score());
+        match key(a).cmp(&key(b)) {
+            std::cmp::Ordering::Greater => Some(a),
+            std::cmp::Ordering::Less => Some(b),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+/// 2人の組み合わせごとの通算成績
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeadToHeadTally {
+    pub players: (String, String),
+    pub wins: (usize, usize),
+    pub draws: usize,
+}
+
+/// 目の休憩を取ったか、スキップしたかの記録
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BreakRecord {
+    pub timestamp: DateTime<Local>,
+    pub duration_secs: u64,
+    pub skipped: bool,
+}
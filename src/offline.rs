@@ -0,0 +1,123 @@
+use crate::config::OfflineConfig;
+use crate::error::AppError;
+use crate::library::{bucket_for, normalize_whitespace};
+use std::fs;
+use std::path::PathBuf;
+
+/// 認証に失敗しなくてもオフラインで起動するフラグ
+pub const OFFLINE_FLAG: &str = "--offline";
+const APP_DIR_NAME: &str = "yomitore";
+const CORPUS_DIR_NAME: &str = "corpus";
+/// 組み込みの文章。`---` だけの行で区切る
+const BUNDLED_TEXTS: &str = include_str!("../assets/offline_texts.txt");
+
+/// `--offline` を付けて起動したか
+pub fn is_forced() -> bool {
+    std::env::args().skip(1).any(|arg| arg == OFFLINE_FLAG)
+}
+
+struct CorpusText {
+    text: String,
+    character_count: u16,
+    used_count: u32,
+}
+
+/// オフラインで出題する文章の集まり。手元のディレクトリに文章がなければ組み込みの文章を使う
+pub struct OfflineCorpus {
+    texts: Vec<CorpusText>,
+}
+
+impl OfflineCorpus {
+    /// `[offline]` の `corpus_dir` (既定は設定ディレクトリの `corpus`) の `.txt` を 1 ファイル 1 文章として読む。
+    /// 指定したディレクトリが読めない場合はエラー
+    pub fn load(config: &OfflineConfig) -> Result<Self, AppError> {
+        let dir = match &config.corpus_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => match dirs::config_dir() {
+                Some(dir) => dir.join(APP_DIR_NAME).join(CORPUS_DIR_NAME),
+                None => return Ok(Self::bundled()),
+            },
+        };
+        if config.corpus_dir.is_none() && !dir.is_dir() {
+            return Ok(Self::bundled());
+        }
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .map_err(|e| {
+                AppError::InvalidConfig(format!("コーパス {} を読み込めません: {e}", dir.display()))
+            })?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .collect();
+        paths.sort();
+        let texts: Vec<String> = paths
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .collect();
+        if texts.iter().all(|text| text.trim().is_empty()) {
+            return Ok(Self::bundled());
+        }
+        Ok(Self {
+            texts: Self::collect(texts),
+        })
+    }
+
+    pub fn bundled() -> Self {
+        Self {
+            texts: Self::collect(BUNDLED_TEXTS.split("\n---\n").map(str::to_string)),
+        }
+    }
+
+    fn collect(texts: impl IntoIterator<Item = String>) -> Vec<CorpusText> {
+        texts
+            .into_iter()
+            .map(|text| normalize_whitespace(&text))
+            .filter(|text| !text.is_empty())
+            .map(|text| CorpusText {
+                character_count: u16::try_from(text.chars().count()).unwrap_or(u16::MAX),
+                text,
+                used_count: 0,
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// メニューの文字数が同じ文章を優先し、出題した回数が少なく文字数が近いものを選ぶ
+    pub fn take_for(&mut self, character_count: u16) -> Option<String> {
+        let bucket = bucket_for(character_count);
+        let item = self.texts.iter_mut().min_by_key(|item| {
+            (
+                bucket_for(item.character_count) != bucket,
+                item.used_count,
+                item.character_count.abs_diff(character_count),
+            )
+        })?;
+        item.used_count += 1;
+        Some(item.text.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_texts_are_available() {
+        let corpus = OfflineCorpus::bundled();
+        assert!(corpus.len() >= 4);
+        assert!(corpus.texts.iter().all(|item| item.character_count >= 200));
+    }
+
+    #[test]
+    fn texts_rotate_preferring_the_selected_length() {
+        let mut corpus = OfflineCorpus {
+            texts: OfflineCorpus::collect(["あ".repeat(400), "い".repeat(700), "う".repeat(380)]),
+        };
+        assert_eq!(corpus.take_for(720), Some("い".repeat(700)));
+        assert_eq!(corpus.take_for(400), Some("あ".repeat(400)));
+        assert_eq!(corpus.take_for(400), Some("う".repeat(380)));
+        assert_eq!(corpus.take_for(720), Some("い".repeat(700)));
+    }
+}
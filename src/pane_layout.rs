@@ -0,0 +1,170 @@
+use crate::config::PaneOrientation;
+use ratatui::layout::{Constraint, Layout, Rect};
+
+/// 原文欄の幅の下限と上限 (%)。どちらの欄も読み書きできる幅を残す
+const MIN_ORIGINAL_PERCENT: u16 = 20;
+const MAX_ORIGINAL_PERCENT: u16 = 80;
+/// `<` / `>` 1 回で動かす幅 (%)
+const RESIZE_STEP: u16 = 5;
+pub const DEFAULT_ORIGINAL_PERCENT: u16 = 50;
+/// 評価結果の欄を出すときに、要約欄の側から割り当てる割合 (%)
+const RESULT_PANE_PERCENT: u16 = 50;
+
+/// トレーニング画面の原文欄と要約欄の並べ方と比率
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaneLayout {
+    original_percent: u16,
+    pub orientation: PaneOrientation,
+}
+
+/// 各欄の領域。評価結果を欄に出さないときは `result` が `None`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaneAreas {
+    pub original: Rect,
+    pub answer: Rect,
+    pub result: Option<Rect>,
+}
+
+/// 入力中に要約欄だけを全画面に出す集中モードの状態
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ZenMode {
+    #[default]
+    Off,
+    /// 要約欄だけを表示する
+    Writing,
+    /// 一時的に原文欄だけを表示する
+    Peeking,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self::new(DEFAULT_ORIGINAL_PERCENT, PaneOrientation::default())
+    }
+}
+
+impl PaneLayout {
+    /// 範囲外の比率は上限・下限に収める
+    pub fn new(original_percent: u16, orientation: PaneOrientation) -> Self {
+        Self {
+            original_percent: original_percent.clamp(MIN_ORIGINAL_PERCENT, MAX_ORIGINAL_PERCENT),
+            orientation,
+        }
+    }
+
+    pub fn original_percent(self) -> u16 {
+        self.original_percent
+    }
+
+    /// 原文欄を広げる (`true`) か狭める。上下に並べているときは高さを変える。端に達していれば変わらない
+    pub fn resized(self, widen_original: bool) -> Self {
+        let percent = if widen_original {
+            self.original_percent.saturating_add(RESIZE_STEP)
+        } else {
+            self.original_percent.saturating_sub(RESIZE_STEP)
+        };
+        Self::new(percent, self.orientation)
+    }
+
+    /// 左右と上下の並べ方を切り替える。比率はそのまま使う
+    pub fn toggled(self) -> Self {
+        let orientation = match self.orientation {
+            PaneOrientation::Horizontal => PaneOrientation::Vertical,
+            PaneOrientation::Vertical => PaneOrientation::Horizontal,
+        };
+        Self {
+            orientation,
+            ..self
+        }
+    }
+
+    /// 原文欄と要約欄に分け、`with_result` なら要約欄の下に評価結果の欄を加える
+    pub fn areas(self, area: Rect, with_result: bool) -> PaneAreas {
+        let constraints = [
+            Constraint::Percentage(self.original_percent),
+            Constraint::Percentage(100 - self.original_percent),
+        ];
+        let [original, rest] = match self.orientation {
+            PaneOrientation::Horizontal => Layout::horizontal(constraints).areas(area),
+            PaneOrientation::Vertical => Layout::vertical(constraints).areas(area),
+        };
+        if !with_result {
+            return PaneAreas {
+                original,
+                answer: rest,
+                result: None,
+            };
+        }
+        let [answer, result] = Layout::vertical([
+            Constraint::Percentage(100 - RESULT_PANE_PERCENT),
+            Constraint::Percentage(RESULT_PANE_PERCENT),
+        ])
+        .areas(rest);
+        PaneAreas {
+            original,
+            answer,
+            result: Some(result),
+        }
+    }
+}
+
+impl PaneAreas {
+    /// 集中モードで 1 つの欄だけを `area` 全体に広げる。隠す欄は高さ 0 にする
+    pub fn single(area: Rect, original: bool) -> Self {
+        let hidden = Rect::new(area.x, area.y, area.width, 0);
+        let (original, answer) = if original {
+            (area, hidden)
+        } else {
+            (hidden, area)
+        };
+        Self {
+            original,
+            answer,
+            result: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ratio_is_clamped_and_moves_in_steps() {
+        let layout = PaneLayout::new(5, PaneOrientation::Horizontal);
+        assert_eq!(layout.original_percent(), MIN_ORIGINAL_PERCENT);
+        let layout = PaneLayout::new(95, PaneOrientation::Horizontal);
+        assert_eq!(layout.original_percent(), MAX_ORIGINAL_PERCENT);
+        assert_eq!(layout.resized(true), layout);
+        let layout = PaneLayout::default().resized(true);
+        assert_eq!(layout.original_percent(), 55);
+        assert_eq!(layout.resized(false), PaneLayout::default());
+        assert_eq!(layout.toggled().toggled(), layout);
+    }
+
+    #[test]
+    fn areas_follow_the_ratio_and_orientation() {
+        let area = Rect::new(0, 0, 100, 20);
+        let horizontal = PaneLayout::new(30, PaneOrientation::Horizontal).areas(area, false);
+        assert_eq!(
+            (horizontal.original.width, horizontal.answer.width),
+            (30, 70)
+        );
+        assert_eq!(horizontal.answer.x, 30);
+        assert_eq!(horizontal.result, None);
+
+        let vertical = PaneLayout::new(50, PaneOrientation::Vertical).areas(area, true);
+        assert_eq!(vertical.original.height, 10);
+        assert_eq!(vertical.original.width, 100);
+        assert_eq!((vertical.answer.y, vertical.answer.height), (10, 5));
+        assert_eq!(vertical.result.map(|result| result.y), Some(15));
+    }
+
+    #[test]
+    fn single_pane_takes_the_whole_area() {
+        let area = Rect::new(0, 0, 80, 24);
+        let writing = PaneAreas::single(area, false);
+        assert_eq!(writing.answer, area);
+        assert_eq!(writing.original.height, 0);
+        assert_eq!(PaneAreas::single(area, true).original, area);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::storage;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PendingText {
+    pub text: String,
+    pub character_count: u16,
+    pub added_at: DateTime<Local>,
+}
+
+/// 後回しにした文章や、要約せずに終了した文章 (未使用テキスト) の待ち行列。
+/// 新しく生成する前にここから取り出す
+#[derive(Serialize, Deserialize, Default)]
+pub struct PendingQueue {
+    pub items: Vec<PendingText>,
+}
+
+impl PendingQueue {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(storage::load_json(storage::global()?, storage::PENDING_DOCUMENT)?.unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        storage::save_json(storage::global()?, storage::PENDING_DOCUMENT, self)?;
+        Ok(())
+    }
+
+    pub fn push(&mut self, text: String, character_count: u16) {
+        self.items.push(PendingText {
+            text,
+            character_count,
+            added_at: Local::now(),
+        });
+    }
+
+    /// 指定文字数の文章のうち、`before` より前に保留したものを古い順に取り出す
+    pub fn take_for(&mut self, character_count: u16, before: DateTime<Local>) -> Option<String> {
+        let index = self
+            .items
+            .iter()
+            .position(|item| item.character_count == character_count && item.added_at < before)?;
+        Some(self.items.remove(index).text)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn take_for_returns_oldest_matching_item() {
+        let mut queue = PendingQueue::default();
+        queue.push("a".to_string(), 400);
+        queue.push("b".to_string(), 720);
+        queue.push("c".to_string(), 400);
+        let later = Local::now() + chrono::Duration::seconds(1);
+
+        assert_eq!(queue.take_for(400, later), Some("a".to_string()));
+        assert_eq!(queue.take_for(400, later), Some("c".to_string()));
+        assert_eq!(queue.take_for(400, later), None);
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn take_for_skips_items_deferred_in_current_session() {
+        let mut queue = PendingQueue::default();
+        let session_started_at = Local::now() - chrono::Duration::seconds(1);
+        queue.push("deferred now".to_string(), 400);
+
+        assert_eq!(queue.take_for(400, session_started_at), None);
+        assert_eq!(queue.len(), 1);
+    }
+}
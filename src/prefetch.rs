@@ -0,0 +1,169 @@
+use crate::api_client::ApiClient;
+use crate::error::AppError;
+use crate::models::{Difficulty, TextStyle, Weakness};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+/// 先読みに失敗したら、この間は次の先読みを始めない
+const RETRY_AFTER_FAILURE: Duration = Duration::from_mins(1);
+
+/// 先読みした文章を使い回せる出題の条件。文体は生成ごとに選ぶので含めない
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrefetchKey {
+    pub character_count: u16,
+    pub difficulty: Difficulty,
+    pub weakness: Option<Weakness>,
+}
+
+/// 先読みした文章と、生成したときの文体
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrefetchedText {
+    pub text: String,
+    pub style: Option<TextStyle>,
+}
+
+type Generated = (PrefetchKey, Option<TextStyle>, Result<String, AppError>);
+
+/// 要約の提出後に次の文章を tokio タスクで生成しておき、`n` ですぐに出題できるようにする。
+/// 同時に生成するのは 1 件だけで、出題の条件ごとに `capacity` 件までためる
+pub struct Prefetcher {
+    capacity: usize,
+    queue: Vec<(PrefetchKey, PrefetchedText)>,
+    sender: UnboundedSender<Generated>,
+    receiver: UnboundedReceiver<Generated>,
+    running: Option<JoinHandle<()>>,
+    failed_at: Option<Instant>,
+}
+
+impl Prefetcher {
+    /// `capacity` が 0 なら先読みしない
+    pub fn new(capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            capacity,
+            queue: Vec::new(),
+            sender,
+            receiver,
+            running: None,
+            failed_at: None,
+        }
+    }
+
+    fn queued(&self, key: PrefetchKey) -> usize {
+        self.queue
+            .iter()
+            .filter(|(queued, _)| *queued == key)
+            .count()
+    }
+
+    /// `key` の文章を新しく生成し始めてよいか
+    pub fn needs(&self, key: PrefetchKey, now: Instant) -> bool {
+        self.running.is_none()
+            && self.queued(key) < self.capacity
+            && self
+                .failed_at
+                .is_none_or(|failed_at| now.duration_since(failed_at) >= RETRY_AFTER_FAILURE)
+    }
+
+    pub fn start(
+        &mut self,
+        client: ApiClient,
+        key: PrefetchKey,
+        prompt: String,
+        style: Option<TextStyle>,
+    ) {
+        let sender = self.sender.clone();
+        self.running = Some(tokio::spawn(async move {
+            let response = client.generate_text(&prompt).await;
+            // 受信側が先に破棄されていれば結果は不要なので無視する
+            let _ = sender.send((key, style, response));
+        }));
+    }
+
+    /// 生成し終えた文章を受け取ってため、ためた文章を返す。失敗は出題時に生成し直すので知らせない
+    pub fn poll(&mut self) -> Vec<(PrefetchKey, PrefetchedText)> {
+        let mut stored = Vec::new();
+        while let Ok((key, style, response)) = self.receiver.try_recv() {
+            self.running = None;
+            match response {
+                Ok(text) => {
+                    let text = PrefetchedText { text, style };
+                    if self.store(key, text.clone()) {
+                        stored.push((key, text));
+                    }
+                }
+                Err(_) => self.failed_at = Some(Instant::now()),
+            }
+        }
+        stored
+    }
+
+    fn store(&mut self, key: PrefetchKey, text: PrefetchedText) -> bool {
+        self.failed_at = None;
+        if self.queued(key) >= self.capacity {
+            return false;
+        }
+        self.queue.push((key, text));
+        true
+    }
+
+    /// ほかの経路で出題した文章を取り除く
+    pub fn discard(&mut self, text: &str) {
+        self.queue.retain(|(_, queued)| queued.text != text);
+    }
+
+    /// `key` の文章のうち、最も早く生成したものを取り出す
+    pub fn take(&mut self, key: PrefetchKey) -> Option<PrefetchedText> {
+        let index = self.queue.iter().position(|(queued, _)| *queued == key)?;
+        Some(self.queue.remove(index).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(character_count: u16) -> PrefetchKey {
+        PrefetchKey {
+            character_count,
+            difficulty: Difficulty::default(),
+            weakness: None,
+        }
+    }
+
+    fn text(text: &str) -> PrefetchedText {
+        PrefetchedText {
+            text: text.to_string(),
+            style: Some(TextStyle::Official),
+        }
+    }
+
+    #[test]
+    fn queue_is_bounded_per_setting_and_first_in_first_out() {
+        let mut prefetcher = Prefetcher::new(2);
+        let now = Instant::now();
+        prefetcher.store(key(400), text("一つ目"));
+        assert!(prefetcher.needs(key(400), now));
+        prefetcher.store(key(400), text("二つ目"));
+        prefetcher.store(key(400), text("三つ目"));
+        prefetcher.store(key(720), text("長い文章"));
+        assert!(!prefetcher.needs(key(400), now));
+        assert!(prefetcher.needs(key(720), now));
+
+        assert_eq!(prefetcher.take(key(400)), Some(text("一つ目")));
+        assert_eq!(prefetcher.take(key(400)), Some(text("二つ目")));
+        assert_eq!(prefetcher.take(key(400)), None);
+        assert_eq!(prefetcher.take(key(720)), Some(text("長い文章")));
+        assert!(!Prefetcher::new(0).needs(key(400), now));
+    }
+
+    #[test]
+    fn failures_pause_prefetching() {
+        let mut prefetcher = Prefetcher::new(1);
+        let now = Instant::now();
+        prefetcher.failed_at = Some(now);
+        assert!(!prefetcher.needs(key(400), now + Duration::from_secs(10)));
+        assert!(prefetcher.needs(key(400), now + RETRY_AFTER_FAILURE));
+    }
+}
@@ -0,0 +1,70 @@
+use crate::config;
+use crate::error::AppError;
+use serde::Deserialize;
+use std::fs;
+
+const PROMPTS_FILE_NAME: &str = "prompts.toml";
+
+/// `prompts.toml` に書いた文章生成と評価のプロンプト。書いていないものは組み込みのプロンプトを使う
+#[derive(Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct PromptTemplates {
+    /// `{char_count}` を出題する文字数に置き換える
+    #[serde(default)]
+    pub generation: Option<String>,
+    /// `{original}` を原文、`{summary}` を要約に置き換える
+    #[serde(default)]
+    pub evaluation: Option<String>,
+}
+
+impl PromptTemplates {
+    /// 空白だけのテンプレートは書かなかったものとみなす
+    pub fn parse(contents: &str) -> Result<Self, AppError> {
+        let templates: Self = toml::from_str(contents).map_err(|e| {
+            AppError::InvalidConfig(format!("{PROMPTS_FILE_NAME} の解析に失敗しました: {e}"))
+        })?;
+        Ok(Self {
+            generation: templates.generation.filter(|t| !t.trim().is_empty()),
+            evaluation: templates.evaluation.filter(|t| !t.trim().is_empty()),
+        })
+    }
+
+    /// 文章生成のテンプレートがあれば、文字数を差し込んだプロンプトを返す
+    pub fn generation_prompt(&self, character_count: u16) -> Option<String> {
+        self.generation
+            .as_ref()
+            .map(|template| template.replace("{char_count}", &character_count.to_string()))
+    }
+}
+
+/// 設定ディレクトリの `prompts.toml` を読む。ファイルがないときや読めないときは組み込みのプロンプトを使う
+pub fn load_prompts() -> PromptTemplates {
+    config::app_config_dir()
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(PROMPTS_FILE_NAME)).ok())
+        .and_then(|contents| PromptTemplates::parse(&contents).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates_fill_char_count_and_skip_blank_entries() {
+        let templates = PromptTemplates::parse(
+            "generation = \"{char_count}字の社説を書いてください。\"\nevaluation = \"  \"\n",
+        )
+        .unwrap_or_default();
+        assert_eq!(
+            templates.generation_prompt(720).as_deref(),
+            Some("720字の社説を書いてください。")
+        );
+        assert_eq!(templates.evaluation, None);
+
+        assert_eq!(
+            PromptTemplates::parse("").unwrap_or_default(),
+            PromptTemplates::default()
+        );
+        assert!(PromptTemplates::parse("generation = 1").is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+pub const QUESTION_COUNT: usize = 3;
+pub const CHOICE_COUNT: usize = 4;
+const CHOICE_LABELS: [&str; CHOICE_COUNT] = ["A", "B", "C", "D"];
+
+/// 本文と 4 択の読解問題
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Quiz {
+    pub passage: String,
+    pub questions: Vec<QuizQuestion>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct QuizQuestion {
+    pub question: String,
+    pub choices: Vec<String>,
+    /// 正解の選択肢の番号 (0 始まり)
+    pub answer: usize,
+}
+
+pub fn build_quiz_prompt(difficulty_hint: &str, character_count: u16) -> String {
+    let instruction = format!(
+        "{difficulty_hint}{character_count}文字程度の説明文と、その内容の理解を確かめる4択問題を{QUESTION_COUNT}問作成してください。\
+         選択肢は本文を読まなければ選べないものにし、正解は1つだけにしてください。\
+         次の JSON だけを出力してください。answer は正解の選択肢の番号 (0 から 3) です。\
+         {{\"passage\": \"本文\", \"questions\": [{{\"question\": \"問い\", \"choices\": [\"選択肢1\", \"選択肢2\", \"選択肢3\", \"選択肢4\"], \"answer\": 0}}]}}"
+    );
+    instruction.repeat(2)
+}
+
+/// 応答から JSON を取り出して問題を読み取る。選択肢が 4 つでない問題や正解の番号が範囲外の問題は不正とする
+pub fn parse_quiz(response: &str) -> Result<Quiz, AppError> {
+    let (Some(start), Some(end)) = (response.find('{'), response.rfind('}')) else {
+        return Err(AppError::InvalidQuiz(
+            "JSON が含まれていません。".to_string(),
+        ));
+    };
+    let quiz: Quiz = serde_json::from_str(response.get(start..=end).unwrap_or_default())?;
+    if quiz.passage.trim().is_empty() {
+        return Err(AppError::InvalidQuiz("本文が空です。".to_string()));
+    }
+    if quiz.questions.is_empty() {
+        return Err(AppError::InvalidQuiz("問題がありません。".to_string()));
+    }
+    for (index, question) in quiz.questions.iter().enumerate() {
+        if question.choices.len() != CHOICE_COUNT || question.answer >= CHOICE_COUNT {
+            return Err(AppError::InvalidQuiz(format!(
+                "問{} の選択肢または正解が不正です。",
+                index + 1
+            )));
+        }
+    }
+    Ok(quiz)
+}
+
+/// 4 択問題の解答の進み具合。問題を生成するまでは `questions` が空
+#[derive(Default)]
+pub struct QuizSession {
+    pub questions: Vec<QuizQuestion>,
+    pub current: usize,
+    pub selected: usize,
+    pub answers: Vec<usize>,
+}
+
+impl QuizSession {
+    pub fn start(&mut self, questions: Vec<QuizQuestion>) {
+        *self = Self {
+            questions,
+            ..Self::default()
+        };
+    }
+
+    /// 問題があり、まだ全問に答えていない
+    pub fn is_answering(&self) -> bool {
+        !self.questions.is_empty() && self.answers.len() < self.questions.len()
+    }
+
+    pub fn current_question(&self) -> Option<&QuizQuestion> {
+        self.questions.get(self.current)
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % CHOICE_COUNT;
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + CHOICE_COUNT - 1) % CHOICE_COUNT;
+    }
+
+    /// 選んでいる選択肢で答え、次の問題に進む。全問に答えたら `true`
+    pub fn confirm(&mut self) -> bool {
+        if !self.is_answering() {
+            return false;
+        }
+        self.answers.push(self.selected);
+        self.current += 1;
+        self.selected = 0;
+        !self.is_answering()
+    }
+
+    pub fn correct_count(&self) -> usize {
+        self.questions
+            .iter()
+            .zip(&self.answers)
+            .filter(|(question, answer)| question.answer == **answer)
+            .count()
+    }
+
+    /// 3 分の 2 以上正解で合格
+    pub fn passed(&self) -> bool {
+        self.correct_count() * 3 >= self.questions.len() * 2
+    }
+
+    /// 履歴に要約の代わりに残す解答 (例: `A, C, B`)
+    pub fn format_answers(&self) -> String {
+        self.answers
+            .iter()
+            .map(|&answer| choice_label(answer))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// 評価結果に表示する採点
+    pub fn format_result(&self) -> String {
+        let mut text = format!(
+            "- 4択問題: {}/{} 問正解\n",
+            self.correct_count(),
+            self.questions.len()
+        );
+        for (index, (question, &answer)) in self.questions.iter().zip(&self.answers).enumerate() {
+            let mark = if question.answer == answer {
+                "○"
+            } else {
+                "×"
+            };
+            let correct = question
+                .choices
+                .get(question.answer)
+                .map(String::as_str)
+                .unwrap_or_default();
+            let _ = write!(
+                text,
+                "\n{mark} 問{}: {}\n  あなたの解答: {}\n  正解: {} {correct}\n",
+                index + 1,
+                question.question,
+                choice_label(answer),
+                choice_label(question.answer),
+            );
+        }
+        text
+    }
+}
+
+pub fn choice_label(index: usize) -> &'static str {
+    CHOICE_LABELS.get(index).copied().unwrap_or("?")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = r#"```json
+{"passage": "本文です。", "questions": [
+  {"question": "問い1", "choices": ["a", "b", "c", "d"], "answer": 1},
+  {"question": "問い2", "choices": ["a", "b", "c", "d"], "answer": 0},
+  {"question": "問い3", "choices": ["a", "b", "c", "d"], "answer": 3}
+]}
+```"#;
+
+    #[test]
+    fn parse_quiz_validates_choices_and_answer() {
+        let quiz = parse_quiz(RESPONSE);
+        assert_eq!(quiz.as_ref().map(|quiz| quiz.questions.len()).ok(), Some(3));
+        assert!(parse_quiz(&RESPONSE.replace("\"answer\": 3", "\"answer\": 4")).is_err());
+        assert!(
+            parse_quiz(&RESPONSE.replace(
+                "[\"a\", \"b\", \"c\", \"d\"], \"answer\": 0",
+                "[\"a\"], \"answer\": 0"
+            ))
+            .is_err()
+        );
+        assert!(parse_quiz("問題を作れませんでした").is_err());
+    }
+
+    #[test]
+    fn session_grades_answers_locally() -> Result<(), String> {
+        let quiz = parse_quiz(RESPONSE).map_err(|e| e.to_string())?;
+        let mut session = QuizSession::default();
+        if session.is_answering() {
+            return Err("session answered before it started".to_string());
+        }
+        session.start(quiz.questions);
+
+        session.select_next();
+        let mut finished = vec![session.confirm()];
+        session.select_previous();
+        session.select_previous();
+        finished.extend([session.confirm(), session.confirm(), session.confirm()]);
+        if finished != [false, false, true, false] {
+            return Err(format!("unexpected progress: {finished:?}"));
+        }
+
+        if session.format_answers() != "B, C, A" {
+            return Err(format!("unexpected answers: {}", session.format_answers()));
+        }
+        if session.correct_count() != 1 || session.passed() {
+            return Err(format!(
+                "unexpected grade: {} correct",
+                session.correct_count()
+            ));
+        }
+        if !session.format_result().starts_with("- 4択問題: 1/3 問正解") {
+            return Err(format!("unexpected result: {}", session.format_result()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+pub struct Rank {
+    pub name: &'static str,
+    /// これまでの合格回数
+    pub passes: usize,
+    /// 評価を受けた結果すべての、3 観点の平均点
+    pub average: f64,
+}
+
+/// 合格回数と平均点の両方を満たすと上がる段位。一度上がった段位は下がらない
+pub const RANKS: [Rank; 7] = [
+    Rank {
+        name: "見習い",
+        passes: 0,
+        average: 0.0,
+    },
+    Rank {
+        name: "初段",
+        passes: 10,
+        average: 3.0,
+    },
+    Rank {
+        name: "二段",
+        passes: 25,
+        average: 3.2,
+    },
+    Rank {
+        name: "三段",
+        passes: 50,
+        average: 3.4,
+    },
+    Rank {
+        name: "四段",
+        passes: 100,
+        average: 3.6,
+    },
+    Rank {
+        name: "五段",
+        passes: 200,
+        average: 3.8,
+    },
+    Rank {
+        name: "師範",
+        passes: 365,
+        average: 4.0,
+    },
+];
+
+/// 段位が上がった記録
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RankUp {
+    pub rank: usize,
+    pub achieved_at: DateTime<Local>,
+}
+
+pub fn rank_at(index: usize) -> &'static Rank {
+    RANKS.get(index).or(RANKS.last()).unwrap_or(&RANKS[0])
+}
+
+pub fn next_rank(index: usize) -> Option<&'static Rank> {
+    RANKS.get(index + 1)
+}
+
+/// 合格回数と平均点 (評価がなければ `None`) から決まる段位
+pub fn rank_for(passes: usize, average: Option<f64>) -> usize {
+    let average = average.unwrap_or(0.0);
+    RANKS
+        .iter()
+        .rposition(|rank| passes >= rank.passes && average >= rank.average)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_needs_both_passes_and_average() {
+        assert_eq!(rank_for(0, None), 0);
+        assert_eq!(rank_for(10, None), 0);
+        assert_eq!(rank_for(10, Some(3.0)), 1);
+        assert_eq!(rank_for(60, Some(3.3)), 2);
+        assert_eq!(rank_for(1000, Some(5.0)), RANKS.len() - 1);
+        assert_eq!(rank_at(99).name, "師範");
+        assert!(next_rank(RANKS.len() - 1).is_none());
+    }
+}
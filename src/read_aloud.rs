@@ -0,0 +1,151 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_TARGET_CHARS_PER_MINUTE: u32 = 300;
+/// 改行のない長い文章は、この文字数を目安に文の区切りでまとめて1段落にする
+const MAX_PARAGRAPH_CHARS: usize = 120;
+
+/// 音読1回分の記録
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReadAloudResult {
+    pub timestamp: DateTime<Local>,
+    pub characters: usize,
+    pub elapsed_secs: f64,
+    pub target_secs: f64,
+}
+
+impl ReadAloudResult {
+    pub fn chars_per_minute(&self) -> f64 {
+        if self.elapsed_secs <= 0.0 {
+            return 0.0;
+        }
+        count_to_f64(self.characters) * 60.0 / self.elapsed_secs
+    }
+}
+
+/// 段落ごとに目標ペースと実際の所要時間を比べる音読セッション
+pub struct ReadAloudSession {
+    pub paragraphs: Vec<String>,
+    pub current: usize,
+    target_chars_per_minute: u32,
+    paragraph_started_at: Instant,
+    laps: Vec<Duration>,
+}
+
+impl ReadAloudSession {
+    pub fn new(text: &str, target_chars_per_minute: u32) -> Self {
+        Self {
+            paragraphs: split_paragraphs(text),
+            current: 0,
+            target_chars_per_minute: target_chars_per_minute.max(1),
+            paragraph_started_at: Instant::now(),
+            laps: Vec::new(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.paragraphs.len()
+    }
+
+    pub fn current_paragraph(&self) -> Option<&str> {
+        self.paragraphs.get(self.current).map(String::as_str)
+    }
+
+    pub fn target_for(&self, paragraph: &str) -> Duration {
+        let chars = count_to_f64(paragraph.chars().count());
+        Duration::from_secs_f64(chars * 60.0 / f64::from(self.target_chars_per_minute))
+    }
+
+    pub fn current_elapsed(&self) -> Duration {
+        self.paragraph_started_at.elapsed()
+    }
+
+    /// 今の段落を読み終えたことを記録し、次の段落に進む
+    pub fn advance(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        self.laps.push(self.paragraph_started_at.elapsed());
+        self.current += 1;
+        self.paragraph_started_at = Instant::now();
+    }
+
+    pub fn result(&self) -> ReadAloudResult {
+        let characters = self
+            .paragraphs
+            .iter()
+            .map(|paragraph| paragraph.chars().count())
+            .sum();
+        let target: Duration = self
+            .paragraphs
+            .iter()
+            .map(|paragraph| self.target_for(paragraph))
+            .sum();
+        ReadAloudResult {
+            timestamp: Local::now(),
+            characters,
+            elapsed_secs: self.laps.iter().sum::<Duration>().as_secs_f64(),
+            target_secs: target.as_secs_f64(),
+        }
+    }
+}
+
+fn count_to_f64(count: usize) -> f64 {
+    f64::from(u32::try_from(count).unwrap_or(u32::MAX))
+}
+
+/// 改行で段落に分け、長すぎる段落は句点で区切って読みやすい長さにまとめる
+pub fn split_paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let mut chunk = String::new();
+        for sentence in line.split_inclusive('。') {
+            if !chunk.is_empty()
+                && chunk.chars().count() + sentence.chars().count() > MAX_PARAGRAPH_CHARS
+            {
+                paragraphs.push(std::mem::take(&mut chunk));
+            }
+            chunk.push_str(sentence);
+        }
+        if !chunk.is_empty() {
+            paragraphs.push(chunk);
+        }
+    }
+    paragraphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_paragraphs_uses_lines_and_long_sentence_groups() {
+        let long_sentence = format!("{}。", "あ".repeat(80));
+        let text = format!("短い段落。\n\n{long_sentence}{long_sentence}\n");
+        let paragraphs = split_paragraphs(&text);
+        assert_eq!(paragraphs.len(), 3);
+        assert_eq!(paragraphs.first().map(String::as_str), Some("短い段落。"));
+        assert_eq!(paragraphs.get(1), Some(&long_sentence));
+    }
+
+    #[test]
+    fn session_advances_until_finished() {
+        let mut session = ReadAloudSession::new("一段落目。\n二段落目。", 300);
+        assert_eq!(session.paragraphs.len(), 2);
+        assert_eq!(
+            session.target_for("あ".repeat(300).as_str()),
+            Duration::from_mins(1)
+        );
+
+        session.advance();
+        assert_eq!(session.current_paragraph(), Some("二段落目。"));
+        session.advance();
+        assert!(session.is_finished());
+        session.advance();
+
+        let result = session.result();
+        assert_eq!(result.characters, 10);
+        assert!((result.target_secs - 2.0).abs() < 1e-9);
+    }
+}
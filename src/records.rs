@@ -0,0 +1,200 @@
+use crate::config::LabelLanguage;
+use crate::i18n::{tr, trf};
+use crate::models::TrainingResult;
+use chrono::{DateTime, Local, NaiveDate};
+use std::collections::BTreeMap;
+
+/// 3 観点の満点の合計
+pub const MAX_TOTAL_SCORE: u8 = 15;
+
+/// 自己ベストの種類
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordKind {
+    /// 文字数ごとの最速合格
+    FastestPass(u16),
+    LongestPass,
+    HighestScore,
+    MostRoundsInDay,
+}
+
+impl RecordKind {
+    pub fn label(self, locale: LabelLanguage) -> String {
+        match self {
+            Self::FastestPass(count) => trf(locale, "{} 文字の最速合格", &[&count]),
+            Self::LongestPass => tr(locale, "最長の合格").to_string(),
+            Self::HighestScore => tr(locale, "最高得点").to_string(),
+            Self::MostRoundsInDay => tr(locale, "1日の最多回数").to_string(),
+        }
+    }
+}
+
+/// 記録の値と、その記録を出した日時
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Record<T> {
+    pub value: T,
+    pub achieved_at: DateTime<Local>,
+}
+
+/// 統計の結果から求めた自己ベスト。同じ値なら先に出した記録を残す
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PersonalRecords {
+    /// 文字数ごとの合格までの最短秒数 (文字数の小さい順)
+    pub fastest_passes: BTreeMap<u16, Record<u32>>,
+    /// 合格した原文の最大文字数
+    pub longest_pass: Option<Record<u16>>,
+    /// AI 評価の 3 観点の合計点の最高値
+    pub highest_score: Option<Record<u8>>,
+    /// 1 日に行ったトレーニングの最多回数
+    pub most_rounds_in_day: Option<Record<usize>>,
+}
+
+impl PersonalRecords {
+    pub fn from_results(results: &[TrainingResult]) -> Self {
+        let mut records = Self::default();
+        let mut rounds: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        for result in results {
+            let at = result.timestamp;
+            if result.passed {
+                if let (Some(count), Some(secs)) = (result.character_count, result.duration_secs) {
+                    let fastest = records.fastest_passes.entry(count).or_insert(Record {
+                        value: secs,
+                        achieved_at: at,
+                    });
+                    if secs < fastest.value {
+                        *fastest = Record {
+                            value: secs,
+                            achieved_at: at,
+                        };
+                    }
+                }
+                if let Some(count) = result.character_count {
+                    keep_best(&mut records.longest_pass, count, at);
+                }
+            }
+            if let Some(scores) = &result.evaluation {
+                let total = scores
+                    .importance
+                    .saturating_add(scores.conciseness)
+                    .saturating_add(scores.accuracy);
+                keep_best(&mut records.highest_score, total, at);
+            }
+            let day = rounds.entry(at.date_naive()).or_default();
+            *day += 1;
+            keep_best(&mut records.most_rounds_in_day, *day, at);
+        }
+        records
+    }
+
+    /// 最後の結果で更新した記録。初めて付いた記録 (比べる相手がないもの) は含めない
+    pub fn broken_by_last(results: &[TrainingResult]) -> Vec<RecordKind> {
+        let Some((_, earlier)) = results.split_last() else {
+            return Vec::new();
+        };
+        let before = Self::from_results(earlier);
+        let after = Self::from_results(results);
+        let mut broken: Vec<RecordKind> = after
+            .fastest_passes
+            .iter()
+            .filter(|(count, record)| {
+                before
+                    .fastest_passes
+                    .get(count)
+                    .is_some_and(|previous| record.value < previous.value)
+            })
+            .map(|(&count, _)| RecordKind::FastestPass(count))
+            .collect();
+        if improved(before.longest_pass, after.longest_pass) {
+            broken.push(RecordKind::LongestPass);
+        }
+        if improved(before.highest_score, after.highest_score) {
+            broken.push(RecordKind::HighestScore);
+        }
+        if improved(before.most_rounds_in_day, after.most_rounds_in_day) {
+            broken.push(RecordKind::MostRoundsInDay);
+        }
+        broken
+    }
+}
+
+fn keep_best<T: Ord + Copy>(record: &mut Option<Record<T>>, value: T, at: DateTime<Local>) {
+    if record.is_none_or(|record| value > record.value) {
+        *record = Some(Record {
+            value,
+            achieved_at: at,
+        });
+    }
+}
+
+fn improved<T: Ord + Copy>(before: Option<Record<T>>, after: Option<Record<T>>) -> bool {
+    matches!((before, after), (Some(before), Some(after)) if after.value > before.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EvaluationScores;
+    use chrono::TimeZone;
+
+    fn result(day: u32, passed: bool, count: u16, secs: u32, score: u8) -> TrainingResult {
+        TrainingResult {
+            timestamp: Local
+                .with_ymd_and_hms(2026, 7, day, 12, 0, 0)
+                .single()
+                .unwrap_or_default(),
+            passed,
+            character_count: Some(count),
+            duration_secs: Some(secs),
+            evaluation: Some(EvaluationScores {
+                appropriate: true,
+                importance: score,
+                conciseness: score,
+                accuracy: score,
+                improvement1: String::new(),
+                improvement2: String::new(),
+                improvement3: String::new(),
+                overall_passed: passed,
+            }),
+            ..TrainingResult::default()
+        }
+    }
+
+    #[test]
+    fn records_keep_the_best_value_and_its_date() {
+        let results = [
+            result(1, true, 400, 300, 3),
+            result(2, true, 400, 200, 4),
+            result(2, false, 1440, 100, 5),
+            result(3, true, 720, 400, 2),
+        ];
+        let records = PersonalRecords::from_results(&results);
+        assert_eq!(records.fastest_passes.get(&400).map(|r| r.value), Some(200));
+        assert_eq!(records.longest_pass.map(|r| r.value), Some(720));
+        let day2 = results.get(2).map(|r| r.timestamp);
+        assert_eq!(records.highest_score.map(|r| r.value), Some(15));
+        assert_eq!(records.highest_score.map(|r| r.achieved_at), day2);
+        assert_eq!(
+            records
+                .most_rounds_in_day
+                .map(|r| (r.value, Some(r.achieved_at))),
+            Some((2, day2))
+        );
+    }
+
+    #[test]
+    fn only_improvements_over_an_existing_record_count_as_broken() {
+        let mut results = vec![result(1, true, 400, 300, 3)];
+        assert!(PersonalRecords::broken_by_last(&results).is_empty());
+
+        results.push(result(2, true, 400, 250, 4));
+        assert_eq!(
+            PersonalRecords::broken_by_last(&results),
+            vec![RecordKind::FastestPass(400), RecordKind::HighestScore]
+        );
+
+        results.push(result(2, true, 720, 500, 1));
+        assert_eq!(
+            PersonalRecords::broken_by_last(&results),
+            vec![RecordKind::LongestPass, RecordKind::MostRoundsInDay]
+        );
+    }
+}
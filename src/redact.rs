@@ -0,0 +1,122 @@
+use crate::config::RedactionConfig;
+use crate::error::AppError;
+use regex::{NoExpand, Regex};
+
+const EMAIL_PATTERN: &str = r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}";
+const NUMBER_PATTERN: &str = r"[0-9０-９][0-9０-９,，.．-]*";
+const DEFAULT_REPLACEMENT: &str = "[伏字]";
+
+/// API に送る前に、個人情報に当たる部分を伏字に置き換える
+#[derive(Clone)]
+pub struct Redactor {
+    patterns: Vec<Regex>,
+    replacement: String,
+}
+
+impl Redactor {
+    /// 無効化されている場合は `None` を返す
+    pub fn from_config(config: &RedactionConfig) -> Result<Option<Self>, AppError> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let mut sources: Vec<&str> = Vec::new();
+        if config.emails {
+            sources.push(EMAIL_PATTERN);
+        }
+        if config.numbers {
+            sources.push(NUMBER_PATTERN);
+        }
+        sources.extend(config.patterns.iter().map(String::as_str));
+
+        let patterns = sources
+            .into_iter()
+            .map(|source| {
+                Regex::new(source).map_err(|e| {
+                    AppError::InvalidConfig(format!("伏字パターン `{source}` が不正です: {e}"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(Self {
+            patterns,
+            replacement: config
+                .replacement
+                .clone()
+                .unwrap_or_else(|| DEFAULT_REPLACEMENT.to_string()),
+        }))
+    }
+
+    /// 置き換え文字列の `$` はキャプチャの参照にせず、そのまま入れる
+    pub fn redact(&self, text: &str) -> String {
+        self.patterns.iter().fold(text.to_string(), |acc, pattern| {
+            pattern
+                .replace_all(&acc, NoExpand(self.replacement.as_str()))
+                .into_owned()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redactor(config: &RedactionConfig) -> Option<Redactor> {
+        Redactor::from_config(config).ok().flatten()
+    }
+
+    #[test]
+    fn disabled_config_builds_no_redactor() {
+        assert!(redactor(&RedactionConfig::default()).is_none());
+    }
+
+    #[test]
+    fn redacts_emails_numbers_and_custom_names() -> Result<(), String> {
+        let config = RedactionConfig {
+            enabled: true,
+            emails: true,
+            numbers: true,
+            patterns: vec!["山田(太郎)?".to_string()],
+            replacement: None,
+        };
+        let redactor = redactor(&config).ok_or("redactor should be built")?;
+
+        let redacted = redactor
+            .redact("山田太郎 (taro@example.co.jp) の売上は 1,200 万円、前年は１５００万円。");
+        if redacted != "[伏字] ([伏字]) の売上は [伏字] 万円、前年は[伏字]万円。"
+        {
+            return Err(format!("unexpected redaction: {redacted}"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn replacement_with_dollar_is_inserted_literally() -> Result<(), String> {
+        let config = RedactionConfig {
+            enabled: true,
+            patterns: vec!["(山田)".to_string()],
+            replacement: Some("$1$$".to_string()),
+            ..RedactionConfig::default()
+        };
+        let redactor = redactor(&config).ok_or("redactor should be built")?;
+
+        let redacted = redactor.redact("山田さん");
+        if redacted != "$1$$さん" {
+            return Err(format!("unexpected redaction: {redacted}"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_pattern_is_reported() {
+        let config = RedactionConfig {
+            enabled: true,
+            patterns: vec!["(".to_string()],
+            ..RedactionConfig::default()
+        };
+        assert!(matches!(
+            Redactor::from_config(&config),
+            Err(AppError::InvalidConfig(_))
+        ));
+    }
+}
@@ -1,15 +1,50 @@
-use crate::models::{DailyStats, WeeklyStats};
+use crate::config::{LabelLanguage, ReportConfig};
+use crate::exam;
+use crate::i18n::{tr, trf};
+use crate::models::{
+    BadgeType, DailyStats, EvaluationSummary, LengthGenreStats, TextStyle, WeeklyStats,
+};
+use crate::ranks;
+use crate::records;
+use crate::score_normalization::ScoreScale;
 use crate::stats::{TrainingStats, required_exp_for_level};
-use chrono::{Datelike, Local, NaiveDate};
+use crate::stats_analysis;
+use crate::summary_metrics::SummaryMetrics;
+use crate::theme::Theme;
+use chrono::{DateTime, Datelike, Local, NaiveDate};
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    symbols::Marker,
+    widgets::{
+        Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Sparkline, Tabs, Wrap,
+    },
 };
 use std::collections::HashMap;
 
 const REPORT_DAYS: usize = 180;
+/// 1 年表示のヒートマップに含める日数 (52 週)
+const YEAR_DAYS: usize = 52 * 7;
+/// 1 年表示で、練習した日を塗り分ける段階の数
+const YEAR_INTENSITY_LEVELS: usize = 4;
 const WEEKS_TO_SHOW: usize = 4;
 const MAX_BADGES_DISPLAY: usize = 20;
+/// バッジ一覧の 1 枚の幅と高さ (枠を含む)
+const BADGE_CARD_WIDTH: u16 = 28;
+const BADGE_CARD_HEIGHT: u16 = 4;
+const MAX_HEAD_TO_HEAD_DISPLAY: usize = 3;
+const SPEED_HISTORY: usize = 30;
+const EXAM_HISTORY: usize = 5;
+const SPEED_CHART_HEIGHT: u16 = 4;
+/// スコアの内訳で週ごとに表示する週数
+const SCORE_WEEKS: usize = 6;
+/// スコアの内訳の棒の長さ (5 点満点で 1 マス 0.25 点)
+const SCORE_BAR_WIDTH: u16 = 20;
+const MAX_SCORE: f32 = 5.0;
+const TREND_DAYS: usize = 90;
+/// 推移グラフで合格率と回数を数える移動窓の日数
+const TREND_WINDOW: usize = 7;
+const TREND_CHART_HEIGHT: u16 = 12;
 const HEATMAP_CELL: &str = "■";
 const HEATMAP_EMPTY_CELL: &str = "·";
 const HEATMAP_LABEL_SUFFIX: &str = " ";
@@ -58,14 +93,106 @@ fn get_buddy_ascii(level: u32) -> &'static str {
     art.strip_prefix('\n').unwrap_or(art)
 }
 
-fn render_badge_section(stats: &TrainingStats) -> Vec<Line<'static>> {
-    let mut lines = Vec::new();
+/// 今の段位と、次の段位までの合格回数・平均点
+fn rank_line(ctx: &ReportContext) -> Line<'static> {
+    let (stats, locale, theme) = (ctx.stats, ctx.locale, ctx.theme);
+    let mut spans = vec![
+        Span::styled(
+            tr(locale, "🥋 段位: "),
+            Style::default().fg(theme.title).bold(),
+        ),
+        Span::styled(
+            ranks::rank_at(stats.rank).name,
+            Style::default().fg(theme.title).bold(),
+        ),
+    ];
+    let (passes, average) = stats.rank_progress();
+    let average = average.map_or_else(|| "-".to_string(), |average| format!("{average:.2}"));
+    match ranks::next_rank(stats.rank) {
+        Some(next) => spans.push(Span::raw(trf(
+            locale,
+            " (次の{}まで: 合格 {}/{} 回, 平均 {}/{} 点)",
+            &[
+                &next.name,
+                &passes,
+                &next.passes,
+                &average,
+                &format!("{:.1}", next.average),
+            ],
+        ))),
+        None => spans.push(Span::raw(trf(
+            locale,
+            " (合格 {} 回, 平均 {} 点)",
+            &[&passes, &average],
+        ))),
+    }
+    if let Some(last) = stats.rank_ups.last() {
+        spans.push(Span::styled(
+            trf(
+                locale,
+                "  最後の昇段: {}",
+                &[&last.achieved_at.format("%Y-%m-%d")],
+            ),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// 継続日数・連続正解・今日のお題・目標達成の日数
+fn streak_line(ctx: &ReportContext) -> Line<'static> {
+    let (stats, locale, theme) = (ctx.stats, ctx.locale, ctx.theme);
+    let mut summary_line = vec![
+        Span::styled(
+            tr(locale, "📅 継続日数: "),
+            Style::default().fg(theme.success).bold(),
+        ),
+        Span::styled(
+            trf(locale, "{} 日", &[&stats.current_daily_streak]),
+            Style::default().fg(theme.success).bold(),
+        ),
+        Span::raw(trf(locale, " (最長 {} 日)", &[&stats.longest_daily_streak])),
+        Span::raw("  "),
+        Span::styled(
+            tr(locale, "🔥 連続正解: "),
+            Style::default().fg(theme.highlight).bold(),
+        ),
+        Span::raw(trf(locale, "{} 回", &[&stats.current_streak])),
+    ];
+    if !stats.daily_challenge_days.is_empty() {
+        summary_line.push(Span::raw("  "));
+        summary_line.push(Span::styled(
+            tr(locale, "🗓 今日のお題: "),
+            Style::default().fg(theme.title).bold(),
+        ));
+        summary_line.push(Span::raw(trf(
+            locale,
+            "{} 日",
+            &[&stats.daily_challenge_days.len()],
+        )));
+    }
+    if !stats.goal_days.is_empty() {
+        summary_line.push(Span::raw("  "));
+        summary_line.push(Span::styled(
+            tr(locale, "🎯 目標達成: "),
+            Style::default().fg(theme.secondary).bold(),
+        ));
+        summary_line.push(Span::raw(trf(locale, "{} 日", &[&stats.goal_days.len()])));
+    }
+    Line::from(summary_line)
+}
+
+fn render_badge_section(ctx: &ReportContext) -> Vec<Line<'static>> {
+    let (stats, locale, theme) = (ctx.stats, ctx.locale, ctx.theme);
+    let mut lines = vec![streak_line(ctx), rank_line(ctx)];
     let (consecutive_badges, cumulative_badges) = stats.get_badges_by_type();
+    let mut daily_badges = stats.get_daily_streak_badges();
+    daily_badges.extend(stats.get_daily_goal_badges());
 
     if !consecutive_badges.is_empty() {
         let mut badge_line = vec![Span::styled(
-            "🔥 連続正解: ",
-            Style::default().fg(Color::Yellow).bold(),
+            tr(locale, "🔥 連続正解: "),
+            Style::default().fg(theme.highlight).bold(),
         )];
         for badge in consecutive_badges.iter().take(10) {
             badge_line.push(Span::raw(format!(
@@ -79,8 +206,8 @@ fn render_badge_section(stats: &TrainingStats) -> Vec<Line<'static>> {
 
     if !cumulative_badges.is_empty() {
         let mut badge_line = vec![Span::styled(
-            "✨ 累積正解: ",
-            Style::default().fg(Color::Cyan).bold(),
+            tr(locale, "✨ 累積正解: "),
+            Style::default().fg(theme.accent).bold(),
         )];
         for badge in cumulative_badges.iter().take(MAX_BADGES_DISPLAY) {
             badge_line.push(Span::raw(format!(
@@ -92,25 +219,60 @@ fn render_badge_section(stats: &TrainingStats) -> Vec<Line<'static>> {
         lines.push(Line::from(badge_line));
     }
 
-    if !consecutive_badges.is_empty() || !cumulative_badges.is_empty() {
-        lines.push(Line::from(""));
+    if !daily_badges.is_empty() {
+        let mut badge_line = vec![Span::styled(
+            tr(locale, "📅 継続: "),
+            Style::default().fg(theme.success).bold(),
+        )];
+        for badge in daily_badges.iter().take(MAX_BADGES_DISPLAY) {
+            badge_line.push(Span::raw(format!(
+                "{}{} ",
+                badge.get_icon(),
+                badge.get_display_text()
+            )));
+        }
+        lines.push(Line::from(badge_line));
+    }
+
+    let achievement_badges = stats.get_achievement_badges();
+    if !achievement_badges.is_empty() {
+        let mut badge_line = vec![Span::styled(
+            tr(locale, "🏆 チャレンジ: "),
+            Style::default().fg(theme.secondary).bold(),
+        )];
+        for badge in achievement_badges {
+            badge_line.push(Span::raw(format!(
+                "{}{} ",
+                badge.get_icon(),
+                badge.get_display_text()
+            )));
+        }
+        lines.push(Line::from(badge_line));
     }
 
     lines
 }
 
-fn render_evaluation_summary(stats: &TrainingStats) -> Vec<Line<'static>> {
-    let summary = stats.get_recent_evaluation_summary(REPORT_DAYS);
+fn render_evaluation_summary(ctx: &ReportContext) -> Vec<Line<'static>> {
+    let (stats, locale, theme) = (ctx.stats, ctx.locale, ctx.theme);
+    let summary = stats.get_recent_evaluation_summary(REPORT_DAYS, ctx.scale);
     let mut lines = Vec::new();
 
-    lines.push(Line::from(Span::styled(
-        "評価スコア (直近180日)",
-        Style::default().fg(Color::Cyan).bold(),
-    )));
+    lines.push(Line::from(vec![
+        Span::styled(
+            tr(locale, "評価スコア (直近180日)"),
+            Style::default().fg(theme.accent).bold(),
+        ),
+        Span::raw(trf(
+            locale,
+            " {} (n: 切り替え)",
+            &[&tr(locale, ctx.scale.label())],
+        )),
+    ]));
 
     if summary.count == 0 {
-        lines.push(Line::from("評価スコア: なし"));
-        lines.push(Line::from("件数: 0"));
+        lines.push(Line::from(tr(locale, "評価スコア: なし")));
+        lines.push(Line::from(trf(locale, "件数: {}", &[&0])));
         return lines;
     }
 
@@ -124,155 +286,914 @@ fn render_evaluation_summary(stats: &TrainingStats) -> Vec<Line<'static>> {
         return lines;
     };
 
-    lines.push(Line::from(format!(
-        "重要情報: 平均 {:.1} / 中央値 {:.1}",
-        importance.average, importance.median
-    )));
-    lines.push(Line::from(format!(
-        "簡潔性: 平均 {:.1} / 中央値 {:.1}",
-        conciseness.average, conciseness.median
-    )));
-    lines.push(Line::from(format!(
-        "正確性: 平均 {:.1} / 中央値 {:.1}",
-        accuracy.average, accuracy.median
-    )));
-    lines.push(Line::from(format!("件数: {}", summary.count)));
+    for (template, score) in [
+        ("重要情報: 平均 {} / 中央値 {}", importance),
+        ("簡潔性: 平均 {} / 中央値 {}", conciseness),
+        ("正確性: 平均 {} / 中央値 {}", accuracy),
+    ] {
+        lines.push(Line::from(trf(
+            locale,
+            template,
+            &[
+                &format!("{:.1}", score.average),
+                &format!("{:.1}", score.median),
+            ],
+        )));
+    }
+    lines.push(Line::from(trf(locale, "件数: {}", &[&summary.count])));
+    let (retries, recovered) = stats.get_retry_summary();
+    if retries > 0 {
+        lines.push(Line::from(trf(
+            locale,
+            "再挑戦: {} 回 (うち合格 {} 回)",
+            &[&retries, &recovered],
+        )));
+    }
 
     lines
 }
 
-pub fn render_unified_report(frame: &mut Frame, area: Rect, stats: &TrainingStats) {
+/// レポート画面のタブ。`Tab` で順に、数字キーで直接切り替える
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReportTab {
+    #[default]
+    Badges,
+    Heatmap,
+    Weekly,
+    Trends,
+    Scores,
+}
+
+impl ReportTab {
+    pub const ALL: [Self; 5] = [
+        Self::Badges,
+        Self::Heatmap,
+        Self::Weekly,
+        Self::Trends,
+        Self::Scores,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Badges => "バッジ",
+            Self::Heatmap => "ヒートマップ",
+            Self::Weekly => "週次",
+            Self::Trends => "推移",
+            Self::Scores => "スコア",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            Self::Badges => 0,
+            Self::Heatmap => 1,
+            Self::Weekly => 2,
+            Self::Trends => 3,
+            Self::Scores => 4,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        Self::ALL.get(self.index() + 1).copied().unwrap_or_default()
+    }
+
+    /// 数字キー (1 始まり) に対応するタブ
+    pub fn from_number(number: char) -> Option<Self> {
+        let index = usize::try_from(number.to_digit(10)?.checked_sub(1)?).ok()?;
+        Self::ALL.get(index).copied()
+    }
+}
+
+/// レポートの各タブを描くのに使う記録と表示の設定
+pub struct ReportContext<'a> {
+    pub stats: &'a TrainingStats,
+    pub report: &'a ReportConfig,
+    pub locale: LabelLanguage,
+    pub scale: ScoreScale,
+    pub theme: &'a Theme,
+}
+
+/// タブの内容を上から積み重ねて描く、画面より高くなりうる描画先。表示する行だけを画面に写す
+struct Canvas {
+    buffer: Buffer,
+}
+
+impl Canvas {
+    fn new(width: u16) -> Self {
+        Self {
+            buffer: Buffer::empty(Rect::new(0, 0, width, 0)),
+        }
+    }
+
+    fn height(&self) -> u16 {
+        self.buffer.area.height
+    }
+
+    /// 末尾に `height` 行を足し、その領域を返す
+    fn push(&mut self, height: u16) -> Rect {
+        let top = self.height();
+        let area = Rect {
+            height: top.saturating_add(height),
+            ..self.buffer.area
+        };
+        self.buffer.resize(area);
+        Rect::new(0, top, area.width, area.height.saturating_sub(top))
+    }
+
+    fn render(&mut self, widget: impl Widget, height: u16) {
+        let area = self.push(height);
+        widget.render(area, &mut self.buffer);
+    }
+
+    /// `scroll` 行目から `area` に収まる分を写す。`scroll` は内容の最後の行が画面の下端に来るところまでに丸める
+    fn show(&self, buffer: &mut Buffer, area: Rect, scroll: &mut u16) {
+        *scroll = (*scroll).min(self.height().saturating_sub(area.height));
+        let width = area.width.min(self.buffer.area.width);
+        let height = area.height.min(self.height().saturating_sub(*scroll));
+        for row in 0..height {
+            for column in 0..width {
+                let Some(cell) = self.buffer.cell((column, row.saturating_add(*scroll))) else {
+                    continue;
+                };
+                if let Some(target) = buffer.cell_mut((area.x + column, area.y + row)) {
+                    *target = cell.clone();
+                }
+            }
+        }
+    }
+
+    /// `show()` で写し、収まらなければ `area` の右端にスクロールバーを出す
+    fn show_in_frame(&self, frame: &mut Frame, area: Rect, scroll: &mut u16) {
+        self.show(frame.buffer_mut(), area, scroll);
+        let overflow = self.height().saturating_sub(area.height);
+        if overflow > 0 {
+            let mut state =
+                ScrollbarState::new(usize::from(overflow) + 1).position(usize::from(*scroll));
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                area,
+                &mut state,
+            );
+        }
+    }
+}
+
+/// 選んだタブの内容を `scroll` 行目から表示する
+pub fn render_report(
+    frame: &mut Frame,
+    area: Rect,
+    tab: ReportTab,
+    scroll: &mut u16,
+    ctx: &ReportContext,
+) {
     let block = Block::default()
-        .title("レポート (r: 閉じる)")
+        .title(trf(
+            ctx.locale,
+            "レポート {} (Tab/1〜5: 切り替え, j/k: スクロール, n: 素点/補正, r: 閉じる)",
+            &[&ctx.scale.label()],
+        ))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(ctx.theme.accent));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [tab_area, body_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
 
+    let tabs = Tabs::new(
+        ReportTab::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| format!("{}:{}", index + 1, tr(ctx.locale, tab.label()))),
+    )
+    .select(tab.index())
+    .style(Style::default().fg(ctx.theme.dim))
+    .highlight_style(
+        Style::default()
+            .fg(ctx.theme.accent)
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+    );
+    frame.render_widget(tabs, tab_area);
+
+    // スクロールバーと重ならないよう、右端の 1 列を空けて描く
+    let mut canvas = Canvas::new(body_area.width.saturating_sub(1));
+    match tab {
+        ReportTab::Badges => render_badges_tab(&mut canvas, ctx),
+        ReportTab::Heatmap => render_heatmap_tab(&mut canvas, ctx),
+        ReportTab::Weekly => render_weekly_section(&mut canvas, ctx),
+        ReportTab::Trends => render_trends_tab(&mut canvas, ctx),
+        ReportTab::Scores => render_scores_tab(&mut canvas, ctx),
+    }
+    if canvas.height() == 0 {
+        canvas.render(Paragraph::new(tr(ctx.locale, "記録なし")), 1);
+    }
+    canvas.show_in_frame(frame, body_area, scroll);
+}
+
+/// 獲得できるすべてのバッジを種類ごとに格子状に並べる。獲得したものは色付きで獲得日を、
+/// まだのものは灰色で獲得の条件を表示する
+pub fn render_badge_gallery(frame: &mut Frame, area: Rect, scroll: &mut u16, ctx: &ReportContext) {
+    let gallery = ctx.stats.get_badge_gallery();
+    let earned = gallery.iter().filter(|(_, at)| at.is_some()).count();
+    let block = Block::default()
+        .title(trf(
+            ctx.locale,
+            "バッジ一覧 {}/{} (j/k: スクロール, b: レポートに戻る, r: 閉じる)",
+            &[&earned, &gallery.len()],
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ctx.theme.accent));
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let vertical_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Length(6), Constraint::Min(0)])
-        .split(inner);
-    let [top_area, bottom_area] = vertical_layout.as_ref() else {
-        return;
-    };
+    let mut canvas = Canvas::new(inner.width.saturating_sub(1));
+    let columns = usize::from((canvas.buffer.area.width / BADGE_CARD_WIDTH).max(1));
+    for group in gallery.chunk_by(|(a, _), (b, _)| badge_group_label(a) == badge_group_label(b)) {
+        let Some((first, _)) = group.first() else {
+            continue;
+        };
+        let heading = Line::styled(
+            badge_group_label(first),
+            Style::default().fg(ctx.theme.title).bold(),
+        );
+        canvas.render(Paragraph::new(heading), 1);
+        for row in group.chunks(columns) {
+            let row_area = canvas.push(BADGE_CARD_HEIGHT);
+            let cards = Layout::horizontal(
+                row.iter()
+                    .map(|_| Constraint::Length(BADGE_CARD_WIDTH))
+                    .collect::<Vec<_>>(),
+            )
+            .split(row_area);
+            for ((badge_type, earned_at), card_area) in row.iter().zip(cards.iter()) {
+                badge_card(badge_type, *earned_at, ctx.locale, ctx.theme)
+                    .render(*card_area, &mut canvas.buffer);
+            }
+        }
+    }
+    canvas.show_in_frame(frame, inner, scroll);
+}
 
-    let top_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
-        .split(*top_area);
-    let [badge_area, buddy_area] = top_layout.as_ref() else {
-        return;
-    };
+fn badge_group_label(badge_type: &BadgeType) -> &'static str {
+    match badge_type {
+        BadgeType::ConsecutiveStreak(_) => "🔥 連続正解",
+        BadgeType::CumulativeMilestone(_) => "✨ 累積正解",
+        BadgeType::DailyStreak(_) => "📅 継続日数",
+        BadgeType::DailyGoal(_) => "🎯 1日の目標",
+        BadgeType::PersonalBest(_) => "🏅 自己ベスト",
+        BadgeType::EarlyBird
+        | BadgeType::Marathon
+        | BadgeType::GenreMaster(_)
+        | BadgeType::Comeback => "🏆 チャレンジ",
+    }
+}
+
+fn badge_card(
+    badge_type: &BadgeType,
+    earned_at: Option<DateTime<Local>>,
+    locale: LabelLanguage,
+    theme: &Theme,
+) -> Paragraph<'static> {
+    let name = badge_type.display_text();
+    let block = Block::default().borders(Borders::ALL);
+    match earned_at {
+        Some(at) => Paragraph::new(trf(locale, "獲得: {}", &[&format_record_date(at)])).block(
+            block
+                .title(format!("{} {name}", badge_type.icon()))
+                .title_style(Style::default().fg(theme.highlight).bold())
+                .border_style(Style::default().fg(theme.success)),
+        ),
+        None => Paragraph::new(badge_type.unlock_condition())
+            .wrap(Wrap { trim: true })
+            .style(Style::default().fg(theme.muted))
+            .block(
+                block
+                    .title(format!("🔒 {name}"))
+                    .border_style(Style::default().fg(theme.muted)),
+            ),
+    }
+}
+
+/// バッジとバディを並べ、その下に自己ベスト・模擬試験・対戦成績を表示する
+fn render_badges_tab(canvas: &mut Canvas, ctx: &ReportContext) {
+    let stats = ctx.stats;
+    let badge_lines = render_badge_section(ctx);
+    let height = u16::try_from(badge_lines.len())
+        .unwrap_or(u16::MAX)
+        .saturating_add(2)
+        .max(6);
+    let [badge_area, buddy_area] =
+        Layout::horizontal([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .areas(canvas.push(height));
 
     let badge_block = Block::default()
-        .title("バッジ")
+        .title(tr(ctx.locale, "バッジ"))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
-    let badge_inner = badge_block.inner(*badge_area);
-    frame.render_widget(badge_block, *badge_area);
-    let badge_content = Text::from(render_badge_section(stats));
-    let badge_paragraph = Paragraph::new(badge_content);
-    frame.render_widget(badge_paragraph, badge_inner);
+        .border_style(Style::default().fg(ctx.theme.highlight));
+    Paragraph::new(badge_lines)
+        .block(badge_block)
+        .render(badge_area, &mut canvas.buffer);
 
     let buddy_block = Block::default()
-        .title(format!("バディ (レベル {})", stats.buddy.level))
+        .title(trf(ctx.locale, "バディ (レベル {})", &[&stats.buddy.level]))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::LightBlue));
-    let buddy_inner = buddy_block.inner(*buddy_area);
-    frame.render_widget(buddy_block, *buddy_area);
-
-    let buddy_ascii = get_buddy_ascii(stats.buddy.level);
-    let required_exp = required_exp_for_level(stats.buddy.level);
+        .border_style(Style::default().fg(ctx.theme.title));
     let buddy_text = format!(
-        "{}\n        経験値: {}/{}",
-        buddy_ascii, stats.buddy.exp, required_exp
+        "{}\n        {}",
+        get_buddy_ascii(stats.buddy.level),
+        trf(
+            ctx.locale,
+            "経験値: {}/{}",
+            &[&stats.buddy.exp, &required_exp_for_level(stats.buddy.level)],
+        )
     );
-    let buddy_paragraph = Paragraph::new(buddy_text);
-    frame.render_widget(buddy_paragraph, buddy_inner);
-
-    let horizontal_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(*bottom_area);
-    let [monthly_area, weekly_area] = horizontal_layout.as_ref() else {
-        return;
-    };
+    Paragraph::new(buddy_text)
+        .block(buddy_block)
+        .render(buddy_area, &mut canvas.buffer);
 
-    let daily_stats = stats.get_daily_stats(REPORT_DAYS);
+    render_records_section(canvas, ctx);
+    render_exam_section(canvas, ctx);
+    render_head_to_head_section(canvas, ctx);
+}
+
+/// 評価スコアの要約と 180 日のヒートマップ、その下に過去 52 週の練習回数を
+/// GitHub のコントリビューショングラフのように表示する
+fn render_heatmap_tab(canvas: &mut Canvas, ctx: &ReportContext) {
+    let mut monthly = Text::from(render_evaluation_summary(ctx));
+    monthly.push_line(Line::from(""));
+    monthly.extend(create_heatmap_without_badges(
+        &ctx.stats.get_daily_stats(REPORT_DAYS),
+        ctx.report,
+        ctx.locale,
+        ctx.theme,
+    ));
     let monthly_block = Block::default()
-        .title("180日 (過去180日)")
+        .title(tr(ctx.locale, "180日 (過去180日)"))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Green));
-    let monthly_inner = monthly_block.inner(*monthly_area);
-    frame.render_widget(monthly_block, *monthly_area);
-    if monthly_inner.height >= 6 {
-        let monthly_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Length(6), Constraint::Min(0)])
-            .split(monthly_inner);
-        let [summary_area, heatmap_area] = monthly_layout.as_ref() else {
-            return;
-        };
-        let summary_text = Text::from(render_evaluation_summary(stats));
-        let summary_paragraph = Paragraph::new(summary_text);
-        frame.render_widget(summary_paragraph, *summary_area);
+        .border_style(Style::default().fg(ctx.theme.success));
+    let height = text_block_height(&monthly);
+    canvas.render(Paragraph::new(monthly).block(monthly_block), height);
+
+    let today = Local::now().date_naive();
+    let daily_stats = stats_analysis::calculate_daily_stats(&ctx.stats.results, YEAR_DAYS, today);
+    let year = create_year_heatmap_for_date(&daily_stats, today, ctx.report, ctx.locale, ctx.theme);
+    let year_block = Block::default()
+        .title(tr(ctx.locale, "過去1年 (52週)"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ctx.theme.success_soft));
+    let height = text_block_height(&year);
+    canvas.render(Paragraph::new(year).block(year_block), height);
+}
 
-        let heatmap = create_heatmap_without_badges(
-            &daily_stats,
-            usize::from(heatmap_area.width),
-            usize::from(heatmap_area.height),
-        );
-        let paragraph = Paragraph::new(heatmap);
-        frame.render_widget(paragraph, *heatmap_area);
-    } else {
-        let heatmap = create_heatmap_without_badges(
-            &daily_stats,
-            usize::from(monthly_inner.width),
-            usize::from(monthly_inner.height),
+/// 推移のグラフの下に、速さ・難易度別・推定レベル別の合格率を表示する
+fn render_trends_tab(canvas: &mut Canvas, ctx: &ReportContext) {
+    render_trend_section(canvas, ctx);
+    render_speed_section(canvas, ctx);
+    render_keyword_section(canvas, ctx);
+    render_metrics_section(canvas, ctx);
+    render_difficulty_section(canvas, ctx);
+    render_text_level_section(canvas, ctx);
+}
+
+/// 枠で囲んだ文章の高さ
+fn text_block_height(text: &Text) -> u16 {
+    u16::try_from(text.height())
+        .unwrap_or(u16::MAX)
+        .saturating_add(2)
+}
+
+/// 3 観点の平均点と中央値を、左に週ごと、右に文体ごとと文字数との組み合わせごとに表示する
+fn render_scores_tab(canvas: &mut Canvas, ctx: &ReportContext) {
+    let (stats, theme) = (ctx.stats, ctx.theme);
+    let mut weekly_lines = Vec::new();
+    for (start, summary) in
+        stats.get_weekly_evaluation_summaries(SCORE_WEEKS, ctx.report.first_weekday(), ctx.scale)
+    {
+        let label = format_week_start(start, ctx.report);
+        weekly_lines.extend(score_breakdown_lines(&label, &summary, ctx));
+    }
+    let weekly = Text::from(weekly_lines);
+
+    let mut genre_lines = Vec::new();
+    for (genre, summary) in stats.get_genre_evaluation_summaries(REPORT_DAYS, ctx.scale) {
+        let label = genre.map_or("読み込んだ文章", TextStyle::label);
+        genre_lines.extend(score_breakdown_lines(tr(ctx.locale, label), &summary, ctx));
+    }
+    if genre_lines.is_empty() {
+        genre_lines.push(Line::from(tr(ctx.locale, "評価スコア: なし")));
+    }
+    let genre = Text::from(genre_lines);
+    let length = Text::from(length_genre_lines(
+        &stats.get_length_genre_stats(ctx.scale),
+        ctx,
+    ));
+
+    let genre_height = text_block_height(&genre);
+    let length_height = text_block_height(&length);
+    let height = text_block_height(&weekly).max(genre_height.saturating_add(length_height));
+    let [weekly_area, right_area] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .areas(canvas.push(height));
+    let [genre_area, length_area] = Layout::vertical([
+        Constraint::Length(genre_height),
+        Constraint::Length(length_height),
+    ])
+    .areas(right_area);
+
+    let weekly_block = Block::default()
+        .title(trf(ctx.locale, "週ごと (過去{}週)", &[&SCORE_WEEKS]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.success));
+    Paragraph::new(weekly)
+        .block(weekly_block)
+        .render(weekly_area, &mut canvas.buffer);
+    let genre_block = Block::default()
+        .title(tr(ctx.locale, "ジャンルごと (直近180日)"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight));
+    Paragraph::new(genre)
+        .block(genre_block)
+        .render(genre_area, &mut canvas.buffer);
+    let length_block = Block::default()
+        .title(tr(ctx.locale, "文字数とジャンル別の合格率"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight_soft));
+    Paragraph::new(length)
+        .block(length_block)
+        .render(length_area, &mut canvas.buffer);
+}
+
+/// 組み合わせごとに合格率と 3 観点の平均の 2 行。合格率が半分に満たない組み合わせは目立たせる
+fn length_genre_lines(stats: &[LengthGenreStats], ctx: &ReportContext) -> Vec<Line<'static>> {
+    let (locale, theme) = (ctx.locale, ctx.theme);
+    let mut lines = Vec::new();
+    for s in stats {
+        let length = s.length.map_or_else(
+            || tr(locale, "2880字超").to_string(),
+            |length| trf(locale, "{}字", &[&length]),
         );
-        let paragraph = Paragraph::new(heatmap);
-        frame.render_widget(paragraph, monthly_inner);
+        let genre = tr(locale, s.genre.map_or("読み込んだ文章", TextStyle::label));
+        let rate_style = if s.pass_rate() < 50.0 {
+            Style::default().fg(theme.danger).bold()
+        } else {
+            Style::default().fg(theme.success)
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("{length} {genre}: ")),
+            Span::styled(format!("{:.0}%", s.pass_rate()), rate_style),
+            Span::raw(format!(" ({}/{})", s.correct, s.total())),
+        ]));
+        if let (Some(importance), Some(conciseness), Some(accuracy)) = (
+            &s.scores.importance,
+            &s.scores.conciseness,
+            &s.scores.accuracy,
+        ) {
+            lines.push(Line::from(trf(
+                locale,
+                "  重要情報 {} / 簡潔性 {} / 正確性 {}",
+                &[
+                    &format!("{:.1}", importance.average),
+                    &format!("{:.1}", conciseness.average),
+                    &format!("{:.1}", accuracy.average),
+                ],
+            )));
+        }
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(tr(locale, "記録なし")));
+    }
+    lines
+}
+
+/// 見出しの行と、3 観点の平均点の棒 (中央値を添える)
+fn score_breakdown_lines(
+    label: &str,
+    summary: &EvaluationSummary,
+    ctx: &ReportContext,
+) -> Vec<Line<'static>> {
+    let (locale, theme) = (ctx.locale, ctx.theme);
+    let mut lines = vec![Line::from(vec![
+        Span::styled(label.to_string(), Style::default().fg(theme.accent).bold()),
+        Span::raw(trf(locale, " ({}件)", &[&summary.count])),
+    ])];
+    let axes = [
+        ("重要情報", &summary.importance),
+        ("簡潔性　", &summary.conciseness),
+        ("正確性　", &summary.accuracy),
+    ];
+    for (name, score) in axes {
+        let Some(score) = score else {
+            continue;
+        };
+        let filled = score_bar_length(score.average);
+        lines.push(Line::from(vec![
+            Span::raw(format!("  {} ", tr(locale, name))),
+            Span::styled("█".repeat(filled), Style::default().fg(theme.success)),
+            Span::styled(
+                "░".repeat(usize::from(SCORE_BAR_WIDTH).saturating_sub(filled)),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(trf(
+                locale,
+                " {} (中央値 {})",
+                &[
+                    &format!("{:.1}", score.average),
+                    &format!("{:.1}", score.median),
+                ],
+            )),
+        ]));
     }
+    lines
+}
+
+/// 平均点を四捨五入したマスの数
+fn score_bar_length(average: f32) -> usize {
+    let per_cell = MAX_SCORE / f32::from(SCORE_BAR_WIDTH);
+    (1..=SCORE_BAR_WIDTH)
+        .take_while(|&cell| f32::from(cell) * per_cell <= average + per_cell / 2.0)
+        .count()
+}
 
-    let weekly_stats = stats.get_weekly_stats(WEEKS_TO_SHOW);
+fn render_weekly_section(canvas: &mut Canvas, ctx: &ReportContext) {
+    let weekly_stats = ctx
+        .stats
+        .get_weekly_stats(WEEKS_TO_SHOW, ctx.report.first_weekday());
     let weekly_block = Block::default()
-        .title("週次 (過去4週)")
+        .title(tr(ctx.locale, "週次 (過去4週)"))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Magenta));
-    let weekly_inner = weekly_block.inner(*weekly_area);
-    frame.render_widget(weekly_block, *weekly_area);
+        .border_style(Style::default().fg(ctx.theme.secondary));
     let chart = create_bar_chart_without_badges(
         &weekly_stats,
-        usize::from(weekly_inner.width),
-        usize::from(weekly_inner.height),
+        usize::from(canvas.buffer.area.width.saturating_sub(2)),
+        ctx.report,
+        ctx.locale,
+        ctx.theme,
     );
-    let paragraph = Paragraph::new(chart);
-    frame.render_widget(paragraph, weekly_inner);
+    let height = text_block_height(&chart);
+    canvas.render(Paragraph::new(chart).block(weekly_block), height);
+}
+
+fn format_record_date(at: chrono::DateTime<Local>) -> String {
+    at.format("%Y-%m-%d").to_string()
+}
+
+/// 自己ベストがあれば、達成した日付とともに表示する
+fn render_records_section(canvas: &mut Canvas, ctx: &ReportContext) {
+    let (stats, locale) = (ctx.stats, ctx.locale);
+    let records = stats.get_personal_records();
+    let mut lines: Vec<Line> = records
+        .fastest_passes
+        .iter()
+        .map(|(count, record)| {
+            Line::from(trf(
+                locale,
+                "最速合格 ({} 文字): {} ({})",
+                &[
+                    count,
+                    &format!("{}:{:02}", record.value / 60, record.value % 60),
+                    &format_record_date(record.achieved_at),
+                ],
+            ))
+        })
+        .collect();
+    if let Some(record) = &records.longest_pass {
+        lines.push(Line::from(trf(
+            locale,
+            "最長の合格: {} 文字 ({})",
+            &[&record.value, &format_record_date(record.achieved_at)],
+        )));
+    }
+    if let Some(record) = &records.highest_score {
+        lines.push(Line::from(trf(
+            locale,
+            "最高得点: {}/{} ({})",
+            &[
+                &record.value,
+                &records::MAX_TOTAL_SCORE,
+                &format_record_date(record.achieved_at),
+            ],
+        )));
+    }
+    if let Some(record) = &records.most_rounds_in_day {
+        lines.push(Line::from(trf(
+            locale,
+            "1日の最多回数: {} 回 ({})",
+            &[&record.value, &format_record_date(record.achieved_at)],
+        )));
+    }
+    if lines.is_empty() {
+        return;
+    }
+
+    let height = u16::try_from(lines.len())
+        .unwrap_or(u16::MAX)
+        .saturating_add(2);
+    let updates = stats.get_personal_best_badges().len();
+    let records_block = Block::default()
+        .title(trf(locale, "記録 (🏅 自己ベスト更新 {} 回)", &[&updates]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ctx.theme.danger_soft));
+    canvas.render(Paragraph::new(lines).block(records_block), height);
+}
+
+/// 模擬試験を受けていれば、直近の得点と推移を表示する
+fn render_exam_section(canvas: &mut Canvas, ctx: &ReportContext) {
+    let (stats, locale) = (ctx.stats, ctx.locale);
+    let Some(latest) = stats.exams.last() else {
+        return;
+    };
+    let best = stats
+        .exams
+        .iter()
+        .map(|e| e.score)
+        .max()
+        .unwrap_or_default();
+    let verdict = tr(locale, if latest.passed { "合格" } else { "不合格" });
+    let skip = stats.exams.len().saturating_sub(EXAM_HISTORY);
+    let trend: Vec<String> = stats
+        .exams
+        .iter()
+        .skip(skip)
+        .map(|e| e.score.to_string())
+        .collect();
+    let lines = vec![
+        Line::from(trf(
+            locale,
+            "直近: {} 点 ({}) {}  最高: {} 点  受験 {} 回",
+            &[
+                &latest.score,
+                &verdict,
+                &format_record_date(latest.timestamp),
+                &best,
+                &stats.exams.len(),
+            ],
+        )),
+        Line::from(trf(locale, "推移: {}", &[&trend.join(" → ")])),
+    ];
+
+    let exam_block = Block::default()
+        .title(trf(
+            locale,
+            "模擬試験 (100 点満点、合格点 {})",
+            &[&exam::EXAM_PASS_SCORE],
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ctx.theme.secondary_soft));
+    canvas.render(Paragraph::new(lines).block(exam_block), 4);
+}
+
+/// 1 分あたりの文字数の記録があれば、直近の推移を表示する
+fn render_speed_section(canvas: &mut Canvas, ctx: &ReportContext) {
+    let theme = ctx.theme;
+    let recent = ctx.stats.get_recent_cpm(SPEED_HISTORY);
+    let Some(&latest) = recent.last() else {
+        return;
+    };
+
+    let total: u64 = recent.iter().map(|&cpm| u64::from(cpm)).sum();
+    let average = total / u64::try_from(recent.len()).unwrap_or(1);
+    let speed_block = Block::default()
+        .title(trf(
+            ctx.locale,
+            "読み書きの速さ (字/分) 最新 {} / 平均 {} (直近{}回)",
+            &[&latest, &average, &recent.len()],
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent_soft));
+    let data: Vec<u64> = recent.iter().map(|&cpm| u64::from(cpm)).collect();
+    let sparkline = Sparkline::default()
+        .block(speed_block)
+        .data(&data)
+        .style(Style::default().fg(theme.accent_soft));
+    canvas.render(sparkline, SPEED_CHART_HEIGHT.saturating_add(2));
+}
+
+/// キーワード予想をしたことがあれば、これまでの的中率を表示する
+fn render_keyword_section(canvas: &mut Canvas, ctx: &ReportContext) {
+    let Some((hits, total, sessions)) = ctx.stats.keyword_prediction_totals() else {
+        return;
+    };
+    let rate = f64::from(hits) * 100.0 / f64::from(total.max(1));
+    let keyword_block = Block::default()
+        .title(tr(ctx.locale, "キーワード予想"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ctx.theme.accent_soft));
+    let line = Line::from(trf(
+        ctx.locale,
+        "的中率: {}% ({} / {} 語、{} 回)",
+        &[&format!("{rate:.0}"), &hits, &total, &sessions],
+    ));
+    canvas.render(Paragraph::new(line).block(keyword_block), 3);
+}
+
+/// 要約の指標を記録していれば、指標ごとに最新の値と直近の平均を表示する
+fn render_metrics_section(canvas: &mut Canvas, ctx: &ReportContext) {
+    let locale = ctx.locale;
+    let recent = ctx.stats.get_recent_summary_metrics(SPEED_HISTORY);
+    let Some(latest) = recent.last() else {
+        return;
+    };
+    let count = u32::try_from(recent.len()).unwrap_or(u32::MAX);
+    let line = |label: &str, unit: &str, value: fn(&SummaryMetrics) -> u32| {
+        let average = recent.iter().map(value).sum::<u32>() / count.max(1);
+        Line::from(trf(
+            locale,
+            "{}: 最新 {}{} / 平均 {}{}",
+            &[&tr(locale, label), &value(latest), &unit, &average, &unit],
+        ))
+    };
+    let lines = vec![
+        line("圧縮率", "%", |m| u32::from(m.compression_percent)),
+        line("文の数", "", |m| u32::from(m.sentence_count)),
+        line("漢字の割合", "%", |m| u32::from(m.kanji_percent)),
+        line("原文と同じ表現", "%", |m| {
+            u32::from(m.verbatim_percent)
+        }),
+    ];
+    let metrics_block = Block::default()
+        .title(trf(locale, "要約の指標 (直近{}回)", &[&recent.len()]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ctx.theme.accent_soft));
+    let height = u16::try_from(lines.len())
+        .unwrap_or(u16::MAX)
+        .saturating_add(2);
+    canvas.render(Paragraph::new(lines).block(metrics_block), height);
+}
+
+/// 直近 90 日の、7 日間の合格率と回数の推移を表示する
+fn render_trend_section(canvas: &mut Canvas, ctx: &ReportContext) {
+    if ctx.stats.results.is_empty() {
+        return;
+    }
+    let (locale, theme) = (ctx.locale, ctx.theme);
+    let trend = ctx.stats.get_rolling_trend(TREND_DAYS, TREND_WINDOW);
+    let as_f64 = |count: usize| f64::from(u32::try_from(count).unwrap_or(u32::MAX));
+    let pass_rates: Vec<(f64, f64)> = trend
+        .iter()
+        .enumerate()
+        .filter_map(|(day, trend)| Some((as_f64(day), trend.pass_rate?)))
+        .collect();
+    let sessions: Vec<(f64, f64)> = trend
+        .iter()
+        .enumerate()
+        .map(|(day, trend)| (as_f64(day), as_f64(trend.sessions)))
+        .collect();
+    // 合格率 (0〜100%) と回数を同じ軸に描く。回数が 100 を超えたら軸を伸ばす
+    let y_max = as_f64(trend.iter().map(|day| day.sessions).max().unwrap_or(0)).max(100.0);
+    let datasets = vec![
+        Dataset::default()
+            .name(tr(locale, "合格率 (%)"))
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.success))
+            .data(&pass_rates),
+        Dataset::default()
+            .name(tr(locale, "回数"))
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.accent_soft))
+            .data(&sessions),
+    ];
+    let date_labels = [trend.first(), trend.last()].map(|day| {
+        day.map(|day| ctx.report.format_date(day.date))
+            .unwrap_or_default()
+    });
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(tr(locale, "推移 (直近90日, 7日間ごとの合格率と回数)"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.success_soft)),
+        )
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, as_f64(TREND_DAYS.saturating_sub(1))])
+                .labels(date_labels)
+                .style(Style::default().fg(theme.muted)),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, y_max])
+                .labels(["0".to_string(), format!("{y_max:.0}")])
+                .style(Style::default().fg(theme.muted)),
+        )
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 1)));
+    canvas.render(chart, TREND_CHART_HEIGHT.saturating_add(2));
+}
+
+/// 難易度ごとの合格率があれば表示する
+fn render_difficulty_section(canvas: &mut Canvas, ctx: &ReportContext) {
+    let difficulty_stats = ctx.stats.get_difficulty_stats();
+    if difficulty_stats.is_empty() {
+        return;
+    }
+
+    let height = u16::try_from(difficulty_stats.len())
+        .unwrap_or(u16::MAX)
+        .saturating_add(2);
+    let difficulty_block = Block::default()
+        .title(tr(ctx.locale, "難易度別の合格率"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ctx.theme.highlight_soft));
+    let lines: Vec<Line> = difficulty_stats
+        .iter()
+        .map(|s| {
+            Line::from(format!(
+                "{}: {:.0}% ({}/{})",
+                s.difficulty.display_name(),
+                s.pass_rate(),
+                s.correct,
+                s.total()
+            ))
+        })
+        .collect();
+    canvas.render(Paragraph::new(lines).block(difficulty_block), height);
+}
+
+/// 原文から推定したレベルごとの合格率があれば表示する
+fn render_text_level_section(canvas: &mut Canvas, ctx: &ReportContext) {
+    let level_stats = ctx.stats.get_text_level_stats();
+    if level_stats.is_empty() {
+        return;
+    }
+
+    let height = u16::try_from(level_stats.len())
+        .unwrap_or(u16::MAX)
+        .saturating_add(2);
+    let level_block = Block::default()
+        .title(tr(ctx.locale, "文章の推定レベル別の合格率"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ctx.theme.success_soft));
+    let lines: Vec<Line> = level_stats
+        .iter()
+        .map(|s| {
+            Line::from(format!(
+                "{}: {:.0}% ({}/{}) 漢字 {}% / 1文 {} 字",
+                s.level.label(),
+                s.pass_rate(),
+                s.correct,
+                s.total(),
+                s.average_kanji_percent,
+                s.average_sentence_chars
+            ))
+        })
+        .collect();
+    canvas.render(Paragraph::new(lines).block(level_block), height);
+}
+
+/// 対戦成績があれば表示する
+fn render_head_to_head_section(canvas: &mut Canvas, ctx: &ReportContext) {
+    let tallies = ctx.stats.get_head_to_head_tallies();
+    if tallies.is_empty() {
+        return;
+    }
+
+    let shown = tallies.len().min(MAX_HEAD_TO_HEAD_DISPLAY);
+    let height = u16::try_from(shown).unwrap_or(u16::MAX).saturating_add(2);
+    let versus_block = Block::default()
+        .title(tr(ctx.locale, "対戦成績"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ctx.theme.danger_soft));
+    let lines: Vec<Line> = tallies
+        .iter()
+        .take(MAX_HEAD_TO_HEAD_DISPLAY)
+        .map(|tally| {
+            Line::from(format!(
+                "{} {} 勝 - {} {} 勝 (引き分け {})",
+                tally.players.0, tally.wins.0, tally.players.1, tally.wins.1, tally.draws
+            ))
+        })
+        .collect();
+    canvas.render(Paragraph::new(lines).block(versus_block), height);
 }
 
 fn create_heatmap_without_badges(
     daily_stats: &HashMap<NaiveDate, DailyStats>,
-    width: usize,
-    height: usize,
+    report: &ReportConfig,
+    locale: LabelLanguage,
+    theme: &Theme,
 ) -> Text<'static> {
-    create_heatmap_for_date(daily_stats, width, height, Local::now().date_naive())
+    create_heatmap_for_date(
+        daily_stats,
+        Local::now().date_naive(),
+        report,
+        locale,
+        theme,
+    )
 }
 
 fn create_heatmap_for_date(
     daily_stats: &HashMap<NaiveDate, DailyStats>,
-    _width: usize,
-    _height: usize,
     today: NaiveDate,
+    report: &ReportConfig,
+    locale: LabelLanguage,
+    theme: &Theme,
 ) -> Text<'static> {
     let mut lines = Vec::new();
 
     let start_offset = i64::try_from(REPORT_DAYS.saturating_sub(1)).unwrap_or(i64::MAX);
     let start_date = today - chrono::Duration::days(start_offset);
 
-    let grid_start =
-        start_date - chrono::Duration::days(i64::from(start_date.weekday().num_days_from_sunday()));
+    let grid_start = start_date.week(report.first_weekday()).first_day();
     let days_in_grid = (today - grid_start).num_days() + 1;
     let week_count = usize::try_from(days_in_grid)
         .unwrap_or(REPORT_DAYS)
@@ -285,22 +1206,15 @@ fn create_heatmap_for_date(
         })
         .collect();
 
-    let weekdays = [
-        ("土", 6_u32),
-        ("金", 5_u32),
-        ("木", 4_u32),
-        ("水", 3_u32),
-        ("火", 2_u32),
-        ("月", 1_u32),
-        ("日", 0_u32),
-    ];
-
-    for (weekday_label, weekday_index) in weekdays {
+    // 週の最後の曜日を一番上に、週の始まりの曜日を一番下に並べる
+    for weekday_index in (0..7_i64).rev() {
+        let weekday_label =
+            report.weekday_label((grid_start + chrono::Duration::days(weekday_index)).weekday());
         let mut line_spans = Vec::new();
         line_spans.push(Span::raw(format!("{weekday_label}{HEATMAP_LABEL_SUFFIX}")));
 
         for week_start in &week_starts {
-            let date = *week_start + chrono::Duration::days(i64::from(weekday_index));
+            let date = *week_start + chrono::Duration::days(weekday_index);
             if date < start_date || date > today {
                 line_spans.push(Span::raw(HEATMAP_EMPTY_CELL));
                 continue;
@@ -310,14 +1224,17 @@ fn create_heatmap_for_date(
                 let total = stats.total();
                 let correct = stats.correct;
 
-                let (symbol, style) = get_heatmap_cell_style(total, correct);
+                let (symbol, style) = get_heatmap_cell_style(total, correct, theme);
+                // 目標を達成した日は下線で示す
+                let style = if stats.goal_met {
+                    style.underlined()
+                } else {
+                    style
+                };
 
                 line_spans.push(Span::styled(symbol, style));
             } else {
-                line_spans.push(Span::styled(
-                    HEATMAP_CELL,
-                    Style::default().fg(Color::DarkGray),
-                ));
+                line_spans.push(Span::styled(HEATMAP_CELL, Style::default().fg(theme.muted)));
             }
         }
 
@@ -326,31 +1243,169 @@ fn create_heatmap_for_date(
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
-        Span::raw("凡例: "),
-        Span::styled(HEATMAP_CELL, Style::default().fg(Color::DarkGray)),
-        Span::raw(" なし  "),
-        Span::styled(HEATMAP_CELL, Style::default().fg(Color::Red)),
-        Span::raw(" 全不正解  "),
-        Span::styled(HEATMAP_CELL, Style::default().fg(Color::Yellow)),
-        Span::raw(" 混在  "),
-        Span::styled(HEATMAP_CELL, Style::default().fg(Color::LightGreen)),
-        Span::raw(" 良  "),
-        Span::styled(HEATMAP_CELL, Style::default().fg(Color::Green)),
-        Span::raw(" 優  "),
+        Span::raw(tr(locale, "凡例: ")),
+        Span::styled(HEATMAP_CELL, Style::default().fg(theme.muted)),
+        Span::raw(tr(locale, " なし  ")),
+        Span::styled(HEATMAP_CELL, Style::default().fg(theme.danger)),
+        Span::raw(tr(locale, " 全不正解  ")),
+        Span::styled(HEATMAP_CELL, Style::default().fg(theme.highlight)),
+        Span::raw(tr(locale, " 混在  ")),
+        Span::styled(HEATMAP_CELL, Style::default().fg(theme.success_soft)),
+        Span::raw(tr(locale, " 良  ")),
+        Span::styled(HEATMAP_CELL, Style::default().fg(theme.success)),
+        Span::raw(tr(locale, " 優  ")),
         Span::styled(
             HEATMAP_CELL,
-            Style::default().fg(Color::Rgb(0, 255, 0)).bold(),
+            Style::default().fg(theme.success_strong).bold(),
         ),
-        Span::raw(" 秀"),
+        Span::raw(tr(locale, " 秀  ")),
+        Span::styled(HEATMAP_CELL, Style::default().underlined()),
+        Span::raw(tr(locale, " 目標達成")),
     ]));
 
     Text::from(lines)
 }
 
+fn create_year_heatmap_for_date(
+    daily_stats: &HashMap<NaiveDate, DailyStats>,
+    today: NaiveDate,
+    report: &ReportConfig,
+    locale: LabelLanguage,
+    theme: &Theme,
+) -> Text<'static> {
+    let start_offset = i64::try_from(YEAR_DAYS.saturating_sub(1)).unwrap_or(i64::MAX);
+    let start_date = today - chrono::Duration::days(start_offset);
+    let grid_start = start_date.week(report.first_weekday()).first_day();
+    let week_starts: Vec<NaiveDate> = grid_start
+        .iter_weeks()
+        .take_while(|week_start| *week_start <= today)
+        .collect();
+    let max_sessions = daily_stats
+        .values()
+        .map(DailyStats::total)
+        .max()
+        .unwrap_or(0);
+    let label_width = Span::raw(format!(
+        "{}{HEATMAP_LABEL_SUFFIX}",
+        report.weekday_label(grid_start.weekday())
+    ))
+    .width();
+
+    let mut lines = vec![Line::from(year_month_labels(
+        &week_starts,
+        start_date,
+        label_width,
+        report.weekday_labels,
+    ))];
+    // 180 日のヒートマップと同じく、週の最後の曜日を一番上に並べる
+    for weekday_index in (0..7_i64).rev() {
+        let weekday_label =
+            report.weekday_label((grid_start + chrono::Duration::days(weekday_index)).weekday());
+        let mut line_spans = vec![Span::raw(format!("{weekday_label}{HEATMAP_LABEL_SUFFIX}"))];
+        for week_start in &week_starts {
+            let date = *week_start + chrono::Duration::days(weekday_index);
+            if date < start_date || date > today {
+                line_spans.push(Span::raw(HEATMAP_EMPTY_CELL));
+                continue;
+            }
+            let sessions = daily_stats.get(&date).map_or(0, DailyStats::total);
+            let level = year_intensity(sessions, max_sessions);
+            line_spans.push(Span::styled(
+                HEATMAP_CELL,
+                year_intensity_style(level, theme),
+            ));
+        }
+        lines.push(Line::from(line_spans));
+    }
+
+    let total_sessions: usize = daily_stats.values().map(DailyStats::total).sum();
+    let active_days = daily_stats
+        .values()
+        .filter(|stats| stats.total() > 0)
+        .count();
+    lines.push(Line::from(""));
+    lines.push(Line::from(trf(
+        locale,
+        "過去1年: {} 回 (練習した日 {} 日, 1日の最多 {} 回)",
+        &[&total_sessions, &active_days, &max_sessions],
+    )));
+    let mut legend = vec![Span::raw(tr(locale, "凡例: 少 "))];
+    legend.extend(
+        (0..=YEAR_INTENSITY_LEVELS)
+            .map(|level| Span::styled(HEATMAP_CELL, year_intensity_style(level, theme))),
+    );
+    legend.push(Span::raw(tr(locale, " 多")));
+    lines.push(Line::from(legend));
+
+    Text::from(lines)
+}
+
+/// 月の最初の日を含む週の列の上に月を表示する。前の月の表示と重なる場合は省く
+fn year_month_labels(
+    week_starts: &[NaiveDate],
+    start_date: NaiveDate,
+    label_width: usize,
+    language: LabelLanguage,
+) -> String {
+    const EN_MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let mut labels = " ".repeat(label_width);
+    let mut column = 0;
+    for (week, week_start) in week_starts.iter().enumerate() {
+        let Some(month_start) = week_start
+            .iter_days()
+            .take(7)
+            .find(|date| date.day() == 1 && *date >= start_date)
+        else {
+            continue;
+        };
+        let label = match language {
+            LabelLanguage::Ja => format!("{}月", month_start.month()),
+            LabelLanguage::En => EN_MONTHS
+                .get(month_start.month0() as usize)
+                .copied()
+                .unwrap_or_default()
+                .to_string(),
+        };
+        if week < column {
+            continue;
+        }
+        labels.push_str(&" ".repeat(week - column));
+        column = week + Span::raw(label.as_str()).width() + 1;
+        labels.push_str(&label);
+        labels.push(' ');
+    }
+    labels.trim_end().to_string()
+}
+
+/// 練習回数を最も多い日に対する割合で 0 (練習なし) から `YEAR_INTENSITY_LEVELS` の段階に分ける
+fn year_intensity(sessions: usize, max_sessions: usize) -> usize {
+    if sessions == 0 || max_sessions == 0 {
+        return 0;
+    }
+    sessions
+        .saturating_mul(YEAR_INTENSITY_LEVELS)
+        .div_ceil(max_sessions)
+        .clamp(1, YEAR_INTENSITY_LEVELS)
+}
+
+fn year_intensity_style(level: usize, theme: &Theme) -> Style {
+    match level {
+        0 => Style::default().fg(theme.muted),
+        1 => Style::default().fg(theme.success_soft),
+        2 => Style::default().fg(theme.success),
+        3 => Style::default().fg(theme.success_strong),
+        _ => Style::default().fg(theme.success_strong).bold(),
+    }
+}
+
 fn create_bar_chart_without_badges(
     weekly_stats: &[WeeklyStats],
     width: usize,
-    _height: usize,
+    report: &ReportConfig,
+    locale: LabelLanguage,
+    theme: &Theme,
 ) -> Text<'static> {
     let mut lines = Vec::new();
 
@@ -366,20 +1421,22 @@ fn create_bar_chart_without_badges(
         let correct_bars = calculate_bar_height(stats.correct, max_value, max_bar_width);
         let incorrect_bars = calculate_bar_height(stats.incorrect, max_value, max_bar_width);
 
-        let mut line_spans = vec![Span::raw(format!("第{}週: ", stats.week_number))];
+        let week_label = Span::raw(format_week_label(stats, report));
+        let label_width = week_label.width();
+        let mut line_spans = vec![week_label];
 
         line_spans.push(Span::styled(
             "█".repeat(correct_bars),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.success),
         ));
         line_spans.push(Span::raw(format!(" {}", stats.correct)));
 
         lines.push(Line::from(line_spans));
 
-        let mut incorrect_line = vec![Span::raw("       ")];
+        let mut incorrect_line = vec![Span::raw(" ".repeat(label_width))];
         incorrect_line.push(Span::styled(
             "█".repeat(incorrect_bars),
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme.danger),
         ));
         incorrect_line.push(Span::raw(format!(" {}", stats.incorrect)));
 
@@ -388,38 +1445,51 @@ fn create_bar_chart_without_badges(
     }
 
     lines.push(Line::from(vec![
-        Span::raw("凡例: "),
-        Span::styled("█", Style::default().fg(Color::Green)),
-        Span::raw(" 正解  "),
-        Span::styled("█", Style::default().fg(Color::Red)),
-        Span::raw(" 不正解"),
+        Span::raw(tr(locale, "凡例: ")),
+        Span::styled("█", Style::default().fg(theme.success)),
+        Span::raw(tr(locale, " 正解  ")),
+        Span::styled("█", Style::default().fg(theme.danger)),
+        Span::raw(tr(locale, " 不正解")),
     ]));
 
     Text::from(lines)
 }
 
-fn get_heatmap_cell_style(total: usize, correct: usize) -> (&'static str, Style) {
+/// 週の始まりの日付で週を表す (例: `06/28〜: `)
+fn format_week_label(stats: &WeeklyStats, report: &ReportConfig) -> String {
+    format!("{}: ", format_week_start(stats.start_date, report))
+}
+
+fn format_week_start(start_date: NaiveDate, report: &ReportConfig) -> String {
+    let suffix = match report.weekday_labels {
+        LabelLanguage::Ja => "〜",
+        LabelLanguage::En => "~",
+    };
+    format!("{}{suffix}", report.format_date(start_date))
+}
+
+fn get_heatmap_cell_style(total: usize, correct: usize, theme: &Theme) -> (&'static str, Style) {
     if total == 0 {
-        return (HEATMAP_CELL, Style::default().fg(Color::DarkGray));
+        return (HEATMAP_CELL, Style::default().fg(theme.muted));
     }
 
     if correct == 0 {
-        return (HEATMAP_CELL, Style::default().fg(Color::Red));
+        return (HEATMAP_CELL, Style::default().fg(theme.danger));
     }
 
     if correct == total {
         return (
             HEATMAP_CELL,
-            Style::default().fg(Color::Rgb(0, 255, 0)).bold(),
+            Style::default().fg(theme.success_strong).bold(),
         );
     }
 
     let color = if correct.saturating_mul(10) >= total.saturating_mul(8) {
-        Color::Green
+        theme.success
     } else if correct.saturating_mul(10) >= total.saturating_mul(5) {
-        Color::LightGreen
+        theme.success_soft
     } else {
-        Color::Yellow
+        theme.highlight
     };
 
     (HEATMAP_CELL, Style::default().fg(color))
@@ -435,6 +1505,7 @@ fn calculate_bar_height(value: usize, max_value: usize, max_len: usize) -> usize
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::theme::ThemeName;
 
     fn date(year: i32, month: u32, day: u32) -> Result<NaiveDate, String> {
         NaiveDate::from_ymd_opt(year, month, day)
@@ -468,7 +1539,13 @@ mod tests {
     #[test]
     fn heatmap_uses_weekdays_as_rows_from_saturday_to_sunday() -> Result<(), String> {
         let today = date(2026, 7, 2)?;
-        let lines = text_content(create_heatmap_for_date(&HashMap::new(), 80, 12, today));
+        let lines = text_content(create_heatmap_for_date(
+            &HashMap::new(),
+            today,
+            &ReportConfig::default(),
+            LabelLanguage::Ja,
+            ThemeName::Default.theme(),
+        ));
 
         let weekday_rows = lines
             .get(0..7)
@@ -497,11 +1574,18 @@ mod tests {
             DailyStats {
                 correct: 1,
                 incorrect: 0,
+                goal_met: false,
             },
         );
 
-        let rendered =
-            text_content(create_heatmap_for_date(&daily_stats, 80, 12, today)).join("\n");
+        let rendered = text_content(create_heatmap_for_date(
+            &daily_stats,
+            today,
+            &ReportConfig::default(),
+            LabelLanguage::Ja,
+            ThemeName::Default.theme(),
+        ))
+        .join("\n");
 
         if !rendered.contains(HEATMAP_CELL) {
             return Err("heatmap did not contain unicode block cells".to_string());
@@ -518,7 +1602,13 @@ mod tests {
     #[test]
     fn heatmap_uses_compact_week_columns_without_header() -> Result<(), String> {
         let today = date(2026, 7, 2)?;
-        let lines = text_content(create_heatmap_for_date(&HashMap::new(), 80, 12, today));
+        let lines = text_content(create_heatmap_for_date(
+            &HashMap::new(),
+            today,
+            &ReportConfig::default(),
+            LabelLanguage::Ja,
+            ThemeName::Default.theme(),
+        ));
         let first_line = lines
             .first()
             .ok_or_else(|| "heatmap did not render any rows".to_string())?;
@@ -550,10 +1640,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn year_heatmap_shades_days_by_sessions_relative_to_the_busiest_day() -> Result<(), String> {
+        let levels: Vec<usize> = [0, 1, 4, 8]
+            .into_iter()
+            .map(|sessions| year_intensity(sessions, 8))
+            .collect();
+        if levels != [0, 1, 2, YEAR_INTENSITY_LEVELS] {
+            return Err(format!("unexpected intensity levels: {levels:?}"));
+        }
+
+        let today = date(2026, 7, 2)?;
+        let lines = text_content(create_year_heatmap_for_date(
+            &HashMap::new(),
+            today,
+            &ReportConfig::default(),
+            LabelLanguage::Ja,
+            ThemeName::Default.theme(),
+        ));
+        let month_labels = lines
+            .first()
+            .ok_or_else(|| "year heatmap did not render month labels".to_string())?;
+        if !month_labels.contains("8月") || !month_labels.contains("7月") {
+            return Err(format!("month labels are missing: {month_labels}"));
+        }
+        let saturday_row = lines
+            .get(1)
+            .ok_or_else(|| "year heatmap did not render saturday row".to_string())?;
+        // 曜日のラベルの後に 53 週分の列が並ぶ
+        if saturday_row.chars().count() != 2 + 53 {
+            return Err(format!("unexpected week count: {saturday_row}"));
+        }
+        Ok(())
+    }
+
     #[test]
     fn heatmap_marks_out_of_range_cells_as_empty() -> Result<(), String> {
         let today = date(2026, 7, 2)?;
-        let lines = text_content(create_heatmap_for_date(&HashMap::new(), 80, 12, today));
+        let lines = text_content(create_heatmap_for_date(
+            &HashMap::new(),
+            today,
+            &ReportConfig::default(),
+            LabelLanguage::Ja,
+            ThemeName::Default.theme(),
+        ));
         let saturday_row = lines
             .first()
             .ok_or_else(|| "heatmap did not render saturday row".to_string())?;
@@ -573,4 +1703,22 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn canvas_shows_rows_from_the_scroll_position_up_to_the_last_row() {
+        let mut canvas = Canvas::new(3);
+        for row in 0..10 {
+            canvas.render(Paragraph::new(format!("行{row}")), 1);
+        }
+        let area = Rect::new(0, 0, 3, 3);
+        let mut screen = Buffer::empty(area);
+        let mut scroll = 20;
+        canvas.show(&mut screen, area, &mut scroll);
+        assert_eq!(scroll, 7);
+        assert_eq!(screen, Buffer::with_lines(["行7", "行8", "行9"]));
+
+        assert_eq!(ReportTab::from_number('3'), Some(ReportTab::Weekly));
+        assert_eq!(ReportTab::from_number('6'), None);
+        assert_eq!(ReportTab::Scores.next(), ReportTab::Badges);
+    }
 }
@@ -0,0 +1,248 @@
+use crate::appeal::Exchange;
+use crate::diff::{self, DiffSegment};
+use crate::evaluation::{EvaluationExtras, KeyPoint};
+use std::fmt::Write;
+
+/// 模範解答タブで、模範解答の後に続ける差分の見出し
+pub const DIFF_HEADING: &str = "差分 (緑: 要約だけにある語 / 赤: 模範解答にあって要約にない語)";
+
+/// 評価結果の表示を切り替えるタブ
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ResultTab {
+    #[default]
+    Evaluation,
+    ModelAnswer,
+    KeyPoints,
+    Conversation,
+}
+
+impl ResultTab {
+    pub const ALL: [Self; 4] = [
+        Self::Evaluation,
+        Self::ModelAnswer,
+        Self::KeyPoints,
+        Self::Conversation,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Evaluation => "評価",
+            Self::ModelAnswer => "模範解答",
+            Self::KeyPoints => "キーポイント",
+            Self::Conversation => "会話",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            Self::Evaluation => 0,
+            Self::ModelAnswer => 1,
+            Self::KeyPoints => 2,
+            Self::Conversation => 3,
+        }
+    }
+
+    /// 数字キー (1 始まり) に対応するタブ
+    pub fn from_number(number: char) -> Option<Self> {
+        let index = usize::try_from(number.to_digit(10)?.checked_sub(1)?).ok()?;
+        Self::ALL.get(index).copied()
+    }
+}
+
+/// 評価結果のタブの状態。タブごとにスクロール位置を持つ
+#[derive(Default)]
+pub struct ResultTabs {
+    pub active: ResultTab,
+    scroll: [u16; 4],
+    pub model_answer: String,
+    pub key_points: Vec<KeyPoint>,
+    /// 評価を受けた自分の要約。模範解答との差分に使う
+    pub summary: String,
+    /// 評価への異議申し立てのやり取り
+    pub conversation: Vec<Exchange>,
+}
+
+impl ResultTabs {
+    pub fn set_extras(&mut self, extras: EvaluationExtras) {
+        self.model_answer = extras.model_answer;
+        self.key_points = extras.key_points;
+    }
+
+    pub fn set_summary(&mut self, summary: &str) {
+        self.summary = summary.to_string();
+    }
+
+    /// 自分の要約と模範解答の語単位の差分。どちらかが空なら比べない
+    pub fn model_answer_diff(&self) -> Vec<DiffSegment> {
+        if self.model_answer.is_empty() || self.summary.trim().is_empty() {
+            return Vec::new();
+        }
+        diff::diff_words(self.summary.trim(), &self.model_answer)
+    }
+
+    /// 表示を最初のタブの先頭に戻す
+    pub fn reset_view(&mut self) {
+        self.active = ResultTab::Evaluation;
+        self.scroll = [0; 4];
+    }
+
+    pub fn select(&mut self, tab: ResultTab) {
+        self.active = tab;
+    }
+
+    pub fn select_next(&mut self) {
+        let index = (self.active.index() + 1) % ResultTab::ALL.len();
+        self.active = ResultTab::ALL.get(index).copied().unwrap_or_default();
+    }
+
+    pub fn select_previous(&mut self) {
+        let index = self
+            .active
+            .index()
+            .checked_sub(1)
+            .unwrap_or(ResultTab::ALL.len() - 1);
+        self.active = ResultTab::ALL.get(index).copied().unwrap_or_default();
+    }
+
+    pub fn scroll(&self) -> u16 {
+        self.scroll.get(self.active.index()).copied().unwrap_or(0)
+    }
+
+    pub fn scroll_down(&mut self, max_scroll: u16) {
+        if let Some(scroll) = self.scroll.get_mut(self.active.index()) {
+            *scroll = scroll.saturating_add(1).min(max_scroll);
+        }
+    }
+
+    pub fn set_scroll(&mut self, value: u16) {
+        if let Some(scroll) = self.scroll.get_mut(self.active.index()) {
+            *scroll = value;
+        }
+    }
+
+    pub fn scroll_up(&mut self) {
+        if let Some(scroll) = self.scroll.get_mut(self.active.index()) {
+            *scroll = scroll.saturating_sub(1);
+        }
+    }
+
+    /// 評価以外のタブに表示する文章。評価タブの本文は呼び出し側が持つ
+    pub fn text(&self, tab: ResultTab) -> String {
+        match tab {
+            ResultTab::Evaluation => String::new(),
+            ResultTab::ModelAnswer if self.model_answer.is_empty() => {
+                "この評価には模範解答が含まれていません。".to_string()
+            }
+            ResultTab::ModelAnswer => {
+                let segments = self.model_answer_diff();
+                if segments.is_empty() {
+                    return self.model_answer.clone();
+                }
+                let diff_text: String = segments
+                    .iter()
+                    .map(|segment| segment.text.as_str())
+                    .collect();
+                format!("{}\n\n{DIFF_HEADING}\n{diff_text}", self.model_answer)
+            }
+            ResultTab::KeyPoints => format_key_points(&self.key_points),
+            ResultTab::Conversation => format_conversation(&self.conversation),
+        }
+    }
+}
+
+fn format_conversation(conversation: &[Exchange]) -> String {
+    if conversation.is_empty() {
+        return "この評価についての会話はまだありません。".to_string();
+    }
+    let mut text = String::new();
+    for exchange in conversation {
+        let verdict = if exchange.passed {
+            "合格"
+        } else {
+            "不合格"
+        };
+        let _ = write!(
+            text,
+            "あなた: {}\n評価者: {}\n→ 考え直した結果: {verdict}\n\n",
+            exchange.argument, exchange.reply
+        );
+    }
+    text
+}
+
+fn format_key_points(key_points: &[KeyPoint]) -> String {
+    if key_points.is_empty() {
+        return "この評価にはキーポイントが含まれていません。".to_string();
+    }
+    let covered = key_points.iter().filter(|point| point.covered).count();
+    let mut text = format!("要約に含まれた要点: {covered} / {}\n\n", key_points.len());
+    for key_point in key_points {
+        let mark = if key_point.covered { "✔" } else { "✘" };
+        text.push_str(mark);
+        text.push(' ');
+        text.push_str(&key_point.point);
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabs_keep_scroll_per_tab_and_wrap_around() {
+        let mut tabs = ResultTabs::default();
+        tabs.scroll_down(5);
+        tabs.scroll_down(5);
+        tabs.select_previous();
+        assert_eq!(tabs.active, ResultTab::Conversation);
+        assert_eq!(tabs.scroll(), 0);
+        tabs.scroll_down(0);
+        assert_eq!(tabs.scroll(), 0);
+
+        tabs.select_next();
+        assert_eq!(tabs.active, ResultTab::Evaluation);
+        assert_eq!(tabs.scroll(), 2);
+        assert_eq!(ResultTab::from_number('3'), Some(ResultTab::KeyPoints));
+        assert_eq!(ResultTab::from_number('0'), None);
+        assert_eq!(ResultTab::from_number('5'), None);
+
+        tabs.reset_view();
+        assert_eq!(tabs.scroll(), 0);
+    }
+
+    #[test]
+    fn model_answer_tab_appends_diff_against_summary() {
+        let mut tabs = ResultTabs {
+            model_answer: "政府は規制を導入した。".to_string(),
+            ..ResultTabs::default()
+        };
+        assert_eq!(tabs.text(ResultTab::ModelAnswer), "政府は規制を導入した。");
+
+        tabs.set_summary("政府は規制を強化した。\n");
+        assert_eq!(tabs.model_answer_diff().len(), 4);
+        assert_eq!(
+            tabs.text(ResultTab::ModelAnswer),
+            format!("政府は規制を導入した。\n\n{DIFF_HEADING}\n政府は規制を強化導入した。")
+        );
+    }
+
+    #[test]
+    fn key_points_show_checklist_with_count() {
+        let key_points = [
+            KeyPoint {
+                point: "要点1".to_string(),
+                covered: true,
+            },
+            KeyPoint {
+                point: "要点2".to_string(),
+                covered: false,
+            },
+        ];
+        assert_eq!(
+            format_key_points(&key_points),
+            "要約に含まれた要点: 1 / 2\n\n✔ 要点1\n✘ 要点2\n"
+        );
+    }
+}
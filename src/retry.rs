@@ -0,0 +1,141 @@
+use crate::config::RetryConfig;
+use crate::error::AppError;
+use rand::RngExt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_BASE_DELAY_MS: u64 = 1000;
+const DEFAULT_MAX_DELAY_MS: u64 = 16_000;
+/// ジッターを入れるときは、待ち時間をこの割合から 1 倍までの間でばらつかせる
+const MIN_JITTER_RATIO: f64 = 0.5;
+
+/// API リクエストが一時的なエラーで失敗したときの再送の方針 (`[retry]` セクション)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// 最初の送信を含めた送信回数の上限
+    pub max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_config(&RetryConfig::default())
+    }
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &RetryConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1),
+            base_delay: Duration::from_millis(
+                config.base_delay_ms.unwrap_or(DEFAULT_BASE_DELAY_MS),
+            ),
+            max_delay: Duration::from_millis(config.max_delay_ms.unwrap_or(DEFAULT_MAX_DELAY_MS)),
+            jitter: config.jitter,
+        }
+    }
+
+    /// `attempt` 回目 (1 始まり) の送信が失敗した後に待つ時間。1 回ごとに倍にし、上限で頭打ちにする。
+    /// `jitter_ratio` は待ち時間に掛ける割合
+    pub fn delay(self, attempt: u32, jitter_ratio: f64) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        self.base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay)
+            .mul_f64(jitter_ratio.clamp(0.0, 1.0))
+    }
+
+    /// 同時に再送するリクエストが重ならないよう、待ち時間をばらつかせる割合
+    pub fn jitter_ratio(self) -> f64 {
+        if self.jitter {
+            rand::rng().random_range(MIN_JITTER_RATIO..=1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// 送り直せば成功するかもしれないエラーなら、その理由。認証エラーや応答の形式の誤りは送り直さない
+pub fn retry_reason(error: &AppError) -> Option<String> {
+    match error {
+        AppError::RateLimited => Some("利用上限 (429)".to_string()),
+        AppError::ApiError(e) if e.is_timeout() => Some("タイムアウト".to_string()),
+        AppError::ApiError(e) if e.is_connect() => Some("接続できません".to_string()),
+        AppError::ApiError(e) => e
+            .status()
+            .filter(reqwest::StatusCode::is_server_error)
+            .map(|status| format!("サーバーエラー ({})", status.as_u16())),
+        _ => None,
+    }
+}
+
+/// 再送を待っている間、ステータスバーに出す内容
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryNotice {
+    /// 次に送るのが何回目か
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub wait: Duration,
+    pub reason: String,
+}
+
+/// 再送の状況。`ApiClient` の複製どうしで共有し、評価のタスクからメインループに伝える
+#[derive(Clone, Default)]
+pub struct RetryStatus(Arc<Mutex<Option<RetryNotice>>>);
+
+impl RetryStatus {
+    pub fn set(&self, notice: Option<RetryNotice>) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = notice;
+        }
+    }
+
+    pub fn current(&self) -> Option<RetryNotice> {
+        self.0.lock().ok().and_then(|current| current.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_doubles_up_to_the_limit() {
+        let policy = RetryPolicy::from_config(&RetryConfig {
+            max_attempts: Some(0),
+            base_delay_ms: Some(500),
+            max_delay_ms: Some(3000),
+            jitter: true,
+        });
+        assert_eq!(policy.max_attempts, 1);
+        assert_eq!(policy.delay(1, 1.0), Duration::from_millis(500));
+        assert_eq!(policy.delay(2, 1.0), Duration::from_secs(1));
+        assert_eq!(policy.delay(4, 1.0), Duration::from_secs(3));
+        assert_eq!(policy.delay(40, 1.0), Duration::from_secs(3));
+        assert_eq!(policy.delay(2, 0.5), Duration::from_millis(500));
+        let ratio = policy.jitter_ratio();
+        assert!((MIN_JITTER_RATIO..=1.0).contains(&ratio));
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried() {
+        assert!(retry_reason(&AppError::RateLimited).is_some());
+        assert!(retry_reason(&AppError::InvalidApiKey).is_none());
+        assert!(retry_reason(&AppError::NoChoicesInResponse).is_none());
+
+        let status = RetryStatus::default();
+        let shared = status.clone();
+        shared.set(Some(RetryNotice {
+            attempt: 2,
+            max_attempts: 3,
+            wait: Duration::from_secs(1),
+            reason: "利用上限 (429)".to_string(),
+        }));
+        assert_eq!(status.current().map(|notice| notice.attempt), Some(2));
+        shared.set(None);
+        assert_eq!(status.current(), None);
+    }
+}
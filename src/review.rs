@@ -0,0 +1,175 @@
+use crate::models::EvaluationScores;
+use crate::storage;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// 忘却しにくさ (SM-2 の EF) の初期値と下限。100 倍した整数で持つ
+const INITIAL_EASE: u16 = 250;
+const MIN_EASE: u16 = 130;
+/// この回数続けて合格した文章は復習を終える
+const GRADUATE_REPETITIONS: u32 = 4;
+/// 品質がこれ未満なら忘れたとみなして最初からやり直す
+const PASSING_QUALITY: u8 = 3;
+
+/// 不合格だった文章と次に復習する日
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReviewItem {
+    pub text: String,
+    pub character_count: u16,
+    pub due: NaiveDate,
+    pub interval_days: u32,
+    pub repetitions: u32,
+    pub ease: u16,
+}
+
+/// 不合格だった文章の復習待ち行列。SM-2 方式で次の復習日を決める
+#[derive(Serialize, Deserialize, Default)]
+pub struct ReviewQueue {
+    pub items: Vec<ReviewItem>,
+}
+
+impl ReviewQueue {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(storage::load_json(storage::global()?, storage::REVIEW_DOCUMENT)?.unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        storage::save_json(storage::global()?, storage::REVIEW_DOCUMENT, self)?;
+        Ok(())
+    }
+
+    /// 不合格だった文章を翌日の復習に入れる。すでにあれば予定を最初からやり直す
+    pub fn add_failed(&mut self, text: String, character_count: u16, today: NaiveDate) {
+        if let Some(item) = self.items.iter_mut().find(|item| item.text == text) {
+            item.repetitions = 0;
+            item.interval_days = 1;
+            item.due = today + Duration::days(1);
+            return;
+        }
+        self.items.push(ReviewItem {
+            text,
+            character_count,
+            due: today + Duration::days(1),
+            interval_days: 1,
+            repetitions: 0,
+            ease: INITIAL_EASE,
+        });
+    }
+
+    pub fn due_count(&self, today: NaiveDate) -> usize {
+        self.items.iter().filter(|item| item.due <= today).count()
+    }
+
+    /// 復習日を過ぎた文章のうち、最も古いもの
+    pub fn next_due(&self, today: NaiveDate) -> Option<&ReviewItem> {
+        self.items
+            .iter()
+            .filter(|item| item.due <= today)
+            .min_by_key(|item| item.due)
+    }
+
+    /// 復習の結果 (品質 0〜5) から次の復習日を決める。十分に定着した文章は行列から外す
+    pub fn grade(&mut self, text: &str, quality: u8, today: NaiveDate) {
+        let Some(index) = self.items.iter().position(|item| item.text == text) else {
+            return;
+        };
+        let Some(item) = self.items.get_mut(index) else {
+            return;
+        };
+        let quality = quality.min(5);
+        let lapse = 5 - quality;
+        let ease_delta = 10 - i32::from(lapse) * (8 + i32::from(lapse) * 2);
+        item.ease = u16::try_from((i32::from(item.ease) + ease_delta).max(i32::from(MIN_EASE)))
+            .unwrap_or(MIN_EASE);
+        if quality < PASSING_QUALITY {
+            item.repetitions = 0;
+            item.interval_days = 1;
+        } else {
+            item.repetitions += 1;
+            item.interval_days = match item.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (item.interval_days * u32::from(item.ease) + 50) / 100,
+            };
+        }
+        if item.repetitions >= GRADUATE_REPETITIONS {
+            self.items.remove(index);
+            return;
+        }
+        item.due = today + Duration::days(i64::from(item.interval_days));
+    }
+}
+
+/// 合否とスコアから SM-2 の品質 (0〜5) を決める。合格なら 3 以上、不合格なら 2 以下
+pub fn quality(passed: bool, scores: Option<&EvaluationScores>) -> u8 {
+    let score = scores.map_or(if passed { 4 } else { 1 }, |scores| {
+        let total = u16::from(scores.importance)
+            + u16::from(scores.conciseness)
+            + u16::from(scores.accuracy);
+        u8::try_from((total + 1) / 3).unwrap_or(5)
+    });
+    if passed {
+        score.clamp(PASSING_QUALITY, 5)
+    } else {
+        score.min(PASSING_QUALITY - 1)
+    }
+}
+
+/// 復習モードの状態。`current` は出題中の復習の文章
+#[derive(Default)]
+pub struct ReviewSession {
+    pub current: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(n: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 5, n).unwrap_or_default()
+    }
+
+    #[test]
+    fn failed_text_is_due_next_day_and_intervals_grow() {
+        let mut queue = ReviewQueue::default();
+        queue.add_failed("原文".to_string(), 400, day(1));
+        assert_eq!(queue.due_count(day(1)), 0);
+        assert_eq!(queue.due_count(day(2)), 1);
+
+        queue.grade("原文", 4, day(2));
+        assert_eq!(queue.next_due(day(3)).map(|item| item.due), Some(day(3)));
+        queue.grade("原文", 4, day(3));
+        assert_eq!(queue.items.first().map(|item| item.due), Some(day(9)));
+        queue.grade("原文", 5, day(9));
+        assert_eq!(queue.items.first().map(|item| item.interval_days), Some(16));
+
+        queue.grade("原文", 1, day(25));
+        assert_eq!(queue.items.first().map(|item| item.repetitions), Some(0));
+        assert_eq!(queue.items.first().map(|item| item.due), Some(day(26)));
+    }
+
+    #[test]
+    fn quality_follows_verdict_and_text_graduates() {
+        assert_eq!(quality(true, None), 4);
+        assert_eq!(quality(false, None), 1);
+        let scores = EvaluationScores {
+            appropriate: true,
+            importance: 5,
+            conciseness: 4,
+            accuracy: 5,
+            improvement1: String::new(),
+            improvement2: String::new(),
+            improvement3: String::new(),
+            overall_passed: false,
+        };
+        assert_eq!(quality(true, Some(&scores)), 5);
+        assert_eq!(quality(false, Some(&scores)), 2);
+
+        let mut queue = ReviewQueue::default();
+        queue.add_failed("原文".to_string(), 400, day(1));
+        for _ in 0..GRADUATE_REPETITIONS {
+            queue.grade("原文", 5, day(2));
+        }
+        assert!(queue.items.is_empty());
+    }
+}
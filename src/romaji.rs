@@ -0,0 +1,277 @@
+/// ローマ字とひらがなの対応表。長い綴りから順に照合する
+const ROMAJI_TABLE: &[(&str, &str)] = &[
+    ("xtsu", "っ"),
+    ("ltsu", "っ"),
+    ("kya", "きゃ"),
+    ("kyu", "きゅ"),
+    ("kyo", "きょ"),
+    ("gya", "ぎゃ"),
+    ("gyu", "ぎゅ"),
+    ("gyo", "ぎょ"),
+    ("sha", "しゃ"),
+    ("shi", "し"),
+    ("shu", "しゅ"),
+    ("she", "しぇ"),
+    ("sho", "しょ"),
+    ("sya", "しゃ"),
+    ("syu", "しゅ"),
+    ("syo", "しょ"),
+    ("zya", "じゃ"),
+    ("zyu", "じゅ"),
+    ("zyo", "じょ"),
+    ("cha", "ちゃ"),
+    ("chi", "ち"),
+    ("chu", "ちゅ"),
+    ("che", "ちぇ"),
+    ("cho", "ちょ"),
+    ("tya", "ちゃ"),
+    ("tyu", "ちゅ"),
+    ("tyo", "ちょ"),
+    ("dya", "ぢゃ"),
+    ("dyu", "ぢゅ"),
+    ("dyo", "ぢょ"),
+    ("tsu", "つ"),
+    ("thi", "てぃ"),
+    ("dhi", "でぃ"),
+    ("nya", "にゃ"),
+    ("nyu", "にゅ"),
+    ("nyo", "にょ"),
+    ("hya", "ひゃ"),
+    ("hyu", "ひゅ"),
+    ("hyo", "ひょ"),
+    ("bya", "びゃ"),
+    ("byu", "びゅ"),
+    ("byo", "びょ"),
+    ("pya", "ぴゃ"),
+    ("pyu", "ぴゅ"),
+    ("pyo", "ぴょ"),
+    ("mya", "みゃ"),
+    ("myu", "みゅ"),
+    ("myo", "みょ"),
+    ("rya", "りゃ"),
+    ("ryu", "りゅ"),
+    ("ryo", "りょ"),
+    ("xtu", "っ"),
+    ("ltu", "っ"),
+    ("xya", "ゃ"),
+    ("xyu", "ゅ"),
+    ("xyo", "ょ"),
+    ("lya", "ゃ"),
+    ("lyu", "ゅ"),
+    ("lyo", "ょ"),
+    ("ja", "じゃ"),
+    ("ji", "じ"),
+    ("ju", "じゅ"),
+    ("je", "じぇ"),
+    ("jo", "じょ"),
+    ("fa", "ふぁ"),
+    ("fi", "ふぃ"),
+    ("fu", "ふ"),
+    ("fe", "ふぇ"),
+    ("fo", "ふぉ"),
+    ("ka", "か"),
+    ("ki", "き"),
+    ("ku", "く"),
+    ("ke", "け"),
+    ("ko", "こ"),
+    ("ga", "が"),
+    ("gi", "ぎ"),
+    ("gu", "ぐ"),
+    ("ge", "げ"),
+    ("go", "ご"),
+    ("sa", "さ"),
+    ("si", "し"),
+    ("su", "す"),
+    ("se", "せ"),
+    ("so", "そ"),
+    ("za", "ざ"),
+    ("zi", "じ"),
+    ("zu", "ず"),
+    ("ze", "ぜ"),
+    ("zo", "ぞ"),
+    ("ta", "た"),
+    ("ti", "ち"),
+    ("tu", "つ"),
+    ("te", "て"),
+    ("to", "と"),
+    ("da", "だ"),
+    ("di", "ぢ"),
+    ("du", "づ"),
+    ("de", "で"),
+    ("do", "ど"),
+    ("na", "な"),
+    ("ni", "に"),
+    ("nu", "ぬ"),
+    ("ne", "ね"),
+    ("no", "の"),
+    ("nn", "ん"),
+    ("n'", "ん"),
+    ("ha", "は"),
+    ("hi", "ひ"),
+    ("hu", "ふ"),
+    ("he", "へ"),
+    ("ho", "ほ"),
+    ("ba", "ば"),
+    ("bi", "び"),
+    ("bu", "ぶ"),
+    ("be", "べ"),
+    ("bo", "ぼ"),
+    ("pa", "ぱ"),
+    ("pi", "ぴ"),
+    ("pu", "ぷ"),
+    ("pe", "ぺ"),
+    ("po", "ぽ"),
+    ("ma", "ま"),
+    ("mi", "み"),
+    ("mu", "む"),
+    ("me", "め"),
+    ("mo", "も"),
+    ("ya", "や"),
+    ("yu", "ゆ"),
+    ("yo", "よ"),
+    ("ra", "ら"),
+    ("ri", "り"),
+    ("ru", "る"),
+    ("re", "れ"),
+    ("ro", "ろ"),
+    ("wa", "わ"),
+    ("wi", "うぃ"),
+    ("we", "うぇ"),
+    ("wo", "を"),
+    ("xa", "ぁ"),
+    ("xi", "ぃ"),
+    ("xu", "ぅ"),
+    ("xe", "ぇ"),
+    ("xo", "ぉ"),
+    ("la", "ぁ"),
+    ("li", "ぃ"),
+    ("lu", "ぅ"),
+    ("le", "ぇ"),
+    ("lo", "ぉ"),
+    ("a", "あ"),
+    ("i", "い"),
+    ("u", "う"),
+    ("e", "え"),
+    ("o", "お"),
+    ("-", "ー"),
+    (",", "、"),
+    (".", "。"),
+];
+
+const MAX_ROMAJI_LEN: usize = 4;
+
+/// ローマ字入力の綴りとして受け付ける文字か
+pub fn is_romaji_char(c: char) -> bool {
+    c.is_ascii_alphabetic() || matches!(c, '-' | ',' | '.' | '\'')
+}
+
+/// ローマ字をひらがなに変換する。
+/// 戻り値は (変換できた部分, まだ綴りの途中で変換できない末尾)
+pub fn to_hiragana(romaji: &str) -> (String, String) {
+    let chars: Vec<char> = romaji.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let mut kana = String::new();
+    let mut index = 0;
+
+    while let Some(&current) = chars.get(index) {
+        let rest = chars.get(index..).unwrap_or_default();
+        if let Some((len, converted)) = match_table(rest) {
+            kana.push_str(converted);
+            index += len;
+            continue;
+        }
+
+        let next = rest.get(1).copied();
+        if is_consonant(current) && current != 'n' && next == Some(current) {
+            kana.push('っ');
+            index += 1;
+            continue;
+        }
+        if current == 'n' && next.is_some_and(|c| is_consonant(c) && c != 'n' && c != 'y') {
+            kana.push('ん');
+            index += 1;
+            continue;
+        }
+        if is_table_prefix(rest) {
+            return (kana, rest.iter().collect());
+        }
+
+        kana.push(current);
+        index += 1;
+    }
+    (kana, String::new())
+}
+
+/// 入力を確定するときに、末尾に残った綴りを変換する (`n` だけなら「ん」)
+pub fn flush(romaji: &str) -> String {
+    let (mut kana, rest) = to_hiragana(romaji);
+    if rest == "n" {
+        kana.push('ん');
+    } else {
+        kana.push_str(&rest);
+    }
+    kana
+}
+
+pub fn to_katakana(hiragana: &str) -> String {
+    hiragana
+        .chars()
+        .map(|c| match c {
+            'ぁ'..='ゖ' => char::from_u32(u32::from(c) + 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+fn match_table(chars: &[char]) -> Option<(usize, &'static str)> {
+    (1..=MAX_ROMAJI_LEN.min(chars.len())).rev().find_map(|len| {
+        let head: String = chars.get(..len)?.iter().collect();
+        ROMAJI_TABLE
+            .iter()
+            .find(|(romaji, _)| *romaji == head)
+            .map(|(_, kana)| (len, *kana))
+    })
+}
+
+fn is_table_prefix(chars: &[char]) -> bool {
+    let head: String = chars.iter().collect();
+    ROMAJI_TABLE
+        .iter()
+        .any(|(romaji, _)| romaji.len() > head.len() && romaji.starts_with(&head))
+}
+
+fn is_consonant(c: char) -> bool {
+    c.is_ascii_lowercase() && !matches!(c, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_romaji_including_sokuon_and_n() {
+        assert_eq!(
+            to_hiragana("kanjihenkan"),
+            ("かんじへんか".to_string(), "n".to_string())
+        );
+        assert_eq!(flush("kanjihenkan"), "かんじへんかん");
+        assert_eq!(
+            to_hiragana("gakkou"),
+            ("がっこう".to_string(), String::new())
+        );
+        assert_eq!(
+            to_hiragana("shinnbun"),
+            ("しんぶ".to_string(), "n".to_string())
+        );
+        assert_eq!(to_hiragana("kony"), ("こ".to_string(), "ny".to_string()));
+        assert_eq!(to_hiragana("ky"), (String::new(), "ky".to_string()));
+        assert_eq!(
+            to_hiragana("yo-so."),
+            ("よーそ。".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn converts_hiragana_to_katakana() {
+        assert_eq!(to_katakana("でーたべーす"), "データベース");
+    }
+}
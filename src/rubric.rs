@@ -0,0 +1,162 @@
+use crate::config::{LabelLanguage, RubricConfig};
+use crate::evaluation::{self, EvaluationResult, OverallEvaluation};
+use crate::i18n::trf;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const MAX_SCORE: u32 = 5;
+
+/// `[rubric]` の観点ごとの点数と、重みを付けて合計した達成率
+#[derive(Debug, PartialEq, Eq)]
+pub struct RubricScores {
+    /// 観点の名前・点数・重み
+    pub rows: Vec<(String, u8, u8)>,
+    /// 満点に対する加重スコア (%)
+    pub percent: u8,
+    pub pass_percent: u8,
+}
+
+#[derive(Deserialize)]
+struct JsonRubric {
+    #[serde(default)]
+    rubric: HashMap<String, u8>,
+}
+
+/// 評価プロンプトの末尾に加える、観点ごとの点数を求める指示。観点がなければ空
+pub fn evaluation_rule(rubric: &RubricConfig) -> String {
+    if rubric.criteria.is_empty() {
+        return String::new();
+    }
+    let mut criteria = String::new();
+    for criterion in &rubric.criteria {
+        criteria.push_str("- ");
+        criteria.push_str(&criterion.name);
+        if !criterion.description.is_empty() {
+            criteria.push_str(": ");
+            criteria.push_str(&criterion.description);
+        }
+        criteria.push('\n');
+    }
+    let example = rubric
+        .criteria
+        .iter()
+        .map(|criterion| format!("\"{}\": 3", criterion.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "\n# 独自の評価観点\n- 出力の JSON に \"rubric\" を加え、次の観点ごとに 1〜5 の整数の点数を、観点名をキーにして入れること\n{criteria}- 例: \"rubric\": {{{example}}}\n"
+    )
+}
+
+/// 応答の `rubric` から観点ごとの点数を取り出す。観点が 1 つでも欠けるか範囲外なら `None`
+pub fn score(rubric: &RubricConfig, evaluation: &str) -> Option<RubricScores> {
+    let (Some(start), Some(end)) = (evaluation.find('{'), evaluation.rfind('}')) else {
+        return None;
+    };
+    let parsed: JsonRubric = serde_json::from_str(evaluation.get(start..=end)?).ok()?;
+    let rows = rubric
+        .criteria
+        .iter()
+        .map(|criterion| {
+            let score = *parsed.rubric.get(&criterion.name)?;
+            (1..=5)
+                .contains(&score)
+                .then(|| (criterion.name.clone(), score, criterion.weight))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let total_weight: u32 = rows.iter().map(|&(_, _, weight)| u32::from(weight)).sum();
+    if total_weight == 0 {
+        return None;
+    }
+    let weighted: u32 = rows
+        .iter()
+        .map(|&(_, score, weight)| u32::from(score) * u32::from(weight))
+        .sum();
+    Some(RubricScores {
+        rows,
+        percent: u8::try_from(weighted * 100 / (total_weight * MAX_SCORE)).unwrap_or(100),
+        pass_percent: rubric.pass_percent(),
+    })
+}
+
+impl RubricScores {
+    /// 加重スコアが合格ラインに届き、適切な要約と判定されていれば合格にする
+    pub fn apply(&self, result: &mut EvaluationResult) {
+        result.overall = if result.appropriate && self.percent >= self.pass_percent {
+            OverallEvaluation::Pass
+        } else {
+            OverallEvaluation::Fail
+        };
+    }
+
+    /// 評価画面のスコア表。組み込みの 3 観点の代わりに表示する
+    pub fn format_table(&self, locale: LabelLanguage) -> String {
+        let mut lines: Vec<String> = self
+            .rows
+            .iter()
+            .map(|(name, score, weight)| {
+                trf(
+                    locale,
+                    "{} (重み {})",
+                    &[&evaluation::format_score_row(name, *score), weight],
+                )
+            })
+            .collect();
+        lines.push(trf(
+            locale,
+            "加重スコア: {}% (合格ライン {}%)",
+            &[&self.percent, &self.pass_percent],
+        ));
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RubricCriterion;
+
+    fn rubric() -> RubricConfig {
+        let criterion = |name: &str, weight| RubricCriterion {
+            name: name.to_string(),
+            description: String::new(),
+            weight,
+        };
+        RubricConfig {
+            criteria: vec![criterion("論理の流れ", 2), criterion("用語の正確さ", 1)],
+            pass_percent: Some(70),
+        }
+    }
+
+    #[test]
+    fn weighted_percent_decides_the_pass() {
+        let scores = score(
+            &rubric(),
+            r#"{"overall": "不合格", "rubric": {"論理の流れ": 4, "用語の正確さ": 3}}"#,
+        );
+        assert_eq!(scores.as_ref().map(|s| s.percent), Some(73));
+        assert!(scores.as_ref().is_some_and(|s| {
+            s.format_table(LabelLanguage::Ja)
+                .contains("加重スコア: 73% (合格ライン 70%)")
+                && s.format_table(LabelLanguage::En)
+                    .contains("Weighted score: 73% (pass mark 70%)")
+        }));
+
+        assert_eq!(score(&rubric(), r#"{"rubric": {"論理の流れ": 4}}"#), None);
+        assert_eq!(
+            score(
+                &rubric(),
+                r#"{"rubric": {"論理の流れ": 6, "用語の正確さ": 3}}"#
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn prompt_lists_every_criterion() {
+        let rule = evaluation_rule(&rubric());
+        assert!(rule.contains("- 論理の流れ\n- 用語の正確さ\n"));
+        assert!(rule.contains(r#""rubric": {"論理の流れ": 3, "用語の正確さ": 3}"#));
+        assert_eq!(evaluation_rule(&RubricConfig::default()), "");
+    }
+}
@@ -0,0 +1,284 @@
+use crate::models::{EvaluationScoreStats, EvaluationScores, EvaluationSummary, TrainingResult};
+use chrono::{Local, NaiveDate};
+
+/// 基準とするのに必要な評価の件数。これ未満のモデルは補正しない
+const MIN_BASELINE_COUNT: usize = 5;
+
+/// レポートのスコアを、評価したままの点で見るか、モデルごとの甘さ辛さを補正して見るか
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScoreScale {
+    #[default]
+    Raw,
+    Normalized,
+}
+
+impl ScoreScale {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Raw => Self::Normalized,
+            Self::Normalized => Self::Raw,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Raw => "素点",
+            Self::Normalized => "モデル補正",
+        }
+    }
+}
+
+/// 評価したモデルごとの 3 観点 (重要情報・簡潔性・正確性) の平均点。
+/// モデルを記録していない古い結果は `model` が `None` の 1 つのモデルとして扱う
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelBaseline {
+    pub model: Option<String>,
+    pub count: usize,
+    pub averages: [f32; 3],
+}
+
+fn axis_scores(scores: &EvaluationScores) -> [f32; 3] {
+    [scores.importance, scores.conciseness, scores.accuracy].map(f32::from)
+}
+
+fn averages<'a>(scores: impl Iterator<Item = &'a EvaluationScores>) -> (usize, [f32; 3]) {
+    let mut count = 0_u16;
+    let mut totals = [0.0_f32; 3];
+    for scores in scores {
+        count = count.saturating_add(1);
+        for (total, score) in totals.iter_mut().zip(axis_scores(scores)) {
+            *total += score;
+        }
+    }
+    let divisor = f32::from(count.max(1));
+    (usize::from(count), totals.map(|total| total / divisor))
+}
+
+/// モデルごとの平均点を、初めて評価に使った順に返す
+pub fn model_baselines(results: &[TrainingResult]) -> Vec<ModelBaseline> {
+    let mut models: Vec<Option<&str>> = Vec::new();
+    for result in results.iter().filter(|result| result.evaluation.is_some()) {
+        let model = result.evaluator_model.as_deref();
+        if !models.contains(&model) {
+            models.push(model);
+        }
+    }
+    models
+        .into_iter()
+        .map(|model| {
+            let (count, averages) = averages(
+                results
+                    .iter()
+                    .filter(|result| result.evaluator_model.as_deref() == model)
+                    .filter_map(|result| result.evaluation.as_ref()),
+            );
+            ModelBaseline {
+                model: model.map(str::to_string),
+                count,
+                averages,
+            }
+        })
+        .collect()
+}
+
+/// 各結果の 3 観点のスコア。補正するときは、そのモデルの平均と全モデルの平均の差だけずらし、1〜5 に収める
+fn scale_scores(
+    results: &[TrainingResult],
+    scale: ScoreScale,
+) -> impl Iterator<Item = (&TrainingResult, [f32; 3])> {
+    let baselines = model_baselines(results);
+    let (_, overall) = averages(
+        results
+            .iter()
+            .filter_map(|result| result.evaluation.as_ref()),
+    );
+    results.iter().filter_map(move |result| {
+        let raw = axis_scores(result.evaluation.as_ref()?);
+        let baseline = baselines
+            .iter()
+            .find(|baseline| baseline.model == result.evaluator_model)
+            .filter(|baseline| baseline.count >= MIN_BASELINE_COUNT);
+        let scores = match (scale, baseline) {
+            (ScoreScale::Normalized, Some(baseline)) => {
+                let mut scores = raw;
+                for ((score, own), all) in scores.iter_mut().zip(baseline.averages).zip(overall) {
+                    *score = (*score - own + all).clamp(1.0, 5.0);
+                }
+                scores
+            }
+            _ => raw,
+        };
+        Some((result, scores))
+    })
+}
+
+fn score_stats(scores: &mut [f32]) -> Option<EvaluationScoreStats> {
+    if scores.is_empty() {
+        return None;
+    }
+    scores.sort_unstable_by(f32::total_cmp);
+    let count = f32::from(u16::try_from(scores.len()).unwrap_or(u16::MAX));
+    let average = scores.iter().sum::<f32>() / count;
+    let mid = scores.len() / 2;
+    let upper = scores.get(mid).copied().unwrap_or_default();
+    let median = if scores.len() % 2 == 1 {
+        upper
+    } else {
+        f32::midpoint(scores.get(mid - 1).copied().unwrap_or_default(), upper)
+    };
+    Some(EvaluationScoreStats { average, median })
+}
+
+/// `today` までの `days` 日間の評価を `scale` で集計する。基準は期間外の評価も含めて求める
+pub fn evaluation_summary(
+    results: &[TrainingResult],
+    days: usize,
+    today: NaiveDate,
+    scale: ScoreScale,
+) -> EvaluationSummary {
+    let start_date =
+        today - chrono::Duration::days(i64::try_from(days.saturating_sub(1)).unwrap_or(i64::MAX));
+    summarize(
+        scale_scores(results, scale)
+            .filter(|(result, _)| result.timestamp.date_naive() >= start_date)
+            .map(|(_, scores)| scores),
+    )
+}
+
+/// 評価を `group` の値ごとに `scale` で集計し、値が最初に現れた順に返す。`group` が `None` の結果は除く
+pub fn grouped_evaluation_summaries<K: PartialEq>(
+    results: &[TrainingResult],
+    scale: ScoreScale,
+    group: impl Fn(&TrainingResult) -> Option<K>,
+) -> Vec<(K, EvaluationSummary)> {
+    let mut groups: Vec<(K, Vec<[f32; 3]>)> = Vec::new();
+    for (key, scores) in
+        scale_scores(results, scale).filter_map(|(result, scores)| Some((group(result)?, scores)))
+    {
+        match groups.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, group_scores)) => group_scores.push(scores),
+            None => groups.push((key, vec![scores])),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(key, scores)| (key, summarize(scores.into_iter())))
+        .collect()
+}
+
+fn summarize(scores: impl Iterator<Item = [f32; 3]>) -> EvaluationSummary {
+    let mut axes: [Vec<f32>; 3] = Default::default();
+    for scores in scores {
+        for (axis, score) in axes.iter_mut().zip(scores) {
+            axis.push(score);
+        }
+    }
+    let [importance, conciseness, accuracy] = &mut axes;
+    EvaluationSummary {
+        count: importance.len(),
+        importance: score_stats(importance),
+        conciseness: score_stats(conciseness),
+        accuracy: score_stats(accuracy),
+    }
+}
+
+/// 今日までの集計
+pub fn recent_evaluation_summary(
+    results: &[TrainingResult],
+    days: usize,
+    scale: ScoreScale,
+) -> EvaluationSummary {
+    evaluation_summary(results, days, Local::now().date_naive(), scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluated(model: &str, score: u8) -> TrainingResult {
+        TrainingResult {
+            timestamp: Local::now(),
+            evaluation: Some(EvaluationScores {
+                appropriate: true,
+                importance: score,
+                conciseness: score,
+                accuracy: score,
+                improvement1: String::new(),
+                improvement2: String::new(),
+                improvement3: String::new(),
+                overall_passed: true,
+            }),
+            evaluator_model: Some(model.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn baselines_are_grouped_by_evaluator_model() {
+        let mut results = vec![evaluated("a", 4), evaluated("a", 2), evaluated("b", 5)];
+        results.push(TrainingResult::default());
+        let baselines = model_baselines(&results);
+        assert_eq!(baselines.len(), 2);
+        let first = baselines.first();
+        assert_eq!(
+            first.map(|b| (b.model.as_deref(), b.count)),
+            Some((Some("a"), 2))
+        );
+        assert!(first.is_some_and(|b| {
+            b.averages
+                .iter()
+                .all(|avg| (avg - 3.0).abs() < f32::EPSILON)
+        }));
+    }
+
+    #[test]
+    fn normalization_removes_the_gap_between_lenient_and_strict_models() {
+        let mut results: Vec<_> = (0..MIN_BASELINE_COUNT)
+            .map(|_| evaluated("strict", 2))
+            .collect();
+        results.extend((0..MIN_BASELINE_COUNT).map(|_| evaluated("lenient", 4)));
+        let today = Local::now().date_naive();
+
+        let close = |stats: Option<EvaluationScoreStats>, expected: f32| {
+            stats.is_some_and(|s| (s.average - expected).abs() < f32::EPSILON)
+        };
+
+        let raw = evaluation_summary(&results, 30, today, ScoreScale::Raw);
+        assert!(
+            raw.importance
+                .is_some_and(|s| (s.median - 3.0).abs() < f32::EPSILON)
+        );
+        let normalized = evaluation_summary(&results, 30, today, ScoreScale::Normalized);
+        assert!(close(normalized.importance, 3.0));
+        assert!(
+            normalized
+                .accuracy
+                .is_some_and(|s| (s.median - 3.0).abs() < f32::EPSILON)
+        );
+
+        // 件数が少ないモデルは補正しない
+        let few = vec![evaluated("new", 5)];
+        let summary = evaluation_summary(&few, 30, today, ScoreScale::Normalized);
+        assert!(close(summary.accuracy, 5.0));
+    }
+
+    #[test]
+    fn summaries_are_grouped_in_order_of_first_appearance() {
+        let mut results = vec![evaluated("a", 2), evaluated("b", 5), evaluated("a", 4)];
+        results.push(TrainingResult::default());
+        let summaries = grouped_evaluation_summaries(&results, ScoreScale::Raw, |result| {
+            result.evaluator_model.clone()
+        });
+        let groups: Vec<_> = summaries
+            .iter()
+            .map(|(model, summary)| (model.as_str(), summary.count))
+            .collect();
+        assert_eq!(groups, [("a", 2), ("b", 1)]);
+        assert!(summaries.first().is_some_and(|(_, summary)| {
+            summary
+                .importance
+                .as_ref()
+                .is_some_and(|s| (s.average - 3.0).abs() < f32::EPSILON)
+        }));
+    }
+}
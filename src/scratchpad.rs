@@ -0,0 +1,75 @@
+use crate::app::App;
+use rat_text::text_area::TextAreaState;
+
+/// 読みながら要点を書き留めるメモ欄。要約とは別に持ち、評価には送らない
+pub struct Scratchpad {
+    pub visible: bool,
+    pub text_area: TextAreaState,
+}
+
+impl Default for Scratchpad {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            text_area: App::new_text_area_state(),
+        }
+    }
+}
+
+impl Scratchpad {
+    /// メモ欄を表示して入力を始める
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.text_area.focus.set(true);
+        self.text_area.scroll_cursor_to_visible();
+    }
+
+    /// 表示を切り替える。隠すときは入力も終える
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        if !self.visible {
+            self.text_area.focus.set(false);
+        }
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.text_area.focus.get()
+    }
+
+    /// 新しい原文に移るときにメモを消す。表示するかどうかは引き継ぐ
+    pub fn clear(&mut self) {
+        self.text_area = App::new_text_area_state();
+    }
+
+    /// 空白だけのメモは書かなかったものとみなす
+    pub fn notes(&self) -> Option<String> {
+        let notes = self.text_area.value();
+        let notes = notes.trim();
+        (!notes.is_empty()).then(|| notes.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_are_trimmed_and_cleared_with_the_text() {
+        let mut scratchpad = Scratchpad::default();
+        assert_eq!(scratchpad.notes(), None);
+        scratchpad.open();
+        scratchpad
+            .text_area
+            .insert_str("  筆者の主張\n- 理由は2つ\n\n");
+        assert_eq!(
+            scratchpad.notes().as_deref(),
+            Some("筆者の主張\n- 理由は2つ")
+        );
+
+        scratchpad.clear();
+        assert_eq!(scratchpad.notes(), None);
+        assert!(scratchpad.visible);
+        scratchpad.toggle();
+        assert!(!scratchpad.visible && !scratchpad.is_editing());
+    }
+}
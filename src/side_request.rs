@@ -0,0 +1,107 @@
+use crate::api_client::ApiClient;
+use crate::appeal::Exchange;
+use crate::error::AppError;
+use crate::history::SessionRecord;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+/// 評価以外に、画面を開いたまま API に問い合わせる依頼。応答を当てはめるのに要る内容を持つ
+#[derive(Clone)]
+pub enum SideRequest {
+    Glossary {
+        text: String,
+    },
+    Furigana {
+        text: String,
+    },
+    WordLookup {
+        word: String,
+        sentence: String,
+    },
+    Appeal {
+        record: Box<SessionRecord>,
+        exchanges: Vec<Exchange>,
+        argument: String,
+    },
+    Coach {
+        question: String,
+    },
+    ParagraphReview {
+        paragraph: String,
+        summary: String,
+    },
+}
+
+impl SideRequest {
+    async fn send(&self, client: &ApiClient) -> Result<String, AppError> {
+        match self {
+            Self::Glossary { text } => client.generate_glossary(text).await,
+            Self::Furigana { text } => client.generate_furigana(text).await,
+            Self::WordLookup { word, sentence } => client.lookup_word(word, sentence).await,
+            Self::Appeal {
+                record,
+                exchanges,
+                argument,
+            } => client.appeal_evaluation(record, exchanges, argument).await,
+            Self::Coach { question } => client.ask_coach(question).await,
+            Self::ParagraphReview { paragraph, summary } => {
+                client.review_paragraph(paragraph, summary).await
+            }
+        }
+    }
+}
+
+type Finished = (SideRequest, Result<String, AppError>);
+
+/// 用語解説・読み仮名・語の意味・異議・コーチ・段落の講評の問い合わせを tokio タスクで実行し、
+/// 結果をチャネル経由でメインループに返す。`EvaluationRunner` と同じく、待つ間も画面を止めない
+pub struct SideRequestRunner {
+    sender: UnboundedSender<Finished>,
+    receiver: UnboundedReceiver<Finished>,
+    /// 問い合わせ中の依頼。取り消したときに、どの状態を戻すかを知るために残す
+    running: Option<(SideRequest, JoinHandle<()>)>,
+}
+
+impl Default for SideRequestRunner {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            sender,
+            receiver,
+            running: None,
+        }
+    }
+}
+
+impl SideRequestRunner {
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    pub fn start(&mut self, client: ApiClient, request: SideRequest) {
+        self.cancel();
+        let sender = self.sender.clone();
+        let sent = request.clone();
+        let handle = tokio::spawn(async move {
+            let response = sent.send(&client).await;
+            // 受信側が先に破棄されていれば結果は不要なので無視する
+            let _ = sender.send((sent, response));
+        });
+        self.running = Some((request, handle));
+    }
+
+    /// 問い合わせを打ち切り、打ち切った依頼を返す
+    pub fn cancel(&mut self) -> Option<SideRequest> {
+        while self.receiver.try_recv().is_ok() {}
+        let (request, handle) = self.running.take()?;
+        handle.abort();
+        Some(request)
+    }
+
+    /// 問い合わせが終わっていれば、その依頼と応答を返す
+    pub fn try_finish(&mut self) -> Option<Finished> {
+        let finished = self.receiver.try_recv().ok()?;
+        self.running = None;
+        Some(finished)
+    }
+}
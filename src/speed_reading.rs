@@ -0,0 +1,142 @@
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_CHARS_PER_MINUTE: u32 = 600;
+/// `+` / `-` で変えるペースの幅
+pub const PACE_STEP: u32 = 50;
+const MIN_CHARS_PER_MINUTE: u32 = 100;
+/// 1 度に表示するかたまりの文字数の目安
+const CHUNK_CHARS: usize = 30;
+const CHUNK_BREAKS: [char; 5] = ['、', '。', '！', '？', '，'];
+
+/// 原文を一定のペースで少しずつ表示し、読み終えたかたまりは隠す速読練習の状態
+pub struct SpeedReadingSession {
+    pub chunks: Vec<String>,
+    pub current: usize,
+    pub chars_per_minute: u32,
+    chunk_started_at: Instant,
+    /// 一時停止した時点で、今のかたまりを表示していた時間
+    paused: Option<Duration>,
+}
+
+impl SpeedReadingSession {
+    pub fn new(text: &str, chars_per_minute: u32, now: Instant) -> Self {
+        Self {
+            chunks: split_chunks(text),
+            current: 0,
+            chars_per_minute: chars_per_minute.max(MIN_CHARS_PER_MINUTE),
+            chunk_started_at: now,
+            paused: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.chunks.len()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    pub fn current_chunk(&self) -> Option<&str> {
+        self.chunks.get(self.current).map(String::as_str)
+    }
+
+    /// 今のかたまりを表示しておく時間
+    pub fn chunk_duration(&self) -> Duration {
+        let chars = self
+            .current_chunk()
+            .map_or(0, |chunk| chunk.chars().count());
+        let chars = f64::from(u32::try_from(chars).unwrap_or(u32::MAX));
+        Duration::from_secs_f64(chars * 60.0 / f64::from(self.chars_per_minute))
+    }
+
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        self.paused
+            .unwrap_or_else(|| now.saturating_duration_since(self.chunk_started_at))
+    }
+
+    /// 表示時間が過ぎていたら次のかたまりに進む。進んだら `true`
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if self.is_finished() || self.is_paused() || self.elapsed(now) < self.chunk_duration() {
+            return false;
+        }
+        self.current += 1;
+        self.chunk_started_at = now;
+        true
+    }
+
+    pub fn toggle_pause(&mut self, now: Instant) {
+        match self.paused.take() {
+            Some(elapsed) => self.chunk_started_at = now.checked_sub(elapsed).unwrap_or(now),
+            None => self.paused = Some(self.elapsed(now)),
+        }
+    }
+
+    pub fn adjust_pace(&mut self, faster: bool) {
+        self.chars_per_minute = if faster {
+            self.chars_per_minute.saturating_add(PACE_STEP)
+        } else {
+            self.chars_per_minute
+                .saturating_sub(PACE_STEP)
+                .max(MIN_CHARS_PER_MINUTE)
+        };
+    }
+}
+
+/// 行ごとに、読点や句点の切れ目でおよそ `CHUNK_CHARS` 文字ずつのかたまりに分ける
+pub fn split_chunks(text: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let mut chunk = String::new();
+        for phrase in line.split_inclusive(CHUNK_BREAKS) {
+            if !chunk.is_empty() && chunk.chars().count() + phrase.chars().count() > CHUNK_CHARS {
+                chunks.push(std::mem::take(&mut chunk));
+            }
+            chunk.push_str(phrase);
+        }
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_break_at_punctuation_within_each_line() {
+        let phrase = format!("{}、", "あ".repeat(20));
+        let text = format!("{phrase}{phrase}終わり。\n\n次の行。");
+        assert_eq!(
+            split_chunks(&text),
+            vec![
+                phrase.clone(),
+                format!("{phrase}終わり。"),
+                "次の行。".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn session_advances_at_the_pace_and_stops_while_paused() {
+        let start = Instant::now();
+        let mut session = SpeedReadingSession::new("あいうえお。\nかきくけこ。", 360, start);
+        assert_eq!(session.chunk_duration(), Duration::from_secs(1));
+        assert!(!session.tick(start + Duration::from_millis(500)));
+
+        session.toggle_pause(start + Duration::from_millis(500));
+        assert!(!session.tick(start + Duration::from_secs(5)));
+        session.toggle_pause(start + Duration::from_secs(5));
+        assert!(!session.tick(start + Duration::from_millis(5400)));
+        assert!(session.tick(start + Duration::from_millis(5500)));
+        assert_eq!(session.current_chunk(), Some("かきくけこ。"));
+
+        session.adjust_pace(false);
+        assert_eq!(session.chars_per_minute, 310);
+        assert!(session.tick(start + Duration::from_secs(7)));
+        assert!(session.is_finished());
+        assert!(!session.tick(start + Duration::from_secs(8)));
+    }
+}
@@ -1,22 +1,47 @@
+use crate::app::MENU_OPTIONS;
+use crate::config::PaneOrientation;
+use crate::exam::ExamRecord;
+use crate::levels::{self, LevelRound, PROMOTION_INTERVAL};
 use crate::models::{
-    Badge, BadgeType, Buddy, DailyStats, EvaluationScores, EvaluationSummary, TrainingResult,
-    WeeklyStats,
+    Badge, BadgeType, BreakRecord, Buddy, DailyStats, Difficulty, DifficultyStats,
+    EvaluationScores, EvaluationSummary, HeadToHeadResult, HeadToHeadTally, LengthGenreStats,
+    RollingTrend, TextLevelStats, TextStyle, TrainingResult, Weakness, WeeklyStats,
 };
+use crate::ranks::{self, RankUp};
+use crate::read_aloud::ReadAloudResult;
+use crate::records::{PersonalRecords, RecordKind};
+use crate::score_normalization::{self, ScoreScale};
 use crate::stats_analysis;
-use chrono::{DateTime, Local, NaiveDate};
+use crate::storage;
+use crate::strictness::Strictness;
+use crate::summary_length::LengthGoal;
+use crate::summary_metrics::SummaryMetrics;
+use crate::text_cache::CacheSavings;
+use crate::text_difficulty::{self, JlptLevel, TextFeatures};
+use crate::theme::ThemeName;
+use chrono::{DateTime, Local, NaiveDate, Timelike, Weekday};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
-use std::path::PathBuf;
 
 const BADGE_INTERVAL: usize = 5;
+const WEAKNESS_WINDOW: usize = 10;
+const WEAKNESS_MIN_EVALUATIONS: usize = 3;
+const WEAKNESS_THRESHOLD: f32 = 3.5;
 const MAX_CONSECUTIVE_STREAK: usize = 50;
 const MAX_CUMULATIVE_MILESTONE: usize = 100;
+const DAILY_STREAK_MILESTONES: [usize; 7] = [3, 7, 14, 30, 60, 100, 365];
+const DAILY_GOAL_MILESTONES: [usize; 5] = [1, 7, 30, 100, 365];
+/// この時刻 (時) より前に練習すると `EarlyBird` バッジ
+const EARLY_BIRD_HOUR: u32 = 7;
+/// 1 日にこの回数練習すると `Marathon` バッジ
+const MARATHON_SESSIONS: usize = 10;
+/// 1 つの文体でこの回数合格すると `GenreMaster` バッジ
+const GENRE_MASTER_PASSES: usize = 20;
+/// この回数以上続けて不合格の直後に合格すると `Comeback` バッジ
+const COMEBACK_SLUMP: usize = 5;
 const BUDDY_EXP_LEVEL2: u32 = 10;
 const BUDDY_EXP_DEFAULT: u32 = 5;
 const BUDDY_PENALTY_DAYS: i64 = 3;
-const APP_DIR_NAME: &str = "yomitore";
-const STATS_FILE_NAME: &str = "stats.json";
 
 pub fn required_exp_for_level(level: u32) -> u32 {
     if level == 2 {
@@ -26,6 +51,46 @@ pub fn required_exp_for_level(level: u32) -> u32 {
     }
 }
 
+/// `results` の最後の結果で達成した実績。すでに授与したかは確かめない
+fn achievements_by_last(results: &[TrainingResult]) -> Vec<BadgeType> {
+    let Some((last, earlier)) = results.split_last() else {
+        return Vec::new();
+    };
+    let mut achieved = Vec::new();
+    if last.timestamp.hour() < EARLY_BIRD_HOUR {
+        achieved.push(BadgeType::EarlyBird);
+    }
+    let date = last.timestamp.date_naive();
+    let sessions = results
+        .iter()
+        .filter(|result| result.timestamp.date_naive() == date)
+        .count();
+    if sessions >= MARATHON_SESSIONS {
+        achieved.push(BadgeType::Marathon);
+    }
+    if !last.passed {
+        return achieved;
+    }
+    if let Some(genre) = last.genre {
+        let passes = results
+            .iter()
+            .filter(|result| result.passed && result.genre == Some(genre))
+            .count();
+        if passes >= GENRE_MASTER_PASSES {
+            achieved.push(BadgeType::GenreMaster(genre));
+        }
+    }
+    let slump = earlier
+        .iter()
+        .rev()
+        .take_while(|result| !result.passed)
+        .count();
+    if slump >= COMEBACK_SLUMP {
+        achieved.push(BadgeType::Comeback);
+    }
+    achieved
+}
+
 #[derive(Serialize, Deserialize, Default)]
 pub struct TrainingStats {
     pub results: Vec<TrainingResult>,
@@ -33,38 +98,75 @@ pub struct TrainingStats {
     pub badges: Vec<Badge>,
     #[serde(default)]
     pub current_streak: usize,
+    #[serde(default)]
+    pub current_daily_streak: usize,
+    /// 最長の連続練習日数
+    #[serde(default)]
+    pub longest_daily_streak: usize,
     #[serde(default, alias = "pet")]
     pub buddy: Buddy,
     #[serde(default)]
     pub last_training_date: Option<DateTime<Local>>,
+    #[serde(default)]
+    pub reading_level: usize,
+    #[serde(default)]
+    pub level_passes: usize,
+    #[serde(default)]
+    pub head_to_head: Vec<HeadToHeadResult>,
+    #[serde(default)]
+    pub read_aloud: Vec<ReadAloudResult>,
+    #[serde(default)]
+    pub breaks: Vec<BreakRecord>,
+    /// 1日の目標回数を達成した日
+    #[serde(default)]
+    pub goal_days: Vec<NaiveDate>,
+    /// 模擬試験の記録。各問の結果は `results` にも通常どおり残す
+    #[serde(default)]
+    pub exams: Vec<ExamRecord>,
+    /// 今日のお題に挑戦した日。通常の練習とは別に、日付ごとに 1 回だけ数える
+    #[serde(default)]
+    pub daily_challenge_days: Vec<NaiveDate>,
+    /// チュートリアルを一度始めたか。始めていれば起動時に自動では出さない
+    #[serde(default)]
+    pub tutorial_completed: bool,
+    /// `<` / `>` で調整した原文欄の幅 (%)。調整していなければ設定の値を使う
+    #[serde(default)]
+    pub split_percent: Option<u16>,
+    /// `L` で切り替えた欄の並べ方。切り替えていなければ設定の値を使う
+    #[serde(default)]
+    pub pane_orientation: Option<PaneOrientation>,
+    /// `C` で切り替えた配色。切り替えていなければ設定の値を使う
+    #[serde(default)]
+    pub theme: Option<ThemeName>,
+    /// メニューの `g` で選んだ要約の長さの条件。選んでいなければ原文の 25%
+    #[serde(default)]
+    pub summary_length: Option<LengthGoal>,
+    /// メニューの `x` で選んだ評価の厳しさ。選んでいなければ設定の値を使う
+    #[serde(default)]
+    pub strictness: Option<Strictness>,
+    /// キャッシュした文章を出題して節約したトークン数の見積もり
+    #[serde(default)]
+    pub text_cache: CacheSavings,
+    /// これまでに到達した最も高い段位 (`ranks::RANKS` の位置)
+    #[serde(default)]
+    pub rank: usize,
+    #[serde(default)]
+    pub rank_ups: Vec<RankUp>,
 }
 
 impl TrainingStats {
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let path = Self::get_stats_file_path()?;
-        if !path.exists() {
-            return Ok(Self::default());
-        }
-        let content = fs::read_to_string(&path)?;
-        let mut stats: TrainingStats = serde_json::from_str(&content)?;
+        let mut stats: TrainingStats =
+            storage::load_json(storage::global()?, storage::STATS_DOCUMENT)?.unwrap_or_default();
 
         stats.recalculate_streak();
+        stats.recalculate_daily_streak();
         stats.check_buddy_penalty();
         stats.rebuild_badges_from_history();
 
         Ok(stats)
     }
 
-    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let path = Self::get_stats_file_path()?;
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(&path, content)?;
-        Ok(())
-    }
-
     fn award_badges_for_progress(
         &mut self,
         current_streak: usize,
@@ -94,6 +196,83 @@ impl TrainingStats {
         }
     }
 
+    fn award_badge(&mut self, badge_type: BadgeType, earned_at: DateTime<Local>) {
+        if !self.badges.iter().any(|b| b.badge_type == badge_type) {
+            self.badges.push(Badge {
+                badge_type,
+                earned_at,
+            });
+        }
+    }
+
+    /// 最後の結果で達成した実績のバッジを授与する
+    fn award_achievement_badges(&mut self) {
+        let Some(earned_at) = self.results.last().map(|result| result.timestamp) else {
+            return;
+        };
+        for badge_type in achievements_by_last(&self.results) {
+            self.award_badge(badge_type, earned_at);
+        }
+    }
+
+    fn award_daily_streak_badge(&mut self, daily_streak: usize, earned_at: DateTime<Local>) {
+        if !DAILY_STREAK_MILESTONES.contains(&daily_streak) {
+            return;
+        }
+        let badge_type = BadgeType::DailyStreak(daily_streak);
+        if !self.badges.iter().any(|b| b.badge_type == badge_type) {
+            self.badges.push(Badge {
+                badge_type,
+                earned_at,
+            });
+        }
+    }
+
+    /// `date` に行ったトレーニングの回数
+    pub fn sessions_on(&self, date: NaiveDate) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.timestamp.date_naive() == date)
+            .count()
+    }
+
+    /// 直前の結果で更新した自己ベストを返し、更新した記録ごとにバッジを授与する
+    pub fn check_personal_records(&mut self, now: DateTime<Local>) -> Vec<RecordKind> {
+        let broken = PersonalRecords::broken_by_last(&self.results);
+        for _ in &broken {
+            let count = self.get_personal_best_badges().len() + 1;
+            self.badges.push(Badge {
+                badge_type: BadgeType::PersonalBest(count),
+                earned_at: now,
+            });
+        }
+        broken
+    }
+
+    pub fn get_personal_records(&self) -> PersonalRecords {
+        PersonalRecords::from_results(&self.results)
+    }
+
+    /// 今日の回数が目標に届いていれば達成日として記録する。初めて届いたときだけ `true` を返す
+    pub fn record_daily_goal(&mut self, goal: usize, now: DateTime<Local>) -> bool {
+        let today = now.date_naive();
+        if goal == 0 || self.sessions_on(today) < goal || self.goal_days.contains(&today) {
+            return false;
+        }
+        self.goal_days.push(today);
+        let goal_days = self.goal_days.len();
+        let badge_type = BadgeType::DailyGoal(goal_days);
+        if DAILY_GOAL_MILESTONES.contains(&goal_days)
+            && !self.badges.iter().any(|b| b.badge_type == badge_type)
+        {
+            self.badges.push(Badge {
+                badge_type,
+                earned_at: now,
+            });
+        }
+        true
+    }
+
     fn add_buddy_exp(&mut self) {
         self.buddy.exp += 1;
 
@@ -124,14 +303,67 @@ impl TrainingStats {
         &mut self,
         passed: bool,
         evaluation: Option<EvaluationScores>,
+        difficulty: Difficulty,
     ) {
-        let now = Local::now();
-        self.results.push(TrainingResult {
-            timestamp: now,
+        self.add_result(TrainingResult {
+            timestamp: Local::now(),
             passed,
             evaluation,
+            self_graded: false,
+            difficulty: Some(difficulty),
+            retry_of: None,
+            character_count: None,
+            genre: None,
+            duration_secs: None,
+            cpm: None,
+            time_limit_secs: None,
+            timed_out: false,
+            quiz_score: None,
+            text_features: None,
+            evaluator_model: None,
+            daily_challenge: None,
+            length_met: None,
+            overridden_from: None,
+            keyword_hits: None,
+            summary_metrics: None,
+        });
+    }
+
+    /// AI 評価を使わず、ユーザー自身が合否を付けた結果を記録する
+    pub fn add_self_graded_result(&mut self, passed: bool, difficulty: Difficulty) {
+        self.add_result(TrainingResult {
+            timestamp: Local::now(),
+            passed,
+            evaluation: None,
+            self_graded: true,
+            difficulty: Some(difficulty),
+            retry_of: None,
+            character_count: None,
+            genre: None,
+            duration_secs: None,
+            cpm: None,
+            time_limit_secs: None,
+            timed_out: false,
+            quiz_score: None,
+            text_features: None,
+            evaluator_model: None,
+            daily_challenge: None,
+            length_met: None,
+            overridden_from: None,
+            keyword_hits: None,
+            summary_metrics: None,
         });
+    }
+
+    fn add_result(&mut self, result: TrainingResult) {
+        let now = result.timestamp;
+        let passed = result.passed;
+        self.results.push(result);
         self.last_training_date = Some(now);
+        self.award_achievement_badges();
+
+        self.recalculate_daily_streak();
+        self.award_daily_streak_badge(self.current_daily_streak, now);
 
         if passed {
             self.add_buddy_exp();
@@ -143,9 +375,364 @@ impl TrainingStats {
         }
     }
 
-    fn get_stats_file_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-        let config_dir = dirs::config_dir().ok_or("設定ディレクトリが見つかりません。")?;
-        Ok(config_dir.join(APP_DIR_NAME).join(STATS_FILE_NAME))
+    /// 現在のレベルで規定回数合格していれば、次の1問を昇級試験にする
+    pub fn next_level_round(&self) -> LevelRound {
+        if self.level_passes >= PROMOTION_INTERVAL && !levels::is_top_level(self.reading_level) {
+            LevelRound::Promotion
+        } else {
+            LevelRound::Practice
+        }
+    }
+
+    /// レベル挑戦の結果を反映する。昇級した場合は新しいレベルを返す
+    pub fn record_level_result(&mut self, round: LevelRound, passed: bool) -> Option<usize> {
+        match round {
+            LevelRound::Practice => {
+                if passed {
+                    self.level_passes += 1;
+                }
+                None
+            }
+            LevelRound::Promotion => {
+                // 不合格なら、もう一度規定回数合格してから再受験する
+                self.level_passes = 0;
+                if passed {
+                    self.reading_level += 1;
+                    Some(self.reading_level)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    pub fn add_exam_record(&mut self, record: ExamRecord) {
+        self.exams.push(record);
+    }
+
+    /// 音読モードの記録は要約の合否とは別に残す
+    pub fn add_read_aloud_result(&mut self, result: ReadAloudResult) {
+        self.read_aloud.push(result);
+    }
+
+    /// 対戦モードの結果は個人の連続正解やバッジとは別に記録する
+    pub fn add_head_to_head_result(&mut self, result: HeadToHeadResult) {
+        self.head_to_head.push(result);
+    }
+
+    /// 参加者の組み合わせごとの通算成績 (最近対戦した順)
+    /// 直前に記録した結果を、`original` に挑戦したセッションの再挑戦として紐付ける
+    /// 直前に記録した結果に、要約の長さの条件を満たしたかを記録する
+    pub fn mark_last_result_length(&mut self, met: bool) {
+        if let Some(result) = self.results.last_mut() {
+            result.length_met = Some(met);
+        }
+    }
+
+    pub fn mark_last_result_metrics(&mut self, metrics: SummaryMetrics) {
+        if let Some(result) = self.results.last_mut() {
+            result.summary_metrics = Some(metrics);
+        }
+    }
+
+    pub fn mark_last_result_as_retry(&mut self, original: DateTime<Local>) {
+        if let Some(result) = self.results.last_mut() {
+            result.retry_of = Some(original);
+        }
+    }
+
+    /// 直前に記録した結果に、出題の文字数と文体を記録する
+    pub fn describe_last_result(&mut self, character_count: u16, genre: Option<TextStyle>) {
+        if let Some(result) = self.results.last_mut() {
+            result.character_count = Some(character_count);
+            result.genre = genre;
+        }
+        // 文体は記録した後にわかるので、文体ごとの実績はここで確かめる
+        self.award_achievement_badges();
+    }
+
+    /// 直前に記録した結果を `date` のお題への挑戦として記録する
+    pub fn mark_last_result_as_daily_challenge(&mut self, date: NaiveDate) {
+        if let Some(result) = self.results.last_mut() {
+            result.daily_challenge = Some(date);
+        }
+        if !self.daily_challenge_days.contains(&date) {
+            self.daily_challenge_days.push(date);
+        }
+    }
+
+    pub fn has_done_daily_challenge(&self, date: NaiveDate) -> bool {
+        self.daily_challenge_days.contains(&date)
+    }
+
+    /// 直前に記録した結果に、評価したモデルを記録する
+    pub fn set_last_result_model(&mut self, model: String) {
+        if let Some(result) = self.results.last_mut() {
+            result.evaluator_model = Some(model);
+        }
+    }
+
+    /// 直前に記録した結果に、原文を分析した難しさを記録する
+    pub fn analyze_last_result_text(&mut self, original_text: &str) {
+        if let Some(result) = self.results.last_mut() {
+            result.text_features = text_difficulty::analyze(original_text);
+        }
+    }
+
+    /// 直前に記録した結果に、提出までの時間と 1 分あたりの文字数を記録する
+    pub fn time_last_result(&mut self, duration_secs: u32, original_chars: usize) {
+        if let Some(result) = self.results.last_mut() {
+            result.duration_secs = Some(duration_secs);
+            result.cpm = Some(stats_analysis::characters_per_minute(
+                original_chars,
+                duration_secs,
+            ));
+        }
+    }
+
+    /// 直前に記録した結果に、制限時間付きモードの持ち時間と時間切れかどうかを記録する
+    pub fn mark_last_result_as_timed(&mut self, time_limit_secs: u32, timed_out: bool) {
+        if let Some(result) = self.results.last_mut() {
+            result.time_limit_secs = Some(time_limit_secs);
+            result.timed_out = timed_out;
+        }
+    }
+
+    /// 時間切れで不合格にした結果は、自己採点ではなく時間切れとして残す
+    pub fn mark_last_result_as_time_up(&mut self) {
+        if let Some(result) = self.results.last_mut() {
+            result.self_graded = false;
+            result.timed_out = true;
+        }
+    }
+
+    /// 異議申し立ての後、直前の結果の合否を書き換える。評価と逆の合否なら `overridden_from` に元の合否を残す。
+    /// 合格にしたときは連続正解とバッジを記録時と同じように数え直す
+    pub fn override_last_result(&mut self, passed: bool) {
+        let Some(result) = self.results.last_mut() else {
+            return;
+        };
+        if result.passed == passed {
+            return;
+        }
+        let original = result.overridden_from.unwrap_or(result.passed);
+        result.overridden_from = (original != passed).then_some(original);
+        result.passed = passed;
+        let earned_at = result.timestamp;
+        self.recalculate_streak();
+        if passed {
+            let total_correct = self.results.iter().filter(|r| r.passed).count();
+            self.award_badges_for_progress(self.current_streak, total_correct, earned_at);
+            self.award_achievement_badges();
+        }
+    }
+
+    /// 4 択問題は手元で採点するので、自己採点ではなく正解数を残す
+    pub fn mark_last_result_as_quiz(&mut self, correct: u8, total: u8) {
+        if let Some(result) = self.results.last_mut() {
+            result.self_graded = false;
+            result.quiz_score = Some((correct, total));
+        }
+    }
+
+    pub fn mark_last_result_keywords(&mut self, hits: u8, total: u8) {
+        if let Some(result) = self.results.last_mut() {
+            result.keyword_hits = Some((hits, total));
+        }
+    }
+
+    /// キーワード予想の的中数・予想数の合計と、予想した回数
+    pub fn keyword_prediction_totals(&self) -> Option<(u32, u32, usize)> {
+        let predictions: Vec<(u8, u8)> = self
+            .results
+            .iter()
+            .filter_map(|result| result.keyword_hits)
+            .collect();
+        if predictions.is_empty() {
+            return None;
+        }
+        let (hits, total) = predictions.iter().fold((0, 0), |(hits, total), &(h, t)| {
+            (hits + u32::from(h), total + u32::from(t))
+        });
+        Some((hits, total, predictions.len()))
+    }
+
+    /// 1 分あたりの文字数を記録した直近 `limit` 件 (古い順)
+    /// 要約の指標を記録した直近 `limit` 件。古い順
+    pub fn get_recent_summary_metrics(&self, limit: usize) -> Vec<SummaryMetrics> {
+        let mut recent: Vec<SummaryMetrics> = self
+            .results
+            .iter()
+            .rev()
+            .filter_map(|result| result.summary_metrics)
+            .take(limit)
+            .collect();
+        recent.reverse();
+        recent
+    }
+
+    pub fn get_recent_cpm(&self, limit: usize) -> Vec<u32> {
+        let mut recent: Vec<u32> = self
+            .results
+            .iter()
+            .rev()
+            .filter_map(|result| result.cpm)
+            .take(limit)
+            .collect();
+        recent.reverse();
+        recent
+    }
+
+    /// 再挑戦の回数と、そのうち合格した回数
+    pub fn get_retry_summary(&self) -> (usize, usize) {
+        self.results
+            .iter()
+            .filter(|result| result.retry_of.is_some())
+            .fold((0, 0), |(attempts, passed), result| {
+                (attempts + 1, passed + usize::from(result.passed))
+            })
+    }
+
+    /// 難易度ごとの合格数・不合格数。難易度を記録する前の結果と、1 件もない難易度は含めない
+    pub fn get_difficulty_stats(&self) -> Vec<DifficultyStats> {
+        Difficulty::ALL
+            .into_iter()
+            .map(|difficulty| {
+                let (correct, incorrect) = self
+                    .results
+                    .iter()
+                    .filter(|r| r.difficulty == Some(difficulty))
+                    .fold((0, 0), |(correct, incorrect), r| {
+                        if r.passed {
+                            (correct + 1, incorrect)
+                        } else {
+                            (correct, incorrect + 1)
+                        }
+                    });
+                DifficultyStats {
+                    difficulty,
+                    correct,
+                    incorrect,
+                }
+            })
+            .filter(|stats| stats.total() > 0)
+            .collect()
+    }
+
+    /// 原文から推定したレベルごとの合格数。分析を記録する前の結果と、1 件もないレベルは含めない
+    pub fn get_text_level_stats(&self) -> Vec<TextLevelStats> {
+        JlptLevel::ALL
+            .into_iter()
+            .filter_map(|level| {
+                let results: Vec<(bool, TextFeatures)> = self
+                    .results
+                    .iter()
+                    .filter_map(|r| r.text_features.map(|features| (r.passed, features)))
+                    .filter(|(_, features)| features.jlpt_level == level)
+                    .collect();
+                let count = results.len();
+                if count == 0 {
+                    return None;
+                }
+                let correct = results.iter().filter(|(passed, _)| *passed).count();
+                let kanji: usize = results
+                    .iter()
+                    .map(|(_, f)| usize::from(f.kanji_percent))
+                    .sum();
+                let sentence: usize = results
+                    .iter()
+                    .map(|(_, f)| usize::from(f.average_sentence_chars))
+                    .sum();
+                Some(TextLevelStats {
+                    level,
+                    correct,
+                    incorrect: count - correct,
+                    average_kanji_percent: u8::try_from(kanji / count).unwrap_or(u8::MAX),
+                    average_sentence_chars: u16::try_from(sentence / count).unwrap_or(u16::MAX),
+                })
+            })
+            .collect()
+    }
+
+    /// 出題の文字数と文体の組み合わせごとの合格数と、`scale` で集計した評価。
+    /// 文字数を記録する前の結果は含めず、読み込んだ文章の文字数はメニューの文字数に切り上げる
+    pub fn get_length_genre_stats(&self, scale: ScoreScale) -> Vec<LengthGenreStats> {
+        let key = |result: &TrainingResult| {
+            let count = result.character_count?;
+            let length = MENU_OPTIONS.into_iter().find(|&option| count <= option);
+            Some((length, result.genre))
+        };
+        let mut summaries =
+            score_normalization::grouped_evaluation_summaries(&self.results, scale, key);
+        let mut stats: Vec<LengthGenreStats> = Vec::new();
+        for result in &self.results {
+            let Some((length, genre)) = key(result) else {
+                continue;
+            };
+            let index = stats
+                .iter()
+                .position(|s| s.length == length && s.genre == genre)
+                .unwrap_or_else(|| {
+                    let scores = summaries
+                        .iter()
+                        .position(|(group, _)| *group == (length, genre))
+                        .map(|index| summaries.swap_remove(index).1)
+                        .unwrap_or_default();
+                    stats.push(LengthGenreStats {
+                        length,
+                        genre,
+                        correct: 0,
+                        incorrect: 0,
+                        scores,
+                    });
+                    stats.len() - 1
+                });
+            if let Some(s) = stats.get_mut(index) {
+                if result.passed {
+                    s.correct += 1;
+                } else {
+                    s.incorrect += 1;
+                }
+            }
+        }
+        // 短い順、同じ長さでは公的文書・新聞記事・読み込んだ文章の順
+        stats.sort_by_key(|s| {
+            (
+                s.length.is_none(),
+                s.length,
+                s.genre.is_none(),
+                s.genre == Some(TextStyle::Newspaper),
+            )
+        });
+        stats
+    }
+
+    pub fn get_head_to_head_tallies(&self) -> Vec<HeadToHeadTally> {
+        let mut tallies: Vec<HeadToHeadTally> = Vec::new();
+        for result in self.head_to_head.iter().rev() {
+            let [a, b] = &result.participants;
+            let players = if a.name <= b.name {
+                (a.name.clone(), b.name.clone())
+            } else {
+                (b.name.clone(), a.name.clone())
+            };
+            if !tallies.iter().any(|t| t.players == players) {
+                tallies.push(HeadToHeadTally {
+                    players: players.clone(),
+                    wins: (0, 0),
+                    draws: 0,
+                });
+            }
+            let Some(tally) = tallies.iter_mut().find(|t| t.players == players) else {
+                continue;
+            };
+            match result.winner() {
+                Some(winner) if winner.name == tally.players.0 => tally.wins.0 += 1,
+                Some(_) => tally.wins.1 += 1,
+                None => tally.draws += 1,
+            }
+        }
+        tallies
     }
 
     fn recalculate_streak(&mut self) {
@@ -157,12 +744,31 @@ impl TrainingStats {
             .count();
     }
 
+    fn recalculate_daily_streak(&mut self) {
+        self.current_daily_streak =
+            stats_analysis::calculate_daily_streak(&self.results, Local::now().date_naive());
+        self.longest_daily_streak = stats_analysis::calculate_longest_daily_streak(&self.results);
+    }
+
     fn rebuild_badges_from_history(&mut self) {
         let mut current_streak: usize = 0;
         let mut total_correct: usize = 0;
+        let mut daily_streak: usize = 0;
+        let mut last_date: Option<NaiveDate> = None;
 
         let results = self.results.clone();
-        for result in results {
+        for (index, result) in results.iter().enumerate() {
+            let date = result.timestamp.date_naive();
+            if last_date != Some(date) {
+                daily_streak = if last_date.is_some_and(|d| d + chrono::Duration::days(1) == date) {
+                    daily_streak + 1
+                } else {
+                    1
+                };
+                last_date = Some(date);
+                self.award_daily_streak_badge(daily_streak, result.timestamp);
+            }
+
             if result.passed {
                 current_streak += 1;
                 total_correct += 1;
@@ -170,15 +776,41 @@ impl TrainingStats {
             } else {
                 current_streak = 0;
             }
+
+            for badge_type in achievements_by_last(results.get(..=index).unwrap_or_default()) {
+                self.award_badge(badge_type, result.timestamp);
+            }
         }
     }
 
     pub fn get_daily_stats(&self, days: usize) -> HashMap<NaiveDate, DailyStats> {
-        stats_analysis::calculate_daily_stats(&self.results, days, Local::now().date_naive())
+        let mut daily_stats =
+            stats_analysis::calculate_daily_stats(&self.results, days, Local::now().date_naive());
+        for date in &self.goal_days {
+            if let Some(stats) = daily_stats.get_mut(date) {
+                stats.goal_met = true;
+            }
+        }
+        daily_stats
+    }
+
+    /// 直近 `days` 日の、`window` 日間の移動窓での回数と合格率
+    pub fn get_rolling_trend(&self, days: usize, window: usize) -> Vec<RollingTrend> {
+        stats_analysis::calculate_rolling_trend(
+            &self.results,
+            days,
+            window,
+            Local::now().date_naive(),
+        )
     }
 
-    pub fn get_weekly_stats(&self, weeks: usize) -> Vec<WeeklyStats> {
-        stats_analysis::calculate_weekly_stats(&self.results, weeks, Local::now())
+    pub fn get_weekly_stats(&self, weeks: usize, first_weekday: Weekday) -> Vec<WeeklyStats> {
+        stats_analysis::calculate_weekly_stats(
+            &self.results,
+            weeks,
+            Local::now().date_naive(),
+            first_weekday,
+        )
     }
 
     pub fn get_badges_by_type(&self) -> (Vec<&Badge>, Vec<&Badge>) {
@@ -197,8 +829,175 @@ impl TrainingStats {
         (consecutive, cumulative)
     }
 
-    pub fn get_recent_evaluation_summary(&self, days: usize) -> EvaluationSummary {
-        stats_analysis::get_recent_evaluation_summary(&self.results, days)
+    pub fn get_daily_streak_badges(&self) -> Vec<&Badge> {
+        self.badges
+            .iter()
+            .filter(|b| matches!(b.badge_type, BadgeType::DailyStreak(_)))
+            .collect()
+    }
+
+    pub fn get_daily_goal_badges(&self) -> Vec<&Badge> {
+        self.badges
+            .iter()
+            .filter(|b| matches!(b.badge_type, BadgeType::DailyGoal(_)))
+            .collect()
+    }
+
+    pub fn get_personal_best_badges(&self) -> Vec<&Badge> {
+        self.badges
+            .iter()
+            .filter(|b| matches!(b.badge_type, BadgeType::PersonalBest(_)))
+            .collect()
+    }
+
+    /// 段位の判定に使う合格回数と、評価を受けた結果の 3 観点の平均点
+    pub fn rank_progress(&self) -> (usize, Option<f64>) {
+        let passes = self.results.iter().filter(|r| r.passed).count();
+        let evaluations: Vec<_> = self
+            .results
+            .iter()
+            .filter_map(|result| result.evaluation.as_ref())
+            .collect();
+        if evaluations.is_empty() {
+            return (passes, None);
+        }
+        let total: u32 = evaluations
+            .iter()
+            .map(|e| u32::from(e.importance) + u32::from(e.conciseness) + u32::from(e.accuracy))
+            .sum();
+        let count = u32::try_from(evaluations.len() * 3).unwrap_or(u32::MAX);
+        (passes, Some(f64::from(total) / f64::from(count)))
+    }
+
+    /// 今の成績で新しい段位に上がっていれば記録し、その段位を返す。段位は下がらない
+    pub fn check_rank_up(&mut self, now: DateTime<Local>) -> Option<usize> {
+        let (passes, average) = self.rank_progress();
+        let rank = ranks::rank_for(passes, average);
+        if rank <= self.rank {
+            return None;
+        }
+        self.rank = rank;
+        self.rank_ups.push(RankUp {
+            rank,
+            achieved_at: now,
+        });
+        Some(rank)
+    }
+
+    /// 早起き・マラソン・文体のマスター・復活のバッジ
+    pub fn get_achievement_badges(&self) -> Vec<&Badge> {
+        self.badges
+            .iter()
+            .filter(|b| {
+                matches!(
+                    b.badge_type,
+                    BadgeType::EarlyBird
+                        | BadgeType::Marathon
+                        | BadgeType::GenreMaster(_)
+                        | BadgeType::Comeback
+                )
+            })
+            .collect()
+    }
+
+    /// 獲得できるすべてのバッジを種類ごとに並べ、獲得したものには日時を添える。
+    /// 自己ベストの更新には上限がないので、獲得した分と次の 1 つだけを含める
+    pub fn get_badge_gallery(&self) -> Vec<(BadgeType, Option<DateTime<Local>>)> {
+        let milestones = |max: usize| (BADGE_INTERVAL..=max).step_by(BADGE_INTERVAL);
+        let personal_bests = self.get_personal_best_badges().len() + 1;
+        milestones(MAX_CONSECUTIVE_STREAK)
+            .map(BadgeType::ConsecutiveStreak)
+            .chain(milestones(MAX_CUMULATIVE_MILESTONE).map(BadgeType::CumulativeMilestone))
+            .chain(DAILY_STREAK_MILESTONES.map(BadgeType::DailyStreak))
+            .chain(DAILY_GOAL_MILESTONES.map(BadgeType::DailyGoal))
+            .chain((1..=personal_bests).map(BadgeType::PersonalBest))
+            .chain([
+                BadgeType::EarlyBird,
+                BadgeType::Marathon,
+                BadgeType::GenreMaster(TextStyle::Newspaper),
+                BadgeType::GenreMaster(TextStyle::Official),
+                BadgeType::Comeback,
+            ])
+            .map(|badge_type| {
+                let earned_at = self
+                    .badges
+                    .iter()
+                    .find(|badge| badge.badge_type == badge_type)
+                    .map(|badge| badge.earned_at);
+                (badge_type, earned_at)
+            })
+            .collect()
+    }
+
+    pub fn get_recent_evaluation_summary(
+        &self,
+        days: usize,
+        scale: ScoreScale,
+    ) -> EvaluationSummary {
+        match scale {
+            ScoreScale::Raw => stats_analysis::get_recent_evaluation_summary(&self.results, days),
+            ScoreScale::Normalized => {
+                score_normalization::recent_evaluation_summary(&self.results, days, scale)
+            }
+        }
+    }
+
+    /// 直近 `weeks` 週の評価を `first_weekday` から始まる週ごとに集計する。評価のない週も含め、古い順
+    pub fn get_weekly_evaluation_summaries(
+        &self,
+        weeks: usize,
+        first_weekday: Weekday,
+        scale: ScoreScale,
+    ) -> Vec<(NaiveDate, EvaluationSummary)> {
+        let this_week = Local::now().date_naive().week(first_weekday).first_day();
+        let mut summaries =
+            score_normalization::grouped_evaluation_summaries(&self.results, scale, |result| {
+                Some(
+                    result
+                        .timestamp
+                        .date_naive()
+                        .week(first_weekday)
+                        .first_day(),
+                )
+            });
+        (0..weeks)
+            .rev()
+            .map(|ago| {
+                let start = this_week - chrono::Duration::weeks(i64::try_from(ago).unwrap_or(0));
+                let summary = summaries
+                    .iter()
+                    .position(|(week, _)| *week == start)
+                    .map(|index| summaries.swap_remove(index).1)
+                    .unwrap_or_default();
+                (start, summary)
+            })
+            .collect()
+    }
+
+    /// 直近 `days` 日の評価を文体ごとに集計する。`None` は読み込んだ文章
+    pub fn get_genre_evaluation_summaries(
+        &self,
+        days: usize,
+        scale: ScoreScale,
+    ) -> Vec<(Option<TextStyle>, EvaluationSummary)> {
+        let start_date = Local::now().date_naive()
+            - chrono::Duration::days(i64::try_from(days.saturating_sub(1)).unwrap_or(i64::MAX));
+        let mut summaries =
+            score_normalization::grouped_evaluation_summaries(&self.results, scale, |result| {
+                (result.timestamp.date_naive() >= start_date).then_some(result.genre)
+            });
+        summaries.sort_by_key(|(genre, _)| genre.is_none());
+        summaries
+    }
+
+    /// 直近の評価から、苦手な観点を1つ選ぶ
+    pub fn get_weakness(&self) -> Option<Weakness> {
+        stats_analysis::find_weakness(
+            &self.results,
+            WEAKNESS_WINDOW,
+            WEAKNESS_MIN_EVALUATIONS,
+            WEAKNESS_THRESHOLD,
+        )
     }
 }
 
@@ -206,15 +1005,17 @@ impl TrainingStats {
 mod tests {
     use super::*;
     use crate::stats_analysis::{
-        calculate_daily_stats, calculate_median, calculate_score_stats, calculate_weekly_stats,
+        calculate_daily_stats, calculate_daily_streak, calculate_longest_daily_streak,
+        calculate_median, calculate_rolling_trend, calculate_score_stats, calculate_weekly_stats,
     };
+    use chrono::TimeZone;
 
     #[test]
     fn test_badge_awarding_consecutive() {
         let mut stats = TrainingStats::default();
 
         for _ in 0..5 {
-            stats.add_result_with_evaluation(true, None);
+            stats.add_result_with_evaluation(true, None, Difficulty::Standard);
         }
 
         let (consecutive, cumulative) = stats.get_badges_by_type();
@@ -222,7 +1023,7 @@ mod tests {
         assert_eq!(cumulative.len(), 1);
 
         for _ in 0..5 {
-            stats.add_result_with_evaluation(true, None);
+            stats.add_result_with_evaluation(true, None, Difficulty::Standard);
         }
 
         let (consecutive, cumulative) = stats.get_badges_by_type();
@@ -230,17 +1031,51 @@ mod tests {
         assert_eq!(cumulative.len(), 2);
     }
 
+    #[test]
+    fn keyword_prediction_totals_skip_results_without_predictions() {
+        let mut stats = TrainingStats::default();
+        stats.add_result_with_evaluation(true, None, Difficulty::Standard);
+        assert_eq!(stats.keyword_prediction_totals(), None);
+
+        stats.mark_last_result_keywords(2, 3);
+        stats.add_result_with_evaluation(true, None, Difficulty::Standard);
+        stats.add_result_with_evaluation(false, None, Difficulty::Standard);
+        stats.mark_last_result_keywords(1, 2);
+        assert_eq!(stats.keyword_prediction_totals(), Some((3, 5, 2)));
+    }
+
+    #[test]
+    fn recent_summary_metrics_skip_results_without_metrics() {
+        let mut stats = TrainingStats::default();
+        let metrics = |sentence_count| SummaryMetrics {
+            compression_percent: 20,
+            sentence_count,
+            kanji_percent: 30,
+            verbatim_percent: 0,
+        };
+        for sentence_count in 1..=3 {
+            stats.add_result_with_evaluation(true, None, Difficulty::Standard);
+            stats.mark_last_result_metrics(metrics(sentence_count));
+        }
+        stats.add_result_with_evaluation(true, None, Difficulty::Standard);
+
+        assert_eq!(
+            stats.get_recent_summary_metrics(2),
+            vec![metrics(2), metrics(3)]
+        );
+    }
+
     #[test]
     fn test_streak_reset_on_incorrect() {
         let mut stats = TrainingStats::default();
 
         for _ in 0..5 {
-            stats.add_result_with_evaluation(true, None);
+            stats.add_result_with_evaluation(true, None, Difficulty::Standard);
         }
 
         assert_eq!(stats.current_streak, 5);
 
-        stats.add_result_with_evaluation(false, None);
+        stats.add_result_with_evaluation(false, None, Difficulty::Standard);
 
         assert_eq!(stats.current_streak, 0);
 
@@ -253,7 +1088,7 @@ mod tests {
         let mut stats = TrainingStats::default();
 
         for _ in 0..10 {
-            stats.add_result_with_evaluation(true, None);
+            stats.add_result_with_evaluation(true, None, Difficulty::Standard);
         }
 
         stats.badges.clear();
@@ -275,11 +1110,13 @@ mod tests {
             timestamp: Local::now(),
             passed: true,
             evaluation: None,
+            ..Default::default()
         });
         stats.results.push(TrainingResult {
             timestamp: Local::now(),
             passed: false,
             evaluation: None,
+            ..Default::default()
         });
 
         let yesterday = Local::now() - chrono::Duration::days(1);
@@ -287,6 +1124,7 @@ mod tests {
             timestamp: yesterday,
             passed: true,
             evaluation: None,
+            ..Default::default()
         });
 
         let daily_stats = calculate_daily_stats(&stats.results, 7, today);
@@ -304,6 +1142,33 @@ mod tests {
         assert_eq!(yesterday_stats.incorrect, 0);
     }
 
+    #[test]
+    fn test_calculate_rolling_trend() {
+        let now = Local::now();
+        let result = |days_ago: i64, passed: bool| TrainingResult {
+            timestamp: now - chrono::Duration::days(days_ago),
+            passed,
+            ..Default::default()
+        };
+        let results = vec![
+            result(0, true),
+            result(0, false),
+            result(3, true),
+            result(8, false),
+        ];
+
+        let trend = calculate_rolling_trend(&results, 10, 7, now.date_naive());
+        assert_eq!(trend.len(), 10);
+        let today = trend.last().cloned();
+        assert_eq!(today.as_ref().map(|day| day.date), Some(now.date_naive()));
+        // 8 日前の結果は今日までの 7 日間に入らない
+        assert_eq!(today.as_ref().map(|day| day.sessions), Some(3));
+        let pass_rate = today.and_then(|day| day.pass_rate).unwrap_or_default();
+        assert!((pass_rate - 200.0 / 3.0).abs() < 1e-9);
+        assert_eq!(trend.first().and_then(|day| day.pass_rate), None);
+        assert_eq!(trend.get(1).and_then(|day| day.pass_rate), Some(0.0));
+    }
+
     #[test]
     fn test_calculate_weekly_stats() {
         let mut stats = TrainingStats::default();
@@ -313,6 +1178,7 @@ mod tests {
             timestamp: now,
             passed: true,
             evaluation: None,
+            ..Default::default()
         });
 
         let last_week = now - chrono::Duration::days(7);
@@ -320,17 +1186,20 @@ mod tests {
             timestamp: last_week,
             passed: false,
             evaluation: None,
+            ..Default::default()
         });
         stats.results.push(TrainingResult {
             timestamp: last_week,
             passed: false,
             evaluation: None,
+            ..Default::default()
         });
 
-        let weekly_stats = calculate_weekly_stats(&stats.results, 4, now);
+        let weekly_stats =
+            calculate_weekly_stats(&stats.results, 4, now.date_naive(), Weekday::Sun);
 
         let this_week_stats = weekly_stats.last().cloned().unwrap_or(WeeklyStats {
-            week_number: 0,
+            start_date: NaiveDate::default(),
             correct: 0,
             incorrect: 0,
         });
@@ -341,7 +1210,7 @@ mod tests {
             .get(weekly_stats.len().saturating_sub(2))
             .cloned()
             .unwrap_or(WeeklyStats {
-                week_number: 0,
+                start_date: NaiveDate::default(),
                 correct: 0,
                 incorrect: 0,
             });
@@ -349,6 +1218,173 @@ mod tests {
         assert_eq!(last_week_stats.incorrect, 2);
     }
 
+    #[test]
+    fn test_time_last_result_records_characters_per_minute() {
+        let mut stats = TrainingStats::default();
+        stats.add_self_graded_result(true, Difficulty::default());
+        stats.time_last_result(240, 800);
+        stats.add_self_graded_result(false, Difficulty::default());
+        stats.add_self_graded_result(true, Difficulty::default());
+        stats.time_last_result(0, 30);
+
+        let first = stats.results.first().cloned().unwrap_or_default();
+        assert_eq!(first.duration_secs, Some(240));
+        assert_eq!(first.cpm, Some(200));
+        assert_eq!(stats.get_recent_cpm(10), vec![200, 1800]);
+        assert_eq!(stats.get_recent_cpm(1), vec![1800]);
+    }
+
+    #[test]
+    fn test_achievement_badges_from_the_last_result() -> Result<(), String> {
+        let morning = Local
+            .with_ymd_and_hms(2026, 7, 5, 6, 30, 0)
+            .single()
+            .ok_or("invalid date")?;
+        let result = |minutes: i64, passed: bool| TrainingResult {
+            timestamp: morning + chrono::Duration::minutes(minutes * 60),
+            passed,
+            genre: Some(TextStyle::Official),
+            ..Default::default()
+        };
+        let early = achievements_by_last(&[result(0, false)]);
+        if early != vec![BadgeType::EarlyBird] {
+            return Err(format!("expected early bird, got {early:?}"));
+        }
+
+        let mut results: Vec<TrainingResult> = (1..=5).map(|hour| result(hour, false)).collect();
+        results.push(result(6, true));
+        let comeback = achievements_by_last(&results);
+        if comeback != vec![BadgeType::Comeback] {
+            return Err(format!("expected comeback, got {comeback:?}"));
+        }
+
+        results.extend((7..=16).map(|hour| result(hour, true)));
+        let marathon = achievements_by_last(&results);
+        if marathon != vec![BadgeType::Marathon] {
+            return Err(format!("expected marathon, got {marathon:?}"));
+        }
+
+        let mut stats = TrainingStats {
+            results: (0..20).map(|day| result(day * 24 + 3, true)).collect(),
+            ..Default::default()
+        };
+        stats.rebuild_badges_from_history();
+        let achievements: Vec<BadgeType> = stats
+            .get_achievement_badges()
+            .into_iter()
+            .map(|b| b.badge_type.clone())
+            .collect();
+        if achievements != vec![BadgeType::GenreMaster(TextStyle::Official)] {
+            return Err(format!("expected genre master, got {achievements:?}"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_rank_up_is_recorded_once_and_never_goes_down() {
+        let now = Local::now();
+        let passed = |score: u8| TrainingResult {
+            timestamp: now,
+            passed: true,
+            evaluation: Some(EvaluationScores {
+                appropriate: true,
+                importance: score,
+                conciseness: score,
+                accuracy: score,
+                improvement1: String::new(),
+                improvement2: String::new(),
+                improvement3: String::new(),
+                overall_passed: true,
+            }),
+            ..Default::default()
+        };
+        let mut stats = TrainingStats {
+            results: (0..9).map(|_| passed(3)).collect(),
+            ..Default::default()
+        };
+        assert_eq!(stats.check_rank_up(now), None);
+
+        stats.results.push(passed(3));
+        assert_eq!(stats.rank_progress(), (10, Some(3.0)));
+        assert_eq!(stats.check_rank_up(now), Some(1));
+        assert_eq!(stats.check_rank_up(now), None);
+
+        stats.results.push(passed(1));
+        assert_eq!(stats.check_rank_up(now), None);
+        assert_eq!(stats.rank, 1);
+        assert_eq!(stats.rank_ups.len(), 1);
+    }
+
+    #[test]
+    fn test_overriding_the_last_result_recounts_the_streak() {
+        let mut stats = TrainingStats::default();
+        for passed in [true, true, true, true, false] {
+            stats.add_result_with_evaluation(passed, None, Difficulty::default());
+        }
+        assert_eq!(stats.current_streak, 0);
+
+        stats.override_last_result(true);
+        assert_eq!(stats.current_streak, 5);
+        assert!(
+            stats
+                .results
+                .last()
+                .is_some_and(|r| r.passed && r.overridden_from == Some(false))
+        );
+        assert!(
+            stats
+                .badges
+                .iter()
+                .any(|b| b.badge_type == BadgeType::ConsecutiveStreak(5))
+        );
+
+        stats.override_last_result(false);
+        assert_eq!(stats.current_streak, 0);
+        assert!(
+            stats
+                .results
+                .last()
+                .is_some_and(|r| !r.passed && r.overridden_from.is_none())
+        );
+    }
+
+    #[test]
+    fn test_weekly_stats_follow_week_start() -> Result<(), String> {
+        // 2026-07-05 は日曜日
+        let sunday = Local
+            .with_ymd_and_hms(2026, 7, 5, 12, 0, 0)
+            .single()
+            .ok_or("invalid date")?;
+        let results = vec![TrainingResult {
+            timestamp: sunday,
+            passed: true,
+            ..Default::default()
+        }];
+        let today = sunday.date_naive() + chrono::Duration::days(1);
+
+        let sunday_start = calculate_weekly_stats(&results, 2, today, Weekday::Sun);
+        let starts: Vec<String> = sunday_start
+            .iter()
+            .map(|week| week.start_date.to_string())
+            .collect();
+        if starts != ["2026-06-28", "2026-07-05"] {
+            return Err(format!("unexpected week starts: {starts:?}"));
+        }
+        if sunday_start.last().map(|week| week.correct) != Some(1) {
+            return Err("this week should count the sunday result".to_string());
+        }
+
+        let monday_start = calculate_weekly_stats(&results, 2, today, Weekday::Mon);
+        let last_start = monday_start.last().map(|week| week.start_date.to_string());
+        if last_start.as_deref() != Some("2026-07-06") {
+            return Err(format!("unexpected week start: {last_start:?}"));
+        }
+        if monday_start.first().map(|week| week.correct) != Some(1) {
+            return Err("last week should count the sunday result".to_string());
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_recent_evaluation_summary() {
         let mut stats = TrainingStats::default();
@@ -367,6 +1403,7 @@ mod tests {
                 improvement3: "なし".to_string(),
                 overall_passed: true,
             }),
+            ..Default::default()
         });
         stats.results.push(TrainingResult {
             timestamp: now,
@@ -381,9 +1418,10 @@ mod tests {
                 improvement3: "不正確".to_string(),
                 overall_passed: false,
             }),
+            ..Default::default()
         });
 
-        let summary = stats.get_recent_evaluation_summary(30);
+        let summary = stats.get_recent_evaluation_summary(30, ScoreScale::Raw);
         assert_eq!(summary.count, 2);
         let importance = summary.importance.as_ref().map(|s| (s.average, s.median));
         assert!(importance.is_some());
@@ -404,6 +1442,44 @@ mod tests {
         assert!((accuracy.1 - 4.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_weakness_uses_lowest_recent_average() {
+        let mut stats = TrainingStats::default();
+        let evaluated = |importance, conciseness, accuracy| TrainingResult {
+            timestamp: Local::now(),
+            evaluation: Some(EvaluationScores {
+                appropriate: true,
+                importance,
+                conciseness,
+                accuracy,
+                improvement1: String::new(),
+                improvement2: String::new(),
+                improvement3: String::new(),
+                overall_passed: true,
+            }),
+            ..Default::default()
+        };
+
+        stats.results.push(evaluated(2, 5, 5));
+        stats.results.push(evaluated(2, 5, 5));
+        assert_eq!(stats.get_weakness(), None);
+
+        stats.results.push(evaluated(4, 3, 5));
+        assert_eq!(stats.get_weakness(), Some(Weakness::Importance));
+
+        for _ in 0..WEAKNESS_WINDOW {
+            stats.results.push(evaluated(5, 4, 5));
+        }
+        assert_eq!(stats.get_weakness(), None);
+
+        stats.results.push(evaluated(5, 4, 1));
+        assert_eq!(stats.get_weakness(), None);
+        for _ in 0..4 {
+            stats.results.push(evaluated(5, 4, 1));
+        }
+        assert_eq!(stats.get_weakness(), Some(Weakness::Accuracy));
+    }
+
     #[test]
     fn test_calculate_median_edge_cases() {
         assert!((calculate_median(&[5]) - 5.0).abs() < f32::EPSILON);
@@ -429,6 +1505,7 @@ mod tests {
                 timestamp: Local::now(),
                 passed: true,
                 evaluation: None,
+                ..Default::default()
             });
         }
         stats.recalculate_streak();
@@ -438,11 +1515,13 @@ mod tests {
             timestamp: Local::now(),
             passed: false,
             evaluation: None,
+            ..Default::default()
         });
         stats.results.push(TrainingResult {
             timestamp: Local::now(),
             passed: true,
             evaluation: None,
+            ..Default::default()
         });
         stats.recalculate_streak();
         assert_eq!(stats.current_streak, 1);
@@ -470,28 +1549,28 @@ mod tests {
         assert_eq!(stats.buddy.exp, 0);
 
         for _ in 0..5 {
-            stats.add_result_with_evaluation(true, None);
+            stats.add_result_with_evaluation(true, None, Difficulty::Standard);
         }
         assert_eq!(stats.buddy.level, 2);
         assert_eq!(stats.buddy.exp, 0);
 
         for _ in 0..9 {
-            stats.add_result_with_evaluation(true, None);
+            stats.add_result_with_evaluation(true, None, Difficulty::Standard);
         }
         assert_eq!(stats.buddy.level, 2);
         assert_eq!(stats.buddy.exp, 9);
 
-        stats.add_result_with_evaluation(true, None);
+        stats.add_result_with_evaluation(true, None, Difficulty::Standard);
         assert_eq!(stats.buddy.level, 3);
         assert_eq!(stats.buddy.exp, 0);
 
         for _ in 0..4 {
-            stats.add_result_with_evaluation(true, None);
+            stats.add_result_with_evaluation(true, None, Difficulty::Standard);
         }
         assert_eq!(stats.buddy.level, 3);
         assert_eq!(stats.buddy.exp, 4);
 
-        stats.add_result_with_evaluation(false, None);
+        stats.add_result_with_evaluation(false, None, Difficulty::Standard);
         assert_eq!(stats.buddy.exp, 4);
     }
 
@@ -512,4 +1591,297 @@ mod tests {
                 .is_some_and(|date| date > Local::now() - chrono::Duration::minutes(1))
         );
     }
+
+    #[test]
+    fn test_daily_streak_counts_consecutive_days() {
+        let today = Local::now().date_naive();
+        let days_ago = |n: i64| TrainingResult {
+            timestamp: Local::now() - chrono::Duration::days(n),
+            passed: n % 2 == 0,
+            evaluation: None,
+            ..Default::default()
+        };
+
+        assert_eq!(calculate_daily_streak(&[], today), 0);
+        assert_eq!(
+            calculate_daily_streak(&[days_ago(0), days_ago(0)], today),
+            1
+        );
+        assert_eq!(
+            calculate_daily_streak(&[days_ago(3), days_ago(2), days_ago(1)], today),
+            3
+        );
+        assert_eq!(
+            calculate_daily_streak(&[days_ago(4), days_ago(2), days_ago(1), days_ago(0)], today),
+            3
+        );
+        assert_eq!(
+            calculate_daily_streak(&[days_ago(3), days_ago(2)], today),
+            0
+        );
+        assert_eq!(calculate_longest_daily_streak(&[]), 0);
+        assert_eq!(
+            calculate_longest_daily_streak(&[
+                days_ago(9),
+                days_ago(8),
+                days_ago(7),
+                days_ago(3),
+                days_ago(3),
+                days_ago(2)
+            ]),
+            3
+        );
+    }
+
+    #[test]
+    fn test_daily_streak_badges_rebuilt_from_history() {
+        let mut stats = TrainingStats::default();
+        for n in (0..7).rev() {
+            stats.results.push(TrainingResult {
+                timestamp: Local::now() - chrono::Duration::days(n),
+                passed: false,
+                evaluation: None,
+                ..Default::default()
+            });
+        }
+
+        stats.rebuild_badges_from_history();
+
+        let daily: Vec<BadgeType> = stats
+            .get_daily_streak_badges()
+            .into_iter()
+            .map(|b| b.badge_type.clone())
+            .collect();
+        assert_eq!(
+            daily,
+            vec![BadgeType::DailyStreak(3), BadgeType::DailyStreak(7)]
+        );
+        assert_eq!(stats.current_streak, 0);
+    }
+
+    #[test]
+    fn test_daily_goal_recorded_once_per_day_with_badge() {
+        let mut stats = TrainingStats::default();
+        let now = Local::now();
+        stats.add_self_graded_result(true, Difficulty::default());
+        assert!(!stats.record_daily_goal(2, now));
+        stats.add_self_graded_result(false, Difficulty::default());
+        assert!(stats.record_daily_goal(2, now));
+        assert!(!stats.record_daily_goal(2, now));
+        assert!(!stats.record_daily_goal(0, now));
+
+        assert_eq!(stats.goal_days, vec![now.date_naive()]);
+        let goal_badges: Vec<BadgeType> = stats
+            .get_daily_goal_badges()
+            .into_iter()
+            .map(|b| b.badge_type.clone())
+            .collect();
+        assert_eq!(goal_badges, vec![BadgeType::DailyGoal(1)]);
+
+        let gallery = stats.get_badge_gallery();
+        assert!(gallery.contains(&(BadgeType::DailyGoal(1), Some(now))));
+        assert!(gallery.contains(&(BadgeType::DailyGoal(7), None)));
+        assert_eq!(gallery.len(), 10 + 20 + 7 + 5 + 1 + 5);
+        assert!(
+            stats
+                .get_daily_stats(1)
+                .get(&now.date_naive())
+                .is_some_and(|day| day.goal_met)
+        );
+    }
+
+    #[test]
+    fn test_level_promotion_flow() {
+        let mut stats = TrainingStats::default();
+        assert_eq!(stats.next_level_round(), LevelRound::Practice);
+
+        for _ in 0..PROMOTION_INTERVAL {
+            assert_eq!(stats.record_level_result(LevelRound::Practice, true), None);
+        }
+        stats.record_level_result(LevelRound::Practice, false);
+        assert_eq!(stats.next_level_round(), LevelRound::Promotion);
+
+        assert_eq!(
+            stats.record_level_result(LevelRound::Promotion, false),
+            None
+        );
+        assert_eq!(stats.reading_level, 0);
+        assert_eq!(stats.next_level_round(), LevelRound::Practice);
+
+        stats.level_passes = PROMOTION_INTERVAL;
+        assert_eq!(
+            stats.record_level_result(LevelRound::Promotion, true),
+            Some(1)
+        );
+        assert_eq!(stats.level_passes, 0);
+    }
+
+    #[test]
+    fn test_no_promotion_at_top_level() {
+        let mut stats = TrainingStats {
+            reading_level: levels::READING_LEVELS.len() - 1,
+            level_passes: PROMOTION_INTERVAL,
+            ..TrainingStats::default()
+        };
+        assert_eq!(stats.next_level_round(), LevelRound::Practice);
+        stats.record_level_result(LevelRound::Practice, true);
+        assert_eq!(stats.next_level_round(), LevelRound::Practice);
+    }
+
+    #[test]
+    fn test_retry_summary_counts_linked_results() {
+        let mut stats = TrainingStats::default();
+        let original = Local::now();
+        stats.add_result_with_evaluation(false, None, Difficulty::Standard);
+        stats.add_result_with_evaluation(false, None, Difficulty::Standard);
+        stats.mark_last_result_as_retry(original);
+        stats.add_result_with_evaluation(true, None, Difficulty::Standard);
+        stats.mark_last_result_as_retry(original);
+
+        assert_eq!(stats.get_retry_summary(), (2, 1));
+        assert_eq!(
+            stats.results.last().and_then(|r| r.retry_of),
+            Some(original)
+        );
+    }
+
+    #[test]
+    fn test_difficulty_stats_skip_unrecorded_results() {
+        let mut stats = TrainingStats::default();
+        stats.add_result_with_evaluation(true, None, Difficulty::Easy);
+        stats.add_self_graded_result(false, Difficulty::Easy);
+        stats.add_result_with_evaluation(true, None, Difficulty::Hard);
+        stats.results.push(TrainingResult {
+            timestamp: Local::now(),
+            passed: true,
+            ..TrainingResult::default()
+        });
+
+        let difficulty_stats = stats.get_difficulty_stats();
+        assert_eq!(difficulty_stats.len(), 2);
+        assert_eq!(
+            difficulty_stats.first(),
+            Some(&DifficultyStats {
+                difficulty: Difficulty::Easy,
+                correct: 1,
+                incorrect: 1,
+            })
+        );
+        assert!(
+            (difficulty_stats
+                .first()
+                .map_or(0.0, DifficultyStats::pass_rate)
+                - 50.0)
+                .abs()
+                < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_text_level_stats_group_by_analyzed_level() {
+        let mut stats = TrainingStats::default();
+        for (passed, text) in [
+            (true, "きょうは はれです。こうえんに いきました。"),
+            (false, "いぬが います。ねこも います。"),
+            (true, "明日は雨が降るそうです。"),
+        ] {
+            stats.add_self_graded_result(passed, Difficulty::default());
+            stats.analyze_last_result_text(text);
+        }
+        stats.add_self_graded_result(true, Difficulty::default());
+
+        let level_stats = stats.get_text_level_stats();
+        assert_eq!(
+            level_stats.first(),
+            Some(&TextLevelStats {
+                level: JlptLevel::N5,
+                correct: 1,
+                incorrect: 1,
+                average_kanji_percent: 0,
+                average_sentence_chars: 8,
+            })
+        );
+        assert_eq!(
+            level_stats.iter().map(TextLevelStats::total).sum::<usize>(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_length_genre_stats_group_by_length_and_genre() {
+        let mut stats = TrainingStats::default();
+        for (passed, count, genre) in [
+            (false, 2880, Some(TextStyle::Official)),
+            (true, 400, Some(TextStyle::Newspaper)),
+            (true, 2880, Some(TextStyle::Official)),
+            (false, 2880, Some(TextStyle::Official)),
+            (true, 500, None),
+            (true, 5000, None),
+        ] {
+            stats.add_self_graded_result(passed, Difficulty::default());
+            stats.describe_last_result(count, genre);
+        }
+        stats.add_self_graded_result(true, Difficulty::default());
+
+        let groups: Vec<_> = stats
+            .get_length_genre_stats(ScoreScale::Raw)
+            .iter()
+            .map(|s| (s.length, s.genre, s.correct, s.total()))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                (Some(400), Some(TextStyle::Newspaper), 1, 1),
+                (Some(720), None, 1, 1),
+                (Some(2880), Some(TextStyle::Official), 1, 3),
+                (None, None, 1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_head_to_head_tallies_group_by_pair() {
+        use crate::models::ParticipantResult;
+
+        let round = |first: (&str, bool), second: (&str, bool)| HeadToHeadResult {
+            timestamp: Local::now(),
+            participants: [
+                ParticipantResult {
+                    name: first.0.to_string(),
+                    passed: first.1,
+                    evaluation: None,
+                },
+                ParticipantResult {
+                    name: second.0.to_string(),
+                    passed: second.1,
+                    evaluation: None,
+                },
+            ],
+        };
+        let mut stats = TrainingStats::default();
+        stats.add_head_to_head_result(round(("B", true), ("A", false)));
+        stats.add_head_to_head_result(round(("A", true), ("B", true)));
+        stats.add_head_to_head_result(round(("A", true), ("B", false)));
+        stats.add_head_to_head_result(round(("A", true), ("C", false)));
+
+        let tallies = stats.get_head_to_head_tallies();
+        assert_eq!(tallies.len(), 2);
+        assert_eq!(
+            tallies.first(),
+            Some(&HeadToHeadTally {
+                players: ("A".to_string(), "C".to_string()),
+                wins: (1, 0),
+                draws: 0,
+            })
+        );
+        assert_eq!(
+            tallies.get(1),
+            Some(&HeadToHeadTally {
+                players: ("A".to_string(), "B".to_string()),
+                wins: (1, 1),
+                draws: 1,
+            })
+        );
+        assert!(stats.results.is_empty());
+    }
 }
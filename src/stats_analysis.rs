@@ -1,7 +1,8 @@
 use crate::models::{
-    DailyStats, EvaluationScoreStats, EvaluationSummary, TrainingResult, WeeklyStats,
+    DailyStats, EvaluationScoreStats, EvaluationScores, EvaluationSummary, RollingTrend,
+    TrainingResult, Weakness, WeeklyStats,
 };
-use chrono::{DateTime, Local, NaiveDate};
+use chrono::{Local, NaiveDate, Weekday};
 use std::collections::HashMap;
 
 pub fn calculate_daily_stats(
@@ -25,21 +26,54 @@ pub fn calculate_daily_stats(
     daily_map
 }
 
+/// 今日までの `days` 日について、各日を最後とする `window` 日間の回数と合格率 (%) を古い順に返す
+pub fn calculate_rolling_trend(
+    results: &[TrainingResult],
+    days: usize,
+    window: usize,
+    today: NaiveDate,
+) -> Vec<RollingTrend> {
+    let daily_map = calculate_daily_stats(results, days + window.saturating_sub(1), today);
+    let days_back = |date: NaiveDate, offset: usize| {
+        date - chrono::Duration::days(i64::try_from(offset).unwrap_or(i64::MAX))
+    };
+    (0..days)
+        .rev()
+        .map(|offset| {
+            let date = days_back(today, offset);
+            let (correct, sessions) = (0..window)
+                .filter_map(|back| daily_map.get(&days_back(date, back)))
+                .fold((0, 0), |(correct, sessions), stats| {
+                    (correct + stats.correct, sessions + stats.total())
+                });
+            let as_f64 = |count: usize| f64::from(u32::try_from(count).unwrap_or(u32::MAX));
+            RollingTrend {
+                date,
+                sessions,
+                pass_rate: (sessions > 0).then(|| as_f64(correct) * 100.0 / as_f64(sessions)),
+            }
+        })
+        .collect()
+}
+
+/// `first_weekday` から始まる暦の週ごとに集計する。最後の要素が今週
 pub fn calculate_weekly_stats(
     results: &[TrainingResult],
     weeks: usize,
-    now: DateTime<Local>,
+    today: NaiveDate,
+    first_weekday: Weekday,
 ) -> Vec<WeeklyStats> {
+    let this_week_start = today.week(first_weekday).first_day();
     let mut weekly_stats = Vec::with_capacity(weeks);
 
     for week in 0..weeks {
         let offset = i64::try_from(weeks - week - 1).unwrap_or(i64::MAX);
-        let week_start = now - chrono::Duration::weeks(offset);
-        let week_end = week_start + chrono::Duration::weeks(1);
-        let (correct, incorrect) = count_results_in_range(results, week_start, week_end);
+        let start_date = this_week_start - chrono::Duration::weeks(offset);
+        let end_date = start_date + chrono::Duration::weeks(1);
+        let (correct, incorrect) = count_results_between(results, start_date, end_date);
 
         weekly_stats.push(WeeklyStats {
-            week_number: week + 1,
+            start_date,
             correct,
             incorrect,
         });
@@ -76,6 +110,39 @@ pub fn get_recent_evaluation_summary(results: &[TrainingResult], days: usize) ->
     }
 }
 
+/// 直近 `recent` 件の評価で平均点が最も低い観点を返す。
+/// 評価が `min_count` 件未満か、最も低い平均が `threshold` 以上なら `None`
+pub fn find_weakness(
+    results: &[TrainingResult],
+    recent: usize,
+    min_count: usize,
+    threshold: f32,
+) -> Option<Weakness> {
+    let evaluations: Vec<_> = results
+        .iter()
+        .rev()
+        .filter_map(|result| result.evaluation.as_ref())
+        .take(recent)
+        .collect();
+    if evaluations.is_empty() || evaluations.len() < min_count {
+        return None;
+    }
+
+    let average = |score: fn(&EvaluationScores) -> u8| {
+        let total: u32 = evaluations.iter().map(|e| u32::from(score(e))).sum();
+        f64::from(total) / f64::from(u32::try_from(evaluations.len()).unwrap_or(u32::MAX))
+    };
+    [
+        (Weakness::Importance, average(|e| e.importance)),
+        (Weakness::Conciseness, average(|e| e.conciseness)),
+        (Weakness::Accuracy, average(|e| e.accuracy)),
+    ]
+    .into_iter()
+    .filter(|(_, avg)| *avg < f64::from(threshold))
+    .min_by(|a, b| a.1.total_cmp(&b.1))
+    .map(|(weakness, _)| weakness)
+}
+
 pub fn calculate_score_stats(scores: &[u8]) -> Option<EvaluationScoreStats> {
     if scores.is_empty() {
         return None;
@@ -102,6 +169,59 @@ pub fn calculate_median(scores: &[u8]) -> f32 {
     }
 }
 
+/// 今日または昨日まで途切れずに練習した日数を数える (正誤は問わない)
+pub fn calculate_daily_streak(results: &[TrainingResult], today: NaiveDate) -> usize {
+    let mut dates: Vec<NaiveDate> = results.iter().map(|r| r.timestamp.date_naive()).collect();
+    dates.sort_unstable();
+    dates.dedup();
+
+    let Some(&last) = dates.last() else {
+        return 0;
+    };
+    if last < today - chrono::Duration::days(1) {
+        return 0;
+    }
+
+    let mut streak = 1;
+    let mut expected = last;
+    for &date in dates.iter().rev().skip(1) {
+        expected -= chrono::Duration::days(1);
+        if date != expected {
+            break;
+        }
+        streak += 1;
+    }
+    streak
+}
+
+/// これまでで最も長く続いた連続練習日数
+pub fn calculate_longest_daily_streak(results: &[TrainingResult]) -> usize {
+    let mut dates: Vec<NaiveDate> = results.iter().map(|r| r.timestamp.date_naive()).collect();
+    dates.sort_unstable();
+    dates.dedup();
+
+    let mut longest = 0;
+    let mut streak = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for date in dates {
+        streak = if previous.is_some_and(|p| p + chrono::Duration::days(1) == date) {
+            streak + 1
+        } else {
+            1
+        };
+        longest = longest.max(streak);
+        previous = Some(date);
+    }
+    longest
+}
+
+/// `secs` 秒で `chars` 文字を読み書きしたときの 1 分あたりの文字数。1 秒未満は 1 秒とみなす
+pub fn characters_per_minute(chars: usize, secs: u32) -> u32 {
+    let per_minute =
+        u64::try_from(chars).unwrap_or(u64::MAX).saturating_mul(60) / u64::from(secs.max(1));
+    u32::try_from(per_minute).unwrap_or(u32::MAX)
+}
+
 fn initialize_daily_stats(days: usize, today: NaiveDate) -> HashMap<NaiveDate, DailyStats> {
     let mut daily_map = HashMap::new();
     for i in 0..days {
@@ -111,16 +231,17 @@ fn initialize_daily_stats(days: usize, today: NaiveDate) -> HashMap<NaiveDate, D
     daily_map
 }
 
-fn count_results_in_range(
+fn count_results_between(
     results: &[TrainingResult],
-    start: DateTime<Local>,
-    end: DateTime<Local>,
+    start: NaiveDate,
+    end: NaiveDate,
 ) -> (usize, usize) {
     let mut correct = 0;
     let mut incorrect = 0;
 
     for result in results {
-        if result.timestamp >= start && result.timestamp < end {
+        let date = result.timestamp.date_naive();
+        if date >= start && date < end {
             if result.passed {
                 correct += 1;
             } else {
@@ -0,0 +1,264 @@
+use crate::error::AppError;
+use crate::models::{Badge, BadgeType, EvaluationScores, TrainingResult};
+use crate::stats::TrainingStats;
+use crate::text_cache::CacheSavings;
+use chrono::{Local, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// `yomitore stats` の引数
+#[derive(Debug, PartialEq, Eq)]
+pub struct StatsOptions {
+    /// この日以降の結果だけを集計する
+    pub since: Option<NaiveDate>,
+}
+
+impl StatsOptions {
+    /// `--json` と `--since YYYY-MM-DD` を解析する。出力形式は今のところ JSON のみ
+    pub fn parse(args: &[String]) -> Result<Self, AppError> {
+        let mut json = false;
+        let mut since = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--json" => json = true,
+                "--since" => {
+                    let value = args.next().ok_or_else(|| {
+                        AppError::InvalidConfig(
+                            "--since には YYYY-MM-DD 形式の日付を指定してください。".to_string(),
+                        )
+                    })?;
+                    since = Some(NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                        AppError::InvalidConfig(format!("日付を解釈できません: {value}"))
+                    })?);
+                }
+                other => {
+                    return Err(AppError::InvalidConfig(format!("不明な引数です: {other}")));
+                }
+            }
+        }
+        if !json {
+            return Err(AppError::InvalidConfig(
+                "出力形式として --json を指定してください。".to_string(),
+            ));
+        }
+        Ok(Self { since })
+    }
+}
+
+/// 外部のダッシュボード向けに集計した統計。`stats.json` の内部形式が変わってもこの形は保つ
+#[derive(Serialize, Debug)]
+pub struct StatsSummary {
+    pub generated_at: String,
+    pub since: Option<NaiveDate>,
+    pub total: usize,
+    pub passed: usize,
+    pub pass_rate: Option<f64>,
+    pub scores: ScoreAverages,
+    pub streaks: Streaks,
+    pub daily: Vec<DailyCount>,
+    pub badges: Vec<BadgeEntry>,
+    /// キャッシュから出題した回数と節約したトークン数の見積もり。`--since` に関係なく累計
+    pub cache: CacheSavings,
+}
+
+/// AI 評価を受けた結果の観点別の平均点
+#[derive(Serialize, Debug, Default)]
+pub struct ScoreAverages {
+    pub count: usize,
+    pub importance: Option<f64>,
+    pub conciseness: Option<f64>,
+    pub accuracy: Option<f64>,
+}
+
+/// 連続記録は `--since` に関係なく現在の値
+#[derive(Serialize, Debug)]
+pub struct Streaks {
+    pub current_correct: usize,
+    pub current_daily: usize,
+    pub longest_daily: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct DailyCount {
+    pub date: NaiveDate,
+    pub total: usize,
+    pub passed: usize,
+    pub pass_rate: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BadgeEntry {
+    pub kind: &'static str,
+    pub count: usize,
+    pub label: String,
+    pub earned_at: String,
+}
+
+impl StatsSummary {
+    pub fn from_stats(stats: &TrainingStats, since: Option<NaiveDate>) -> Self {
+        let included = |date: NaiveDate| since.is_none_or(|since| date >= since);
+        let results: Vec<&TrainingResult> = stats
+            .results
+            .iter()
+            .filter(|result| included(result.timestamp.date_naive()))
+            .collect();
+        let total = results.len();
+        let passed = results.iter().filter(|result| result.passed).count();
+
+        Self {
+            generated_at: Local::now().to_rfc3339(),
+            since,
+            total,
+            passed,
+            pass_rate: rate(passed, total),
+            scores: score_averages(&results),
+            streaks: Streaks {
+                current_correct: stats.current_streak,
+                current_daily: stats.current_daily_streak,
+                longest_daily: stats.longest_daily_streak,
+            },
+            daily: daily_counts(&results),
+            badges: stats
+                .badges
+                .iter()
+                .filter(|badge| included(badge.earned_at.date_naive()))
+                .map(BadgeEntry::from_badge)
+                .collect(),
+            cache: stats.text_cache,
+        }
+    }
+}
+
+impl BadgeEntry {
+    fn from_badge(badge: &Badge) -> Self {
+        let (kind, count) = match badge.badge_type {
+            BadgeType::ConsecutiveStreak(n) => ("consecutive_streak", n),
+            BadgeType::CumulativeMilestone(n) => ("cumulative_milestone", n),
+            BadgeType::DailyStreak(n) => ("daily_streak", n),
+            BadgeType::DailyGoal(n) => ("daily_goal", n),
+            BadgeType::PersonalBest(n) => ("personal_best", n),
+            BadgeType::EarlyBird => ("early_bird", 1),
+            BadgeType::Marathon => ("marathon", 1),
+            BadgeType::GenreMaster(_) => ("genre_master", 1),
+            BadgeType::Comeback => ("comeback", 1),
+        };
+        Self {
+            kind,
+            count,
+            label: format!("{} {}", badge.get_icon(), badge.get_display_text()),
+            earned_at: badge.earned_at.to_rfc3339(),
+        }
+    }
+}
+
+/// 集計した統計を JSON で標準出力に書き出す
+pub fn print_summary(stats: &TrainingStats, options: &StatsOptions) -> Result<(), AppError> {
+    let summary = StatsSummary::from_stats(stats, options.since);
+    let mut stdout = io::stdout();
+    stdout.write_all((serde_json::to_string_pretty(&summary)? + "\n").as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn rate(passed: usize, total: usize) -> Option<f64> {
+    let total = u32::try_from(total).ok().filter(|&total| total > 0)?;
+    let passed = u32::try_from(passed).unwrap_or(u32::MAX);
+    Some(f64::from(passed) / f64::from(total))
+}
+
+fn score_averages(results: &[&TrainingResult]) -> ScoreAverages {
+    let evaluations: Vec<_> = results
+        .iter()
+        .filter_map(|result| result.evaluation.as_ref())
+        .collect();
+    let Ok(count) = u32::try_from(evaluations.len()) else {
+        return ScoreAverages::default();
+    };
+    if count == 0 {
+        return ScoreAverages::default();
+    }
+    let average = |score: &dyn Fn(&EvaluationScores) -> u8| {
+        let total: u32 = evaluations.iter().map(|e| u32::from(score(e))).sum();
+        Some(f64::from(total) / f64::from(count))
+    };
+    ScoreAverages {
+        count: evaluations.len(),
+        importance: average(&|e| e.importance),
+        conciseness: average(&|e| e.conciseness),
+        accuracy: average(&|e| e.accuracy),
+    }
+}
+
+/// 練習した日だけを日付順に並べる
+fn daily_counts(results: &[&TrainingResult]) -> Vec<DailyCount> {
+    let mut days: BTreeMap<NaiveDate, (usize, usize)> = BTreeMap::new();
+    for result in results {
+        let entry = days.entry(result.timestamp.date_naive()).or_default();
+        entry.0 += 1;
+        entry.1 += usize::from(result.passed);
+    }
+    days.into_iter()
+        .map(|(date, (total, passed))| DailyCount {
+            date,
+            total,
+            passed,
+            pass_rate: rate(passed, total).unwrap_or_default(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_string()).collect()
+    }
+
+    #[test]
+    fn parse_options_requires_json_and_valid_date() {
+        let options = StatsOptions::parse(&args(&["--json", "--since", "2026-07-01"]));
+        assert_eq!(
+            options.ok().and_then(|options| options.since),
+            NaiveDate::from_ymd_opt(2026, 7, 1)
+        );
+        assert!(StatsOptions::parse(&args(&[])).is_err());
+        assert!(StatsOptions::parse(&args(&["--json", "--since", "7/1"])).is_err());
+    }
+
+    #[test]
+    fn summary_counts_results_since_date() {
+        let day = |d: u32| {
+            Local
+                .with_ymd_and_hms(2026, 7, d, 12, 0, 0)
+                .single()
+                .unwrap_or_default()
+        };
+        let mut stats = TrainingStats::default();
+        for (d, passed) in [(1, true), (2, true), (2, false), (3, true)] {
+            stats.results.push(TrainingResult {
+                timestamp: day(d),
+                passed,
+                ..TrainingResult::default()
+            });
+        }
+
+        let summary = StatsSummary::from_stats(&stats, NaiveDate::from_ymd_opt(2026, 7, 2));
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.passed, 2);
+        assert_eq!(summary.scores.count, 0);
+        assert_eq!(
+            summary.daily.first(),
+            Some(&DailyCount {
+                date: day(2).date_naive(),
+                total: 2,
+                passed: 1,
+                pass_rate: 0.5,
+            })
+        );
+        assert_eq!(summary.daily.len(), 2);
+    }
+}
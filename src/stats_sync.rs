@@ -0,0 +1,185 @@
+use crate::error::AppError;
+use crate::stats::TrainingStats;
+use crate::storage;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+/// 続けて保存を頼まれたときに、最後の 1 回だけ書き込むための待ち時間
+const DEBOUNCE: Duration = Duration::from_millis(300);
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_ATTEMPTS: u32 = 3;
+
+type Writer = Arc<dyn Fn(&str) -> Result<(), AppError> + Send + Sync>;
+
+/// 統計の保存を tokio タスクで行う。
+/// 遅いディスクやネットワーク上のホームディレクトリでも、評価結果の表示を待たせないため
+pub struct StatsSaver {
+    writer: Writer,
+    debounce: Duration,
+    retry_delay: Duration,
+    sender: Option<UnboundedSender<String>>,
+    worker: Option<JoinHandle<()>>,
+    failures: (UnboundedSender<String>, UnboundedReceiver<String>),
+}
+
+impl Default for StatsSaver {
+    fn default() -> Self {
+        Self::new(Arc::new(write_stats), DEBOUNCE, RETRY_DELAY)
+    }
+}
+
+impl StatsSaver {
+    fn new(writer: Writer, debounce: Duration, retry_delay: Duration) -> Self {
+        Self {
+            writer,
+            debounce,
+            retry_delay,
+            sender: None,
+            worker: None,
+            failures: mpsc::unbounded_channel(),
+        }
+    }
+
+    /// 現在の統計の保存を予約する。書き込みはバックグラウンドで行い、失敗は `take_failure` で受け取る
+    pub fn request(&mut self, stats: &TrainingStats) -> Result<(), AppError> {
+        let content = serde_json::to_string_pretty(stats)?;
+        // 非同期ランタイムの外 (テストなど) ではその場で保存する
+        let Ok(runtime) = Handle::try_current() else {
+            return (self.writer)(&content);
+        };
+        let sender = self
+            .sender
+            .get_or_insert_with(|| {
+                let (sender, receiver) = mpsc::unbounded_channel();
+                self.worker = Some(runtime.spawn(run_worker(
+                    receiver,
+                    self.failures.0.clone(),
+                    Arc::clone(&self.writer),
+                    self.debounce,
+                    self.retry_delay,
+                )));
+                sender
+            })
+            .clone();
+        sender
+            .send(content)
+            .map_err(|_| AppError::Storage("統計の保存タスクが停止しています。".to_string()))
+    }
+
+    /// 再試行しても保存できなかったときのエラーメッセージ
+    pub fn take_failure(&mut self) -> Option<String> {
+        self.failures.1.try_recv().ok()
+    }
+
+    /// 予約済みの保存がすべて終わるまで待つ。終了時に呼ぶ
+    pub async fn flush(&mut self) {
+        self.sender = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.await;
+        }
+    }
+}
+
+fn write_stats(content: &str) -> Result<(), AppError> {
+    storage::global()?.save(storage::STATS_DOCUMENT, content)
+}
+
+async fn run_worker(
+    mut receiver: UnboundedReceiver<String>,
+    failures: UnboundedSender<String>,
+    writer: Writer,
+    debounce: Duration,
+    retry_delay: Duration,
+) {
+    while let Some(mut content) = receiver.recv().await {
+        // 待っている間に新しい保存が来たら、古い内容は書かずに捨てる
+        while let Ok(Some(newer)) = tokio::time::timeout(debounce, receiver.recv()).await {
+            content = newer;
+        }
+        let mut attempt = 1;
+        loop {
+            let writer = Arc::clone(&writer);
+            let snapshot = content.clone();
+            let result = tokio::task::spawn_blocking(move || writer(&snapshot))
+                .await
+                .unwrap_or_else(|e| Err(AppError::Storage(e.to_string())));
+            let Err(e) = result else {
+                break;
+            };
+            if attempt >= MAX_ATTEMPTS {
+                let _ = failures.send(e.to_string());
+                break;
+            }
+            attempt += 1;
+            tokio::time::sleep(retry_delay * attempt).await;
+            while let Ok(newer) = receiver.try_recv() {
+                content = newer;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Difficulty;
+    use std::sync::Mutex;
+
+    fn recording_saver(fail: bool) -> (StatsSaver, Arc<Mutex<Vec<String>>>) {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&written);
+        let writer: Writer = Arc::new(move |content: &str| {
+            if let Ok(mut log) = log.lock() {
+                log.push(content.to_string());
+            }
+            if fail {
+                Err(AppError::Storage("disk full".to_string()))
+            } else {
+                Ok(())
+            }
+        });
+        let saver = StatsSaver::new(writer, Duration::from_millis(50), Duration::ZERO);
+        (saver, written)
+    }
+
+    fn written_count(written: &Arc<Mutex<Vec<String>>>) -> usize {
+        written.lock().map(|log| log.len()).unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn consecutive_requests_are_written_once() {
+        let (mut saver, written) = recording_saver(false);
+        let mut stats = TrainingStats::default();
+        for _ in 0..3 {
+            stats.add_self_graded_result(true, Difficulty::default());
+            assert!(saver.request(&stats).is_ok());
+        }
+        saver.flush().await;
+        assert_eq!(written_count(&written), 1);
+        let last = written
+            .lock()
+            .ok()
+            .and_then(|log| log.last().cloned())
+            .unwrap_or_default();
+        assert_eq!(
+            last,
+            serde_json::to_string_pretty(&stats).unwrap_or_default()
+        );
+        assert_eq!(saver.take_failure(), None);
+    }
+
+    #[tokio::test]
+    async fn failed_save_is_retried_and_reported() {
+        let (mut saver, written) = recording_saver(true);
+        assert!(saver.request(&TrainingStats::default()).is_ok());
+        saver.flush().await;
+        assert_eq!(written_count(&written), 3);
+        assert_eq!(
+            saver.take_failure().as_deref(),
+            Some("保存先を開けません: disk full")
+        );
+    }
+}
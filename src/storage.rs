@@ -0,0 +1,441 @@
+use crate::config::{self, StorageBackend, StorageConfig};
+use crate::error::AppError;
+use crate::migration;
+use chrono::Local;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+const APP_DIR_NAME: &str = "yomitore";
+const LOCK_FILE_NAME: &str = ".lock";
+const DATABASE_FILE_NAME: &str = "yomitore.db";
+const BACKUP_DIR_NAME: &str = "backups";
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 統計 (`stats.json`)
+pub const STATS_DOCUMENT: &str = "stats";
+/// 未使用テキスト (`pending.json`)
+pub const PENDING_DOCUMENT: &str = "pending";
+/// 復習待ちの文章 (`review.json`)
+pub const REVIEW_DOCUMENT: &str = "review";
+/// 読み込んだ文章のライブラリ (`library.json`)
+pub const LIBRARY_DOCUMENT: &str = "library";
+/// 単語帳 (`vocab.json`)
+pub const VOCAB_DOCUMENT: &str = "vocab";
+/// 評価に出す前の下書き (`session.json`)
+pub const SESSION_DOCUMENT: &str = "session";
+/// 生成した文章のキャッシュ (`text_cache.json`)
+pub const TEXT_CACHE_DOCUMENT: &str = "text_cache";
+/// 保存形式の版数 (`meta.json`)
+pub const META_DOCUMENT: &str = "meta";
+/// 要約の履歴 (`history.jsonl`)
+pub const HISTORY_COLLECTION: &str = "history";
+/// 保存形式の移行の記録 (`migrations.jsonl`)
+pub const MIGRATION_COLLECTION: &str = "migrations";
+
+pub const DOCUMENTS: [&str; 8] = [
+    STATS_DOCUMENT,
+    PENDING_DOCUMENT,
+    REVIEW_DOCUMENT,
+    LIBRARY_DOCUMENT,
+    VOCAB_DOCUMENT,
+    SESSION_DOCUMENT,
+    TEXT_CACHE_DOCUMENT,
+    META_DOCUMENT,
+];
+pub const COLLECTIONS: [&str; 2] = [HISTORY_COLLECTION, MIGRATION_COLLECTION];
+
+/// 統計や履歴の保存先。丸ごと読み書きする「文書」と、1 件ずつ追記する「記録」を扱う
+pub trait Storage: Send + Sync {
+    /// 文書を読み込む。まだ保存されていなければ `None`
+    fn load(&self, key: &str) -> Result<Option<String>, AppError>;
+    /// 文書を置き換える。途中で失敗しても元の内容は壊さない
+    fn save(&self, key: &str, content: &str) -> Result<(), AppError>;
+    /// 記録を 1 件追加する
+    fn append(&self, collection: &str, record: &str) -> Result<(), AppError>;
+    /// 記録を古い順に返す。`limit` を指定すると新しいものからその件数だけ返す
+    fn query(&self, collection: &str, limit: Option<usize>) -> Result<Vec<String>, AppError>;
+    /// エラーメッセージに出す文書の場所
+    fn location(&self, key: &str) -> String {
+        key.to_string()
+    }
+}
+
+static STORAGE: OnceLock<Result<Box<dyn Storage>, String>> = OnceLock::new();
+
+/// 設定 (`[storage]` セクション) で選んだ保存先。最初の呼び出しで開き、以後は同じものを使う。
+/// 開いたときに保存形式が古ければ、バックアップを取ってから移行する
+pub fn global() -> Result<&'static dyn Storage, AppError> {
+    STORAGE
+        .get_or_init(|| {
+            let config = config::load_config().unwrap_or_default();
+            open_and_migrate(&config.storage).map_err(|e| e.to_string())
+        })
+        .as_deref()
+        .map_err(|e| AppError::Storage(e.clone()))
+}
+
+fn open_and_migrate(config: &StorageConfig) -> Result<Box<dyn Storage>, AppError> {
+    let dir = data_dir(config)?;
+    let storage = open(config, &dir)?;
+    migration::migrate(storage.as_ref(), &dir.join(BACKUP_DIR_NAME), Local::now())?;
+    Ok(storage)
+}
+
+fn data_dir(config: &StorageConfig) -> Result<PathBuf, AppError> {
+    match &config.dir {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => Ok(dirs::config_dir()
+            .ok_or(AppError::IoError(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "設定ディレクトリが見つかりません。",
+            )))?
+            .join(APP_DIR_NAME)),
+    }
+}
+
+fn open(config: &StorageConfig, dir: &Path) -> Result<Box<dyn Storage>, AppError> {
+    let json = JsonStorage::open(dir.to_path_buf())?;
+    match config.backend {
+        StorageBackend::Json => Ok(Box::new(json)),
+        StorageBackend::Sqlite => {
+            let path = dir.join(DATABASE_FILE_NAME);
+            let is_new = !path.exists();
+            let sqlite = SqliteStorage::open(&path)?;
+            // 切り替えた直後は、それまでの JSON のデータを取り込む
+            if is_new {
+                sqlite.import_from(&json)?;
+            }
+            Ok(Box::new(sqlite))
+        }
+    }
+}
+
+pub fn load_json<T: DeserializeOwned>(
+    storage: &dyn Storage,
+    key: &str,
+) -> Result<Option<T>, AppError> {
+    storage
+        .load(key)?
+        .map(|content| serde_json::from_str(&content))
+        .transpose()
+        .map_err(|e| {
+            AppError::Storage(format!(
+                "{} の内容を読み込めません: {e}",
+                storage.location(key)
+            ))
+        })
+}
+
+pub fn save_json<T: Serialize>(
+    storage: &dyn Storage,
+    key: &str,
+    value: &T,
+) -> Result<(), AppError> {
+    storage.save(key, &serde_json::to_string_pretty(value)?)
+}
+
+pub fn append_json<T: Serialize>(
+    storage: &dyn Storage,
+    collection: &str,
+    value: &T,
+) -> Result<(), AppError> {
+    storage.append(collection, &serde_json::to_string(value)?)
+}
+
+/// 壊れた記録は読み飛ばし、読めたものだけを返す
+pub fn query_json<T: DeserializeOwned>(
+    storage: &dyn Storage,
+    collection: &str,
+    limit: Option<usize>,
+) -> Result<Vec<T>, AppError> {
+    Ok(storage
+        .query(collection, limit)?
+        .iter()
+        .filter_map(|record| serde_json::from_str(record).ok())
+        .collect())
+}
+
+/// 文書を `<key>.json`、記録を `<collection>.jsonl` に保存する。
+/// 書き込み中は `.lock` をロックし、文書は一時ファイルからの置き換えで保存する
+pub struct JsonStorage {
+    dir: PathBuf,
+}
+
+impl JsonStorage {
+    pub fn open(dir: PathBuf) -> Result<Self, AppError> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn document_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    fn collection_path(&self, collection: &str) -> PathBuf {
+        self.dir.join(format!("{collection}.jsonl"))
+    }
+
+    /// 返したファイルを閉じるとロックが外れる
+    fn lock(&self, exclusive: bool) -> Result<File, AppError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.dir.join(LOCK_FILE_NAME))?;
+        if exclusive {
+            file.lock()?;
+        } else {
+            file.lock_shared()?;
+        }
+        Ok(file)
+    }
+}
+
+impl Storage for JsonStorage {
+    fn load(&self, key: &str) -> Result<Option<String>, AppError> {
+        let _lock = self.lock(false)?;
+        let path = self.document_path(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(fs::read_to_string(path)?))
+    }
+
+    fn save(&self, key: &str, content: &str) -> Result<(), AppError> {
+        let _lock = self.lock(true)?;
+        let path = self.document_path(key);
+        let temp_path = path.with_extension("json.tmp");
+        let mut file = File::create(&temp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(temp_path, path)?;
+        Ok(())
+    }
+
+    fn append(&self, collection: &str, record: &str) -> Result<(), AppError> {
+        let _lock = self.lock(true)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.collection_path(collection))?;
+        writeln!(file, "{record}")?;
+        Ok(())
+    }
+
+    fn query(&self, collection: &str, limit: Option<usize>) -> Result<Vec<String>, AppError> {
+        let _lock = self.lock(false)?;
+        let path = self.collection_path(collection);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let records: Vec<String> = fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+        let skip = limit.map_or(0, |limit| records.len().saturating_sub(limit));
+        Ok(records.into_iter().skip(skip).collect())
+    }
+
+    fn location(&self, key: &str) -> String {
+        self.document_path(key).display().to_string()
+    }
+}
+
+/// 1 つのデータベースファイルに文書と記録を保存する。ロックと原子性はデータベースに任せる
+pub struct SqliteStorage {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteStorage {
+    pub fn open(path: &Path) -> Result<Self, AppError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, AppError> {
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS documents (
+                key TEXT PRIMARY KEY,
+                content TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS records (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                collection TEXT NOT NULL,
+                content TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS records_collection ON records (collection, id);",
+        )?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> Result<std::sync::MutexGuard<'_, Connection>, AppError> {
+        self.connection
+            .lock()
+            .map_err(|_| AppError::Storage("データベースの接続が使えません。".to_string()))
+    }
+
+    /// JSON で保存していたデータを 1 つのトランザクションで取り込む
+    fn import_from(&self, json: &JsonStorage) -> Result<(), AppError> {
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction()?;
+        for key in DOCUMENTS {
+            if let Some(content) = json.load(key)? {
+                transaction.execute(
+                    "INSERT OR REPLACE INTO documents (key, content) VALUES (?1, ?2)",
+                    params![key, content],
+                )?;
+            }
+        }
+        for collection in COLLECTIONS {
+            for record in json.query(collection, None)? {
+                transaction.execute(
+                    "INSERT INTO records (collection, content) VALUES (?1, ?2)",
+                    params![collection, record],
+                )?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn load(&self, key: &str) -> Result<Option<String>, AppError> {
+        Ok(self
+            .connection()?
+            .query_row(
+                "SELECT content FROM documents WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    fn save(&self, key: &str, content: &str) -> Result<(), AppError> {
+        self.connection()?.execute(
+            "INSERT INTO documents (key, content) VALUES (?1, ?2)
+             ON CONFLICT (key) DO UPDATE SET content = excluded.content",
+            params![key, content],
+        )?;
+        Ok(())
+    }
+
+    fn append(&self, collection: &str, record: &str) -> Result<(), AppError> {
+        self.connection()?.execute(
+            "INSERT INTO records (collection, content) VALUES (?1, ?2)",
+            params![collection, record],
+        )?;
+        Ok(())
+    }
+
+    fn query(&self, collection: &str, limit: Option<usize>) -> Result<Vec<String>, AppError> {
+        let connection = self.connection()?;
+        // LIMIT -1 は件数の制限なし
+        let limit = limit.map_or(-1, |limit| i64::try_from(limit).unwrap_or(i64::MAX));
+        let mut statement = connection.prepare(
+            "SELECT content FROM (
+                SELECT id, content FROM records WHERE collection = ?1 ORDER BY id DESC LIMIT ?2
+             ) ORDER BY id",
+        )?;
+        let records = statement
+            .query_map(params![collection, limit], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Entry {
+        value: u32,
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("yomitore-storage-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn exercise(storage: &dyn Storage) {
+        assert!(matches!(load_json::<Entry>(storage, "doc"), Ok(None)));
+        assert!(save_json(storage, "doc", &Entry { value: 1 }).is_ok());
+        assert!(save_json(storage, "doc", &Entry { value: 2 }).is_ok());
+        assert_eq!(
+            load_json::<Entry>(storage, "doc").ok().flatten(),
+            Some(Entry { value: 2 })
+        );
+        assert!(storage.save("broken", "{").is_ok());
+        assert!(matches!(
+            load_json::<Entry>(storage, "broken"),
+            Err(AppError::Storage(message)) if message.contains("broken")
+        ));
+
+        for value in 1..=3 {
+            assert!(append_json(storage, "log", &Entry { value }).is_ok());
+        }
+        assert!(storage.append("log", "not json").is_ok());
+        let all: Vec<Entry> = query_json(storage, "log", None).unwrap_or_default();
+        assert_eq!(all.len(), 3);
+        let recent = storage.query("log", Some(2)).unwrap_or_default();
+        assert_eq!(recent, ["{\"value\":3}", "not json"]);
+    }
+
+    #[test]
+    fn json_storage_round_trips_documents_and_records() {
+        let dir = temp_dir("json");
+        let storage = JsonStorage::open(dir.clone());
+        assert!(storage.is_ok());
+        if let Ok(storage) = storage {
+            exercise(&storage);
+            assert!(dir.join("doc.json").exists());
+            assert!(!dir.join("doc.json.tmp").exists());
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn sqlite_storage_round_trips_and_imports_json() -> Result<(), String> {
+        let storage = Connection::open_in_memory()
+            .map_err(AppError::from)
+            .and_then(SqliteStorage::with_connection)
+            .map_err(|e| e.to_string())?;
+        exercise(&storage);
+
+        let dir = temp_dir("import");
+        let json = JsonStorage::open(dir.clone()).map_err(|e| e.to_string())?;
+        json.save(STATS_DOCUMENT, "{\"results\":[]}")
+            .map_err(|e| e.to_string())?;
+        json.append(HISTORY_COLLECTION, "{}")
+            .map_err(|e| e.to_string())?;
+        storage.import_from(&json).map_err(|e| e.to_string())?;
+        let stats = storage.load(STATS_DOCUMENT).map_err(|e| e.to_string())?;
+        if stats.as_deref() != Some("{\"results\":[]}") {
+            return Err(format!("unexpected imported stats: {stats:?}"));
+        }
+        let history = storage
+            .query(HISTORY_COLLECTION, None)
+            .map_err(|e| e.to_string())?;
+        if history.len() != 1 {
+            return Err(format!(
+                "expected one imported record, got {}",
+                history.len()
+            ));
+        }
+        let _ = fs::remove_dir_all(&dir);
+        Ok(())
+    }
+}
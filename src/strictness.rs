@@ -0,0 +1,144 @@
+use crate::evaluation::{EvaluationResult, OverallEvaluation};
+use serde::{Deserialize, Serialize};
+
+/// 甘口では、評価者が不合格としても 3 観点の合計がこの点以上なら合格にする
+const LENIENT_PASS_TOTAL: u8 = 9;
+/// 辛口では、評価者が合格としても 3 観点のどれかがこの点に満たなければ不合格にする
+const STRICT_MIN_SCORE: u8 = 4;
+/// `[rubric]` の合格ラインを甘口で下げ、辛口で上げる幅 (%)
+const RUBRIC_PASS_PERCENT_STEP: u8 = 10;
+
+/// 評価の厳しさ。評価プロンプトへの指示と、3 観点の点数による合否の補正を変える
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Strictness {
+    /// 甘口。よい点を認め、大筋をつかめていれば合格にする
+    Lenient,
+    /// 中辛。組み込みの評価プロンプトのまま
+    #[default]
+    Standard,
+    /// 辛口。細部の正確さと簡潔さまで求める
+    Strict,
+}
+
+impl Strictness {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Lenient => "甘口",
+            Self::Standard => "中辛",
+            Self::Strict => "辛口",
+        }
+    }
+
+    /// メニューで切り替える次の厳しさ。辛口の次は甘口に戻る
+    pub fn next(self) -> Self {
+        match self {
+            Self::Lenient => Self::Standard,
+            Self::Standard => Self::Strict,
+            Self::Strict => Self::Lenient,
+        }
+    }
+
+    /// 評価プロンプトの末尾に加える指示。中辛では何も加えない
+    pub fn evaluation_rule(self) -> &'static str {
+        match self {
+            Self::Lenient => {
+                "\n# 評価の厳しさ: 甘口\n- 受講者は初心者です。細かな言い回しより、原文の大筋をつかめているかを重視すること\n- 大筋をつかめていれば合格とし、改善点は前向きな表現で書くこと\n"
+            }
+            Self::Standard => "",
+            Self::Strict => {
+                "\n# 評価の厳しさ: 辛口\n- 受講者は上級者です。要点の漏れ、不正確な表現、冗長な部分を厳しく減点すること\n- 3 つの観点がすべて 4 以上のときだけ合格とすること\n"
+            }
+        }
+    }
+
+    /// 3 観点の点数で合否を補正する。「適切な要約か」がいいえなら甘口でも合格にしない
+    pub fn adjust(self, result: &mut EvaluationResult) {
+        let scores = [result.importance, result.conciseness, result.accuracy];
+        let passed = match self {
+            Self::Lenient => {
+                result.passed()
+                    || (result.appropriate && scores.iter().sum::<u8>() >= LENIENT_PASS_TOTAL)
+            }
+            Self::Standard => result.passed(),
+            Self::Strict => {
+                result.passed() && scores.iter().all(|&score| score >= STRICT_MIN_SCORE)
+            }
+        };
+        result.overall = if passed {
+            OverallEvaluation::Pass
+        } else {
+            OverallEvaluation::Fail
+        };
+    }
+
+    /// `[rubric]` の合格ライン (%) を、甘口では下げ、辛口では上げる
+    pub fn rubric_pass_percent(self, pass_percent: u8) -> u8 {
+        match self {
+            Self::Lenient => pass_percent.saturating_sub(RUBRIC_PASS_PERCENT_STEP),
+            Self::Standard => pass_percent,
+            Self::Strict => pass_percent
+                .saturating_add(RUBRIC_PASS_PERCENT_STEP)
+                .min(100),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(total: [u8; 3], passed: bool) -> EvaluationResult {
+        let [importance, conciseness, accuracy] = total;
+        EvaluationResult {
+            appropriate: true,
+            importance,
+            conciseness,
+            accuracy,
+            improvement1: String::new(),
+            improvement2: String::new(),
+            improvement3: String::new(),
+            overall: if passed {
+                OverallEvaluation::Pass
+            } else {
+                OverallEvaluation::Fail
+            },
+        }
+    }
+
+    #[test]
+    fn strictness_moves_the_pass_threshold() {
+        let adjusted = |strictness: Strictness, scores, passed| {
+            let mut result = result(scores, passed);
+            strictness.adjust(&mut result);
+            result.passed()
+        };
+        assert!(adjusted(Strictness::Lenient, [3, 3, 3], false));
+        assert!(!adjusted(Strictness::Standard, [3, 3, 3], false));
+        assert!(adjusted(Strictness::Standard, [3, 3, 3], true));
+        assert!(!adjusted(Strictness::Strict, [4, 4, 3], true));
+        assert!(!adjusted(Strictness::Strict, [5, 5, 2], true));
+        assert!(adjusted(Strictness::Strict, [4, 4, 4], true));
+
+        let mut inappropriate = result([5, 5, 5], false);
+        inappropriate.appropriate = false;
+        Strictness::Lenient.adjust(&mut inappropriate);
+        assert!(!inappropriate.passed());
+    }
+
+    #[test]
+    fn only_non_standard_levels_change_the_prompt() {
+        assert_eq!(Strictness::Standard.evaluation_rule(), "");
+        assert!(Strictness::Strict.evaluation_rule().contains("辛口"));
+        assert_eq!(Strictness::Strict.next(), Strictness::Lenient);
+    }
+
+    #[test]
+    fn rubric_pass_line_moves_with_strictness() {
+        assert_eq!(Strictness::Lenient.rubric_pass_percent(60), 50);
+        assert_eq!(Strictness::Standard.rubric_pass_percent(60), 60);
+        assert_eq!(Strictness::Strict.rubric_pass_percent(60), 70);
+        assert_eq!(Strictness::Lenient.rubric_pass_percent(5), 0);
+        assert_eq!(Strictness::Strict.rubric_pass_percent(95), 100);
+    }
+}
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+/// 目安から許す幅 (目安の文字数に対する %)
+const TOLERANCE_PERCENT: usize = 20;
+
+/// 要約の文字数。空白と改行は数えない
+pub fn count_chars(text: &str) -> usize {
+    text.chars().filter(|c| !c.is_whitespace()).count()
+}
+
+/// メニューの `g` で選ぶ要約の長さの条件
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthGoal {
+    /// 原文の文字数に対する割合 (%)
+    Percent(u16),
+    /// 原文によらない文字数
+    Chars(u16),
+}
+
+impl Default for LengthGoal {
+    fn default() -> Self {
+        Self::Percent(25)
+    }
+}
+
+impl LengthGoal {
+    const PRESETS: [Self; 7] = [
+        Self::Percent(25),
+        Self::Percent(20),
+        Self::Percent(10),
+        Self::Percent(30),
+        Self::Chars(100),
+        Self::Chars(200),
+        Self::Chars(400),
+    ];
+
+    /// `g` で切り替える次の条件。最後まで進んだら最初に戻る
+    pub fn next(self) -> Self {
+        let index = Self::PRESETS
+            .iter()
+            .position(|&goal| goal == self)
+            .unwrap_or_default();
+        Self::PRESETS.get(index + 1).copied().unwrap_or_default()
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            Self::Percent(percent) => format!("原文の {percent}%"),
+            Self::Chars(chars) => format!("{chars} 文字"),
+        }
+    }
+}
+
+/// 要約の目安の文字数と許す幅
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthTarget {
+    pub chars: usize,
+    pub tolerance: usize,
+}
+
+/// 要約の文字数が目安に収まっているか
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthFit {
+    Under,
+    Within,
+    Over,
+}
+
+impl LengthTarget {
+    /// 条件と原文から目安を決める。目安が 0 文字になる (原文が空) なら目安はない
+    pub fn new(goal: LengthGoal, original: &str) -> Option<Self> {
+        let chars = match goal {
+            LengthGoal::Percent(percent) => {
+                (count_chars(original) * usize::from(percent)).div_ceil(100)
+            }
+            LengthGoal::Chars(_) if original.trim().is_empty() => 0,
+            LengthGoal::Chars(chars) => usize::from(chars),
+        };
+        (chars > 0).then(|| Self {
+            chars,
+            tolerance: (chars * TOLERANCE_PERCENT).div_ceil(100),
+        })
+    }
+
+    pub fn fit(self, count: usize) -> LengthFit {
+        if count + self.tolerance < self.chars {
+            LengthFit::Under
+        } else if count > self.chars + self.tolerance {
+            LengthFit::Over
+        } else {
+            LengthFit::Within
+        }
+    }
+
+    pub fn is_met(self, summary: &str) -> bool {
+        self.fit(count_chars(summary)) == LengthFit::Within
+    }
+
+    /// 評価プロンプトの末尾に加える条件。評価には `length_met` で満たしたかを答えさせる
+    pub fn evaluation_rule(self, summary: &str) -> String {
+        format!(
+            "\n# 文字数の条件\n\
+             要約文は空白を除いて {count} 文字です。目標は {chars}±{tolerance} 文字です。\
+             JSON に \"length_met\" を加え、要約文がこの範囲に収まっていれば true、外れていれば false を入れること。\
+             範囲を外れている場合は overall を \"不合格\" にすること。\n",
+            count = count_chars(summary),
+            chars = self.chars,
+            tolerance = self.tolerance,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_follows_the_goal_and_allows_a_margin() -> Result<(), String> {
+        let original = "あ".repeat(400);
+        let target =
+            LengthTarget::new(LengthGoal::default(), &original).ok_or("target should be set")?;
+        if target
+            != (LengthTarget {
+                chars: 100,
+                tolerance: 20,
+            })
+        {
+            return Err(format!("unexpected target: {target:?}"));
+        }
+        let fits = [79, 80, 120, 121].map(|count| target.fit(count));
+        if fits
+            != [
+                LengthFit::Under,
+                LengthFit::Within,
+                LengthFit::Within,
+                LengthFit::Over,
+            ]
+        {
+            return Err(format!("unexpected fits: {fits:?}"));
+        }
+        if count_chars("要約 です。\n続き") != 7 {
+            return Err("whitespace should not be counted".to_string());
+        }
+        if LengthTarget::new(LengthGoal::default(), " \n").is_some() {
+            return Err("blank text should have no target".to_string());
+        }
+        let chars = LengthTarget::new(LengthGoal::Chars(200), &original).map(|target| target.chars);
+        if chars != Some(200) {
+            return Err(format!("unexpected target length: {chars:?}"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn goals_cycle_and_the_rule_reports_the_count() {
+        let mut goal = LengthGoal::default();
+        for _ in 0..LengthGoal::PRESETS.len() {
+            goal = goal.next();
+        }
+        assert_eq!(goal, LengthGoal::default());
+        let rule = LengthTarget {
+            chars: 100,
+            tolerance: 20,
+        }
+        .evaluation_rule("要約 です。");
+        assert!(rule.contains("空白を除いて 5 文字"));
+        assert!(rule.contains("100±20"));
+        assert!(rule.contains("length_met"));
+    }
+}
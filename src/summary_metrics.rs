@@ -0,0 +1,70 @@
+use crate::summary_length::count_chars;
+use crate::text_difficulty;
+use crate::verbatim;
+use serde::{Deserialize, Serialize};
+
+/// 提出した要約を手元で測った指標。評価者の採点とは別に、長い期間の推移を見るために記録する
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SummaryMetrics {
+    /// 原文の文字数に対する要約の文字数の割合 (%、空白を除く)
+    pub compression_percent: u16,
+    pub sentence_count: u16,
+    /// 空白と句読点を除いた文字に占める漢字の割合 (%)
+    pub kanji_percent: u8,
+    /// 原文と同じ表現の割合 (%)
+    pub verbatim_percent: u8,
+}
+
+impl SummaryMetrics {
+    /// 要約か原文が空なら測らない
+    pub fn measure(summary: &str, original: &str) -> Option<Self> {
+        let original_chars = count_chars(original);
+        if original_chars == 0 {
+            return None;
+        }
+        let kanji_percent = text_difficulty::kanji_percent(summary)?;
+        Some(Self {
+            compression_percent: u16::try_from(count_chars(summary) * 100 / original_chars)
+                .unwrap_or(u16::MAX),
+            sentence_count: u16::try_from(text_difficulty::count_sentences(summary))
+                .unwrap_or(u16::MAX),
+            kanji_percent,
+            verbatim_percent: verbatim::overlap_percent(summary, original),
+        })
+    }
+
+    /// 評価結果の末尾に付ける指標の一覧
+    pub fn format(self) -> String {
+        format!(
+            "\n\n# 要約の指標\n- 圧縮率: {}%\n- 文の数: {}\n- 漢字の割合: {}%\n- 原文と同じ表現: {}%\n",
+            self.compression_percent,
+            self.sentence_count,
+            self.kanji_percent,
+            self.verbatim_percent
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_compare_the_summary_with_the_original() {
+        let original = "政府は少子化対策として児童手当を拡充する方針を固めた。財源は今後検討する。";
+        let metrics =
+            SummaryMetrics::measure("政府が児童手当の拡充を決めた。財源は未定。", original);
+        assert_eq!(
+            metrics,
+            Some(SummaryMetrics {
+                compression_percent: 56,
+                sentence_count: 2,
+                kanji_percent: 68,
+                verbatim_percent: 0,
+            })
+        );
+        assert!(metrics.is_some_and(|m| m.format().contains("- 文の数: 2")));
+        assert_eq!(SummaryMetrics::measure(" ", original), None);
+        assert_eq!(SummaryMetrics::measure("要約。", ""), None);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::error::AppError;
+use crate::evaluation::{self, build_evaluation_prompt};
+use serde::Deserialize;
+
+/// 原文欄と評価で使う資料の名前
+pub const SOURCE_LABELS: [&str; 2] = ["資料A", "資料B"];
+/// 資料ごとの反映度がこれ未満なら、スコアによらず不合格にする
+const MIN_SOURCE_COVERAGE: u8 = 3;
+
+/// 関連する 2 つの資料を読み、両方をまとめた 1 つの要約を書く統合要約モード。
+/// 資料を生成するまでは `sources` が空
+#[derive(Default)]
+pub struct SynthesisSession {
+    pub sources: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct GeneratedSources {
+    first: String,
+    second: String,
+}
+
+#[derive(Deserialize)]
+struct CoverageResponse {
+    source_coverage: Vec<u8>,
+}
+
+/// 合計がおよそ `character_count` 文字になる、同じ話題を別の立場から書いた 2 つの資料を求める
+pub fn build_synthesis_prompt(difficulty_hint: &str, character_count: u16) -> String {
+    let instruction = format!(
+        "{difficulty_hint}同じ話題について、立場や観点の異なる2つの短い文章をそれぞれ{}文字程度で作成してください。\
+         片方だけを読んでも全体がわからないよう、それぞれに相手にない情報を含めてください。\
+         次の JSON だけを出力してください。{{\"first\": \"1つ目の文章\", \"second\": \"2つ目の文章\"}}",
+        character_count / 2
+    );
+    instruction.repeat(2)
+}
+
+/// 応答から 2 つの資料を読み取る。どちらかが空なら不正とする
+pub fn parse_sources(response: &str) -> Result<Vec<String>, AppError> {
+    let (Some(start), Some(end)) = (response.find('{'), response.rfind('}')) else {
+        return Err(AppError::InvalidQuiz(
+            "JSON が含まれていません。".to_string(),
+        ));
+    };
+    let generated: GeneratedSources =
+        serde_json::from_str(response.get(start..=end).unwrap_or_default())?;
+    let sources = vec![
+        generated.first.trim().to_string(),
+        generated.second.trim().to_string(),
+    ];
+    if sources.iter().any(String::is_empty) {
+        return Err(AppError::InvalidQuiz("資料が空です。".to_string()));
+    }
+    Ok(sources)
+}
+
+/// 統計や履歴に残す原文。資料名の見出しを付けて 1 つにつなげる
+pub fn combined_text(sources: &[String]) -> String {
+    SOURCE_LABELS
+        .iter()
+        .zip(sources)
+        .map(|(label, source)| format!("【{label}】\n{source}"))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// 通常の評価プロンプトに、資料ごとの反映度を答えさせる指示を加える
+pub fn build_synthesis_evaluation_prompt(original_text: &str, summary_text: &str) -> String {
+    let [first, second] = SOURCE_LABELS;
+    format!(
+        "{}\n# 統合要約の追加ルール\n\
+         - 原文は【{first}】と【{second}】の 2 つの資料であり、要約文は両方を統合した 1 つの要約であること\n\
+         - JSON に \"source_coverage\": [{first}の反映度, {second}の反映度] を 1〜5 の整数で加えること\n\
+         - どちらかの資料の反映度が {MIN_SOURCE_COVERAGE} 未満なら overall は \"不合格\" とすること\n",
+        build_evaluation_prompt(original_text, summary_text),
+    )
+}
+
+/// 評価から資料ごとの反映度を取り出す。2 つ揃っていないときや範囲外のときは `None`
+pub fn parse_source_coverage(evaluation: &str) -> Option<[u8; 2]> {
+    let start = evaluation.find('{')?;
+    let end = evaluation.rfind('}')?;
+    let parsed: CoverageResponse = serde_json::from_str(evaluation.get(start..=end)?).ok()?;
+    let coverage: [u8; 2] = parsed.source_coverage.try_into().ok()?;
+    coverage
+        .iter()
+        .all(|score| (1..=5).contains(score))
+        .then_some(coverage)
+}
+
+pub fn covers_both(coverage: [u8; 2]) -> bool {
+    coverage.iter().all(|&score| score >= MIN_SOURCE_COVERAGE)
+}
+
+/// 評価画面のスコア表の後に続ける、資料ごとの反映度
+pub fn format_coverage(coverage: [u8; 2]) -> String {
+    let mut text = "\n資料ごとの反映度\n".to_string();
+    for (label, score) in SOURCE_LABELS.iter().zip(coverage) {
+        text.push_str(&evaluation::format_score_row(label, score));
+        text.push('\n');
+    }
+    if !covers_both(coverage) {
+        text.push_str("片方の資料の内容が要約に十分入っていないため不合格です。\n");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_are_parsed_and_combined_with_labels() {
+        let sources =
+            parse_sources("出力: {\"first\": \" 賛成の意見。 \", \"second\": \"反対の意見。\"}")
+                .unwrap_or_default();
+        assert_eq!(
+            combined_text(&sources),
+            "【資料A】\n賛成の意見。\n\n【資料B】\n反対の意見。"
+        );
+        assert!(parse_sources("{\"first\": \"a\", \"second\": \" \"}").is_err());
+        assert!(parse_sources("JSON なし").is_err());
+    }
+
+    #[test]
+    fn coverage_is_read_and_fails_when_a_source_is_left_out() {
+        let evaluation = r#"{"overall": "合格", "source_coverage": [4, 2]}"#;
+        let coverage = parse_source_coverage(evaluation);
+        assert_eq!(coverage, Some([4, 2]));
+        assert!(!coverage.is_some_and(covers_both));
+        assert!(
+            format_coverage([4, 2]).contains("資料B\u{3000}\u{3000}\u{3000}\u{3000} ■■□□□ 2/5")
+        );
+
+        assert_eq!(parse_source_coverage(r#"{"source_coverage": [4]}"#), None);
+        assert_eq!(
+            parse_source_coverage(r#"{"source_coverage": [4, 6]}"#),
+            None
+        );
+        assert_eq!(parse_source_coverage(r#"{"overall": "合格"}"#), None);
+    }
+}
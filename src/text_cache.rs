@@ -0,0 +1,166 @@
+use crate::models::{Difficulty, TextStyle};
+use crate::storage;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// 生成した文章のキャッシュを読み書きせずに起動するフラグ
+pub const NO_CACHE_FLAG: &str = "--no-cache";
+/// これを超えたら、出題済みの古い文章から消す
+const MAX_CACHED_TEXTS: usize = 200;
+
+/// `--no-cache` を付けて起動したか
+pub fn is_disabled() -> bool {
+    std::env::args().skip(1).any(|arg| arg == NO_CACHE_FLAG)
+}
+
+/// キャッシュした文章を使い回せる出題の条件 (文字数・文体・難易度)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheKey {
+    pub character_count: u16,
+    pub style: Option<TextStyle>,
+    pub difficulty: Difficulty,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CachedText {
+    #[serde(flatten)]
+    key: CacheKey,
+    text: String,
+    generated_at: DateTime<Local>,
+    /// 一度でも出題したか。出題していない文章だけを使い回す
+    seen: bool,
+}
+
+/// 生成した文章のキャッシュ (`text_cache.json`)。API の応答が遅いときや利用上限に達したときに、
+/// まだ出題していない文章を代わりに出題する
+#[derive(Serialize, Deserialize, Default)]
+pub struct TextCache {
+    entries: Vec<CachedText>,
+}
+
+impl TextCache {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(
+            storage::load_json(storage::global()?, storage::TEXT_CACHE_DOCUMENT)?
+                .unwrap_or_default(),
+        )
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        storage::save_json(storage::global()?, storage::TEXT_CACHE_DOCUMENT, self)?;
+        Ok(())
+    }
+
+    /// 生成した文章を加える。上限を超えたら、出題済みの文章を古いものから消す
+    pub fn add(&mut self, key: CacheKey, text: &str, seen: bool, now: DateTime<Local>) {
+        if self.entries.iter().any(|entry| entry.text == text) {
+            return;
+        }
+        self.entries.push(CachedText {
+            key,
+            text: text.to_string(),
+            generated_at: now,
+            seen,
+        });
+        while self.entries.len() > MAX_CACHED_TEXTS {
+            let Some(index) = self.entries.iter().position(|entry| entry.seen) else {
+                break;
+            };
+            self.entries.remove(index);
+        }
+    }
+
+    pub fn mark_seen(&mut self, text: &str) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.text == text) {
+            entry.seen = true;
+        }
+    }
+
+    pub fn has_unseen(&self, key: CacheKey) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| !entry.seen && entry.key == key)
+    }
+
+    /// まだ出題していない `key` の文章を古いものから取り出し、出題済みにする
+    pub fn take_unseen(&mut self, key: CacheKey) -> Option<String> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .filter(|entry| !entry.seen && entry.key == key)
+            .min_by_key(|entry| entry.generated_at)?;
+        entry.seen = true;
+        Some(entry.text.clone())
+    }
+}
+
+/// キャッシュから出題して節約した量
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheSavings {
+    /// キャッシュから出題した回数
+    pub served: u32,
+    /// 送らずに済んだプロンプトと生成されるはずだった文章のトークン数の見積もり
+    pub saved_tokens: u64,
+}
+
+impl CacheSavings {
+    pub fn record(&mut self, prompt: &str, text: &str) {
+        self.served += 1;
+        self.saved_tokens += estimate_tokens(prompt) + estimate_tokens(text);
+    }
+}
+
+/// 日本語はおおむね 1 文字 1 トークンとして見積もる
+fn estimate_tokens(text: &str) -> u64 {
+    u64::try_from(text.chars().count()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(style: TextStyle) -> CacheKey {
+        CacheKey {
+            character_count: 400,
+            style: Some(style),
+            difficulty: Difficulty::default(),
+        }
+    }
+
+    #[test]
+    fn only_unseen_texts_of_the_same_setting_are_served() {
+        let mut cache = TextCache::default();
+        let now = Local::now();
+        cache.add(key(TextStyle::Official), "出題済み", true, now);
+        cache.add(key(TextStyle::Official), "先読み", false, now);
+        cache.add(key(TextStyle::Official), "先読み", false, now);
+        assert_eq!(cache.entries.len(), 2);
+        assert!(!cache.has_unseen(key(TextStyle::Newspaper)));
+        assert!(cache.has_unseen(key(TextStyle::Official)));
+
+        assert_eq!(
+            cache.take_unseen(key(TextStyle::Official)).as_deref(),
+            Some("先読み")
+        );
+        assert_eq!(cache.take_unseen(key(TextStyle::Official)), None);
+
+        let mut savings = CacheSavings::default();
+        savings.record("プロンプト", "文章です。");
+        assert_eq!(savings.served, 1);
+        assert_eq!(savings.saved_tokens, 10);
+    }
+
+    #[test]
+    fn seen_texts_are_dropped_first_when_full() {
+        let mut cache = TextCache::default();
+        let now = Local::now();
+        cache.add(key(TextStyle::Official), "未出題", false, now);
+        for index in 0..MAX_CACHED_TEXTS {
+            cache.add(key(TextStyle::Official), &format!("文章{index}"), true, now);
+        }
+        assert_eq!(cache.entries.len(), MAX_CACHED_TEXTS);
+        assert!(cache.has_unseen(key(TextStyle::Official)));
+        cache.mark_seen("未出題");
+        assert!(!cache.has_unseen(key(TextStyle::Official)));
+    }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+/// 文の区切りとみなす文字
+const SENTENCE_ENDINGS: [char; 5] = ['。', '！', '？', '!', '?'];
+/// 漢字の割合 (%) の段階。日本語能力試験の読解文のおおよその傾向に合わせている
+const KANJI_PERCENT_STEPS: [u8; 4] = [20, 25, 30, 35];
+/// 1 文の平均文字数の段階
+const SENTENCE_CHARS_STEPS: [u16; 4] = [25, 35, 45, 60];
+
+/// 文章から推定した日本語能力試験のレベル
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JlptLevel {
+    N5,
+    N4,
+    N3,
+    N2,
+    N1,
+}
+
+impl JlptLevel {
+    /// やさしい順
+    pub const ALL: [Self; 5] = [Self::N5, Self::N4, Self::N3, Self::N2, Self::N1];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::N5 => "N5",
+            Self::N4 => "N4",
+            Self::N3 => "N3",
+            Self::N2 => "N2",
+            Self::N1 => "N1",
+        }
+    }
+}
+
+/// 出題した文章そのものの難しさ。メニューで選んだ難易度とは別に、文章を分析して求める
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextFeatures {
+    /// 1 文の平均文字数 (空白を除く)
+    pub average_sentence_chars: u16,
+    /// 空白と句読点を除いた文字に占める漢字の割合 (%)
+    pub kanji_percent: u8,
+    pub jlpt_level: JlptLevel,
+}
+
+/// 文の長さと漢字の割合からレベルを推定する。どちらも段階に分け、その平均をレベルとする
+pub fn analyze(text: &str) -> Option<TextFeatures> {
+    let kanji_percent = kanji_percent(text)?;
+    let chars = text.chars().filter(|c| !c.is_whitespace()).count();
+    let sentences = count_sentences(text).max(1);
+
+    let average_sentence_chars = u16::try_from(chars / sentences).unwrap_or(u16::MAX);
+    let steps = KANJI_PERCENT_STEPS
+        .iter()
+        .filter(|&&step| kanji_percent >= step)
+        .count()
+        + SENTENCE_CHARS_STEPS
+            .iter()
+            .filter(|&&step| average_sentence_chars >= step)
+            .count();
+    let jlpt_level = JlptLevel::ALL
+        .get(steps.div_ceil(2))
+        .copied()
+        .unwrap_or(JlptLevel::N1);
+    Some(TextFeatures {
+        average_sentence_chars,
+        kanji_percent,
+        jlpt_level,
+    })
+}
+
+/// 空白と句読点を除いた文字に占める漢字の割合 (%)。文字がなければ `None`
+pub fn kanji_percent(text: &str) -> Option<u8> {
+    let letters = text
+        .chars()
+        .filter(|&c| !c.is_whitespace() && !is_punctuation(c))
+        .count();
+    if letters == 0 {
+        return None;
+    }
+    let kanji = text.chars().filter(|&c| is_kanji(c)).count();
+    Some(u8::try_from(kanji * 100 / letters).unwrap_or(100))
+}
+
+/// 文末の記号か改行で区切った文の数
+pub fn count_sentences(text: &str) -> usize {
+    text.split(|c: char| SENTENCE_ENDINGS.contains(&c) || c == '\n')
+        .filter(|sentence| sentence.chars().any(|c| !c.is_whitespace()))
+        .count()
+}
+
+/// 常用漢字を含む CJK 統合漢字と拡張 A、繰り返し記号の「々」
+fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '々')
+}
+
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() || matches!(c, '\u{3000}'..='\u{303F}' | '\u{FF01}'..='\u{FF0F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_kana_sentences_are_easier_than_long_kanji_sentences() {
+        let easy = analyze("きょうは はれです。こうえんに いきました。いぬが いました。");
+        assert_eq!(easy.map(|f| f.jlpt_level), Some(JlptLevel::N5));
+        assert_eq!(easy.map(|f| f.kanji_percent), Some(0));
+
+        let hard = analyze(
+            "政府は経済成長率の低迷を受け、財政出動と金融緩和を組み合わせた総合的な景気刺激策を閣議決定し、地方自治体への交付金を大幅に増額する方針を示した。",
+        );
+        assert_eq!(hard.map(|f| f.jlpt_level), Some(JlptLevel::N1));
+        assert!(hard.is_some_and(|f| f.kanji_percent >= 50 && f.average_sentence_chars >= 60));
+    }
+
+    #[test]
+    fn empty_text_has_no_features() {
+        assert_eq!(analyze(" \n。"), None);
+    }
+}
@@ -0,0 +1,253 @@
+use crate::error::AppError;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// 読み込める文章の上限 (文字数)
+pub const MAX_IMPORT_CHARS: usize = 20_000;
+const TEXT_EXTENSIONS: [&str; 3] = ["txt", "md", "text"];
+const PARENT_LABEL: &str = "../";
+
+/// 生成せずに出題する文章の入手元
+pub enum TextSource {
+    File(PathBuf),
+    Stdin,
+    Clipboard,
+    Library,
+}
+
+impl TextSource {
+    pub fn label(&self) -> String {
+        match self {
+            Self::File(path) => path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+            Self::Stdin => "標準入力".to_string(),
+            Self::Clipboard => "クリップボード".to_string(),
+            Self::Library => "ライブラリ".to_string(),
+        }
+    }
+}
+
+/// UTF-8 のテキストファイルを読み込み、出題用の文章にする
+pub fn load_text(path: &Path) -> Result<String, AppError> {
+    let bytes = fs::read(path)?;
+    let text = String::from_utf8(bytes)
+        .map_err(|_| AppError::InvalidText("UTF-8 のテキストではありません。".to_string()))?;
+    normalize(&text)
+}
+
+/// 標準入力を最後まで読み込む (`yomitore --stdin`)
+pub fn read_stdin() -> Result<String, AppError> {
+    let mut bytes = Vec::new();
+    io::stdin().read_to_end(&mut bytes)?;
+    let text = String::from_utf8(bytes)
+        .map_err(|_| AppError::InvalidText("UTF-8 のテキストではありません。".to_string()))?;
+    normalize(&text)
+}
+
+pub fn read_clipboard() -> Result<String, AppError> {
+    let text = arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|e| AppError::Clipboard(e.to_string()))?;
+    normalize(&text)
+}
+
+/// 前後の空白と BOM を取り除き、空や長すぎる文章を弾く
+fn normalize(text: &str) -> Result<String, AppError> {
+    let text = text.trim_start_matches('\u{feff}').trim();
+    if text.is_empty() {
+        return Err(AppError::InvalidText("文章が空です。".to_string()));
+    }
+    let count = text.chars().count();
+    if count > MAX_IMPORT_CHARS {
+        return Err(AppError::InvalidText(format!(
+            "{count} 文字あります。{MAX_IMPORT_CHARS} 文字以下にしてください。"
+        )));
+    }
+    Ok(text.to_string())
+}
+
+pub struct BrowserEntry {
+    pub path: PathBuf,
+    pub label: String,
+    pub is_dir: bool,
+}
+
+/// テキストファイルを選ぶための簡易ファイルブラウザ
+pub struct FileBrowser {
+    pub dir: PathBuf,
+    pub entries: Vec<BrowserEntry>,
+    pub selected: usize,
+}
+
+impl FileBrowser {
+    pub fn open(dir: PathBuf) -> io::Result<Self> {
+        let entries = read_entries(&dir)?;
+        Ok(Self {
+            dir,
+            entries,
+            selected: 0,
+        })
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// 選択中がディレクトリなら移動して `None`、ファイルならそのパスを返す
+    pub fn enter_selected(&mut self) -> io::Result<Option<PathBuf>> {
+        let Some(entry) = self.entries.get(self.selected) else {
+            return Ok(None);
+        };
+        if !entry.is_dir {
+            return Ok(Some(entry.path.clone()));
+        }
+        let dir = entry.path.clone();
+        self.change_dir(dir)?;
+        Ok(None)
+    }
+
+    pub fn go_parent(&mut self) -> io::Result<()> {
+        let Some(parent) = self.dir.parent().map(Path::to_path_buf) else {
+            return Ok(());
+        };
+        self.change_dir(parent)
+    }
+
+    fn change_dir(&mut self, dir: PathBuf) -> io::Result<()> {
+        self.entries = read_entries(&dir)?;
+        self.dir = dir;
+        self.selected = 0;
+        Ok(())
+    }
+}
+
+/// 親ディレクトリ、ディレクトリ、テキストファイルの順に並べる。隠しファイルは除く
+fn read_entries(dir: &Path) -> io::Result<Vec<BrowserEntry>> {
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            dirs.push(BrowserEntry {
+                label: format!("{name}/"),
+                path,
+                is_dir: true,
+            });
+        } else if is_text_file(&path) {
+            files.push(BrowserEntry {
+                label: name,
+                path,
+                is_dir: false,
+            });
+        }
+    }
+    dirs.sort_by(|a, b| a.label.cmp(&b.label));
+    files.sort_by(|a, b| a.label.cmp(&b.label));
+
+    let mut entries = Vec::with_capacity(dirs.len() + files.len() + 1);
+    if let Some(parent) = dir.parent() {
+        entries.push(BrowserEntry {
+            path: parent.to_path_buf(),
+            label: PARENT_LABEL.to_string(),
+            is_dir: true,
+        });
+    }
+    entries.extend(dirs);
+    entries.extend(files);
+    Ok(entries)
+}
+
+fn is_text_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            TEXT_EXTENSIONS
+                .iter()
+                .any(|allowed| ext.eq_ignore_ascii_case(allowed))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テストごとの空のディレクトリ。前回の残りがあれば消してから作る
+    fn temp_dir(name: &str) -> Result<PathBuf, String> {
+        let dir = std::env::temp_dir().join(format!(
+            "yomitore-text-import-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+        Ok(dir)
+    }
+
+    fn write(path: &Path, content: impl AsRef<[u8]>) -> Result<(), String> {
+        fs::write(path, content).map_err(|e| format!("cannot write {}: {e}", path.display()))
+    }
+
+    #[test]
+    fn load_text_trims_and_rejects_invalid_files() -> Result<(), String> {
+        let dir = temp_dir("load")?;
+        let text_path = dir.join("article.txt");
+        write(&text_path, "\u{feff}\n  本文です。\n")?;
+        let text = load_text(&text_path).map_err(|e| e.to_string())?;
+        if text != "本文です。" {
+            return Err(format!("unexpected text: {text:?}"));
+        }
+
+        let empty_path = dir.join("empty.txt");
+        write(&empty_path, " \n")?;
+        if !matches!(load_text(&empty_path), Err(AppError::InvalidText(_))) {
+            return Err("a blank file should be rejected".to_string());
+        }
+
+        let binary_path = dir.join("binary.txt");
+        write(&binary_path, [0xff, 0xfe, 0x00])?;
+        if !matches!(load_text(&binary_path), Err(AppError::InvalidText(_))) {
+            return Err("a binary file should be rejected".to_string());
+        }
+        let _ = fs::remove_dir_all(&dir);
+        Ok(())
+    }
+
+    #[test]
+    fn browser_lists_directories_before_text_files() -> Result<(), String> {
+        let dir = temp_dir("browse")?;
+        fs::create_dir_all(dir.join("notes")).map_err(|e| e.to_string())?;
+        write(&dir.join("b.md"), "b")?;
+        write(&dir.join("a.TXT"), "a")?;
+        write(&dir.join("image.png"), "x")?;
+        write(&dir.join(".hidden.txt"), "x")?;
+
+        let mut browser = FileBrowser::open(dir.clone()).map_err(|e| e.to_string())?;
+        let labels: Vec<&str> = browser.entries.iter().map(|e| e.label.as_str()).collect();
+        if labels != [PARENT_LABEL, "notes/", "a.TXT", "b.md"] {
+            return Err(format!("unexpected entries: {labels:?}"));
+        }
+
+        browser.select_next();
+        if !matches!(browser.enter_selected(), Ok(None)) {
+            return Err("entering a directory should not pick a file".to_string());
+        }
+        if browser.dir != dir.join("notes") {
+            return Err(format!("unexpected directory: {}", browser.dir.display()));
+        }
+        let _ = fs::remove_dir_all(&dir);
+        Ok(())
+    }
+}
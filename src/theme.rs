@@ -0,0 +1,206 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// 設定 `theme` と `C` キーで選ぶ配色
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    Dark,
+    Light,
+    Solarized,
+    HighContrast,
+}
+
+impl ThemeName {
+    const ALL: [Self; 5] = [
+        Self::Default,
+        Self::Dark,
+        Self::Light,
+        Self::Solarized,
+        Self::HighContrast,
+    ];
+
+    /// `C` で切り替える次の配色。最後まで進んだら最初に戻る
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|&name| name == self)
+            .unwrap_or_default();
+        Self::ALL.get(index + 1).copied().unwrap_or_default()
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::Solarized => "solarized",
+            Self::HighContrast => "high-contrast",
+        }
+    }
+
+    pub fn theme(self) -> &'static Theme {
+        match self {
+            Self::Default => &DEFAULT,
+            Self::Dark => &DARK,
+            Self::Light => &LIGHT,
+            Self::Solarized => &SOLARIZED,
+            Self::HighContrast => &HIGH_CONTRAST,
+        }
+    }
+}
+
+/// 画面で使う色の役割。描画では `Color::` を直接書かず、ここから選ぶ
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// 本文の文字と、オーバーレイなどの背景
+    pub text: Color,
+    pub background: Color,
+    /// 色付きの背景の上に載せる文字 (選択中の行や判定の帯)
+    pub on_accent: Color,
+    /// 補足や未選択の項目
+    pub muted: Color,
+    pub dim: Color,
+    /// 選択中の項目と、一覧や案内の枠
+    pub accent: Color,
+    pub accent_soft: Color,
+    /// 原文欄の枠や見出しのラベル
+    pub highlight: Color,
+    pub highlight_soft: Color,
+    /// 画面のタイトルと、入力中の欄の枠
+    pub title: Color,
+    pub info: Color,
+    pub success: Color,
+    pub success_soft: Color,
+    /// ヒートマップで全問正解した日
+    pub success_strong: Color,
+    pub danger: Color,
+    pub danger_soft: Color,
+    pub secondary: Color,
+    pub secondary_soft: Color,
+}
+
+/// これまでの配色
+const DEFAULT: Theme = Theme {
+    text: Color::White,
+    background: Color::Black,
+    on_accent: Color::Black,
+    muted: Color::DarkGray,
+    dim: Color::Gray,
+    accent: Color::Cyan,
+    accent_soft: Color::LightCyan,
+    highlight: Color::Yellow,
+    highlight_soft: Color::LightYellow,
+    title: Color::LightBlue,
+    info: Color::Blue,
+    success: Color::Green,
+    success_soft: Color::LightGreen,
+    success_strong: Color::Rgb(0, 255, 0),
+    danger: Color::Red,
+    danger_soft: Color::LightRed,
+    secondary: Color::Magenta,
+    secondary_soft: Color::LightMagenta,
+};
+
+/// 暗い背景向け。黒地で沈む濃い青と濃い灰色を明るい色に替える
+const DARK: Theme = Theme {
+    muted: Color::Gray,
+    dim: Color::White,
+    info: Color::LightBlue,
+    title: Color::LightCyan,
+    ..DEFAULT
+};
+
+/// 明るい背景向け。黄色や水色の枠は白地で読めないので、濃い色に替える
+const LIGHT: Theme = Theme {
+    text: Color::Black,
+    background: Color::White,
+    on_accent: Color::White,
+    muted: Color::Gray,
+    dim: Color::DarkGray,
+    accent: Color::Blue,
+    accent_soft: Color::Rgb(0, 95, 135),
+    highlight: Color::Rgb(175, 95, 0),
+    highlight_soft: Color::Rgb(135, 95, 0),
+    title: Color::Rgb(0, 0, 175),
+    info: Color::Rgb(0, 95, 175),
+    success: Color::Rgb(0, 135, 0),
+    success_soft: Color::Rgb(95, 135, 0),
+    success_strong: Color::Rgb(0, 95, 0),
+    danger: Color::Rgb(175, 0, 0),
+    danger_soft: Color::Rgb(175, 0, 95),
+    secondary: Color::Rgb(135, 0, 135),
+    secondary_soft: Color::Rgb(95, 0, 175),
+};
+
+/// Solarized (dark) のパレット
+const SOLARIZED: Theme = Theme {
+    text: Color::Rgb(147, 161, 161),
+    background: Color::Rgb(0, 43, 54),
+    on_accent: Color::Rgb(0, 43, 54),
+    muted: Color::Rgb(88, 110, 117),
+    dim: Color::Rgb(131, 148, 150),
+    accent: Color::Rgb(42, 161, 152),
+    accent_soft: Color::Rgb(38, 139, 210),
+    highlight: Color::Rgb(181, 137, 0),
+    highlight_soft: Color::Rgb(203, 75, 22),
+    title: Color::Rgb(38, 139, 210),
+    info: Color::Rgb(108, 113, 196),
+    success: Color::Rgb(133, 153, 0),
+    success_soft: Color::Rgb(42, 161, 152),
+    success_strong: Color::Rgb(133, 153, 0),
+    danger: Color::Rgb(220, 50, 47),
+    danger_soft: Color::Rgb(203, 75, 22),
+    secondary: Color::Rgb(211, 54, 130),
+    secondary_soft: Color::Rgb(108, 113, 196),
+};
+
+/// はっきり見分けられる明るい色だけを使う
+const HIGH_CONTRAST: Theme = Theme {
+    text: Color::White,
+    background: Color::Black,
+    on_accent: Color::Black,
+    muted: Color::White,
+    dim: Color::White,
+    accent: Color::LightCyan,
+    accent_soft: Color::LightCyan,
+    highlight: Color::LightYellow,
+    highlight_soft: Color::LightYellow,
+    title: Color::White,
+    info: Color::LightCyan,
+    success: Color::LightGreen,
+    success_soft: Color::LightGreen,
+    success_strong: Color::LightGreen,
+    danger: Color::LightRed,
+    danger_soft: Color::LightRed,
+    secondary: Color::LightMagenta,
+    secondary_soft: Color::LightMagenta,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn themes_cycle_through_all_names() {
+        let mut name = ThemeName::default();
+        let mut seen = Vec::new();
+        for _ in 0..ThemeName::ALL.len() {
+            seen.push(name);
+            name = name.next();
+        }
+        assert_eq!(name, ThemeName::Default);
+        assert_eq!(seen, ThemeName::ALL);
+    }
+
+    #[test]
+    fn light_theme_avoids_pale_borders_on_white() {
+        let light = ThemeName::Light.theme();
+        assert_eq!(light.background, Color::White);
+        assert_ne!(light.highlight, Color::Yellow);
+        assert_ne!(light.accent, Color::Cyan);
+        assert_eq!(ThemeName::Default.theme().highlight, Color::Yellow);
+    }
+}
@@ -0,0 +1,109 @@
+/// チュートリアルで読む文章。API を使わずに出題する
+pub const SAMPLE_TEXT: &str = "市は、来年4月から市内の図書館の開館時間を延長すると発表した。\
+平日は午後9時まで開館し、仕事や学校の帰りにも利用しやすくする。\
+延長に伴う人件費は、貸出手続きの自動化で生じる余力で賄う方針だ。\
+一方で、夜間の利用者が少なければ見直すとしており、\
+半年間の利用状況を調べたうえで継続するかどうかを判断する。";
+
+/// チュートリアルで返す評価。API の評価と同じ形式で、採点の見方を示すための固定の内容
+pub const MOCK_EVALUATION: &str = r#"{
+  "appropriate": true,
+  "importance": 4,
+  "conciseness": 4,
+  "accuracy": 5,
+  "improvements": [
+    "延長の費用をどう賄うかにも触れると、要点がそろいます。",
+    "「半年後に継続を判断する」という条件を入れましょう。",
+    "本番では原文の 1/3〜1/5 程度の長さを目安にしてください。"
+  ],
+  "overall": "合格",
+  "model_answer": "市は来年4月から図書館の平日の開館を午後9時まで延長する。費用は貸出の自動化で賄い、半年間の利用状況を見て継続を判断する。",
+  "key_points": [
+    {"point": "来年4月から平日は午後9時まで開館する", "covered": true},
+    {"point": "費用は貸出手続きの自動化で賄う", "covered": false},
+    {"point": "半年間の利用状況を見て継続を判断する", "covered": false}
+  ]
+}"#;
+
+/// 画面の状態から決まる、チュートリアルの今の段階
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TutorialStep {
+    Read,
+    Write,
+    Submit,
+    Review,
+}
+
+impl TutorialStep {
+    pub const COUNT: usize = 4;
+
+    /// 評価を受け取った後は `Review`、要約を書き始めたら `Submit`、入力中は `Write`、それ以外は `Read`
+    pub fn current(editing: bool, has_summary: bool, evaluated: bool) -> Self {
+        if evaluated {
+            Self::Review
+        } else if has_summary {
+            Self::Submit
+        } else if editing {
+            Self::Write
+        } else {
+            Self::Read
+        }
+    }
+
+    pub fn number(self) -> usize {
+        match self {
+            Self::Read => 1,
+            Self::Write => 2,
+            Self::Submit => 3,
+            Self::Review => 4,
+        }
+    }
+
+    /// 吹き出しに出す説明。`{}` には設定したキーが入る
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::Read => "左の原文を読みましょう。{} で入力を始めます",
+            Self::Write => "右の欄に、原文の要点を短くまとめて書きましょう",
+            Self::Submit => "書き終えたら {} で提出します。Esc で入力を中断できます",
+            Self::Review => "評価結果です。1-4 でタブを切り替え、{} で本番のトレーニングを始めます",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluation::{parse_evaluation, parse_evaluation_extras};
+
+    #[test]
+    fn step_follows_the_screen_state() {
+        assert_eq!(
+            TutorialStep::current(false, false, false),
+            TutorialStep::Read
+        );
+        assert_eq!(
+            TutorialStep::current(true, false, false),
+            TutorialStep::Write
+        );
+        assert_eq!(
+            TutorialStep::current(true, true, false),
+            TutorialStep::Submit
+        );
+        assert_eq!(
+            TutorialStep::current(false, true, false),
+            TutorialStep::Submit
+        );
+        assert_eq!(
+            TutorialStep::current(false, true, true),
+            TutorialStep::Review
+        );
+    }
+
+    #[test]
+    fn mock_evaluation_uses_the_real_format() {
+        assert!(parse_evaluation(MOCK_EVALUATION).is_ok_and(|parsed| parsed.passed()));
+        let extras = parse_evaluation_extras(MOCK_EVALUATION);
+        assert!(!extras.model_answer.is_empty());
+        assert_eq!(extras.key_points.len(), 3);
+    }
+}
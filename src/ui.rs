@@ -1,13 +1,43 @@
-use crate::app::{App, MENU_OPTIONS, OVERLAY_MARGIN, TEXT_WRAP_MARGIN, ViewMode};
+use crate::api_client::ApiClient;
+use crate::api_key_setup::KeyFormat;
+use crate::app::{
+    App, GLOSSARY_FOOTER_HEIGHT, MENU_OPTIONS, MIN_OVERLAY_WIDTH, OVERLAY_MARGIN, Popup,
+    RESULT_TAB_BAR_HEIGHT, STATUS_EVALUATING, TEXT_WRAP_MARGIN, VERDICT_BANNER_HEIGHT, ViewMode,
+    ZEN_HINT_HEIGHT,
+};
+use crate::appeal::AppealStatus;
+use crate::coach::CoachRole;
+use crate::config::{LabelLanguage, Provider};
+use crate::daily_challenge::DailyChallenge;
+use crate::diff::{DiffKind, DiffSegment};
+use crate::error::ErrorNotice;
+use crate::evaluation::Verdict;
+use crate::exam;
+use crate::furigana::Annotation;
+use crate::guided::GuidedSession;
 use crate::help;
+use crate::history;
+use crate::i18n::{tr, trf};
+use crate::keyword_prediction;
+use crate::levels::{self, LevelRound};
+use crate::pane_layout::ZenMode;
+use crate::quiz;
+use crate::ranks;
 use crate::reports;
+use crate::result_tabs::{self, ResultTab};
+use crate::summary_length::{self, LengthFit};
+use crate::synthesis;
+use crate::theme::Theme;
+use crate::tutorial::TutorialStep;
+use crate::word_lookup::{LookupState, Word, WordSelection};
 use rat_text::text_area::{TextArea, TextWrap};
+use rat_text::text_input::TextInput;
 use rat_text::{HasScreenCursor, text_area::TextAreaState};
 use ratatui::{
     prelude::*,
     style::Modifier,
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Tabs, Wrap},
 };
 
 const MENU_TITLE_ART: [&str; 6] = [
@@ -18,27 +48,23 @@ const MENU_TITLE_ART: [&str; 6] = [
     "   ██║   ╚██████╔╝██║ ╚═╝ ██║██║   ██║   ╚██████╔╝██║  ██║███████╗",
     "   ╚═╝    ╚═════╝ ╚═╝     ╚═╝╚═╝   ╚═╝    ╚═════╝ ╚═╝  ╚═╝╚══════╝",
 ];
-const MENU_TITLE_COLOR: Color = Color::LightBlue;
+const HELP_TOC_WIDTH_PERCENT: u16 = 60;
+const ERROR_POPUP_WIDTH_PERCENT: u16 = 60;
+/// 異議申し立てとキーワード予想のダイアログの入力欄 (1 行と枠)
+const APPEAL_INPUT_HEIGHT: u16 = 3;
 const MENU_LOGO_GAP_HEIGHT: u16 = 1;
 const MENU_TITLE_BLOCK_GAP_HEIGHT: u16 = 3;
 
 pub fn render(app: &mut App, frame: &mut Frame) {
     app.update_terminal_size(frame.area().width, frame.area().height);
 
-    match app.view_mode {
-        ViewMode::Menu => {
-            render_menu_view(app, frame);
-            return;
-        }
-        ViewMode::Report => {
-            render_report_view(app, frame);
-            return;
-        }
-        ViewMode::Help => {
-            render_help_view(app, frame);
-            return;
-        }
-        ViewMode::Normal => {}
+    if render_aux_view(app, frame) {
+        return;
+    }
+    let zen = app.active_zen_mode();
+    if zen != ZenMode::Off {
+        render_zen_view(app, frame, zen);
+        return;
     }
 
     let main_layout = Layout::default()
@@ -46,29 +72,39 @@ pub fn render(app: &mut App, frame: &mut Frame) {
         .constraints([
             Constraint::Length(1),
             Constraint::Min(0),
+            Constraint::Length(app.error_line_height()),
             Constraint::Length(3),
         ])
         .split(frame.area());
 
-    let [header_area, body_area, status_area] = main_layout.as_ref() else {
+    let [header_area, body_area, error_area, status_area] = main_layout.as_ref() else {
         return;
     };
-    render_header(frame, *header_area);
+    render_header(app, frame, *header_area);
+    render_error_line(app, frame, *error_area);
 
-    let content_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(*body_area);
-    let [original_area, summary_area] = content_layout.as_ref() else {
-        return;
-    };
+    let areas = app.pane_layout.areas(*body_area, app.shows_result_pane());
 
-    render_original_text(app, frame, *original_area);
-    render_summary_input(app, frame, *summary_area);
+    let original_area = render_original_pane(app, frame, areas.original);
+    let summary_area = render_answer_pane(app, frame, areas.answer);
 
-    if app.show_evaluation_overlay {
+    if let Some(result_area) = areas.result {
+        render_result_pane(app, frame, result_area);
+    } else if app.show_evaluation_overlay {
         render_evaluation_overlay(app, frame);
     }
+    if let Some(step) = app.tutorial_step() {
+        let anchor = match step {
+            TutorialStep::Read => original_area,
+            TutorialStep::Write | TutorialStep::Submit => summary_area,
+            TutorialStep::Review => *body_area,
+        };
+        render_tutorial_callout(app, frame, step, anchor);
+    }
+
+    render_popup(app, frame);
+    render_appeal_popup(app, frame);
+    render_keyword_prediction_popup(app, frame);
 
     render_status_bar(app, frame, *status_area);
 
@@ -77,42 +113,330 @@ pub fn render(app: &mut App, frame: &mut Frame) {
     {
         frame.set_cursor_position((cx, cy));
     }
+    if app.scratchpad.is_editing()
+        && let Some((cx, cy)) = app.scratchpad.text_area.screen_cursor()
+    {
+        frame.set_cursor_position((cx, cy));
+    }
+}
+
+/// 原文欄と、表示していれば用語解説欄を描く。原文を描いた領域を返す
+fn render_original_pane(app: &App, frame: &mut Frame, area: Rect) -> Rect {
+    let original_area = if app.glossary.visible {
+        let [text_area, glossary_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(GLOSSARY_FOOTER_HEIGHT),
+        ])
+        .areas(area);
+        render_glossary(app, frame, glossary_area);
+        text_area
+    } else {
+        area
+    };
+    render_original_text(app, frame, original_area);
+    original_area
+}
+
+/// 集中モードでは要約欄か原文欄の一方だけを全画面に出し、下端に操作の案内を出す
+fn render_zen_view(app: &mut App, frame: &mut Frame, zen: ZenMode) {
+    let [body_area, hint_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(ZEN_HINT_HEIGHT)])
+            .areas(frame.area());
+    let hint = if zen == ZenMode::Peeking {
+        render_original_pane(app, frame, body_area);
+        "原文をのぞいています  ↑/↓: スクロール  ほかのキー: 要約欄に戻る"
+    } else {
+        render_summary_input(app, frame, body_area);
+        if let Some((cx, cy)) = app.text_area_state.screen_cursor() {
+            frame.set_cursor_position((cx, cy));
+        }
+        "集中モード  Ctrl+P: 原文をのぞく  Esc: 入力を終える"
+    };
+    let hint =
+        Paragraph::new(tr(app.config.locale, hint)).style(Style::default().fg(app.theme().muted));
+    frame.render_widget(hint, hint_area);
+}
+
+/// トレーニング画面以外の画面を描く。描いたら `true`
+fn render_aux_view(app: &mut App, frame: &mut Frame) -> bool {
+    match app.view_mode {
+        ViewMode::Menu => render_menu_view(app, frame),
+        ViewMode::Report | ViewMode::BadgeGallery => render_report_view(app, frame),
+        ViewMode::Help => render_help_view(app, frame),
+        ViewMode::ModelSelect => render_model_select_view(app, frame),
+        ViewMode::ReadAloud => render_read_aloud_view(app, frame),
+        ViewMode::SpeedReading => render_speed_reading_view(app, frame),
+        ViewMode::FileBrowser => render_file_browser_view(app, frame),
+        ViewMode::History => render_history_view(app, frame),
+        ViewMode::ApiKeySetup => render_api_key_setup_view(app, frame),
+        ViewMode::ProfileSelect => render_profile_select_view(app, frame),
+        ViewMode::Coach => render_coach_view(app, frame),
+        ViewMode::Normal => return false,
+    }
+    true
 }
 
-fn render_header(frame: &mut Frame, area: Rect) {
-    let title = Paragraph::new(" yomitore: 読解力トレーニング ")
+fn render_header(app: &App, frame: &mut Frame, area: Rect) {
+    let locale = app.config.locale;
+    let level = levels::describe_level(app.stats.reading_level);
+    let streak = match app.stats.current_daily_streak {
+        0 => String::new(),
+        days => format!("| 📅 {} ", trf(locale, "{}日連続", &[&days])),
+    };
+    let app_title = tr(locale, "yomitore: 読解力トレーニング");
+    let rank = ranks::rank_at(app.stats.rank).name;
+    let title = match app.level_round {
+        Some(LevelRound::Promotion) => {
+            format!(
+                " {app_title} | {level} {} {streak}| 🥋 {rank} ",
+                tr(locale, "昇級試験")
+            )
+        }
+        _ => format!(" {app_title} | {level} {streak}| 🥋 {rank} "),
+    };
+    let title = Paragraph::new(title)
         .style(Style::new().bold())
         .alignment(Alignment::Center);
     frame.render_widget(title, area);
 }
 
 fn render_original_text(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let mut title = if app.has_training_started() {
+        trf(
+            locale,
+            "原文 | {} (↑/↓ or j/k: スクロール)",
+            &[&app.breadcrumb().join(" ▸ ")],
+        )
+    } else {
+        tr(locale, "原文 (↑/↓ or j/k: スクロール)").to_string()
+    };
+    if app.furigana.visible && app.synthesis_sources().is_none() {
+        let note = match app.furigana.annotation(&app.original_text) {
+            Annotation::Pending => "読み仮名を取得中…",
+            Annotation::Failed => "読み仮名を取得できませんでした (f を 2 回押すと再取得)",
+            Annotation::Ready(_) => "読み仮名 (f: 切り替え)",
+        };
+        title = format!("{title} [{}]", tr(locale, note));
+    }
+    if let Some(sources) = app.synthesis_sources().filter(|_| !app.is_paused()) {
+        render_synthesis_sources(app, frame, area, &title, sources);
+        return;
+    }
     let block = Block::default()
-        .title("原文 (↑/↓ or j/k: スクロール)")
+        .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
-    let paragraph = Paragraph::new(app.original_text.as_str())
+        .border_style(Style::default().fg(theme.highlight));
+    let text = match (
+        app.word_selection.as_ref().and_then(WordSelection::current),
+        app.guided.as_ref(),
+    ) {
+        _ if app.is_paused() => Text::styled(
+            tr(locale, "一時停止中は原文を隠しています。'P' で再開します。"),
+            Style::default().fg(theme.muted),
+        ),
+        _ if app.is_predicting_keywords() => Text::styled(
+            tr(locale, "キーワードを予想すると本文が表示されます。"),
+            Style::default().fg(theme.muted),
+        ),
+        (Some(word), _) => highlight_word(&app.original_text, word, theme),
+        (None, Some(guided)) if !guided.paragraphs.is_empty() => {
+            guided_paragraphs(guided, locale, theme)
+        }
+        (None, _) => Text::from(app.displayed_original_text()),
+    };
+    let paragraph = Paragraph::new(text)
         .wrap(Wrap { trim: false })
         .scroll((app.original_text_scroll, 0))
         .block(block);
     frame.render_widget(paragraph, area);
 }
 
+/// 段階練習では要約している段落を強調し、要約し終えた段落の下に要約と講評を添える
+fn guided_paragraphs(
+    guided: &GuidedSession,
+    locale: LabelLanguage,
+    theme: &Theme,
+) -> Text<'static> {
+    let current = guided.current_paragraph();
+    let mut lines = Vec::new();
+    for (index, paragraph) in guided.paragraphs.iter().enumerate() {
+        if index > 0 {
+            lines.push(Line::default());
+        }
+        let style = match current {
+            Some(current) if current == index => Style::default().fg(theme.text).bold(),
+            Some(_) => Style::default().fg(theme.muted),
+            None => Style::default().fg(theme.text),
+        };
+        lines.push(Line::styled(
+            trf(
+                locale,
+                "【段落 {}/{}】",
+                &[&(index + 1), &guided.paragraphs.len()],
+            ),
+            style.fg(theme.highlight),
+        ));
+        lines.push(Line::styled(paragraph.clone(), style));
+        if let Some(review) = guided.reviews.get(index) {
+            let (mark, color) = if review.captured {
+                ("✔", theme.success)
+            } else {
+                ("✘", theme.danger)
+            };
+            lines.push(Line::styled(
+                trf(locale, "→ 要約: {}", &[&review.summary]),
+                Style::default().fg(theme.info),
+            ));
+            lines.push(Line::styled(
+                format!("{mark} {}", review.feedback),
+                Style::default().fg(color),
+            ));
+        }
+    }
+    Text::from(lines)
+}
+
+/// 語を選んでいる間は、その語だけ色を反転して示す
+fn highlight_word<'a>(text: &'a str, word: &Word, theme: &Theme) -> Text<'a> {
+    let mut offset = 0;
+    let lines = text.split('\n').map(|line| {
+        let line_start = offset;
+        offset += line.len() + 1;
+        let Some(start) = word
+            .start
+            .checked_sub(line_start)
+            .filter(|&start| start < line.len())
+        else {
+            return Line::from(line);
+        };
+        let end = start + word.text.len();
+        Line::from(vec![
+            Span::raw(line.get(..start).unwrap_or_default()),
+            Span::styled(
+                line.get(start..end).unwrap_or_default(),
+                Style::default().fg(theme.on_accent).bg(theme.accent),
+            ),
+            Span::raw(line.get(end..).unwrap_or_default()),
+        ])
+    });
+    Text::from(lines.collect::<Vec<_>>())
+}
+
+/// 原文の難しい用語を 1 行ずつ解説する。取得前や失敗したときは案内を出す
+fn render_glossary(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let lines: Vec<Line> = match app.glossary.entries(&app.original_text) {
+        None if app.has_training_started() => vec![Line::from(tr(locale, "用語を調べています…"))],
+        None => Vec::new(),
+        Some([]) => vec![Line::from(tr(
+            locale,
+            "用語の解説を取得できませんでした。g を 2 回押すと再取得します。",
+        ))],
+        Some(entries) => entries
+            .iter()
+            .map(|entry| {
+                Line::from(vec![
+                    Span::styled(
+                        entry.term.clone(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!(": {}", entry.gloss)),
+                ])
+            })
+            .collect(),
+    };
+    let block = Block::default()
+        .title(tr(locale, "用語 (g: 表示切り替え)"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.muted));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// 統合要約モードの 2 つの資料を上下に並べ、同じ位置までスクロールする
+fn render_synthesis_sources(
+    app: &App,
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    sources: &[String],
+) {
+    let theme = app.theme();
+    let source_areas =
+        Layout::vertical([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).split(area);
+    for (index, ((label, source), source_area)) in synthesis::SOURCE_LABELS
+        .iter()
+        .zip(sources)
+        .zip(source_areas.iter())
+        .enumerate()
+    {
+        let title = if index == 0 {
+            format!("{title} | {label}")
+        } else {
+            (*label).to_string()
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.highlight));
+        let paragraph = Paragraph::new(source.as_str())
+            .wrap(Wrap { trim: false })
+            .scroll((app.original_text_scroll, 0))
+            .block(block);
+        frame.render_widget(paragraph, *source_area);
+    }
+}
+
 fn render_summary_input(app: &mut App, frame: &mut Frame, area: Rect) {
-    let title = "あなたの要約 (i:入力モード Esc:通常モード Ctrl+S:送信)";
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let writer = match (app.hot_seat.as_ref(), app.guided.as_ref()) {
+        (Some(hot_seat), _) => trf(locale, "{} さん", &[&hot_seat.current_player()]),
+        (None, Some(guided)) => match guided.current_paragraph() {
+            Some(index) => trf(
+                locale,
+                "段落 {}/{} ",
+                &[&(index + 1), &guided.paragraphs.len()],
+            ),
+            None => tr(locale, "全体").to_string(),
+        },
+        (None, None) => tr(locale, "あなた").to_string(),
+    };
+    let input_mode = if app.kana_input.enabled { " [あ]" } else { "" };
+    let title = trf(
+        locale,
+        "{}の要約{} (i:入力モード Esc:通常モード Ctrl+S:送信 Ctrl+J:かな入力)",
+        &[&writer, &input_mode],
+    );
 
     clamp_textarea_scroll(&mut app.text_area_state);
 
     let border_style = if app.text_area_state.focus.get() {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(theme.accent)
     } else {
-        Style::default().fg(Color::Blue)
+        Style::default().fg(theme.info)
     };
 
-    let block = Block::default()
+    let mut block = Block::default()
         .title(title)
         .borders(Borders::ALL)
         .border_style(border_style);
+    if let Some(target) = app.summary_length_target() {
+        let count = summary_length::count_chars(&app.text_area_state.value());
+        let color = match target.fit(count) {
+            LengthFit::Under => theme.text,
+            LengthFit::Within => theme.success,
+            LengthFit::Over => theme.danger,
+        };
+        let counter = trf(
+            locale,
+            " 現在 {} / 目標 {}±{} 文字 ",
+            &[&count, &target.chars, &target.tolerance],
+        );
+        block = block.title(Line::styled(counter, Style::default().fg(color)).right_aligned());
+    }
 
     let textarea = TextArea::new()
         .block(block)
@@ -120,6 +444,118 @@ fn render_summary_input(app: &mut App, frame: &mut Frame, area: Rect) {
         .style(Style::default());
 
     frame.render_stateful_widget(textarea, area, &mut app.text_area_state);
+
+    if app.kana_input.is_composing() {
+        render_kana_preedit(app, frame, area);
+    }
+}
+
+/// 要約欄の上に、評価に送らないメモ欄を表示する
+fn render_scratchpad(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    clamp_textarea_scroll(&mut app.scratchpad.text_area);
+    let border_style = if app.scratchpad.is_editing() {
+        Style::default().fg(theme.accent)
+    } else {
+        Style::default().fg(theme.muted)
+    };
+    let block = Block::default()
+        .title(tr(
+            app.config.locale,
+            "メモ (s:入力 Esc:通常モード S:隠す) 評価には送られません",
+        ))
+        .borders(Borders::ALL)
+        .border_style(border_style);
+    let textarea = TextArea::new()
+        .block(block)
+        .text_wrap(TextWrap::Word(TEXT_WRAP_MARGIN))
+        .style(Style::default());
+    frame.render_stateful_widget(textarea, area, &mut app.scratchpad.text_area);
+}
+
+/// 要約欄の代わりに、4 択問題の問いと選択肢を表示する
+fn render_quiz(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let Some(session) = app.quiz.as_ref() else {
+        return;
+    };
+    let locale = app.config.locale;
+    let total = session.questions.len();
+    let title = if session.is_answering() {
+        trf(
+            locale,
+            "4択問題 {}/{} (↑/↓ or j/k: 選択 Enter: 解答)",
+            &[&(session.current + 1), &total],
+        )
+    } else {
+        tr(locale, "4択問題").to_string()
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.info));
+    let mut lines = Vec::new();
+    if let Some(question) = session.current_question() {
+        lines.push(Line::from(question.question.clone()).bold());
+        lines.push(Line::from(""));
+        for (index, choice) in question.choices.iter().enumerate() {
+            let text = format!("{} {}", quiz::choice_label(index), choice);
+            lines.push(if index == session.selected {
+                Line::from(format!("▶ {text}")).style(Style::default().fg(theme.accent).bold())
+            } else {
+                Line::from(format!("  {text}"))
+            });
+        }
+    } else if total > 0 {
+        lines.push(Line::from(trf(
+            locale,
+            "{}/{} 問正解 ('e' で結果を表示)",
+            &[&session.correct_count(), &total],
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(block),
+        area,
+    );
+}
+
+/// かな入力で変換中の文字列と候補を、要約欄の下端に重ねて表示する
+fn render_kana_preedit(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let height = 3;
+    if area.height < height + 2 || area.width < 4 {
+        return;
+    }
+    let preedit_area = Rect {
+        x: area.x + 1,
+        y: area.y + area.height - height - 1,
+        width: area.width - 2,
+        height,
+    };
+    let mut spans = vec![Span::styled(
+        app.kana_input.preedit(),
+        Style::default().add_modifier(Modifier::UNDERLINED),
+    )];
+    for (index, candidate) in app.kana_input.candidates.iter().enumerate() {
+        let style = if index == app.kana_input.selected {
+            Style::default().fg(theme.on_accent).bg(theme.accent)
+        } else {
+            Style::default()
+        };
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(format!("{}.{candidate}", index + 1), style));
+    }
+    let block = Block::default()
+        .title(tr(
+            app.config.locale,
+            "変換 (Space: 次の候補 Enter: 確定 Esc: 取消)",
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.success));
+    frame.render_widget(Clear, preedit_area);
+    frame.render_widget(Paragraph::new(Line::from(spans)).block(block), preedit_area);
 }
 
 fn clamp_textarea_scroll(state: &mut TextAreaState) {
@@ -184,46 +620,731 @@ fn render_evaluation_overlay(app: &App, frame: &mut Frame) {
         frame.render_widget(Clear, right);
     }
 
-    frame.render_widget(Clear, overlay_area);
-
-    let black_background = Paragraph::new("").style(Style::default().bg(Color::Black));
-    frame.render_widget(black_background, overlay_area);
+    render_evaluation_panel(
+        app,
+        frame,
+        overlay_area,
+        " 評価結果 (e: 閉じる, 1-4 or h/l: タブ, Shift+↑/↓ or Shift+j/k: スクロール, i: 改善点, n: 次の問題, c: チャレンジ書き出し, x: 記録を保存, y: コピー, a: 異議, t: コーチ) ",
+    );
+}
 
-    let border_color = if app.evaluation_passed {
-        Color::Green
+/// 要約欄の下の評価結果の欄。評価が届くまでは案内だけを表示し、`e` で操作する状態を切り替える
+fn render_result_pane(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let locale = app.config.locale;
+    if app.evaluation_text.is_empty() {
+        let message = if app.is_evaluating() {
+            STATUS_EVALUATING
+        } else {
+            "要約を提出すると、ここに評価結果が表示されます。"
+        };
+        let placeholder = Paragraph::new(tr(locale, message))
+            .wrap(Wrap { trim: false })
+            .style(Style::default().fg(theme.muted))
+            .block(
+                Block::default()
+                    .title(tr(locale, " 評価結果 "))
+                    .borders(Borders::ALL),
+            );
+        frame.render_widget(placeholder, area);
+        return;
+    }
+    let title = if app.show_evaluation_overlay {
+        " 評価結果 (e: 操作をやめる, 1-4: タブ, n: 次の問題) "
     } else {
-        Color::Red
+        " 評価結果 (e: 操作する) "
     };
+    render_evaluation_panel(app, frame, area, title);
+}
+
+/// 評価結果のタブ・判定の帯・本文を `area` に描画する。オーバーレイと評価結果の欄で共通
+fn render_evaluation_panel(app: &App, frame: &mut Frame, area: Rect, title: &str) {
+    let theme = app.theme();
+    frame.render_widget(Clear, area);
+
+    let black_background = Paragraph::new("").style(Style::default().bg(theme.background));
+    frame.render_widget(black_background, area);
 
+    let border_color = verdict_color(app.verdict, theme);
+
+    let locale = app.config.locale;
     let block = Block::default()
-        .title(" 評価結果 (e: 閉じる, Shift+↑/↓ or Shift+j/k: スクロール, n: 次の問題) ")
+        .title(tr(locale, title))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
+
+    let inner_area = block.inner(area);
+
+    frame.render_widget(block, area);
 
-    let inner_area = block.inner(overlay_area);
+    let [tab_area, banner_area, content_area] = Layout::vertical([
+        Constraint::Length(RESULT_TAB_BAR_HEIGHT),
+        Constraint::Length(VERDICT_BANNER_HEIGHT),
+        Constraint::Min(0),
+    ])
+    .areas(inner_area);
 
-    frame.render_widget(block, overlay_area);
+    let tabs = Tabs::new(
+        ResultTab::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| format!("{}:{}", index + 1, tr(locale, tab.label()))),
+    )
+    .select(app.result_tabs.active.index())
+    .style(Style::default().fg(theme.dim))
+    .highlight_style(
+        Style::default()
+            .fg(border_color)
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+    );
+    frame.render_widget(tabs, tab_area);
+
+    // モデルの文章に関係なく、合否をひと目でわかるように色付きの帯で示す
+    let banner = Paragraph::new(format!(" {} ", tr(locale, app.verdict.label())))
+        .alignment(Alignment::Center)
+        .style(
+            Style::default()
+                .bg(border_color)
+                .fg(theme.on_accent)
+                .add_modifier(Modifier::BOLD),
+        );
+    frame.render_widget(banner, banner_area);
 
-    let paragraph = Paragraph::new(app.evaluation_text.as_str())
+    let paragraph = Paragraph::new(result_tab_content(app))
         .wrap(Wrap { trim: false })
-        .scroll((app.evaluation_overlay_scroll, 0))
-        .style(Style::default().bg(Color::Black).fg(Color::White));
+        .scroll((app.result_tabs.scroll(), 0))
+        .style(Style::default().bg(theme.background).fg(theme.text));
+
+    frame.render_widget(paragraph, content_area);
+}
+
+fn result_tab_content(app: &App) -> Text<'_> {
+    if app.result_tabs.active == ResultTab::ModelAnswer {
+        let segments = app.result_tabs.model_answer_diff();
+        if !segments.is_empty() {
+            return model_answer_diff_text(&app.result_tabs.model_answer, &segments, app.theme());
+        }
+    }
+    Text::from(app.result_tab_text())
+}
+
+/// 模範解答の後に、要約との差分を色分けして並べる。行の並びは `ResultTabs::text` と同じにする
+fn model_answer_diff_text<'a>(
+    model_answer: &'a str,
+    segments: &[DiffSegment],
+    theme: &Theme,
+) -> Text<'a> {
+    let mut lines: Vec<Line> = model_answer.lines().map(Line::from).collect();
+    lines.push(Line::default());
+    lines.push(Line::styled(
+        result_tabs::DIFF_HEADING,
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    let mut spans = Vec::new();
+    for segment in segments {
+        let style = match segment.kind {
+            DiffKind::Same => Style::default(),
+            DiffKind::Added => Style::default().fg(theme.success),
+            DiffKind::Missing => Style::default()
+                .fg(theme.danger)
+                .add_modifier(Modifier::UNDERLINED),
+        };
+        for (index, part) in segment.text.split('\n').enumerate() {
+            if index > 0 {
+                lines.push(Line::from(std::mem::take(&mut spans)));
+            }
+            if !part.is_empty() {
+                spans.push(Span::styled(part.to_string(), style));
+            }
+        }
+    }
+    lines.push(Line::from(spans));
+    Text::from(lines)
+}
+
+/// 要約欄 (4 択問題では選択肢) と、表示中ならその上のメモ欄を描画し、要約欄の領域を返す
+fn render_answer_pane(app: &mut App, frame: &mut Frame, area: Rect) -> Rect {
+    let summary_area = if app.scratchpad.visible {
+        let [scratchpad_area, rest_area] =
+            Layout::vertical([Constraint::Percentage(35), Constraint::Min(0)]).areas(area);
+        render_scratchpad(app, frame, scratchpad_area);
+        rest_area
+    } else {
+        area
+    };
+    if app.quiz.is_some() {
+        render_quiz(app, frame, summary_area);
+    } else {
+        render_summary_input(app, frame, summary_area);
+    }
+    summary_area
+}
+
+/// チュートリアルの案内を `anchor` の下端に吹き出しとして重ねる
+fn render_tutorial_callout(app: &App, frame: &mut Frame, step: TutorialStep, anchor: Rect) {
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let key = match step {
+        TutorialStep::Read => app.keys.edit,
+        TutorialStep::Write | TutorialStep::Submit => app.keys.submit,
+        TutorialStep::Review => app.keys.next,
+    };
+    let message = trf(locale, step.message(), &[&key.label()]);
+    let title = trf(
+        locale,
+        " チュートリアル ({}/{}) ",
+        &[&step.number(), &TutorialStep::COUNT],
+    );
+    let width = u16::try_from(
+        Line::from(message.as_str())
+            .width()
+            .max(Line::from(title.as_str()).width()),
+    )
+    .unwrap_or(u16::MAX)
+    .saturating_add(4)
+    .min(anchor.width);
+    let height = 3.min(anchor.height);
+    let area = Rect {
+        x: anchor.x + anchor.width.saturating_sub(width) / 2,
+        y: anchor.y + anchor.height.saturating_sub(height + 1),
+        width,
+        height,
+    };
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight_soft).bold())
+        .style(Style::default().bg(theme.background));
+    let paragraph = Paragraph::new(format!(" {message}"))
+        .block(block)
+        .style(Style::default().bg(theme.background).fg(theme.text));
+    frame.render_widget(paragraph, area);
+}
+
+fn render_prompt_popup(
+    frame: &mut Frame,
+    title: &str,
+    lines: &[&str],
+    color: Color,
+    theme: &Theme,
+) {
+    let area = frame.area();
+    let width = lines
+        .iter()
+        .map(|line| Line::from(*line).width())
+        .max()
+        .unwrap_or(0)
+        .max(Line::from(title).width());
+    let width = u16::try_from(width)
+        .unwrap_or(u16::MAX)
+        .saturating_add(4)
+        .min(area.width);
+    let height = u16::try_from(lines.len())
+        .unwrap_or(u16::MAX)
+        .saturating_add(2)
+        .min(area.height);
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(title.to_string())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .style(Style::default().bg(theme.background));
+    let text: Vec<Line> = lines
+        .iter()
+        .map(|line| Line::from(format!(" {line}")))
+        .collect();
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .style(Style::default().bg(theme.background).fg(theme.text));
+    frame.render_widget(paragraph, popup_area);
+}
+
+/// 直近のエラーを 1 行で表示する。原文欄とは別に表示し、原文は書き換えない
+fn render_error_line(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let Some(notice) = app.last_error.as_ref() else {
+        frame.render_widget(Paragraph::new(""), area);
+        return;
+    };
+    let line = format!(
+        " ⚠ {} {} ({})",
+        notice.summary(),
+        notice.detail,
+        notice.category.title()
+    );
+    let paragraph = Paragraph::new(line).style(Style::default().fg(theme.text).bg(theme.danger));
+    frame.render_widget(paragraph, area);
+}
+
+/// 通常画面の上に重ねるダイアログ
+fn render_popup(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let locale = app.config.locale;
+    match app.popup {
+        Some(Popup::SlowEvaluation) => render_prompt_popup(
+            frame,
+            tr(locale, " 評価に時間がかかっています "),
+            &[
+                tr(locale, "w: このまま待つ"),
+                tr(locale, "c: キャンセル"),
+                tr(locale, "s: 自己採点に切り替える"),
+            ],
+            theme.highlight,
+            theme,
+        ),
+        Some(Popup::SelfGrade) => render_prompt_popup(
+            frame,
+            tr(locale, " 自己採点 "),
+            &[
+                tr(locale, "原文と見比べて、要約の出来を判定してください。"),
+                tr(locale, "y: 合格"),
+                tr(locale, "n: 不合格"),
+                tr(locale, "Esc: 戻る"),
+            ],
+            theme.accent,
+            theme,
+        ),
+        Some(Popup::Break { .. }) => {
+            let remaining = app.break_remaining().unwrap_or_default();
+            let countdown = trf(locale, "残り {} 秒", &[&(remaining.as_secs() + 1)]);
+            render_prompt_popup(
+                frame,
+                tr(locale, " ひと休み "),
+                &[
+                    tr(
+                        locale,
+                        "画面から目を離し、遠くを眺めて肩の力を抜きましょう。",
+                    ),
+                    &countdown,
+                    tr(locale, "Esc: スキップ"),
+                ],
+                theme.success,
+                theme,
+            );
+        }
+        Some(Popup::WordLookup) => render_word_lookup_popup(app, frame),
+        Some(Popup::RankUp { rank }) => render_rank_up_popup(app, frame, rank),
+        Some(Popup::Error) => {
+            if let Some(notice) = app.last_error.as_ref() {
+                render_error_popup(
+                    frame,
+                    notice,
+                    tr(locale, "Enter/Esc: 閉じる"),
+                    locale,
+                    theme,
+                );
+            }
+        }
+        Some(Popup::EvaluationFailed) => {
+            if let Some(notice) = app.last_error.as_ref() {
+                let keys = tr(
+                    locale,
+                    "自己採点で記録する (y: 合格 / n: 不合格) / Esc: 閉じて再送信を待つ",
+                );
+                render_error_popup(frame, notice, keys, locale, theme);
+            }
+        }
+        // 異議申し立ては入力欄の状態を書き換えるので `render_appeal_popup` で描く
+        Some(Popup::Appeal | Popup::KeywordPrediction) | None => {}
+    }
+}
 
-    frame.render_widget(paragraph, inner_area);
+/// 上がった段位と、今の成績、次の段位の条件
+fn render_rank_up_popup(app: &App, frame: &mut Frame, rank: usize) {
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let (passes, average) = app.stats.rank_progress();
+    let average = average.map_or_else(|| "-".to_string(), |average| format!("{average:.2}"));
+    let next = match ranks::next_rank(rank) {
+        Some(next) => trf(
+            locale,
+            "次の{}: 合格 {} 回・平均 {} 点",
+            &[&next.name, &next.passes, &format!("{:.1}", next.average)],
+        ),
+        None => tr(locale, "最高の段位です").to_string(),
+    };
+    render_prompt_popup(
+        frame,
+        tr(locale, " 🎉 昇段 "),
+        &[
+            &trf(locale, "{} に昇段しました！", &[&ranks::rank_at(rank).name]),
+            &trf(locale, "合格 {} 回 / 平均 {} 点", &[&passes, &average]),
+            &next,
+            tr(locale, "Enter/Esc: 閉じる"),
+        ],
+        theme.success,
+        theme,
+    );
+}
+
+/// 選んだ語の読みと意味。調べ終わったら単語帳に追加できる
+fn render_word_lookup_popup(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let (word, lines) = match &app.word_lookup {
+        Some(LookupState::Pending { word, .. }) => {
+            (word, vec![tr(locale, "調べています…").to_string()])
+        }
+        Some(LookupState::Failed { word }) => (
+            word,
+            vec![tr(locale, "意味を調べられませんでした。").to_string()],
+        ),
+        Some(LookupState::Found(entry)) => (
+            &entry.word,
+            vec![
+                trf(locale, "読み: {}", &[&entry.reading]),
+                trf(locale, "意味: {}", &[&entry.meaning]),
+                String::new(),
+                tr(locale, "a: 単語帳に追加").to_string(),
+            ],
+        ),
+        None => return,
+    };
+    let mut lines = lines;
+    lines.push(tr(locale, "Enter/Esc: 閉じる").to_string());
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    render_prompt_popup(frame, &format!(" {word} "), &lines, theme.accent, theme);
+}
+
+/// 異議申し立てのやり取りと入力欄。長くなったら新しいやり取りが見えるよう下端に合わせる
+fn render_appeal_popup(app: &mut App, frame: &mut Frame) {
+    if app.popup != Some(Popup::Appeal) {
+        return;
+    }
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let lines = appeal_lines(app, locale, theme);
+    let Some(appeal) = app.appeal.as_mut() else {
+        return;
+    };
+
+    let area = frame.area();
+    let width = area
+        .width
+        .saturating_mul(ERROR_POPUP_WIDTH_PERCENT)
+        .saturating_div(100)
+        .max(MIN_OVERLAY_WIDTH)
+        .min(area.width);
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+    let text_height =
+        u16::try_from(paragraph.line_count(width.saturating_sub(2))).unwrap_or(u16::MAX);
+    let height = text_height
+        .saturating_add(APPEAL_INPUT_HEIGHT + 2)
+        .min(area.height);
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .title(tr(locale, " 評価への異議 "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .style(Style::default().bg(theme.background).fg(theme.text));
+    let inner = block.inner(popup_area);
+    let [text_area, input_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(APPEAL_INPUT_HEIGHT)])
+            .areas(inner);
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(block, popup_area);
+    let scroll = text_height.saturating_sub(text_area.height);
+    frame.render_widget(paragraph.scroll((scroll, 0)), text_area);
+    let input = TextInput::new()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.title)),
+        )
+        .style(Style::default())
+        .focus_style(Style::default());
+    frame.render_stateful_widget(input, input_area, &mut appeal.input);
+    if appeal.pending_argument().is_none()
+        && let Some((cx, cy)) = appeal.input.screen_cursor()
+    {
+        frame.set_cursor_position((cx, cy));
+    }
+}
+
+/// 冒頭の段落を見せて、本文のキーワードを予想させるダイアログ
+fn render_keyword_prediction_popup(app: &mut App, frame: &mut Frame) {
+    if app.popup != Some(Popup::KeywordPrediction) {
+        return;
+    }
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let Some(prediction) = app.keyword_prediction.as_mut() else {
+        return;
+    };
+    let lines = vec![
+        Line::from(trf(
+            locale,
+            "冒頭を読んで、本文のキーワードを {} つ予想してください（読点か空白で区切ります）。",
+            &[&keyword_prediction::KEYWORD_COUNT],
+        )),
+        Line::default(),
+        Line::styled(prediction.preview.clone(), Style::default().fg(theme.text)),
+        Line::default(),
+        Line::styled(
+            tr(locale, "Enter: 予想して本文へ, Esc: 予想せずに本文へ"),
+            Style::default().fg(theme.muted),
+        ),
+    ];
+
+    let area = frame.area();
+    let width = area
+        .width
+        .saturating_mul(ERROR_POPUP_WIDTH_PERCENT)
+        .saturating_div(100)
+        .max(MIN_OVERLAY_WIDTH)
+        .min(area.width);
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+    let text_height =
+        u16::try_from(paragraph.line_count(width.saturating_sub(2))).unwrap_or(u16::MAX);
+    let height = text_height
+        .saturating_add(APPEAL_INPUT_HEIGHT + 2)
+        .min(area.height);
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .title(tr(locale, " キーワード予想 "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .style(Style::default().bg(theme.background).fg(theme.text));
+    let inner = block.inner(popup_area);
+    let [text_area, input_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(APPEAL_INPUT_HEIGHT)])
+            .areas(inner);
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(block, popup_area);
+    frame.render_widget(paragraph, text_area);
+    let input = TextInput::new()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.title)),
+        )
+        .style(Style::default())
+        .focus_style(Style::default());
+    frame.render_stateful_widget(input, input_area, &mut prediction.input);
+    if let Some((cx, cy)) = prediction.input.screen_cursor() {
+        frame.set_cursor_position((cx, cy));
+    }
+}
+
+/// 異議申し立てのダイアログの本文
+fn appeal_lines(app: &App, locale: LabelLanguage, theme: &Theme) -> Vec<Line<'static>> {
+    let Some(appeal) = app.appeal.as_ref() else {
+        return Vec::new();
+    };
+    let recorded = app.last_session.as_ref().map(|record| record.passed);
+    let overridden = app
+        .stats
+        .results
+        .last()
+        .is_some_and(|result| result.overridden_from.is_some());
+    let verdict_label = |passed: bool| tr(locale, if passed { "合格" } else { "不合格" });
+    let label_style = Style::default().fg(theme.highlight).bold();
+    let mut lines = vec![Line::from(tr(
+        locale,
+        "評価に納得できない理由を書いて Enter で送ると、評価者が考え直して答えます。",
+    ))];
+    for exchange in &app.result_tabs.conversation {
+        lines.push(Line::default());
+        lines.push(Line::from(vec![
+            Span::styled(tr(locale, "あなた: "), label_style),
+            Span::raw(exchange.argument.clone()),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled(tr(locale, "評価者: "), label_style),
+            Span::raw(exchange.reply.clone()),
+        ]));
+        lines.push(Line::styled(
+            trf(
+                locale,
+                "→ 考え直した結果: {}",
+                &[&verdict_label(exchange.passed)],
+            ),
+            Style::default().fg(verdict_color(Verdict::from_passed(exchange.passed), theme)),
+        ));
+    }
+    lines.push(Line::default());
+    match &appeal.status {
+        AppealStatus::Pending(_) => lines.push(Line::styled(
+            tr(locale, "評価者に問い合わせています…"),
+            Style::default().fg(theme.muted),
+        )),
+        AppealStatus::Failed(error) => lines.push(Line::styled(
+            trf(locale, "返事を受け取れませんでした: {}", &[error]),
+            Style::default().fg(theme.danger),
+        )),
+        AppealStatus::Writing => {}
+    }
+    if let Some(passed) = recorded {
+        let note = if overridden {
+            trf(
+                locale,
+                "記録: {} (異議により変更)",
+                &[&verdict_label(passed)],
+            )
+        } else {
+            trf(locale, "記録: {}", &[&verdict_label(passed)])
+        };
+        lines.push(Line::from(note));
+    }
+    let keys = if app.result_tabs.conversation.is_empty() {
+        tr(locale, "Enter: 送る / Esc: 閉じる")
+    } else {
+        tr(
+            locale,
+            "Enter: 送る / Tab: 記録した合否を逆にする / Esc: 閉じる",
+        )
+    };
+    lines.push(Line::styled(keys, label_style));
+    lines
+}
+
+/// 長いエラー内容でも収まるよう、幅を固定して折り返す
+/// `keys` はダイアログで使えるキーの案内
+fn render_error_popup(
+    frame: &mut Frame,
+    notice: &ErrorNotice,
+    keys: &str,
+    locale: LabelLanguage,
+    theme: &Theme,
+) {
+    let area = frame.area();
+    let width = area
+        .width
+        .saturating_mul(ERROR_POPUP_WIDTH_PERCENT)
+        .saturating_div(100)
+        .max(MIN_OVERLAY_WIDTH)
+        .min(area.width);
+    let label_style = Style::default().fg(theme.highlight);
+    let lines = vec![
+        Line::from(notice.summary()),
+        Line::from(notice.detail.clone()),
+        Line::default(),
+        Line::from(Span::styled(tr(locale, "対処方法"), label_style)),
+        Line::from(notice.category.remediation()),
+        Line::default(),
+        Line::from(Span::styled(keys.to_string(), label_style)),
+    ];
+    let block = Block::default()
+        .title(format!(" {} ", notice.category.title()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.danger))
+        .style(Style::default().bg(theme.background));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .style(Style::default().bg(theme.background).fg(theme.text));
+    let height = u16::try_from(paragraph.line_count(width))
+        .unwrap_or(u16::MAX)
+        .min(area.height);
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn verdict_color(verdict: Verdict, theme: &Theme) -> Color {
+    match verdict {
+        Verdict::Passed => theme.success,
+        Verdict::Failed => theme.danger,
+        Verdict::Undetermined => theme.highlight,
+    }
 }
 
 fn render_status_bar(app: &App, frame: &mut Frame, area: Rect) {
+    let locale = app.config.locale;
     let block = Block::default().borders(Borders::TOP);
-    let status_message = &app.status_message;
-    let status_text = format!(" {status_message} | r: レポート | h: ヘルプ | q: 終了 ");
+    let retrying = app.retry_notice().map(|notice| {
+        trf(
+            locale,
+            "再送しています ({}/{}): {}。{} 秒後に送ります",
+            &[
+                &notice.attempt,
+                &notice.max_attempts,
+                &notice.reason,
+                &notice.wait.as_secs_f32().ceil(),
+            ],
+        )
+    });
+    let message = retrying
+        .as_deref()
+        .unwrap_or_else(|| tr(locale, &app.status_message));
+    let status_message = match (app.spinner_frame(), app.api_wait_elapsed()) {
+        (Some(spinner), Some(elapsed)) => format!(
+            "{spinner} {message} {}{}",
+            trf(locale, "({} 秒経過)", &[&elapsed.as_secs()]),
+            if app.is_waiting_side_request() {
+                tr(locale, " (Esc: 取り消し)")
+            } else {
+                ""
+            }
+        ),
+        _ => message.to_string(),
+    };
+    let goal_progress = app
+        .daily_goal_progress()
+        .map(|(done, goal)| {
+            let mark = if done >= goal { "✔" } else { "" };
+            format!("🎯 {done}/{goal}{mark} | ")
+        })
+        .unwrap_or_default();
+    let timer = match (app.time_remaining(), app.elapsed_since_shown()) {
+        _ if app.view_mode != ViewMode::Normal => String::new(),
+        (_, Some(elapsed)) if app.is_paused() => format!("⏸ {} | ", format_clock(elapsed)),
+        (Some(remaining), _) => format!(
+            "⏳ {} | ",
+            trf(locale, "残り {}", &[&format_clock(remaining)])
+        ),
+        (None, Some(elapsed)) => format!("⏱ {} | ", format_clock(elapsed)),
+        (None, None) => String::new(),
+    };
+    let status_text = format!(
+        " {status_message} | {timer}{goal_progress}{} ",
+        trf(
+            locale,
+            "{}: レポート | {}: ヘルプ | {}: 終了",
+            &[
+                &app.keys.report.label(),
+                &app.keys.help.label(),
+                &app.keys.quit.label()
+            ],
+        )
+    );
     let paragraph = Paragraph::new(status_text)
         .alignment(Alignment::Right)
         .block(block);
     frame.render_widget(paragraph, area);
 }
 
-fn render_report_view(app: &App, frame: &mut Frame) {
+/// `m:ss` 形式。負の時間は 0 とみなす
+fn format_clock(duration: chrono::Duration) -> String {
+    let secs = duration.num_seconds().max(0);
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+fn render_report_view(app: &mut App, frame: &mut Frame) {
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -235,12 +1356,28 @@ fn render_report_view(app: &App, frame: &mut Frame) {
     let [header_area, body_area, status_area] = layout.as_ref() else {
         return;
     };
-    render_header(frame, *header_area);
-    reports::render_unified_report(frame, *body_area, &app.stats);
+    render_header(app, frame, *header_area);
+    let ctx = reports::ReportContext {
+        stats: &app.stats,
+        report: &app.config.report,
+        locale: app.config.locale,
+        scale: app.score_scale,
+        theme: app.theme(),
+    };
+    if app.view_mode == ViewMode::BadgeGallery {
+        let mut scroll = app.badge_gallery_scroll;
+        reports::render_badge_gallery(frame, *body_area, &mut scroll, &ctx);
+        app.badge_gallery_scroll = scroll;
+    } else {
+        let mut scroll = app.report_scroll;
+        reports::render_report(frame, *body_area, app.report_tab, &mut scroll, &ctx);
+        app.report_scroll = scroll;
+    }
     render_status_bar(app, frame, *status_area);
 }
 
 fn render_menu_view(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -252,7 +1389,7 @@ fn render_menu_view(app: &App, frame: &mut Frame) {
     let [header_area, body_area, status_area] = layout.as_ref() else {
         return;
     };
-    frame.render_widget(Paragraph::new(""), *header_area);
+    render_error_line(app, frame, *header_area);
 
     let body_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -265,16 +1402,17 @@ fn render_menu_view(app: &App, frame: &mut Frame) {
             Constraint::Min(0),
         ])
         .split(*body_area);
-    let [logo_area, _, title_area, _, menu_area, _] = body_layout.as_ref() else {
+    let [logo_area, _, title_area, _, menu_area, info_area] = body_layout.as_ref() else {
         return;
     };
 
-    let logo = Paragraph::new(build_menu_title_lines())
+    let logo = Paragraph::new(build_menu_title_lines(theme))
         .alignment(Alignment::Center)
-        .style(Style::default().fg(MENU_TITLE_COLOR));
+        .style(Style::default().fg(theme.title));
     frame.render_widget(logo, *logo_area);
 
-    let title = Paragraph::new(" yomitore: 読解力トレーニング ")
+    let locale = app.config.locale;
+    let title = Paragraph::new(format!(" {} ", tr(locale, "yomitore: 読解力トレーニング")))
         .style(Style::new().bold())
         .alignment(Alignment::Center);
     frame.render_widget(title, *title_area);
@@ -292,12 +1430,12 @@ fn render_menu_view(app: &App, frame: &mut Frame) {
     };
 
     let block = Block::default()
-        .title("文字数を選択してください")
+        .title(tr(locale, "文字数を選択してください"))
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(theme.accent));
 
-    let menu_lines = build_menu_lines(app.selected_menu_item);
+    let menu_lines = build_menu_lines(app.selected_menu_item, locale, theme);
 
     let paragraph = Paragraph::new(menu_lines)
         .block(block)
@@ -305,10 +1443,915 @@ fn render_menu_view(app: &App, frame: &mut Frame) {
         .style(Style::default());
 
     frame.render_widget(paragraph, *menu_area);
+
+    let info_lines = build_menu_info_lines(app);
+    let info = Paragraph::new(info_lines)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.muted));
+    frame.render_widget(info, *info_area);
+
     render_status_bar(app, frame, *status_area);
+
+    if app.recovered_draft.is_some() {
+        render_draft_recovery_overlay(app, frame);
+    } else if app.flashback.is_some() {
+        render_flashback_overlay(app, frame);
+    }
+}
+
+fn daily_challenge_line(app: &App) -> String {
+    let locale = app.config.locale;
+    let today = chrono::Local::now().date_naive();
+    let challenge = DailyChallenge::for_date(today);
+    let mut line = trf(locale, "今日のお題: {} (o: 挑戦)", &[&challenge.describe()]);
+    if app.stats.has_done_daily_challenge(today) {
+        line.push(' ');
+        line.push_str(tr(locale, "挑戦済み"));
+    }
+    line
+}
+
+/// 接続先のプロファイルと使っているモデル。オフラインならその旨と、API キーを設定し直せること
+fn connection_lines(app: &App) -> Vec<String> {
+    let locale = app.config.locale;
+    let mut lines = Vec::new();
+    if app.profiles.len() > 1
+        && let Some(name) = app.active_profile_name()
+    {
+        lines.push(trf(locale, "プロファイル: {} (P: 切り替え)", &[&name]));
+    }
+    if let Some(model) = app.current_model() {
+        lines.push(trf(locale, "モデル: {} (m: 変更)", &[&model]));
+    } else if let Some(corpus) = &app.offline {
+        let label = if app.config.provider == Provider::Ollama {
+            "オフライン: 手元の {} 件の文章から出題し、自己採点します"
+        } else {
+            "オフライン: 手元の {} 件の文章から出題し、自己採点します (a: API キーを設定)"
+        };
+        lines.push(trf(locale, label, &[&corpus.len()]));
+    }
+    lines
+}
+
+/// メニューの下に表示する難易度・レベル・復習などの案内
+/// メニューに出す読解レベルと、次の昇級試験までの合格回数
+fn level_line(app: &App) -> String {
+    let locale = app.config.locale;
+    let level = levels::describe_level(app.stats.reading_level);
+    if levels::is_top_level(app.stats.reading_level) {
+        trf(locale, "レベル: {} (l: レベル挑戦)", &[&level])
+    } else {
+        trf(
+            locale,
+            "レベル: {} (l: レベル挑戦 / 昇級試験まであと {} 回合格)",
+            &[
+                &level,
+                &levels::PROMOTION_INTERVAL.saturating_sub(app.stats.level_passes),
+            ],
+        )
+    }
+}
+
+fn build_menu_info_lines(app: &App) -> Vec<Line<'static>> {
+    let locale = app.config.locale;
+    let time_limit = format_clock(
+        chrono::Duration::from_std(app.config.timed_challenge.time_limit(app.character_count))
+            .unwrap_or_default(),
+    );
+    let mut info_lines = vec![
+        Line::from(trf(
+            locale,
+            "難易度: {} (d: 変更)",
+            &[&app.selected_difficulty.display_name()],
+        )),
+        Line::from(trf(
+            locale,
+            "要約の長さ: {} (g: 変更)",
+            &[&app.length_goal.label()],
+        )),
+        Line::from(trf(
+            locale,
+            "評価の厳しさ: {} (x: 変更)",
+            &[&tr(locale, app.strictness.label())],
+        )),
+        Line::from(level_line(app)),
+        Line::from(trf(
+            locale,
+            "制限時間付き: {} 文字を {} 以内 (t: 開始)",
+            &[&app.character_count, &time_limit],
+        )),
+        Line::from(trf(
+            locale,
+            "4択問題: {} 文字の本文と {} 問 (c: 開始)",
+            &[&app.character_count, &quiz::QUESTION_COUNT],
+        )),
+        Line::from(trf(
+            locale,
+            "模擬試験: {} 文字の {} 問、制限時間付き (e: 開始)",
+            &[
+                &exam::EXAM_LENGTHS.map(|count| count.to_string()).join("/"),
+                &exam::EXAM_LENGTHS.len(),
+            ],
+        )),
+        Line::from(trf(
+            locale,
+            "統合要約: 約 {} 文字の資料 2 つを 1 つに要約 (s: 開始)",
+            &[&(app.character_count / 2)],
+        )),
+        Line::from(trf(
+            locale,
+            "段階練習: {} 文字の文章を段落ごとに要約してからまとめる (u: 開始)",
+            &[&app.character_count],
+        )),
+        Line::from(daily_challenge_line(app)),
+    ];
+    info_lines.extend(connection_lines(app).into_iter().map(Line::from));
+    if let Some(weakness) = app.weakness_focus() {
+        info_lines.push(Line::from(trf(
+            locale,
+            "苦手対策: {} を重点的に出題します",
+            &[&weakness.label()],
+        )));
+    }
+    let due_reviews = app
+        .review_queue
+        .due_count(chrono::Local::now().date_naive());
+    if due_reviews > 0 {
+        info_lines.push(Line::from(trf(
+            locale,
+            "復習: {} 件 (v: 新しい文章の前に復習する)",
+            &[&due_reviews],
+        )));
+    }
+    if app.library.len() > 0 {
+        let bucket = MENU_OPTIONS
+            .get(app.selected_menu_item)
+            .copied()
+            .unwrap_or_default();
+        info_lines.push(Line::from(trf(
+            locale,
+            "ライブラリ: {} 文字前後 {} 件 / 全 {} 件 (b: ライブラリから出題)",
+            &[&bucket, &app.library.count_for(bucket), &app.library.len()],
+        )));
+    }
+    let pending_count = app.pending.len();
+    if pending_count > 0 {
+        info_lines.push(Line::from(trf(
+            locale,
+            "未使用テキスト: {} 件 (API を使わずに出題します)",
+            &[&pending_count],
+        )));
+    }
+    info_lines
+}
+
+fn render_flashback_overlay(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let Some(record) = app.flashback.as_ref() else {
+        return;
+    };
+    let overlay_area = app.calculate_overlay_area();
+    frame.render_widget(Clear, overlay_area);
+
+    let label = history::flashback_label(record, chrono::Local::now().date_naive());
+    let block = Block::default()
+        .title(trf(
+            locale,
+            " {} (Enter: 再挑戦, Esc: 閉じる) ",
+            &[&tr(locale, label)],
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.secondary))
+        .style(Style::default().bg(theme.background));
+
+    let lines = vec![
+        Line::from(trf(
+            locale,
+            "{} / {} 文字",
+            &[
+                &record.timestamp.format("%Y-%m-%d %H:%M"),
+                &record.character_count,
+            ],
+        )),
+        Line::from(trf(
+            locale,
+            "結果: {}",
+            &[&history::format_result(
+                record.passed,
+                record.evaluation.as_ref(),
+            )],
+        )),
+        Line::default(),
+        Line::from(Span::styled(
+            tr(locale, "あなたの要約"),
+            Style::default().fg(theme.accent).bold(),
+        )),
+        Line::from(record.summary.clone()),
+        Line::default(),
+        Line::from(Span::styled(
+            tr(locale, "原文"),
+            Style::default().fg(theme.highlight).bold(),
+        )),
+        Line::from(record.original_text.clone()),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .style(Style::default().bg(theme.background).fg(theme.text));
+    frame.render_widget(paragraph, overlay_area);
+}
+
+/// 前回のセッションで評価に出さなかった下書きを見せ、復元するか選んでもらう
+fn render_draft_recovery_overlay(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let Some(draft) = app.recovered_draft.as_ref() else {
+        return;
+    };
+    let overlay_area = app.calculate_overlay_area();
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .title(tr(
+            locale,
+            " 前回の書きかけの要約があります (Enter/y: 復元, Esc/n: 破棄) ",
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight))
+        .style(Style::default().bg(theme.background));
+
+    let lines = vec![
+        Line::from(format!(
+            "{} / {} 文字",
+            draft.saved_at.format("%Y-%m-%d %H:%M"),
+            draft.character_count
+        )),
+        Line::default(),
+        Line::from(Span::styled(
+            tr(locale, "あなたの要約"),
+            Style::default().fg(theme.accent).bold(),
+        )),
+        Line::from(draft.summary.clone()),
+        Line::default(),
+        Line::from(Span::styled(
+            tr(locale, "原文"),
+            Style::default().fg(theme.highlight).bold(),
+        )),
+        Line::from(draft.original_text.clone()),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .style(Style::default().bg(theme.background).fg(theme.text));
+    frame.render_widget(paragraph, overlay_area);
+}
+
+fn render_model_select_view(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let current = app.current_model();
+    let lines: Vec<Line> = if app.available_models.is_empty() {
+        vec![Line::from(tr(
+            app.config.locale,
+            "利用できるモデルがありません。",
+        ))]
+    } else {
+        app.available_models
+            .iter()
+            .enumerate()
+            .map(|(index, model)| {
+                build_model_line(
+                    model,
+                    index == app.selected_model_item,
+                    current == Some(model.as_str()),
+                    theme,
+                )
+            })
+            .collect()
+    };
+    render_select_list(
+        app,
+        frame,
+        "モデルを選択してください (Enter: 決定, Esc: 戻る)",
+        lines,
+        app.selected_model_item,
+    );
+}
+
+fn render_profile_select_view(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let lines = app
+        .profiles
+        .iter()
+        .enumerate()
+        .map(|(index, profile)| {
+            build_model_line(
+                &profile.describe(),
+                index == app.selected_profile_item,
+                index == app.active_profile,
+                theme,
+            )
+        })
+        .collect();
+    render_select_list(
+        app,
+        frame,
+        "接続先のプロファイルを選択してください (Enter: 接続, Esc: 戻る)",
+        lines,
+        app.selected_profile_item,
+    );
+}
+
+/// 選択中の行が見えるようにスクロールする一覧の画面
+fn render_select_list(
+    app: &App,
+    frame: &mut Frame,
+    title: &str,
+    lines: Vec<Line<'static>>,
+    selected: usize,
+) {
+    let theme = app.theme();
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+    let [header_area, body_area, status_area] = layout.as_ref() else {
+        return;
+    };
+    render_header(app, frame, *header_area);
+
+    let block = Block::default()
+        .title(tr(app.config.locale, title))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let visible_height = usize::from(body_area.height.saturating_sub(2));
+    let scroll = selected.saturating_sub(visible_height.saturating_sub(1));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0));
+    frame.render_widget(paragraph, *body_area);
+    render_status_bar(app, frame, *status_area);
+}
+
+fn render_api_key_setup_view(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme();
+    let locale = app.config.locale;
+    let layout = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(3),
+    ])
+    .split(frame.area());
+    let [header_area, body_area, status_area] = layout.as_ref() else {
+        return;
+    };
+    render_header(app, frame, *header_area);
+
+    let block = Block::default()
+        .title(tr(
+            locale,
+            "API キーの設定 (Enter: 確かめて保存, Esc: 戻る)",
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+    let inner = block.inner(*body_area);
+    frame.render_widget(block, *body_area);
+    let [intro_area, input_area, info_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Min(0),
+    ])
+    .areas(inner);
+
+    let intro = Paragraph::new(tr(
+        locale,
+        "Groq の API キー (https://console.groq.com/keys で発行) を貼り付けてください。確かめたキーは config.toml に保存します。",
+    ))
+    .wrap(Wrap { trim: false });
+    frame.render_widget(intro, intro_area);
+
+    let format = app.api_key_format();
+    let Some(setup) = app.api_key_setup.as_mut() else {
+        return;
+    };
+    let input = TextInput::new()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.title)),
+        )
+        .style(Style::default())
+        .focus_style(Style::default())
+        .passwd();
+    frame.render_stateful_widget(input, input_area, &mut setup.input);
+
+    let format_color = match format {
+        KeyFormat::Plausible => theme.success,
+        KeyFormat::UnexpectedPrefix => theme.highlight,
+        KeyFormat::Empty | KeyFormat::ContainsWhitespace => theme.danger,
+    };
+    let mut lines = vec![Line::styled(
+        tr(locale, format.message()),
+        Style::default().fg(format_color),
+    )];
+    if let Some((count, suffix)) = setup.describe_key() {
+        lines.push(Line::styled(
+            trf(locale, "{} 文字 / 末尾 {}", &[&count, &suffix]),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    if let Some(error) = &setup.error {
+        lines.push(Line::styled(
+            trf(locale, "確かめられませんでした: {}", &[error]),
+            Style::default().fg(theme.danger),
+        ));
+    }
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), info_area);
+    let cursor = (!setup.checking)
+        .then(|| setup.input.screen_cursor())
+        .flatten();
+
+    render_status_bar(app, frame, *status_area);
+    if let Some((cx, cy)) = cursor {
+        frame.set_cursor_position((cx, cy));
+    }
+}
+
+fn render_history_view(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+    let [header_area, body_area, status_area] = layout.as_ref() else {
+        return;
+    };
+    render_header(app, frame, *header_area);
+    render_status_bar(app, frame, *status_area);
+
+    let Some(browser) = app.history_browser.as_ref() else {
+        return;
+    };
+    if let Some(record) = browser.opened_record() {
+        render_history_detail(app, frame, *body_area, record, browser.detail_scroll);
+        return;
+    }
+
+    let lines: Vec<Line> = if browser.records.is_empty() {
+        vec![Line::from(tr(app.config.locale, "まだ履歴がありません。"))]
+    } else {
+        browser
+            .records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| {
+                let style = if index == browser.selected {
+                    Style::default().fg(theme.on_accent).bg(theme.accent)
+                } else if record.passed {
+                    Style::default().fg(theme.success)
+                } else {
+                    Style::default().fg(theme.danger)
+                };
+                Line::from(Span::styled(history::format_list_line(record), style))
+            })
+            .collect()
+    };
+
+    let block = Block::default()
+        .title(trf(
+            app.config.locale,
+            "履歴 ({} 件) (Enter: 開く, Esc: 戻る)",
+            &[&browser.records.len()],
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+    let visible_height = usize::from(body_area.height.saturating_sub(2));
+    let scroll = browser
+        .selected
+        .saturating_sub(visible_height.saturating_sub(1));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0));
+    frame.render_widget(paragraph, *body_area);
+}
+
+/// 直前の評価についてコーチと話す画面。会話は末尾が見えるように表示する
+fn render_coach_view(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme();
+    let [header_area, body_area, input_area, status_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(APPEAL_INPUT_HEIGHT),
+        Constraint::Length(3),
+    ])
+    .areas(frame.area());
+    render_header(app, frame, header_area);
+    render_status_bar(app, frame, status_area);
+
+    let lines = coach_lines(app, app.config.locale, theme);
+    let Some(coach) = app.coach.as_mut() else {
+        return;
+    };
+    let block = Block::default()
+        .title(tr(
+            app.config.locale,
+            " コーチ (Enter: 送信, ↑/↓: スクロール, Esc: 戻る) ",
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+    let text_height =
+        u16::try_from(paragraph.line_count(body_area.width.saturating_sub(2))).unwrap_or(u16::MAX);
+    let max_scroll = text_height.saturating_sub(body_area.height.saturating_sub(2));
+    coach.scroll_from_bottom = coach.scroll_from_bottom.min(max_scroll);
+    let scroll = max_scroll - coach.scroll_from_bottom;
+    frame.render_widget(paragraph.block(block).scroll((scroll, 0)), body_area);
+
+    let input = TextInput::new()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.title)),
+        )
+        .style(Style::default())
+        .focus_style(Style::default());
+    frame.render_stateful_widget(input, input_area, &mut coach.input);
+    if coach.pending.is_none()
+        && let Some((cx, cy)) = coach.input.screen_cursor()
+    {
+        frame.set_cursor_position((cx, cy));
+    }
+}
+
+/// コーチ画面の会話。送信中の質問は返事が届くまで末尾に仮に表示する
+fn coach_lines(app: &App, locale: LabelLanguage, theme: &Theme) -> Vec<Line<'static>> {
+    let Some(coach) = app.coach.as_ref() else {
+        return Vec::new();
+    };
+    let transcript = app
+        .api_client
+        .as_ref()
+        .map(ApiClient::coach_transcript)
+        .unwrap_or_default();
+    let label_style = Style::default().fg(theme.highlight).bold();
+    let mut lines = vec![Line::from(tr(
+        locale,
+        "評価について気になることを質問してください。例: なぜ重要度が3なの？",
+    ))];
+    let mut push_message = |label: &'static str, content: &str| {
+        lines.push(Line::default());
+        lines.push(Line::styled(tr(locale, label), label_style));
+        lines.extend(content.lines().map(|line| Line::from(line.to_string())));
+    };
+    for message in &transcript {
+        let label = match message.role {
+            CoachRole::User => "あなた: ",
+            CoachRole::Assistant | CoachRole::System => "コーチ: ",
+        };
+        push_message(label, &message.content);
+    }
+    if let Some(question) = &coach.pending {
+        push_message("あなた: ", question);
+        lines.push(Line::styled(
+            tr(locale, "考えています…"),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    if let Some(error) = &coach.error {
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            trf(locale, "返事を受け取れませんでした: {}", &[error]),
+            Style::default().fg(theme.danger),
+        ));
+    }
+    lines
+}
+
+/// 過去のセッションを原文・要約・評価の3列で読み取り専用表示する
+fn render_history_detail(
+    app: &App,
+    frame: &mut Frame,
+    area: Rect,
+    record: &history::SessionRecord,
+    scroll: u16,
+) {
+    let (theme, locale) = (app.theme(), app.config.locale);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+        ])
+        .split(area);
+    let [original_area, summary_area, evaluation_area] = columns.as_ref() else {
+        return;
+    };
+
+    let mut heading = trf(
+        locale,
+        "{} / {}字 / {}",
+        &[
+            &record.timestamp.format("%Y-%m-%d %H:%M"),
+            &record.character_count,
+            &history::format_result(record.passed, record.evaluation.as_ref()),
+        ],
+    );
+    if let Some(duration) = history::format_duration(record) {
+        heading.push_str(&trf(locale, " / 所要 {}", &[&duration]));
+    }
+
+    let summary = match &record.notes {
+        Some(notes) => format!(
+            "{}\n\n{}\n{notes}",
+            record.summary,
+            tr(locale, "--- メモ ---")
+        ),
+        None => record.summary.clone(),
+    };
+    for (text, title, color, target) in [
+        (
+            record.original_text.as_str(),
+            trf(locale, "原文 | {}", &[&heading]),
+            theme.highlight,
+            original_area,
+        ),
+        (
+            summary.as_str(),
+            tr(locale, "あなたの要約").to_string(),
+            theme.info,
+            summary_area,
+        ),
+        (
+            record.evaluation_text.as_str(),
+            tr(locale, "評価 (j/k: スクロール, Esc: 一覧へ)").to_string(),
+            theme.success,
+            evaluation_area,
+        ),
+    ] {
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color));
+        let paragraph = Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0))
+            .block(block);
+        frame.render_widget(paragraph, *target);
+    }
+}
+
+fn render_file_browser_view(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+    let [header_area, body_area, status_area] = layout.as_ref() else {
+        return;
+    };
+    render_header(app, frame, *header_area);
+
+    let Some(browser) = app.file_browser.as_ref() else {
+        return;
+    };
+    let lines: Vec<Line> = browser
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let style = if index == browser.selected {
+                Style::default().fg(theme.on_accent).bg(theme.accent)
+            } else if entry.is_dir {
+                Style::default().fg(theme.title)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(format!(" {} ", entry.label), style))
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(trf(
+            app.config.locale,
+            "テキストファイルを選択してください: {}",
+            &[&browser.dir.display()],
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let visible_height = usize::from(body_area.height.saturating_sub(2));
+    let scroll = browser
+        .selected
+        .saturating_sub(visible_height.saturating_sub(1));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0));
+    frame.render_widget(paragraph, *body_area);
+    render_status_bar(app, frame, *status_area);
+}
+
+fn build_model_line(
+    model: &str,
+    is_selected: bool,
+    is_current: bool,
+    theme: &Theme,
+) -> Line<'static> {
+    let marker = if is_current { "✔" } else { " " };
+    let style = if is_selected {
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    Line::from(Span::styled(format!(" {marker} {model}"), style))
+}
+
+fn render_read_aloud_view(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+    let [header_area, body_area, pace_area, status_area] = layout.as_ref() else {
+        return;
+    };
+    render_header(app, frame, *header_area);
+    render_status_bar(app, frame, *status_area);
+
+    let Some(session) = app.read_aloud.as_ref() else {
+        return;
+    };
+    let total = session.paragraphs.len();
+
+    let Some(paragraph) = session.current_paragraph() else {
+        let result = session.result();
+        let locale = app.config.locale;
+        let lines = vec![
+            Line::from(trf(locale, "文字数: {} 文字", &[&result.characters])),
+            Line::from(trf(
+                locale,
+                "所要時間: {} 秒 (目標 {} 秒)",
+                &[
+                    &format!("{:.1}", result.elapsed_secs),
+                    &format!("{:.1}", result.target_secs),
+                ],
+            )),
+            Line::from(trf(
+                locale,
+                "ペース: {} 文字/分",
+                &[&format!("{:.0}", result.chars_per_minute())],
+            )),
+        ];
+        let block = Block::default()
+            .title(tr(app.config.locale, "音読の結果 (Esc: 戻る)"))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.success));
+        frame.render_widget(Paragraph::new(lines).block(block), *body_area);
+        return;
+    };
+
+    let block = Block::default()
+        .title(trf(
+            app.config.locale,
+            "音読 (段落 {}/{}, Space: 次の段落, Esc: 戻る)",
+            &[&(session.current + 1), &total],
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight));
+    frame.render_widget(
+        Paragraph::new(paragraph)
+            .wrap(Wrap { trim: false })
+            .block(block),
+        *body_area,
+    );
+
+    let elapsed = session.current_elapsed().as_secs_f64();
+    let target = session.target_for(paragraph).as_secs_f64();
+    let ratio = if target > 0.0 {
+        (elapsed / target).min(1.0)
+    } else {
+        1.0
+    };
+    let color = if elapsed > target {
+        theme.danger
+    } else {
+        theme.success
+    };
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .title(tr(app.config.locale, "目標ペース"))
+                .borders(Borders::ALL),
+        )
+        .gauge_style(Style::default().fg(color))
+        .ratio(ratio)
+        .label(trf(
+            app.config.locale,
+            "経過 {} 秒 / 目標 {} 秒",
+            &[&format!("{elapsed:.1}"), &format!("{target:.1}")],
+        ));
+    frame.render_widget(gauge, *pace_area);
+}
+
+/// 今のかたまりだけを表示し、読み終えたかたまりは隠す
+fn render_speed_reading_view(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+    let [header_area, body_area, progress_area, status_area] = layout.as_ref() else {
+        return;
+    };
+    render_header(app, frame, *header_area);
+    render_status_bar(app, frame, *status_area);
+
+    let Some(session) = app.speed_reading.as_ref() else {
+        return;
+    };
+    let total = session.chunks.len();
+    let title = if session.is_paused() {
+        tr(
+            app.config.locale,
+            "速読 (一時停止中, Space: 再開, Esc: 戻る)",
+        )
+        .to_string()
+    } else {
+        trf(
+            app.config.locale,
+            "速読 ({} 文字/分, Space: 一時停止, +/-: ペース, Esc: 戻る)",
+            &[&session.chars_per_minute],
+        )
+    };
+    let body = session.current_chunk().map_or_else(
+        || tr(app.config.locale, "最後まで表示しました。").to_string(),
+        str::to_string,
+    );
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight));
+    frame.render_widget(
+        Paragraph::new(body)
+            .wrap(Wrap { trim: false })
+            .alignment(Alignment::Center)
+            .block(block),
+        *body_area,
+    );
+
+    let shown = session.current.min(total);
+    let ratio = if total == 0 {
+        1.0
+    } else {
+        f64::from(u32::try_from(shown).unwrap_or(u32::MAX))
+            / f64::from(u32::try_from(total).unwrap_or(u32::MAX))
+    };
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .title(tr(app.config.locale, "進み具合"))
+                .borders(Borders::ALL),
+        )
+        .gauge_style(Style::default().fg(theme.success))
+        .ratio(ratio)
+        .label(format!("{shown} / {total}"));
+    frame.render_widget(gauge, *progress_area);
 }
 
 fn render_help_view(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -320,20 +2363,23 @@ fn render_help_view(app: &App, frame: &mut Frame) {
     let [header_area, body_area, status_area] = layout.as_ref() else {
         return;
     };
-    render_header(frame, *header_area);
+    render_header(app, frame, *header_area);
 
     let help_content = help::HELP_CONTENT;
-    let help_text = if help_content.is_empty() {
-        "ヘルプファイルが見つかりません。\n\ndocs/HELP.md を作成してください。"
+    let help_text: Text = if help_content.is_empty() {
+        Text::from("ヘルプファイルが見つかりません。\n\ndocs/HELP.md を作成してください。")
     } else {
-        help_content
+        build_help_lines(app, help_content).into()
     };
 
     let block = Block::default()
-        .title("ヘルプ (↑/↓ or j/k: スクロール, h: 閉じる)")
+        .title(tr(
+            app.config.locale,
+            "ヘルプ (↑/↓ or j/k: スクロール, /: 検索, t: 目次, [/]: 前/次の見出し, T: チュートリアル, h: 閉じる)",
+        ))
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Green));
+        .border_style(Style::default().fg(theme.success));
 
     let paragraph = Paragraph::new(help_text)
         .block(block)
@@ -342,24 +2388,102 @@ fn render_help_view(app: &App, frame: &mut Frame) {
         .style(Style::default());
 
     frame.render_widget(paragraph, *body_area);
+    if let Some(selected) = app.help_toc {
+        render_help_toc(frame, *body_area, selected, app.config.locale, theme);
+    }
     render_status_bar(app, frame, *status_area);
 }
 
-fn build_menu_lines(selected_menu_item: usize) -> Vec<Line<'static>> {
+/// 検索に一致した行を強調する (選択中の一致はさらに目立たせる)
+fn build_help_lines<'a>(app: &App, content: &'a str) -> Vec<Line<'a>> {
+    let theme = app.theme();
+    let search = &app.help_search;
+    let current = search.current_line();
+    content
+        .lines()
+        .enumerate()
+        .map(|(index, text)| {
+            if current == Some(index) {
+                Line::styled(
+                    text,
+                    Style::default().fg(theme.on_accent).bg(theme.highlight),
+                )
+            } else if search.matches.contains(&index) {
+                Line::styled(text, Style::default().fg(theme.highlight))
+            } else {
+                Line::raw(text)
+            }
+        })
+        .collect()
+}
+
+fn render_help_toc(
+    frame: &mut Frame,
+    area: Rect,
+    selected: usize,
+    locale: LabelLanguage,
+    theme: &Theme,
+) {
+    let headings = App::help_toc_headings();
+    let lines: Vec<Line> = headings
+        .iter()
+        .enumerate()
+        .map(|(index, heading)| {
+            let indent = "  ".repeat(heading.level.saturating_sub(2));
+            let style = if index == selected {
+                Style::default().fg(theme.on_accent).bg(theme.accent)
+            } else {
+                Style::default()
+            };
+            Line::styled(format!("{indent}{}", heading.title), style)
+        })
+        .collect();
+
+    let width = area.width.saturating_mul(HELP_TOC_WIDTH_PERCENT) / 100;
+    let toc_area = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y,
+        width,
+        height: area.height,
+    };
+    let visible_height = usize::from(toc_area.height.saturating_sub(2));
+    let scroll = selected.saturating_sub(visible_height.saturating_sub(1));
+    let block = Block::default()
+        .title(tr(locale, "目次 (j/k: 選択, Enter: 移動, Esc: 閉じる)"))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0));
+    frame.render_widget(Clear, toc_area);
+    frame.render_widget(paragraph, toc_area);
+}
+
+fn build_menu_lines(
+    selected_menu_item: usize,
+    locale: LabelLanguage,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
     let mut lines = Vec::with_capacity(MENU_OPTIONS.len().saturating_add(2));
     lines.push(Line::default());
     for (index, &count) in MENU_OPTIONS.iter().enumerate() {
-        lines.push(build_menu_option_line(count, index == selected_menu_item));
+        lines.push(build_menu_option_line(
+            count,
+            index == selected_menu_item,
+            locale,
+            theme,
+        ));
     }
     lines.push(Line::default());
 
     lines
 }
 
-fn build_menu_title_lines() -> Vec<Line<'static>> {
+fn build_menu_title_lines(theme: &Theme) -> Vec<Line<'static>> {
     MENU_TITLE_ART
         .into_iter()
-        .map(|art| Line::from(Span::styled(art, Style::default().fg(MENU_TITLE_COLOR))))
+        .map(|art| Line::from(Span::styled(art, Style::default().fg(theme.title))))
         .collect()
 }
 
@@ -375,21 +2499,30 @@ fn menu_block_height() -> u16 {
     menu_options_height().saturating_add(4)
 }
 
-fn build_menu_option_line(count: u16, is_selected: bool) -> Line<'static> {
+fn build_menu_option_line(
+    count: u16,
+    is_selected: bool,
+    locale: LabelLanguage,
+    theme: &Theme,
+) -> Line<'static> {
     let style = if is_selected {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme.accent)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
     };
 
-    Line::from(Span::styled(format!("{count:>4} 文字"), style))
+    Line::from(Span::styled(
+        trf(locale, "{} 文字", &[&format!("{count:>4}")]),
+        style,
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::theme::ThemeName;
 
     #[test]
     fn test_calculate_overlay_area_standard() {
@@ -424,7 +2557,7 @@ mod tests {
 
     #[test]
     fn test_build_menu_lines_center_selected_without_widening() {
-        let lines = build_menu_lines(1);
+        let lines = build_menu_lines(1, LabelLanguage::Ja, ThemeName::Default.theme());
 
         assert_eq!(lines.len(), MENU_OPTIONS.len().saturating_add(2));
         assert_eq!(lines.first().map(|line| line.spans.len()), Some(0));
@@ -461,7 +2594,7 @@ mod tests {
 
     #[test]
     fn test_build_menu_title_lines() {
-        let lines = build_menu_title_lines();
+        let lines = build_menu_title_lines(ThemeName::Default.theme());
 
         assert_eq!(lines.len(), MENU_TITLE_ART.len());
         let Some(first_line) = lines.first() else {
@@ -478,7 +2611,7 @@ mod tests {
         let Some(first_span) = first_line.spans.first() else {
             return;
         };
-        assert_eq!(first_span.style.fg, Some(MENU_TITLE_COLOR));
+        assert_eq!(first_span.style.fg, Some(Color::LightBlue));
     }
 
     #[test]
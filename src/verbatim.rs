@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+/// 原文と同じ並びとみなす文字数。これより短い一致は用語の重なりとして数えない
+const NGRAM_CHARS: usize = 8;
+
+/// 要約のうち、原文と `NGRAM_CHARS` 文字以上続けて一致する部分の割合 (%)。空白と改行は無視する
+pub fn overlap_percent(summary: &str, original: &str) -> u8 {
+    let summary: Vec<char> = summary.chars().filter(|c| !c.is_whitespace()).collect();
+    let original: Vec<char> = original.chars().filter(|c| !c.is_whitespace()).collect();
+    if summary.len() < NGRAM_CHARS {
+        return 0;
+    }
+    let ngrams: HashSet<&[char]> = original.windows(NGRAM_CHARS).collect();
+    let mut copied = vec![false; summary.len()];
+    for (start, window) in summary.windows(NGRAM_CHARS).enumerate() {
+        if ngrams.contains(window) {
+            copied
+                .iter_mut()
+                .skip(start)
+                .take(NGRAM_CHARS)
+                .for_each(|c| *c = true);
+        }
+    }
+    let copied = copied.iter().filter(|&&c| c).count();
+    u8::try_from(copied * 100 / summary.len()).unwrap_or(100)
+}
+
+/// 評価結果の末尾に付ける、原文の書き写しが多いことの注意
+pub fn format_warning(percent: u8, max_percent: u8) -> String {
+    format!(
+        "\n\n# 原文との重複\n- 要約の {percent}% が原文と同じ表現です (目安 {max_percent}% 以下)。自分の言葉で言い換えましょう。\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copied_runs_are_counted_but_shared_terms_are_not() {
+        let original = "政府は少子化対策として児童手当を拡充する方針を固めた。";
+        assert_eq!(
+            overlap_percent("政府は少子化対策として児童手当を拡充する。", original),
+            95
+        );
+        assert_eq!(overlap_percent("少子化対策で手当を増やす。", original), 0);
+        assert_eq!(
+            overlap_percent("国は、少子化対策として児童 手当を増額へ。", original),
+            65
+        );
+        assert_eq!(overlap_percent("短い", original), 0);
+    }
+}
@@ -0,0 +1,303 @@
+use crate::config::LookupConfig;
+use crate::error::AppError;
+use crate::storage;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+/// 語の区切りに使う文字の種類。同じ種類が続く範囲を 1 語とみなす
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Kanji,
+    Hiragana,
+    Katakana,
+    Alphanumeric,
+}
+
+fn script(c: char) -> Option<Script> {
+    match c {
+        '々' | '〆' | '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' => Some(Script::Kanji),
+        'ぁ'..='ゖ' => Some(Script::Hiragana),
+        'ァ'..='ヺ' | 'ー' => Some(Script::Katakana),
+        c if c.is_alphanumeric() => Some(Script::Alphanumeric),
+        _ => None,
+    }
+}
+
+/// 原文のなかの 1 語。`start` は原文でのバイト位置
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Word {
+    pub start: usize,
+    pub text: String,
+}
+
+/// 文字の種類が変わる位置で区切った語の一覧。記号と空白は飛ばす
+pub fn split_words(text: &str) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut previous = None;
+    for (start, c) in text.char_indices() {
+        let current = script(c);
+        match (current, words.last_mut()) {
+            (Some(kind), Some(word)) if previous == Some(kind) => word.text.push(c),
+            (Some(_), _) => words.push(Word {
+                start,
+                text: c.to_string(),
+            }),
+            (None, _) => {}
+        }
+        previous = current;
+    }
+    words
+}
+
+/// 原文欄で語を選ぶカーソル
+#[derive(Clone, Debug)]
+pub struct WordSelection {
+    words: Vec<Word>,
+    index: usize,
+}
+
+impl WordSelection {
+    /// 語がない文章では選べない
+    pub fn new(text: &str) -> Option<Self> {
+        let words = split_words(text);
+        (!words.is_empty()).then_some(Self { words, index: 0 })
+    }
+
+    pub fn current(&self) -> Option<&Word> {
+        self.words.get(self.index)
+    }
+
+    /// 前後の語へ移る。端では止まる
+    pub fn move_by(&mut self, forward: bool) {
+        self.index = if forward {
+            (self.index + 1).min(self.words.len().saturating_sub(1))
+        } else {
+            self.index.saturating_sub(1)
+        };
+    }
+}
+
+/// 語を含む一文。モデルに文脈として渡す
+pub fn sentence_around(text: &str, word: &Word) -> String {
+    let is_end = |c: char| matches!(c, '。' | '！' | '？' | '\n');
+    let before = text.get(..word.start).unwrap_or_default();
+    let after = text.get(word.start..).unwrap_or_default();
+    let start = before
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| is_end(c))
+        .map_or(0, |(index, c)| index + c.len_utf8());
+    let end = after
+        .find(is_end)
+        .map_or(text.len(), |index| word.start + index);
+    text.get(start..end).unwrap_or_default().trim().to_string()
+}
+
+/// 調べた語の読みと意味
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WordEntry {
+    pub word: String,
+    pub reading: String,
+    pub meaning: String,
+}
+
+#[derive(Deserialize)]
+struct LookupResponse {
+    reading: String,
+    meaning: String,
+}
+
+pub fn build_lookup_prompt(word: &str, sentence: &str) -> String {
+    format!(
+        "次の文の中で使われている「{word}」の読み (ひらがな) と、この文での意味を 40 文字以内で答えてください。\
+         次の JSON だけを出力してください。{{\"reading\": \"よみ\", \"meaning\": \"意味\"}}\n\n# 文\n{sentence}"
+    )
+}
+
+pub fn parse_lookup(response: &str, word: &str) -> Result<WordEntry, AppError> {
+    let (Some(start), Some(end)) = (response.find('{'), response.rfind('}')) else {
+        return Err(AppError::InvalidText(
+            "JSON が含まれていません。".to_string(),
+        ));
+    };
+    let parsed: LookupResponse =
+        serde_json::from_str(response.get(start..=end).unwrap_or_default())?;
+    if parsed.meaning.trim().is_empty() {
+        return Err(AppError::InvalidText("意味が空です。".to_string()));
+    }
+    Ok(WordEntry {
+        word: word.to_string(),
+        reading: parsed.reading.trim().to_string(),
+        meaning: parsed.meaning.trim().to_string(),
+    })
+}
+
+/// 手元の辞書 (`見出し<TAB>読み<TAB>意味` の TSV)。載っている語は API を使わずに引く
+#[derive(Default)]
+pub struct Dictionary {
+    entries: HashMap<String, (String, String)>,
+}
+
+impl Dictionary {
+    /// 辞書を設定していなければ空の辞書。設定したファイルが読めない場合はエラー
+    pub fn from_config(config: &LookupConfig) -> Result<Self, AppError> {
+        let Some(path) = config.dictionary.as_ref() else {
+            return Ok(Self::default());
+        };
+        let content = fs::read_to_string(path).map_err(|e| {
+            AppError::InvalidConfig(format!("辞書 {} を読み込めません: {e}", path.display()))
+        })?;
+        Ok(Self::parse(&content))
+    }
+
+    /// 同じ見出しが複数あるときは、最初の行を使う
+    fn parse(content: &str) -> Self {
+        let mut entries = HashMap::new();
+        for line in content.lines() {
+            let mut columns = line.split('\t').map(str::trim);
+            if let (Some(word), Some(reading), Some(meaning)) =
+                (columns.next(), columns.next(), columns.next())
+                && !word.is_empty()
+                && !meaning.is_empty()
+            {
+                entries
+                    .entry(word.to_string())
+                    .or_insert_with(|| (reading.to_string(), meaning.to_string()));
+            }
+        }
+        Self { entries }
+    }
+
+    pub fn lookup(&self, word: &str) -> Option<WordEntry> {
+        self.entries.get(word).map(|(reading, meaning)| WordEntry {
+            word: word.to_string(),
+            reading: reading.clone(),
+            meaning: meaning.clone(),
+        })
+    }
+}
+
+/// 語を調べた結果のポップアップの中身
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LookupState {
+    /// モデルに問い合わせる前。`sentence` は語を含む一文
+    Pending {
+        word: String,
+        sentence: String,
+    },
+    Found(WordEntry),
+    Failed {
+        word: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct VocabEntry {
+    #[serde(flatten)]
+    pub entry: WordEntry,
+    pub added_at: DateTime<Local>,
+}
+
+/// 調べて追加した語の単語帳
+#[derive(Serialize, Deserialize, Default)]
+pub struct VocabList {
+    pub entries: Vec<VocabEntry>,
+}
+
+impl VocabList {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(storage::load_json(storage::global()?, storage::VOCAB_DOCUMENT)?.unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        storage::save_json(storage::global()?, storage::VOCAB_DOCUMENT, self)?;
+        Ok(())
+    }
+
+    /// 同じ語がすでにあれば追加せず `false` を返す
+    pub fn add(&mut self, entry: WordEntry, now: DateTime<Local>) -> bool {
+        if self
+            .entries
+            .iter()
+            .any(|existing| existing.entry.word == entry.word)
+        {
+            return false;
+        }
+        self.entries.push(VocabEntry {
+            entry,
+            added_at: now,
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_split_on_script_changes_and_selection_stays_in_bounds() {
+        let text = "市は、AI を活用した図書館サービスを始める。";
+        let words: Vec<String> = split_words(text).into_iter().map(|w| w.text).collect();
+        assert_eq!(
+            words,
+            vec![
+                "市",
+                "は",
+                "AI",
+                "を",
+                "活用",
+                "した",
+                "図書館",
+                "サービス",
+                "を",
+                "始",
+                "める"
+            ]
+        );
+
+        let mut selection = WordSelection::new(text).unwrap_or_else(|| WordSelection {
+            words: Vec::new(),
+            index: 0,
+        });
+        selection.move_by(false);
+        assert_eq!(selection.current().map(|w| w.text.as_str()), Some("市"));
+        for _ in 0..20 {
+            selection.move_by(true);
+        }
+        assert_eq!(selection.current().map(|w| w.text.as_str()), Some("める"));
+        assert!(WordSelection::new("、。 ").is_none());
+
+        let text = "前の文です。市は図書館を延長する。次の文。";
+        let word = split_words(text)
+            .into_iter()
+            .find(|word| word.text == "図書館");
+        assert_eq!(
+            word.map(|word| sentence_around(text, &word)).as_deref(),
+            Some("市は図書館を延長する")
+        );
+    }
+
+    #[test]
+    fn dictionary_and_vocab_list_avoid_duplicates() {
+        let dictionary =
+            Dictionary::parse("延長\tえんちょう\t長さや期間をのばすこと\n延長\tx\ty\n壊れた行\n");
+        assert_eq!(
+            dictionary.lookup("延長").map(|entry| entry.reading),
+            Some("えんちょう".to_string())
+        );
+        assert_eq!(dictionary.lookup("図書館"), None);
+        assert!(parse_lookup(r#"{"reading": "しさ", "meaning": " "}"#, "示唆").is_err());
+
+        let entry = parse_lookup(
+            r#"答え: {"reading": "しさ", "meaning": "それとなく示すこと"}"#,
+            "示唆",
+        );
+        let mut vocab = VocabList::default();
+        let now = Local::now();
+        assert!(entry.is_ok_and(|entry| vocab.add(entry.clone(), now) && !vocab.add(entry, now)));
+        assert_eq!(vocab.entries.len(), 1);
+    }
+}